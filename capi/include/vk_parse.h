@@ -0,0 +1,65 @@
+/*
+ * C API of vk-parse, the Vulkan registry parser.
+ *
+ * Registries are parsed into opaque handles, which can be queried for names of their items,
+ * and for the items serialized as JSON. All strings are UTF-8 and null-terminated.
+ */
+#ifndef VK_PARSE_H
+#define VK_PARSE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct VkParseRegistry VkParseRegistry;
+
+/* Kind of registry items. */
+typedef enum VkParseKind {
+    VK_PARSE_KIND_TYPE = 0,
+    VK_PARSE_KIND_COMMAND = 1,
+    VK_PARSE_KIND_ENUM = 2,
+    VK_PARSE_KIND_FEATURE = 3,
+    VK_PARSE_KIND_EXTENSION = 4
+} VkParseKind;
+
+/* Parses the registry file at path, e.g. vk.xml. Returns NULL on failure, see
+ * vk_parse_last_error. The handle must be released by vk_parse_free. */
+VkParseRegistry* vk_parse_file(const char* path);
+
+/* Parses the registry from len bytes of XML at data, see vk_parse_file. */
+VkParseRegistry* vk_parse_bytes(const uint8_t* data, size_t len);
+
+/* Description of the last failure of vk_parse_file, vk_parse_bytes or vk_parse_json on the
+ * calling thread, or NULL. Valid until the next failure on the thread. */
+const char* vk_parse_last_error(void);
+
+/* Releases the registry. Does nothing for NULL. */
+void vk_parse_free(VkParseRegistry* registry);
+
+/* Number of non-fatal errors found while parsing, such as unexpected elements. */
+size_t vk_parse_error_count(const VkParseRegistry* registry);
+
+/* Number of distinct names of items of given VkParseKind, or 0 if the kind is unknown. */
+size_t vk_parse_count(const VkParseRegistry* registry, int kind);
+
+/* Name of the item of given VkParseKind at index, in order of the registry, or NULL if index is
+ * out of range or the kind is unknown. Owned by the registry. */
+const char* vk_parse_name(const VkParseRegistry* registry, int kind, size_t index);
+
+/* Item of given VkParseKind serialized as JSON, or NULL if there's no such item. Types and
+ * commands are serialized as arrays of their definitions for different APIs. Must be released
+ * by vk_parse_string_free. Other failures, such as unknown kind, also return NULL, see
+ * vk_parse_last_error. */
+char* vk_parse_json(const VkParseRegistry* registry, int kind, const char* name);
+
+/* Releases string returned by vk_parse_json. Does nothing for NULL. */
+void vk_parse_string_free(char* string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* VK_PARSE_H */
@@ -0,0 +1,349 @@
+//! C API of `vk-parse`, for tools written in C or C++ which need the Vulkan registry without
+//! embedding Rust. The library is built as `cdylib` and `staticlib`, and its functions are
+//! declared by `include/vk_parse.h`.
+//!
+//! A registry is parsed into an opaque handle, which can be queried for names of its types,
+//! commands, enums, features and extensions, and for any of these items serialized as JSON.
+//! Strings returned by the library are UTF-8 and null-terminated.
+
+extern crate serde_json;
+extern crate vk_parse;
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::ptr;
+use std::slice;
+
+use vk_parse::{FatalError, InterfaceItem, ParseOptions, Registry};
+
+/// Kind of registry items, see `vk_parse_count`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VkParseKind {
+    Type = 0,
+    Command = 1,
+    Enum = 2,
+    Feature = 3,
+    Extension = 4,
+}
+
+const KINDS: usize = 5;
+
+impl VkParseKind {
+    /// Kind of given value, or `None` if it isn't one of the kinds declared by the header, as
+    /// functions taking the kind may be passed any `int` from C.
+    fn from_raw(kind: c_int) -> Option<VkParseKind> {
+        match kind {
+            0 => Some(VkParseKind::Type),
+            1 => Some(VkParseKind::Command),
+            2 => Some(VkParseKind::Enum),
+            3 => Some(VkParseKind::Feature),
+            4 => Some(VkParseKind::Extension),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed registry along with names of its items, see `vk_parse_file`.
+pub struct VkParseRegistry {
+    registry: Registry,
+    errors: usize,
+    /// Names of items by `VkParseKind`, kept so that pointers to them stay valid.
+    names: [Vec<CString>; KINDS],
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+fn set_last_error(desc: String) {
+    let desc = CString::new(desc.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(desc));
+}
+
+/// Creates the handle, or records the error and returns null.
+fn into_handle(
+    result: std::thread::Result<Result<(Registry, Vec<vk_parse::Error>), FatalError>>,
+) -> *mut VkParseRegistry {
+    let (registry, errors) = match result {
+        Ok(Ok(v)) => v,
+        Ok(Err(e)) => {
+            set_last_error(format!("{:?}", e));
+            return ptr::null_mut();
+        }
+        Err(_) => {
+            set_last_error(String::from("internal error while parsing the registry"));
+            return ptr::null_mut();
+        }
+    };
+    let names = [
+        item_names(registry.types().filter_map(|ty| ty.declared_name())),
+        item_names(registry.commands().map(|command| command.name())),
+        item_names(enums(&registry).map(|e| &*e.name)),
+        item_names(registry.features().map(|f| &*f.name)),
+        item_names(registry.extensions().map(|e| &*e.name)),
+    ];
+    Box::into_raw(Box::new(VkParseRegistry {
+        registry,
+        errors: errors.len(),
+        names,
+    }))
+}
+
+/// Distinct names, in order of first appearance.
+fn item_names<'a, I: Iterator<Item = &'a str>>(names: I) -> Vec<CString> {
+    let mut seen = BTreeSet::new();
+    names
+        .filter(|&name| seen.insert(name))
+        .filter_map(|name| CString::new(name).ok())
+        .collect()
+}
+
+/// Enums defined by `<enums>` blocks, followed by those defined by features and extensions.
+fn enums(registry: &Registry) -> impl Iterator<Item = &vk_parse::Enum> {
+    let block_enums = registry
+        .enums_blocks()
+        .flat_map(|block| &block.children)
+        .filter_map(|child| match *child {
+            vk_parse::EnumsChild::Enum(ref e) => Some(e),
+            _ => None,
+        });
+    let children = registry
+        .features()
+        .flat_map(|f| &f.children)
+        .chain(registry.extensions().flat_map(|e| &e.children));
+    let item_enums = children
+        .flat_map(|child| match *child {
+            vk_parse::ExtensionChild::Require { ref items, .. } => &items[..],
+            _ => &[],
+        })
+        .filter_map(|item| match *item {
+            InterfaceItem::Enum(ref e) if e.spec != vk_parse::EnumSpec::None => Some(e),
+            _ => None,
+        });
+    block_enums.chain(item_enums)
+}
+
+/// Parses the registry file at null-terminated `path` with default options. Returns null if
+/// the file can't be parsed, see `vk_parse_last_error`. The handle must be released by
+/// `vk_parse_free`.
+///
+/// # Safety
+///
+/// `path` must be a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vk_parse_file(path: *const c_char) -> *mut VkParseRegistry {
+    if path.is_null() {
+        set_last_error(String::from("path is null"));
+        return ptr::null_mut();
+    }
+    let path = match CStr::from_ptr(path).to_str() {
+        Ok(path) => path,
+        Err(e) => {
+            set_last_error(format!("path is not valid UTF-8: {}", e));
+            return ptr::null_mut();
+        }
+    };
+    into_handle(panic::catch_unwind(|| {
+        vk_parse::parse_file_with_options(Path::new(path), &ParseOptions::default())
+    }))
+}
+
+/// Parses the registry from `len` bytes of XML at `data`, see `vk_parse_file`.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn vk_parse_bytes(data: *const u8, len: usize) -> *mut VkParseRegistry {
+    if data.is_null() {
+        set_last_error(String::from("data is null"));
+        return ptr::null_mut();
+    }
+    let bytes = slice::from_raw_parts(data, len);
+    into_handle(panic::catch_unwind(AssertUnwindSafe(|| {
+        vk_parse::parse_bytes_with_options(bytes, &ParseOptions::default())
+    })))
+}
+
+/// Description of the last failure of `vk_parse_file`, `vk_parse_bytes` or `vk_parse_json` on
+/// the calling thread, or null if there was none. The string is valid until the next failure on the thread.
+#[no_mangle]
+pub extern "C" fn vk_parse_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+}
+
+/// Releases the registry. Does nothing if `registry` is null.
+///
+/// # Safety
+///
+/// `registry` must be null or returned by `vk_parse_file` or `vk_parse_bytes`, and not released
+/// yet.
+#[no_mangle]
+pub unsafe extern "C" fn vk_parse_free(registry: *mut VkParseRegistry) {
+    if !registry.is_null() {
+        drop(Box::from_raw(registry));
+    }
+}
+
+/// Number of non-fatal errors found while parsing the registry, such as unexpected elements.
+///
+/// # Safety
+///
+/// `registry` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn vk_parse_error_count(registry: *const VkParseRegistry) -> usize {
+    registry.as_ref().map_or(0, |r| r.errors)
+}
+
+/// Number of distinct names of items of given kind, see `VkParseKind`, or 0 if the kind is
+/// unknown. Types and commands defined for multiple APIs are counted once.
+///
+/// # Safety
+///
+/// `registry` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn vk_parse_count(registry: *const VkParseRegistry, kind: c_int) -> usize {
+    match (registry.as_ref(), VkParseKind::from_raw(kind)) {
+        (Some(r), Some(kind)) => r.names[kind as usize].len(),
+        _ => 0,
+    }
+}
+
+/// Name of the item of given kind at `index`, in order of the registry, or null if `index` is
+/// out of range or the kind is unknown. The string is owned by the registry and valid until it's released.
+///
+/// # Safety
+///
+/// `registry` must be a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn vk_parse_name(
+    registry: *const VkParseRegistry,
+    kind: c_int,
+    index: usize,
+) -> *const c_char {
+    let kind = match VkParseKind::from_raw(kind) {
+        Some(kind) => kind,
+        None => return ptr::null(),
+    };
+    registry
+        .as_ref()
+        .and_then(|r| r.names[kind as usize].get(index))
+        .map_or(ptr::null(), |name| name.as_ptr())
+}
+
+/// Item of given kind with null-terminated `name` serialized as JSON, or null if there's no
+/// such item. Types and commands are serialized as an array of their definitions, as they may
+/// be defined repeatedly for different APIs. The string must be released by
+/// `vk_parse_string_free`.
+///
+/// Also returns null, with the error recorded for `vk_parse_last_error`, if the kind is
+/// unknown, `name` isn't valid UTF-8 or the item can't be serialized.
+///
+/// # Safety
+///
+/// `registry` must be a valid handle and `name` a valid null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn vk_parse_json(
+    registry: *const VkParseRegistry,
+    kind: c_int,
+    name: *const c_char,
+) -> *mut c_char {
+    let registry = match registry.as_ref() {
+        Some(r) => &r.registry,
+        None => {
+            set_last_error(String::from("registry is null"));
+            return ptr::null_mut();
+        }
+    };
+    let kind = match VkParseKind::from_raw(kind) {
+        Some(kind) => kind,
+        None => {
+            set_last_error(format!("unknown kind {}", kind));
+            return ptr::null_mut();
+        }
+    };
+    if name.is_null() {
+        set_last_error(String::from("name is null"));
+        return ptr::null_mut();
+    }
+    let name = match CStr::from_ptr(name).to_str() {
+        Ok(name) => name,
+        Err(e) => {
+            set_last_error(format!("name is not valid UTF-8: {}", e));
+            return ptr::null_mut();
+        }
+    };
+    let json = match panic::catch_unwind(AssertUnwindSafe(|| item_json(registry, kind, name))) {
+        Ok(Ok(json)) => json,
+        Ok(Err(e)) => {
+            set_last_error(format!("failed to serialize {}: {}", name, e));
+            return ptr::null_mut();
+        }
+        Err(_) => {
+            set_last_error(String::from("internal error while serializing the item"));
+            return ptr::null_mut();
+        }
+    };
+    json.and_then(|json| CString::new(json).ok())
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Item of given kind serialized as JSON, or `None` if there's no such item.
+fn item_json(
+    registry: &Registry,
+    kind: VkParseKind,
+    name: &str,
+) -> serde_json::Result<Option<String>> {
+    let json = match kind {
+        VkParseKind::Type => {
+            let types: Vec<_> = registry
+                .types()
+                .filter(|ty| ty.declared_name() == Some(name))
+                .collect();
+            if types.is_empty() {
+                return Ok(None);
+            }
+            serde_json::to_string(&types)?
+        }
+        VkParseKind::Command => {
+            let commands: Vec<_> = registry
+                .commands()
+                .filter(|command| command.name() == name)
+                .collect();
+            if commands.is_empty() {
+                return Ok(None);
+            }
+            serde_json::to_string(&commands)?
+        }
+        VkParseKind::Enum => match enums(registry).find(|e| &*e.name == name) {
+            Some(e) => serde_json::to_string(e)?,
+            None => return Ok(None),
+        },
+        VkParseKind::Feature => match registry.features().find(|f| &*f.name == name) {
+            Some(f) => serde_json::to_string(f)?,
+            None => return Ok(None),
+        },
+        VkParseKind::Extension => match registry.extensions().find(|e| &*e.name == name) {
+            Some(e) => serde_json::to_string(e)?,
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(json))
+}
+
+/// Releases string returned by `vk_parse_json`. Does nothing if `string` is null.
+///
+/// # Safety
+///
+/// `string` must be null or returned by `vk_parse_json`, and not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn vk_parse_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
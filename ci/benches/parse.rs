@@ -0,0 +1,61 @@
+//! Benchmarks of parsing the current `vk.xml`.
+//!
+//! The registry is downloaded from Vulkan-Docs repository, unless path to a local copy is given
+//! using `VK_XML` environment variable.
+
+#[macro_use]
+extern crate criterion;
+extern crate minreq;
+extern crate vk_parse;
+
+use criterion::{Criterion, Throughput};
+use vk_parse::{ParseOptions, RegistrySection};
+
+const URL_MAIN: &str = "https://raw.githubusercontent.com/KhronosGroup/Vulkan-Docs/main/xml/vk.xml";
+
+fn load_registry() -> Vec<u8> {
+    if let Some(path) = std::env::var_os("VK_XML") {
+        return std::fs::read(&path).unwrap_or_else(|_| panic!("Failed to read {:?}", path));
+    }
+
+    let resp = minreq::get(URL_MAIN)
+        .send()
+        .unwrap_or_else(|_| panic!("Failed to GET resource: {:?}", URL_MAIN));
+    let is_success = 200 <= resp.status_code && resp.status_code < 300;
+    if !is_success {
+        panic!(
+            "Download request failed with status: {:?}",
+            resp.status_code
+        )
+    }
+    resp.into_bytes()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let xml = load_registry();
+
+    let mut group = c.benchmark_group("vk.xml");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(xml.len() as u64));
+
+    group.bench_function("parse_bytes", |b| {
+        b.iter(|| vk_parse::parse_bytes(&xml).unwrap())
+    });
+
+    // Struct members and command parameters, which dominate parsing time.
+    let options =
+        ParseOptions::new().only_sections(&[RegistrySection::Types, RegistrySection::Commands]);
+    group.bench_function("types_and_commands", |b| {
+        b.iter(|| vk_parse::parse_bytes_with_options(&xml, &options).unwrap())
+    });
+
+    let options = ParseOptions::new();
+    group.bench_function("parse_bytes_parallel", |b| {
+        b.iter(|| vk_parse::parse_bytes_parallel(&xml, &options).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,4958 @@
+#![deny(warnings)]
+
+extern crate flate2;
+extern crate ron;
+extern crate tokio;
+extern crate vk_parse;
+extern crate vk_parse_capi;
+extern crate xml;
+extern crate zstd;
+
+use vk_parse::{FatalError, ParseOptions};
+
+fn parse(
+    xml: &str,
+    options: &ParseOptions,
+) -> Result<(vk_parse::Registry, Vec<vk_parse::Error>), FatalError> {
+    vk_parse::parse_str_with_options(xml, options)
+}
+
+fn apis(text: &str) -> vk_parse::ApiSet {
+    vk_parse::ApiSet::parse(text).unwrap()
+}
+
+const UNEXPECTED_ATTRIBUTE: &str =
+    r#"<registry><tags><tag name="KHR" author="Khronos" contact="-" bogus="1"/></tags></registry>"#;
+
+#[test]
+fn test_default_options_collect_errors() {
+    let (_registry, errors) = parse(UNEXPECTED_ATTRIBUTE, &ParseOptions::new()).unwrap();
+    assert_eq!(errors.len(), 1);
+    match errors[0] {
+        vk_parse::Error::UnexpectedAttribute {
+            ref xpath,
+            ref name,
+        } => {
+            assert_eq!(xpath, "/registry/tags/tag");
+            assert_eq!(name, "bogus");
+        }
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_strict_options_fail() {
+    match parse(UNEXPECTED_ATTRIBUTE, &ParseOptions::new().strict(true)) {
+        Err(FatalError::Strict(errors)) => assert_eq!(errors.len(), 1),
+        other => panic!("{:?}", other),
+    }
+}
+
+const SECTIONS: &str = r#"<registry>
+    <comment>Comment</comment>
+    <tags><tag name="KHR" author="Khronos" contact="-"/></tags>
+    <commands><command><proto><type>void</type> <name>vkFoo</name></proto></command></commands>
+    <extensions><extension name="VK_KHR_foo" number="1"/></extensions>
+    <unknown/>
+</registry>"#;
+
+#[test]
+fn test_only_sections() {
+    use vk_parse::{RegistryChild, RegistrySection};
+    let options = ParseOptions::new().only_sections(&[RegistrySection::Extensions]);
+    let (registry, errors) = parse(SECTIONS, &options).unwrap();
+    assert_eq!(errors.len(), 1); // <unknown> is still reported
+    assert_eq!(registry.0.len(), 1);
+    match registry.0[0] {
+        RegistryChild::Extensions(ref e) => assert_eq!(&*e.children[0].name, "VK_KHR_foo"),
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_skip_sections() {
+    use vk_parse::RegistrySection;
+    let options =
+        ParseOptions::new().skip_sections(&[RegistrySection::Commands, RegistrySection::Comment]);
+    let (registry, _errors) = parse(SECTIONS, &options).unwrap();
+    assert_eq!(registry.0.len(), 2);
+}
+
+#[test]
+fn test_parse_bytes_with_bom() {
+    let mut bytes = vec![0xEF, 0xBB, 0xBF];
+    bytes.extend_from_slice(SECTIONS.as_bytes());
+    let (registry, _errors) = vk_parse::parse_bytes(&bytes).unwrap();
+    assert_eq!(registry.0.len(), 4);
+}
+
+#[test]
+fn test_parse_parallel() {
+    let with_bogus = SECTIONS.replace("</registry>", &UNEXPECTED_ATTRIBUTE[10..]);
+    let options = ParseOptions::new().skip_sections(&[vk_parse::RegistrySection::Comment]);
+    let sequential = parse(&with_bogus, &options).unwrap();
+    let parallel = vk_parse::parse_str_parallel(&with_bogus, &options).unwrap();
+    assert_eq!(sequential, parallel);
+    assert_eq!(parallel.1.len(), 2);
+
+    // Documents whose prolog or root element affect sections are parsed as a whole.
+    let documents: [&[u8]; 3] = [
+        br#"<!DOCTYPE registry [<!ENTITY author "Khronos">]>
+            <registry><tags><tag name="KHR" author="&author;" contact="-"/></tags></registry>"#,
+        b"<?xml version=\"1.0\" encoding=\"ISO-8859-1\"?>
+            <registry><tags><tag name=\"KHR\" author=\"Khronos\" contact=\"\xe9\"/></tags></registry>",
+        br#"<registry xmlns:v="urn:v">
+            <tags><tag name="KHR" author="Khronos" contact="-" v:x="1"/></tags></registry>"#,
+    ];
+    for xml in &documents {
+        let sequential = vk_parse::parse_bytes_with_options(xml, &options).unwrap();
+        let parallel = vk_parse::parse_bytes_parallel(xml, &options).unwrap();
+        assert_eq!(sequential, parallel);
+        assert_eq!(vk_parse::parse_stream_lossy(*xml, &options), sequential);
+        assert_eq!(sequential.0.tags().count(), 1);
+    }
+}
+
+#[test]
+fn test_parse_compressed_files() {
+    use std::io::Write;
+
+    let dir = std::env::temp_dir();
+    let expected = parse(SECTIONS, &ParseOptions::new()).unwrap();
+
+    let gz_path = dir.join(format!("vk-parse-{}.xml.gz", std::process::id()));
+    let mut encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&gz_path).unwrap(),
+        flate2::Compression::default(),
+    );
+    encoder.write_all(SECTIONS.as_bytes()).unwrap();
+    encoder.finish().unwrap();
+
+    let zst_path = dir.join(format!("vk-parse-{}.xml.zst", std::process::id()));
+    let compressed = zstd::encode_all(SECTIONS.as_bytes(), 0).unwrap();
+    std::fs::write(&zst_path, compressed).unwrap();
+
+    for path in &[gz_path, zst_path] {
+        let result = vk_parse::parse_file(path);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(result.unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_parse_stream_async() {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let result = runtime.block_on(vk_parse::parse_stream_async(SECTIONS.as_bytes()));
+    assert_eq!(
+        result.unwrap(),
+        parse(SECTIONS, &ParseOptions::new()).unwrap()
+    );
+}
+
+#[test]
+fn test_resource_limits() {
+    use vk_parse::ResourceLimit;
+    let cases = [
+        (ParseOptions::new().max_depth(3), ResourceLimit::Depth),
+        (ParseOptions::new().max_elements(8), ResourceLimit::Elements),
+        (
+            ParseOptions::new().max_text_len(4),
+            ResourceLimit::TextLength,
+        ),
+    ];
+    for &(ref options, expected) in &cases {
+        match parse(SECTIONS, options) {
+            Err(FatalError::LimitExceeded { limit, .. }) => assert_eq!(limit, expected),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    let options = ParseOptions::new()
+        .max_depth(5)
+        .max_elements(100)
+        .max_text_len(100);
+    assert!(parse(SECTIONS, &options).is_ok());
+
+    // Long attribute values and text are rejected by the XML reader before being read whole.
+    let long = "x".repeat(1000);
+    let documents = [
+        format!(
+            r#"<registry><tags><tag name="{}"/></tags></registry>"#,
+            long
+        ),
+        format!("<registry><comment>{}</comment></registry>", long),
+    ];
+    for xml in &documents {
+        match parse(xml, &ParseOptions::new().max_text_len(100)) {
+            Err(FatalError::LimitExceeded { limit, .. }) => {
+                assert_eq!(limit, ResourceLimit::TextLength)
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_xml_config() {
+    let xml = r#"<registry><comment>&custom;</comment></registry>"#;
+    let config = vk_parse::ParserConfig::new().add_entity("custom", "Expanded");
+    let (registry, _errors) = parse(xml, &ParseOptions::new().xml_config(config)).unwrap();
+    match registry.0[0] {
+        vk_parse::RegistryChild::Comment(ref text) => assert_eq!(&**text, "Expanded"),
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_stream_utf16() {
+    fn encode(text: &str, little_endian: bool, bom: bool) -> Vec<u8> {
+        let bom = if bom { Some(0xFEFF) } else { None };
+        bom.into_iter()
+            .chain(text.encode_utf16())
+            .flat_map(|c| {
+                if little_endian {
+                    c.to_le_bytes()
+                } else {
+                    c.to_be_bytes()
+                }
+            })
+            .collect()
+    }
+
+    let expected = parse(SECTIONS, &ParseOptions::new()).unwrap();
+    let declared = format!(r#"<?xml version="1.0" encoding="UTF-16"?>{}"#, SECTIONS);
+    for text in &[SECTIONS, &declared] {
+        for &little_endian in &[true, false] {
+            for &bom in &[true, false] {
+                let bytes = encode(text, little_endian, bom);
+                let result = vk_parse::parse_stream(&bytes[..]).unwrap();
+                assert_eq!(result, expected, "{} {} {}", text, little_endian, bom);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_registry_cache() {
+    let mut cache = vk_parse::RegistryCache::new(ParseOptions::new());
+    assert!(cache.update(SECTIONS.as_bytes()).unwrap());
+    assert!(!cache.update(SECTIONS.as_bytes()).unwrap());
+    assert_eq!(
+        (cache.registry().clone(), cache.errors().to_vec()),
+        parse(SECTIONS, &ParseOptions::new()).unwrap()
+    );
+
+    let edited = SECTIONS.replace("VK_KHR_foo", "VK_KHR_bar");
+    assert!(cache.update(edited.as_bytes()).unwrap());
+    assert_eq!(
+        (cache.registry().clone(), cache.errors().to_vec()),
+        parse(&edited, &ParseOptions::new()).unwrap()
+    );
+}
+
+#[test]
+fn test_parse_dir() {
+    let dir = std::env::temp_dir().join(format!("vk-parse-dir-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("video.xml"), UNEXPECTED_ATTRIBUTE).unwrap();
+    std::fs::write(dir.join("vk.xml"), SECTIONS).unwrap();
+    std::fs::write(dir.join("other.xml"), "<other/>").unwrap();
+    std::fs::write(dir.join("readme.txt"), "").unwrap();
+    let result = vk_parse::parse_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let set = result.unwrap();
+    let names: Vec<_> = set
+        .files
+        .iter()
+        .map(|f| f.path.file_name().unwrap())
+        .collect();
+    assert_eq!(names, ["vk.xml", "video.xml"]);
+    assert_eq!(set.get("video.xml").unwrap().errors.len(), 1);
+    assert_eq!(set.combined().0.len(), 5);
+}
+
+#[test]
+fn test_strip_comments() {
+    let xml = r#"<registry>
+        <comment>Copyright</comment>
+        <types comment="Types">
+            <comment>Structs</comment>
+            <type category="struct" name="VkFoo" comment="Foo">
+                <member><type>uint32_t</type> <name>x</name><comment>The x</comment></member>
+            </type>
+        </types>
+        <extensions><extension name="VK_KHR_foo" number="1">
+            <require comment="Foo"><comment>Items</comment><type name="VkFoo"/></require>
+        </extension></extensions>
+    </registry>"#;
+
+    let (registry, errors) = parse(xml, &ParseOptions::new().strip_comments(true)).unwrap();
+    assert_eq!(errors, []);
+    let debug = format!("{:?}", registry);
+    assert!(!debug.contains("Comment("), "{}", debug);
+    assert!(!debug.contains("comment: Some"), "{}", debug);
+    assert!(debug.contains("VkFoo"), "{}", debug);
+}
+
+#[test]
+fn test_raw_attributes() {
+    let xml = r#"<registry><extensions>
+        <extension name="VK_KHR_foo" number="0x10" provisional="true"/>
+    </extensions></registry>"#;
+
+    let (registry, _errors) = parse(xml, &ParseOptions::new()).unwrap();
+    match registry.0[0] {
+        vk_parse::RegistryChild::Extensions(ref e) => assert!(e.children[0].raw.is_empty()),
+        ref other => panic!("{:?}", other),
+    }
+
+    let (registry, _errors) = parse(xml, &ParseOptions::new().raw_attributes(true)).unwrap();
+    match registry.0[0] {
+        vk_parse::RegistryChild::Extensions(ref e) => {
+            let extension = &e.children[0];
+            assert_eq!(extension.number, Some(16));
+            assert_eq!(extension.raw.len(), 3);
+            assert_eq!(extension.raw.get("number").map(|v| &**v), Some("0x10"));
+        }
+        ref other => panic!("{:?}", other),
+    }
+
+    // Malformed values are dropped from the typed fields, but kept in `raw`.
+    let xml = r#"<registry>
+    <types>
+        <type category="struct" name="VkFoo" deprecated="maybe">
+            <member api="vulkan," deprecated="soon"><type>uint32_t</type> <name>x</name></member>
+        </type>
+    </types>
+    <commands>
+        <command queues="graphics,,compute" videocoding="never"><proto><type>void</type> <name>vkCmdFoo</name></proto></command>
+        <command name="vkCmdFooKHR" alias="vkCmdFoo" deprecated="maybe"/>
+    </commands>
+    <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+        <require depends="VK_KHR_a+(" api="vulkan,"/>
+    </feature>
+</registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new().raw_attributes(true)).unwrap();
+    assert_eq!(errors.len(), 8, "{:?}", errors);
+    let raw = |raw: &vk_parse::RawAttributes, name: &str| raw.get(name).map(|v| v.to_string());
+
+    let ty = registry.types().next().unwrap();
+    assert_eq!(ty.deprecated, None);
+    assert_eq!(raw(&ty.raw, "deprecated").as_deref(), Some("maybe"));
+    match ty.spec {
+        vk_parse::TypeSpec::Members(ref members) => match members[0] {
+            vk_parse::TypeMember::Definition(ref def) => {
+                assert_eq!(def.api, None);
+                assert_eq!(raw(&def.raw, "api").as_deref(), Some("vulkan,"));
+            }
+            ref other => panic!("{:?}", other),
+        },
+        ref other => panic!("{:?}", other),
+    }
+
+    let mut commands = registry.commands();
+    match *commands.next().unwrap() {
+        vk_parse::Command::Definition(ref def) => {
+            assert_eq!(def.queues, []);
+            assert_eq!(def.videocoding, None);
+            assert_eq!(
+                raw(&def.raw, "queues").as_deref(),
+                Some("graphics,,compute")
+            );
+            assert_eq!(raw(&def.raw, "videocoding").as_deref(), Some("never"));
+        }
+        ref other => panic!("{:?}", other),
+    }
+    match *commands.next().unwrap() {
+        vk_parse::Command::Alias { ref raw, .. } => assert_eq!(raw.len(), 3),
+        ref other => panic!("{:?}", other),
+    }
+
+    let feature = registry.features().next().unwrap();
+    assert_eq!(raw(&feature.raw, "number").as_deref(), Some("1.0"));
+    match feature.children[0] {
+        vk_parse::ExtensionChild::Require {
+            ref depends,
+            ref raw,
+            ..
+        } => {
+            assert_eq!(*depends, None);
+            assert_eq!(raw.get("depends").map(|v| &**v), Some("VK_KHR_a+("));
+        }
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_param_c_type() {
+    use vk_parse::{CType, Command, RegistryChild};
+    let xml = r#"<registry><commands><command>
+        <proto><type>void</type> <name>vkFoo</name></proto>
+        <param>const <type>char</type>* const* <name>ppNames</name></param>
+    </command></commands></registry>"#;
+
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert!(errors.is_empty(), "{:?}", errors);
+    let command = match registry.0[0] {
+        RegistryChild::Commands(ref c) => match c.children[0] {
+            Command::Definition(ref d) => d.clone(),
+            ref other => panic!("{:?}", other),
+        },
+        ref other => panic!("{:?}", other),
+    };
+    match command.proto.c_type {
+        Some(CType::Named { ref name, .. }) => assert_eq!(&**name, "void"),
+        ref other => panic!("{:?}", other),
+    }
+    match command.params[0].definition.c_type {
+        Some(CType::Pointer {
+            is_const: false,
+            ref inner,
+        }) => match **inner {
+            CType::Pointer { is_const: true, .. } => {}
+            ref other => panic!("{:?}", other),
+        },
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_omit_code() {
+    use vk_parse::{Command, RegistryChild, TypeMember, TypeSpec, TypesChild};
+    let xml = r#"<registry>
+    <types><type category="struct" name="VkFoo">
+        <member><type>uint32_t</type> <name>mask</name>:8</member>
+        <member>const <type>float</type> <name>matrix</name>[3][<enum>VK_SIZE</enum>]</member>
+    </type></types>
+    <commands><command>
+        <proto><type>void</type> <name>vkFoo</name></proto>
+        <param>const <type>char</type>* const* <name>ppNames</name></param>
+        <param><type>VkFoo</type>* <name>pFoo</name></param>
+    </command></commands>
+</registry>"#;
+
+    let declarations = |registry: &vk_parse::Registry| {
+        let mut declarations = Vec::new();
+        for child in &registry.0 {
+            match *child {
+                RegistryChild::Types(ref t) => {
+                    for child in &t.children {
+                        if let TypesChild::Type(ref t) = *child {
+                            if let TypeSpec::Members(ref members) = t.spec {
+                                for member in members {
+                                    if let TypeMember::Definition(ref d) = *member {
+                                        declarations.push((d.code.to_string(), d.to_string()));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                RegistryChild::Commands(ref c) => {
+                    for command in &c.children {
+                        if let Command::Definition(ref d) = *command {
+                            declarations.push((d.code.to_string(), d.to_string()));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        declarations
+    };
+
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert!(errors.is_empty(), "{:?}", errors);
+    for (code, regenerated) in declarations(&registry) {
+        let strip = |s: &str| s.split_whitespace().collect::<String>();
+        assert_eq!(strip(&code), strip(&regenerated));
+    }
+
+    let (registry, _errors) = parse(xml, &ParseOptions::new().omit_code(true)).unwrap();
+    let declarations = declarations(&registry);
+    assert_eq!(declarations.len(), 3);
+    assert!(declarations.iter().all(|(code, _)| code.is_empty()));
+    assert_eq!(
+        declarations[2].1,
+        "void vkFoo(const char* const* ppNames, VkFoo* pFoo);"
+    );
+}
+
+fn parse_types(xml: &str) -> Vec<vk_parse::Type> {
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert!(errors.is_empty(), "{:?}", errors);
+    let mut types = Vec::new();
+    for child in registry.0 {
+        if let vk_parse::RegistryChild::Types(t) = child {
+            for child in t.children {
+                if let vk_parse::TypesChild::Type(t) = child {
+                    types.push(t);
+                }
+            }
+        }
+    }
+    types
+}
+
+#[test]
+fn test_handle_and_bitmask_aliases() {
+    use vk_parse::TypeSpec;
+    let types = parse_types(
+        r#"<registry><types>
+        <type category="handle" name="VkFooKHR" alias="VkFoo"/>
+        <type category="bitmask" name="VkFooFlagsKHR" alias="VkFooFlags"/>
+        <type requires="X11/Xlib.h" name="Display"/>
+    </types></registry>"#,
+    );
+    assert_eq!(
+        types[0].spec,
+        TypeSpec::HandleAlias {
+            alias: "VkFoo".into()
+        }
+    );
+    assert_eq!(
+        types[1].spec,
+        TypeSpec::BitmaskAlias {
+            alias: "VkFooFlags".into()
+        }
+    );
+    assert_eq!(types[2].spec, TypeSpec::None);
+}
+
+#[test]
+fn test_struct_aliases() {
+    use vk_parse::TypeSpec;
+    let types = parse_types(
+        r#"<registry><types>
+        <type category="struct" name="VkFooKHR" alias="VkFoo"/>
+        <type category="union" name="VkBarKHR" alias="VkBar"/>
+        <type category="struct" name="VkEmpty"></type>
+    </types></registry>"#,
+    );
+    assert_eq!(
+        types[0].spec,
+        TypeSpec::StructAlias {
+            alias: "VkFoo".into()
+        }
+    );
+    assert_eq!(
+        types[1].spec,
+        TypeSpec::UnionAlias {
+            alias: "VkBar".into()
+        }
+    );
+    assert_eq!(types[2].spec, TypeSpec::None);
+}
+
+#[test]
+fn test_define_expansions() {
+    use vk_parse::TypeSpec;
+    let types = parse_types(
+        r#"<registry><types>
+        <type category="define" requires="VK_USE_64_BIT_PTR_DEFINES" name="VK_DEFINE_NON_DISPATCHABLE_HANDLE">
+#ifndef VK_DEFINE_NON_DISPATCHABLE_HANDLE
+    #if (VK_USE_64_BIT_PTR_DEFINES==1)
+        #define VK_DEFINE_NON_DISPATCHABLE_HANDLE(object) typedef struct object##_T *object;
+    #else
+        #define VK_DEFINE_NON_DISPATCHABLE_HANDLE(object) typedef uint64_t object;
+    #endif
+#endif</type>
+    </types></registry>"#,
+    );
+    let code = match types[0].spec {
+        TypeSpec::Code(ref code) => code,
+        ref other => panic!("{:?}", other),
+    };
+
+    let expansions = code.define_expansions("VK_DEFINE_NON_DISPATCHABLE_HANDLE");
+    assert_eq!(expansions.len(), 2);
+    assert_eq!(
+        expansions[0].conditions,
+        vec![
+            ("!defined(VK_DEFINE_NON_DISPATCHABLE_HANDLE)", true),
+            ("(VK_USE_64_BIT_PTR_DEFINES==1)", true)
+        ]
+    );
+    assert_eq!(
+        expansions[0].define.expand(&["VkFence"]).as_deref(),
+        Some("typedef struct VkFence_T *VkFence;")
+    );
+    assert_eq!(
+        expansions[1].conditions,
+        vec![
+            ("!defined(VK_DEFINE_NON_DISPATCHABLE_HANDLE)", true),
+            ("(VK_USE_64_BIT_PTR_DEFINES==1)", false)
+        ]
+    );
+    assert_eq!(
+        expansions[1].define.expand(&["VkFence"]).as_deref(),
+        Some("typedef uint64_t VkFence;")
+    );
+    assert_eq!(expansions[1].define.expand(&[]), None);
+}
+
+#[test]
+fn test_define_literals() {
+    use vk_parse::{CLiteral, PreprocessorItem, TypeSpec};
+    let types = parse_types(
+        r#"<registry><types>
+        <type category="define">// Version of this file
+#define <name>VK_HEADER_VERSION</name> 250</type>
+        <type category="define">#define <name>VK_MAX_DEPTH</name> (1.0f)</type>
+        <type category="define">#define <name>VK_NAME</name> "a\"b"</type>
+        <type category="define">#define <name>VK_MASK</name> 0xFFFFFFFFULL</type>
+        <type category="define">#define <name>VK_EXPR</name> (~0U)</type>
+    </types></registry>"#,
+    );
+    let literals: Vec<_> = types
+        .iter()
+        .map(|t| match t.spec {
+            TypeSpec::Code(ref code) => code
+                .directives
+                .iter()
+                .find_map(|item| match *item {
+                    PreprocessorItem::Define(ref define) => Some(define.value.literal()),
+                    _ => None,
+                })
+                .unwrap(),
+            ref other => panic!("{:?}", other),
+        })
+        .collect();
+
+    assert_eq!(
+        literals[0],
+        Some(CLiteral::Integer {
+            value: 250,
+            radix: 10,
+            suffix: "".into()
+        })
+    );
+    assert_eq!(literals[1].as_ref().and_then(|l| l.as_f64()), Some(1.0));
+    assert_eq!(literals[2], Some(CLiteral::String("a\"b".into())));
+    assert_eq!(
+        literals[3],
+        Some(CLiteral::Integer {
+            value: 0xFFFFFFFF,
+            radix: 16,
+            suffix: "ULL".into()
+        })
+    );
+    assert_eq!(literals[4], None);
+}
+
+#[test]
+fn test_code_alternatives() {
+    use vk_parse::TypeSpec;
+    let types = parse_types(
+        r#"<registry><types>
+        <type category="basetype">#ifdef __OBJC__
+@protocol MTLDevice;
+typedef __unsafe_unretained id&lt;MTLDevice&gt; MTLDevice_id;
+#else
+typedef void* <name>MTLDevice_id</name>;
+#endif</type>
+        <type category="basetype">typedef <type>uint32_t</type> <name>VkBool32</name>;</type>
+    </types></registry>"#,
+    );
+    let alternatives: Vec<_> = types
+        .iter()
+        .map(|t| match t.spec {
+            TypeSpec::Code(ref code) => code
+                .code_alternatives()
+                .into_iter()
+                .map(|a| (a.conditions, a.code))
+                .collect::<Vec<_>>(),
+            ref other => panic!("{:?}", other),
+        })
+        .collect();
+
+    assert_eq!(
+        alternatives[0],
+        vec![
+            (
+                vec![("defined(__OBJC__)", true)],
+                String::from(
+                    "@protocol MTLDevice;\ntypedef __unsafe_unretained id<MTLDevice> MTLDevice_id;"
+                )
+            ),
+            (
+                vec![("defined(__OBJC__)", false)],
+                String::from("typedef void* MTLDevice_id;")
+            ),
+        ]
+    );
+    assert_eq!(
+        alternatives[1],
+        vec![(Vec::new(), String::from("typedef uint32_t VkBool32;"))]
+    );
+}
+
+#[test]
+fn test_include_types() {
+    use vk_parse::TypeSpec;
+    let types = parse_types(
+        r#"<registry><types>
+        <type category="include" name="vk_platform">#include "vk_platform.h"</type>
+        <type category="include" name="X11/Xlib.h">#include &lt;<name>X11/Xlib.h</name>&gt;</type>
+        <type category="include" name="wayland-client.h"/>
+    </types></registry>"#,
+    );
+    let includes: Vec<_> = types
+        .iter()
+        .map(|t| match t.spec {
+            TypeSpec::Code(ref code) => code.include().map(|i| (i.path.to_string(), i.system)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        includes,
+        vec![
+            Some((String::from("vk_platform.h"), false)),
+            Some((String::from("X11/Xlib.h"), true)),
+            None
+        ]
+    );
+}
+
+#[test]
+fn test_integer_attributes() {
+    use vk_parse::RegistryChild;
+    let xml = r#"<registry>
+        <extensions>
+            <extension name="VK_KHR_octal" number="010"/>
+            <extension name="VK_KHR_suffixed" number="12U"/>
+            <extension name="VK_KHR_negative" number="-3"/>
+        </extensions>
+        <formats>
+            <format name="VK_FORMAT_A" class="8-bit" blockSize="0x4" texelsPerBlock="+1"/>
+            <format name="VK_FORMAT_B" class="8-bit" blockSize="0x1G" texelsPerBlock="1"/>
+        </formats>
+    </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+
+    match registry.0[0] {
+        RegistryChild::Extensions(ref e) => {
+            let numbers: Vec<_> = e.children.iter().map(|e| e.number).collect();
+            assert_eq!(numbers, vec![Some(8), Some(12), Some(-3)]);
+        }
+        ref other => panic!("{:?}", other),
+    }
+    match registry.0[1] {
+        RegistryChild::Formats(ref f) => {
+            assert_eq!(f.children[0].blockSize, 4);
+            assert_eq!(f.children[0].texelsPerBlock, 1);
+        }
+        ref other => panic!("{:?}", other),
+    }
+    assert_eq!(errors.len(), 1);
+    match errors[0] {
+        vk_parse::Error::ParseIntError {
+            ref text, radix, ..
+        } => {
+            assert_eq!(text, "0x1G");
+            assert_eq!(radix, 16);
+        }
+        ref other => panic!("{:?}", other),
+    }
+}
+
+const VERSION_DEFINES: &str = r#"<registry><types>
+    <type category="define">// Vulkan 1.3 version number
+#define <name>VK_API_VERSION_1_3</name> <type>VK_MAKE_API_VERSION</type>(0, 1, 3, 0)// Patch version should always be set to 0</type>
+    <type category="define">// Version of this file
+#define <name>VK_HEADER_VERSION</name> 250</type>
+    <type category="define" requires="VK_HEADER_VERSION">// Complete version of this file
+#define <name>VK_HEADER_VERSION_COMPLETE</name> <type>VK_MAKE_API_VERSION</type>(0, 1, 3, VK_HEADER_VERSION)</type>
+</types></registry>"#;
+
+#[test]
+fn test_api_versions() {
+    use vk_parse::PackedVersion;
+    let (registry, _errors) = parse(VERSION_DEFINES, &ParseOptions::new()).unwrap();
+    assert_eq!(
+        registry.api_versions(),
+        vec![
+            ("VK_API_VERSION_1_3", PackedVersion::new(0, 1, 3, 0)),
+            (
+                "VK_HEADER_VERSION_COMPLETE",
+                PackedVersion::new(0, 1, 3, 250)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_header_version() {
+    use vk_parse::PackedVersion;
+    let (registry, _errors) = parse(VERSION_DEFINES, &ParseOptions::new()).unwrap();
+    assert_eq!(registry.header_version(), Some(250));
+    assert_eq!(
+        registry.header_version_complete(),
+        Some(PackedVersion::new(0, 1, 3, 250))
+    );
+
+    let (registry, _errors) = parse(SECTIONS, &ParseOptions::new()).unwrap();
+    assert_eq!(registry.header_version(), None);
+    assert_eq!(registry.header_version_complete(), None);
+}
+
+const PRETTY: &str = r#"<registry>
+<types>
+    <type category="basetype">typedef <type>uint32_t</type> <name>VkFlags</name>;</type>
+    <type category="handle"><type>VK_DEFINE_HANDLE</type>(<name>VkInstance</name>)</type>
+    <type category="struct" name="VkExtent2D">
+        <member><type>uint32_t</type> <name>width</name></member>
+        <member><type>uint32_t</type> <name>height</name></member>
+    </type>
+</types>
+<enums name="VkResult" type="enum">
+    <enum value="0" name="VK_SUCCESS"/>
+    <enum value="-1" name="VK_ERROR_OUT_OF_HOST_MEMORY"/>
+</enums>
+<commands>
+    <command>
+        <proto><type>void</type> <name>vkDestroyInstance</name></proto>
+        <param><type>VkInstance</type> <name>instance</name></param>
+    </command>
+</commands>
+<feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+    <require>
+        <type name="VkExtent2D"/>
+        <command name="vkDestroyInstance"/>
+    </require>
+</feature>
+</registry>"#;
+
+#[test]
+fn test_pretty_print() {
+    let (registry, errors) = parse(PRETTY, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(
+        registry.pretty_print(|_| true),
+        "types
+  VkFlags (basetype): typedef uint32_t VkFlags;
+  VkInstance (handle): VK_DEFINE_HANDLE(VkInstance)
+  VkExtent2D (struct)
+    uint32_t width
+    uint32_t height
+enums VkResult (enum)
+  VK_SUCCESS = 0
+  VK_ERROR_OUT_OF_HOST_MEMORY = -1
+commands
+  void vkDestroyInstance(VkInstance instance);
+feature VK_VERSION_1_0 (vulkan 1.0)
+  require
+    type VkExtent2D
+    command vkDestroyInstance
+"
+    );
+    assert_eq!(
+        registry.pretty_print(|name| name.starts_with("VK_ERROR")),
+        "enums VkResult (enum)
+  VK_ERROR_OUT_OF_HOST_MEMORY = -1
+"
+    );
+}
+
+#[test]
+fn test_registry_visitor() {
+    use vk_parse::{CommandDefinition, CommandParam, InterfaceItem, RegistryVisitor};
+
+    #[derive(Default)]
+    struct Names<'a>(Vec<String>, std::marker::PhantomData<&'a ()>);
+
+    impl<'a> RegistryVisitor<'a> for Names<'a> {
+        fn visit_type(&mut self, ty: &'a vk_parse::Type) {
+            if let Some(ref name) = ty.name {
+                self.0.push(format!("type {}", name));
+            }
+        }
+        fn visit_type_member(
+            &mut self,
+            _: &'a vk_parse::Type,
+            m: &'a vk_parse::TypeMemberDefinition,
+        ) {
+            self.0.push(format!("member {}", m));
+        }
+        fn visit_enum(&mut self, _: &'a vk_parse::Enums, e: &'a vk_parse::Enum) {
+            self.0.push(format!("enum {}", e.name));
+        }
+        fn visit_command_param(&mut self, c: &'a CommandDefinition, p: &'a CommandParam) {
+            self.0.push(format!("param {} {}", c.proto.name, p));
+        }
+        fn visit_feature(&mut self, f: &'a vk_parse::Feature) {
+            self.0.push(format!("feature {}", f.name));
+        }
+        fn visit_interface_item(&mut self, item: &'a InterfaceItem) {
+            if let InterfaceItem::Command { ref name, .. } = *item {
+                self.0.push(format!("require {}", name));
+            }
+        }
+    }
+
+    let (registry, _errors) = parse(PRETTY, &ParseOptions::new()).unwrap();
+    let mut names = Names::default();
+    vk_parse::walk(&registry, &mut names);
+    assert_eq!(
+        names.0,
+        [
+            "type VkExtent2D",
+            "member uint32_t width",
+            "member uint32_t height",
+            "enum VK_SUCCESS",
+            "enum VK_ERROR_OUT_OF_HOST_MEMORY",
+            "param vkDestroyInstance VkInstance instance",
+            "feature VK_VERSION_1_0",
+            "require vkDestroyInstance",
+        ]
+    );
+}
+
+#[test]
+fn test_registry_iterators() {
+    let (registry, _errors) = parse(PRETTY, &ParseOptions::new()).unwrap();
+    assert_eq!(registry.types().count(), 3);
+    assert_eq!(
+        registry
+            .types()
+            .filter_map(|t| t.name.as_deref())
+            .collect::<Vec<_>>(),
+        ["VkExtent2D"]
+    );
+    assert_eq!(registry.commands().count(), 1);
+    assert_eq!(
+        registry
+            .enums_blocks()
+            .map(|e| e.children.len())
+            .collect::<Vec<_>>(),
+        [2]
+    );
+    assert_eq!(
+        registry.features().map(|f| &*f.name).collect::<Vec<_>>(),
+        ["VK_VERSION_1_0"]
+    );
+    assert_eq!(registry.extensions().count(), 0);
+}
+
+#[test]
+fn test_shared_registry() {
+    let (registry, _errors) = parse(PRETTY, &ParseOptions::new()).unwrap();
+    let shared = vk_parse::SharedRegistry::new(registry);
+    let clone = shared.clone();
+    let handle = std::thread::spawn(move || {
+        clone
+            .get_type("VkInstance")
+            .map(|t| t.category.as_deref() == Some("handle"))
+    });
+    assert_eq!(handle.join().unwrap(), Some(true));
+
+    assert!(shared.get_type("VkExtent2D").is_some());
+    assert!(shared.get_type("VkUnknown").is_none());
+    assert!(shared.get_command("vkDestroyInstance").is_some());
+    assert_eq!(shared.get_enums("VkResult").unwrap().children.len(), 2);
+    match shared.get_enum("VK_ERROR_OUT_OF_HOST_MEMORY").unwrap().spec {
+        vk_parse::EnumSpec::Value { ref value, .. } => assert_eq!(&**value, "-1"),
+        ref other => panic!("{:?}", other),
+    }
+    assert!(shared.get_feature("VK_VERSION_1_0").is_some());
+    assert!(shared.get_extension("VK_KHR_surface").is_none());
+    assert_eq!(shared.commands().count(), 1);
+}
+
+#[test]
+fn test_hash_and_ord() {
+    use std::collections::{BTreeSet, HashSet};
+
+    let (registry, _errors) = parse(PRETTY, &ParseOptions::new()).unwrap();
+    let mut types: Vec<_> = registry.types().cloned().collect();
+    types.extend(registry.types().cloned());
+    assert_eq!(types.iter().collect::<HashSet<_>>().len(), 3);
+    assert_eq!(types.iter().collect::<BTreeSet<_>>().len(), 3);
+
+    let names: Vec<_> = registry.commands().map(|c| c.name()).collect();
+    assert_eq!(names, ["vkDestroyInstance"]);
+    let mut registries = [registry.clone(), vk_parse::Registry::default()];
+    registries.sort();
+    assert_eq!(registries[1], registry);
+}
+
+#[test]
+fn test_merge() {
+    use vk_parse::{MergeItemKind, MergePolicy};
+
+    let (base, _errors) = parse(PRETTY, &ParseOptions::new()).unwrap();
+    let (other, _errors) = parse(
+        r#"<registry>
+        <types>
+            <comment>Changed and new types</comment>
+            <type category="struct" name="VkExtent2D">
+                <member><type>uint64_t</type> <name>width</name></member>
+            </type>
+            <type category="struct" name="VkOffset2D">
+                <member><type>int32_t</type> <name>x</name></member>
+            </type>
+        </types>
+        <enums name="VkResult" type="enum">
+            <enum value="0" name="VK_SUCCESS"/>
+            <enum value="-1" name="VK_ERROR_OUT_OF_HOST_MEMORY"/>
+        </enums>
+        </registry>"#,
+        &ParseOptions::new(),
+    )
+    .unwrap();
+    let width_type = |registry: &vk_parse::Registry| {
+        registry
+            .types()
+            .filter(|t| t.name.as_deref() == Some("VkExtent2D"))
+            .map(|t| match t.spec {
+                vk_parse::TypeSpec::Members(ref members) => match members[0] {
+                    vk_parse::TypeMember::Definition(ref def) => def.to_string(),
+                    _ => panic!(),
+                },
+                _ => panic!(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut merged = base.clone();
+    let conflicts = merged.merge(other.clone(), MergePolicy::KeepExisting);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, MergeItemKind::Type);
+    assert_eq!(&*conflicts[0].name, "VkExtent2D");
+    assert_eq!(merged.types().count(), 4);
+    assert_eq!(merged.enums_blocks().count(), 1);
+    assert_eq!(width_type(&merged), ["uint32_t width"]);
+
+    let mut merged = base.clone();
+    merged.merge(other.clone(), MergePolicy::Replace);
+    assert_eq!(merged.types().count(), 4);
+    assert_eq!(width_type(&merged), ["uint64_t width"]);
+
+    let mut merged = base.clone();
+    merged.merge(other.clone(), MergePolicy::KeepBoth);
+    assert_eq!(merged.types().count(), 5);
+    assert_eq!(width_type(&merged), ["uint32_t width", "uint64_t width"]);
+
+    let mut merged = base.clone();
+    assert_eq!(merged.merge(base.clone(), MergePolicy::KeepBoth), []);
+    assert_eq!(merged, base);
+
+    let command = |api: &str, param: &str| {
+        format!(
+            r#"<registry><commands><command api="{}"><proto><type>void</type> <name>vkCmdFoo</name></proto><param><type>{}</type> <name>x</name></param></command></commands></registry>"#,
+            api, param
+        )
+    };
+    let (mut merged, _errors) =
+        parse(&command("vulkan", "uint32_t"), &ParseOptions::new()).unwrap();
+    let (other, _errors) = parse(&command("vulkansc", "uint64_t"), &ParseOptions::new()).unwrap();
+    assert_eq!(merged.merge(other, MergePolicy::KeepExisting), []);
+    assert_eq!(merged.commands().count(), 2);
+    let (other, _errors) = parse(&command("vulkansc", "int64_t"), &ParseOptions::new()).unwrap();
+    let conflicts = merged.merge(other, MergePolicy::KeepExisting);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, MergeItemKind::Command);
+    assert_eq!(conflicts[0].api, Some(apis("vulkansc")));
+}
+
+#[test]
+fn test_diff() {
+    use vk_parse::Change;
+
+    let (old, _errors) = parse(PRETTY, &ParseOptions::new()).unwrap();
+    let (new, _errors) = parse(
+        r#"<registry>
+        <types>
+            <type category="basetype">typedef <type>uint32_t</type> <name>VkFlags</name>;</type>
+            <type category="struct" name="VkExtent2D">
+                <member><type>uint32_t</type> <name>width</name></member>
+                <member><type>uint32_t</type> <name>depth</name></member>
+            </type>
+        </types>
+        <enums name="VkResult" type="enum">
+            <enum value="0" name="VK_SUCCESS"/>
+            <enum value="-2" name="VK_ERROR_OUT_OF_HOST_MEMORY"/>
+        </enums>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require>
+                <type name="VkExtent2D"/>
+                <command name="vkDestroyInstance"/>
+            </require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_surface" number="1" supported="vulkan">
+                <require>
+                    <enum value="25" name="VK_KHR_SURFACE_SPEC_VERSION"/>
+                    <enum offset="0" extends="VkResult" dir="-" name="VK_ERROR_SURFACE_LOST_KHR"/>
+                </require>
+            </extension>
+        </extensions>
+        </registry>"#,
+        &ParseOptions::new(),
+    )
+    .unwrap();
+
+    assert!(vk_parse::diff(&old, &old).is_empty());
+    let diff = vk_parse::diff(&old, &new);
+
+    let types: Vec<_> = diff
+        .types
+        .iter()
+        .map(|t| match t.change {
+            Change::Removed(ty) => format!("-{}", ty.declared_name().unwrap()),
+            Change::Changed { new, .. } => format!("~{}", new.declared_name().unwrap()),
+            Change::Added(ty) => format!("+{}", ty.declared_name().unwrap()),
+        })
+        .collect();
+    assert_eq!(types, ["-VkInstance", "~VkExtent2D"]);
+    let members: Vec<_> = diff.types[1]
+        .members
+        .iter()
+        .map(|m| match *m {
+            Change::Removed(m) => format!("-{}", m.name().unwrap()),
+            Change::Added(m) => format!("+{}", m.name().unwrap()),
+            Change::Changed { new, .. } => format!("~{}", new.name().unwrap()),
+        })
+        .collect();
+    assert_eq!(members, ["-height", "+depth"]);
+
+    match diff.commands[..] {
+        [Change::Removed(c)] => assert_eq!(c.name(), "vkDestroyInstance"),
+        ref other => panic!("{:?}", other),
+    }
+    let enums: Vec<_> = diff
+        .enums
+        .iter()
+        .map(|e| (e.before().is_some(), &*e.after().unwrap().name))
+        .collect();
+    assert_eq!(
+        enums,
+        [
+            (true, "VK_ERROR_OUT_OF_HOST_MEMORY"),
+            (false, "VK_ERROR_SURFACE_LOST_KHR")
+        ]
+    );
+    match diff.constants[..] {
+        [Change::Added(e)] => assert_eq!(&*e.name, "VK_KHR_SURFACE_SPEC_VERSION"),
+        ref other => panic!("{:?}", other),
+    }
+    assert!(diff.features.is_empty());
+    assert_eq!(diff.extensions.len(), 1);
+
+    let command =
+        r#"<command api="vulkan"><proto><type>void</type> <name>vkCmdFoo</name></proto></command>"#;
+    let (old, _errors) = parse(
+        &format!("<registry><commands>{}</commands></registry>", command),
+        &ParseOptions::new(),
+    )
+    .unwrap();
+    let (new, _errors) = parse(
+        &format!(
+            "<registry><commands>{}{}</commands></registry>",
+            command,
+            command.replace(r#"api="vulkan""#, r#"api="vulkansc""#)
+        ),
+        &ParseOptions::new(),
+    )
+    .unwrap();
+    match vk_parse::diff(&old, &new).commands[..] {
+        [Change::Added(c)] => assert_eq!(c.api(), Some(&apis("vulkansc"))),
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_changelog() {
+    let (old, _errors) = parse(
+        r#"<registry>
+        <enums name="VkResult" type="enum"><enum value="0" name="VK_SUCCESS"/></enums>
+        <extensions>
+            <extension name="VK_KHR_old" number="1" supported="vulkan"/>
+        </extensions>
+        </registry>"#,
+        &ParseOptions::new(),
+    )
+    .unwrap();
+    let (new, _errors) = parse(
+        r#"<registry>
+        <enums name="VkResult" type="enum">
+            <enum value="0" name="VK_SUCCESS"/>
+            <enum value="1" name="VK_NOT_READY"/>
+        </enums>
+        <extensions>
+            <extension name="VK_KHR_old" number="1" supported="vulkan"
+                promotedto="VK_VERSION_1_1" deprecatedby="VK_KHR_new"/>
+            <extension name="VK_KHR_new" number="2" supported="vulkan" provisional="true">
+                <require><enum value="1" name="VK_KHR_NEW_SPEC_VERSION"/></require>
+            </extension>
+        </extensions>
+        </registry>"#,
+        &ParseOptions::new(),
+    )
+    .unwrap();
+    assert_eq!(vk_parse::changelog(&old, &old), "");
+    assert_eq!(
+        vk_parse::changelog(&old, &new),
+        "## New extensions
+
+- `VK_KHR_new` (provisional)
+
+## Promoted extensions
+
+- `VK_KHR_old` to `VK_VERSION_1_1`
+
+## Deprecated extensions
+
+- `VK_KHR_old` by `VK_KHR_new`
+
+## New enum values
+
+- `VK_NOT_READY` in `VkResult`
+
+## New constants
+
+- `VK_KHR_NEW_SPEC_VERSION`
+"
+    );
+}
+
+#[test]
+fn test_skip_disabled_extensions() {
+    let xml = r#"<registry><extensions>
+        <extension name="VK_KHR_surface" number="1" supported="vulkan">
+            <require><command name="vkDestroySurfaceKHR"/></require>
+        </extension>
+        <extension name="VK_KHR_reserved" number="2" supported="disabled">
+            <require><enum value="0" name="VK_KHR_RESERVED_SPEC_VERSION"/></require>
+        </extension>
+        </extensions></registry>"#;
+    let children = |options: &ParseOptions| {
+        let (registry, errors) = parse(xml, options).unwrap();
+        assert_eq!(errors, []);
+        registry
+            .extensions()
+            .map(|e| (e.is_disabled(), e.children.len()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(children(&ParseOptions::new()), [(false, 1), (true, 1)]);
+    assert_eq!(
+        children(&ParseOptions::new().skip_disabled_extensions(true)),
+        [(false, 1), (true, 0)]
+    );
+}
+
+#[test]
+fn test_provisional_extensions() {
+    let xml = r#"<registry>
+        <platforms>
+            <platform name="provisional" protect="VK_ENABLE_BETA_EXTENSIONS"/>
+        </platforms>
+        <types>
+            <type category="struct" name="VkStable"/>
+            <type category="struct" name="VkShared"/>
+            <type category="struct" name="VkBeta"/>
+        </types>
+        <commands>
+            <command><proto><type>void</type> <name>vkBeta</name></proto></command>
+        </commands>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require><type name="VkStable"/><type name="VkShared"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_beta" number="1" supported="vulkan" provisional="true"
+                platform="provisional">
+                <require>
+                    <type name="VkShared"/>
+                    <type name="VkBeta"/>
+                    <command name="vkBeta"/>
+                </require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (mut registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let provisional: Vec<_> = registry.provisional_extensions().collect();
+    assert_eq!(provisional.len(), 1);
+    assert_eq!(
+        registry.extension_protect(provisional[0]),
+        Some("VK_ENABLE_BETA_EXTENSIONS")
+    );
+
+    let removed = registry.remove_provisional_extensions();
+    assert_eq!(removed.len(), 1);
+    assert_eq!(registry.extensions().count(), 0);
+    assert_eq!(
+        registry
+            .types()
+            .filter_map(|t| t.name.as_deref())
+            .collect::<Vec<_>>(),
+        ["VkStable", "VkShared"]
+    );
+    assert_eq!(registry.commands().count(), 0);
+}
+
+#[test]
+fn test_extensions_by_platform() {
+    let xml = r#"<registry>
+        <platforms>
+            <platform name="xlib" protect="VK_USE_PLATFORM_XLIB_KHR"/>
+            <platform name="win32" protect="VK_USE_PLATFORM_WIN32_KHR"/>
+        </platforms>
+        <extensions>
+            <extension name="VK_KHR_surface" number="1" supported="vulkan">
+                <require>
+                    <type name="VkSurfaceKHR"/>
+                    <command name="vkDestroySurfaceKHR"/>
+                </require>
+            </extension>
+            <extension name="VK_KHR_xlib_surface" number="5" supported="vulkan" platform="xlib">
+                <require>
+                    <type name="VkSurfaceKHR"/>
+                    <command name="vkCreateXlibSurfaceKHR"/>
+                </require>
+                <require><type name="VkSurfaceKHR"/></require>
+            </extension>
+            <extension name="VK_FOO_bar" number="6" supported="vulkan" platform="foo"/>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let groups = registry.extensions_by_platform();
+    let summary: Vec<_> = groups
+        .iter()
+        .map(|g| {
+            (
+                g.platform,
+                g.protect,
+                g.extensions.iter().map(|e| &*e.name).collect::<Vec<_>>(),
+                g.types.clone(),
+                g.commands.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                None,
+                None,
+                vec!["VK_KHR_surface"],
+                vec!["VkSurfaceKHR"],
+                vec!["vkDestroySurfaceKHR"]
+            ),
+            (
+                Some("xlib"),
+                Some("VK_USE_PLATFORM_XLIB_KHR"),
+                vec!["VK_KHR_xlib_surface"],
+                vec!["VkSurfaceKHR"],
+                vec!["vkCreateXlibSurfaceKHR"]
+            ),
+            (Some("foo"), None, vec!["VK_FOO_bar"], vec![], vec![]),
+        ]
+    );
+}
+
+#[test]
+fn test_extensions_by_vendor() {
+    let xml = r#"<registry>
+        <tags>
+            <tag name="KHR" author="Khronos" contact="-"/>
+            <tag name="EXT" author="Multivendor" contact="-"/>
+            <tag name="NV" author="NVIDIA Corporation" contact="-"/>
+        </tags>
+        <extensions>
+            <extension name="VK_KHR_surface" number="1" supported="vulkan"/>
+            <extension name="VK_NV_foo" number="2" supported="vulkan" promotedto="VK_KHR_foo"/>
+            <extension name="VK_KHR_foo" number="3" supported="vulkan"/>
+            <extension name="VK_ACME_bar" number="4" supported="vulkan" author="ACME"/>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let summary: Vec<_> = registry
+        .extensions_by_vendor()
+        .iter()
+        .map(|g| (g.tag, g.author, g.extensions.len(), g.promoted))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("KHR", Some("Khronos"), 2, 0),
+            ("NV", Some("NVIDIA Corporation"), 1, 1),
+            ("ACME", None, 1, 0),
+        ]
+    );
+}
+
+#[test]
+fn test_command_aliases() {
+    let xml = r#"<registry>
+        <commands>
+            <command><proto><type>void</type> <name>vkGetPhysicalDeviceFeatures2</name></proto><param><type>VkPhysicalDevice</type> <name>physicalDevice</name></param></command>
+            <command name="vkGetPhysicalDeviceFeatures2KHR" alias="vkGetPhysicalDeviceFeatures2"/>
+            <command name="vkGetPhysicalDeviceFeatures2ACME" alias="vkGetPhysicalDeviceFeatures2KHR"/>
+        </commands>
+        <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+            <require><command name="vkGetPhysicalDeviceFeatures2"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_get_physical_device_properties2" number="60" supported="vulkan">
+                <require><command name="vkGetPhysicalDeviceFeatures2KHR"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let group = registry
+        .command_aliases("vkGetPhysicalDeviceFeatures2ACME")
+        .unwrap();
+    assert_eq!(
+        &*group.definition.proto.name,
+        "vkGetPhysicalDeviceFeatures2"
+    );
+    let names: Vec<_> = group
+        .names
+        .iter()
+        .map(|n| (n.name, n.required_by.clone()))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("vkGetPhysicalDeviceFeatures2", vec!["VK_VERSION_1_1"]),
+            (
+                "vkGetPhysicalDeviceFeatures2KHR",
+                vec!["VK_KHR_get_physical_device_properties2"]
+            ),
+            ("vkGetPhysicalDeviceFeatures2ACME", vec![]),
+        ]
+    );
+    assert!(registry.command_aliases("vkMissing").is_none());
+}
+
+#[test]
+fn test_materialize_enums() {
+    use vk_parse::{EnumSpec, EnumsChild};
+
+    let xml = r#"<registry>
+        <enums name="VkResult" type="enum">
+            <enum value="0" name="VK_SUCCESS"/>
+        </enums>
+        <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+            <require>
+                <enum extends="VkResult" extnumber="70" offset="0" dir="-" name="VK_ERROR_OUT_OF_POOL_MEMORY"/>
+                <enum name="VK_SUCCESS"/>
+            </require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_maintenance1" number="70" supported="vulkan">
+                <require>
+                    <enum extends="VkResult" extnumber="70" offset="0" dir="-" name="VK_ERROR_OUT_OF_POOL_MEMORY"/>
+                    <enum alias="VK_ERROR_OUT_OF_POOL_MEMORY" extends="VkResult" name="VK_ERROR_OUT_OF_POOL_MEMORY_KHR"/>
+                </require>
+            </extension>
+            <extension name="VK_KHR_swapchain" number="2" supported="vulkan">
+                <require api="vulkan">
+                    <enum offset="4" extends="VkResult" name="VK_SUBOPTIMAL_KHR"/>
+                    <enum offset="0" extends="VkUnknown" name="VK_UNKNOWN_KHR"/>
+                </require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (mut registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    registry.materialize_enums();
+
+    let enums = registry.enums_blocks().next().unwrap();
+    let values: Vec<_> = enums
+        .children
+        .iter()
+        .filter_map(|child| match *child {
+            EnumsChild::Enum(ref e) => Some((
+                &*e.name,
+                match e.spec {
+                    EnumSpec::Value { ref value, .. } => &**value,
+                    EnumSpec::Alias { ref alias, .. } => &**alias,
+                    _ => "",
+                },
+                e.api.as_ref(),
+                e.required_by.as_deref(),
+            )),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        values,
+        [
+            ("VK_SUCCESS", "0", None, None),
+            (
+                "VK_ERROR_OUT_OF_POOL_MEMORY",
+                "-1000069000",
+                None,
+                Some("VK_VERSION_1_1")
+            ),
+            (
+                "VK_ERROR_OUT_OF_POOL_MEMORY_KHR",
+                "VK_ERROR_OUT_OF_POOL_MEMORY",
+                None,
+                Some("VK_KHR_maintenance1")
+            ),
+            (
+                "VK_SUBOPTIMAL_KHR",
+                "1000001004",
+                Some(&apis("vulkan")),
+                Some("VK_KHR_swapchain")
+            ),
+        ]
+    );
+
+    // Moved enums are left as references, others in place.
+    let references: Vec<_> = registry
+        .extensions()
+        .flat_map(|ext| ext.children.iter())
+        .flat_map(|child| match *child {
+            vk_parse::ExtensionChild::Require { ref items, .. } => items.iter(),
+            _ => [].iter(),
+        })
+        .filter_map(|item| match *item {
+            vk_parse::InterfaceItem::Enum(ref e) => Some((&*e.name, e.spec == EnumSpec::None)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        references,
+        [
+            ("VK_ERROR_OUT_OF_POOL_MEMORY", true),
+            ("VK_ERROR_OUT_OF_POOL_MEMORY_KHR", true),
+            ("VK_SUBOPTIMAL_KHR", true),
+            ("VK_UNKNOWN_KHR", false),
+        ]
+    );
+}
+
+#[test]
+fn test_handle_lifetimes() {
+    let xml = r#"<registry>
+        <types>
+            <type category="handle"><type>VK_DEFINE_HANDLE</type>(<name>VkDevice</name>)</type>
+            <type category="handle" parent="VkDevice"><type>VK_DEFINE_HANDLE</type>(<name>VkQueue</name>)</type>
+            <type category="handle" parent="VkDevice"><type>VK_DEFINE_NON_DISPATCHABLE_HANDLE</type>(<name>VkCommandPool</name>)</type>
+            <type category="handle" parent="VkCommandPool"><type>VK_DEFINE_HANDLE</type>(<name>VkCommandBuffer</name>)</type>
+            <type category="struct" name="VkAllocationCallbacks"><member><type>void</type>* <name>pUserData</name></member></type>
+            <type category="struct" name="VkCommandBufferAllocateInfo"><member><type>VkCommandPool</type> <name>commandPool</name></member></type>
+        </types>
+        <commands>
+            <command><proto><type>VkResult</type> <name>vkCreateCommandPool</name></proto><param><type>VkDevice</type> <name>device</name></param><param>const <type>VkAllocationCallbacks</type>* <name>pAllocator</name></param><param><type>VkCommandPool</type>* <name>pCommandPool</name></param></command>
+            <command><proto><type>void</type> <name>vkDestroyCommandPool</name></proto><param><type>VkDevice</type> <name>device</name></param><param><type>VkCommandPool</type> <name>commandPool</name></param><param>const <type>VkAllocationCallbacks</type>* <name>pAllocator</name></param></command>
+            <command><proto><type>VkResult</type> <name>vkAllocateCommandBuffers</name></proto><param><type>VkDevice</type> <name>device</name></param><param>const <type>VkCommandBufferAllocateInfo</type>* <name>pAllocateInfo</name></param><param><type>VkCommandBuffer</type>* <name>pCommandBuffers</name></param></command>
+            <command><proto><type>void</type> <name>vkFreeCommandBuffers</name></proto><param><type>VkDevice</type> <name>device</name></param><param><type>VkCommandPool</type> <name>commandPool</name></param><param><type>uint32_t</type> <name>commandBufferCount</name></param><param>const <type>VkCommandBuffer</type>* <name>pCommandBuffers</name></param></command>
+            <command><proto><type>void</type> <name>vkCmdExecuteCommands</name></proto><param><type>VkCommandBuffer</type> <name>commandBuffer</name></param><param><type>uint32_t</type> <name>commandBufferCount</name></param><param>const <type>VkCommandBuffer</type>* <name>pCommandBuffers</name></param></command>
+            <command><proto><type>void</type> <name>vkGetDeviceQueue</name></proto><param><type>VkDevice</type> <name>device</name></param><param><type>uint32_t</type> <name>queueIndex</name></param><param><type>VkQueue</type>* <name>pQueue</name></param></command>
+        </commands>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let lifetimes: Vec<_> = registry
+        .handle_lifetimes()
+        .iter()
+        .map(|l| {
+            let names = |commands: &[&vk_parse::CommandDefinition]| {
+                commands
+                    .iter()
+                    .map(|c| c.proto.name.to_string())
+                    .collect::<Vec<_>>()
+            };
+            (l.name, names(&l.create), names(&l.destroy))
+        })
+        .collect();
+    assert_eq!(
+        lifetimes,
+        [
+            ("VkDevice", vec![], vec![]),
+            ("VkQueue", vec![], vec![]),
+            (
+                "VkCommandPool",
+                vec!["vkCreateCommandPool".to_string()],
+                vec!["vkDestroyCommandPool".to_string()]
+            ),
+            (
+                "VkCommandBuffer",
+                vec!["vkAllocateCommandBuffers".to_string()],
+                vec!["vkFreeCommandBuffers".to_string()]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_array_counts() {
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkSubmitInfo">
+                <member><type>uint32_t</type> <name>waitSemaphoreCount</name></member>
+                <member len="waitSemaphoreCount">const <type>VkSemaphore</type>* <name>pWaitSemaphores</name></member>
+                <member len="waitSemaphoreCount">const <type>VkPipelineStageFlags</type>* <name>pWaitDstStageMask</name></member>
+                <member><type>uint32_t</type> <name>enabledLayerCount</name></member>
+                <member len="enabledLayerCount,null-terminated">const <type>char</type>* const* <name>ppEnabledLayerNames</name></member>
+                <member len="codeSize / 4">const <type>uint32_t</type>* <name>pCode</name></member>
+            </type>
+        </types>
+        <commands>
+            <command><proto><type>VkResult</type> <name>vkEnumerateInstanceExtensionProperties</name></proto><param optional="true" len="null-terminated">const <type>char</type>* <name>pLayerName</name></param><param optional="false,true"><type>uint32_t</type>* <name>pPropertyCount</name></param><param optional="true" len="pPropertyCount"><type>VkExtensionProperties</type>* <name>pProperties</name></param></command>
+        </commands>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let ty = registry.types().next().unwrap();
+    let counts: Vec<_> = ty
+        .array_counts()
+        .iter()
+        .map(|c| {
+            let arrays: Vec<_> = c.arrays.iter().map(|a| a.name().unwrap()).collect();
+            (c.count.name().unwrap(), arrays)
+        })
+        .collect();
+    assert_eq!(
+        counts,
+        [
+            (
+                "waitSemaphoreCount",
+                vec!["pWaitSemaphores", "pWaitDstStageMask"]
+            ),
+            ("enabledLayerCount", vec!["ppEnabledLayerNames"]),
+        ]
+    );
+
+    let command = match registry.commands().next().unwrap() {
+        vk_parse::Command::Definition(ref def) => def,
+        _ => panic!(),
+    };
+    let counts: Vec<_> = command
+        .array_counts()
+        .iter()
+        .map(|c| {
+            let arrays: Vec<_> = c.arrays.iter().map(|a| &*a.definition.name).collect();
+            (&*c.count.definition.name, arrays)
+        })
+        .collect();
+    assert_eq!(counts, [("pPropertyCount", vec!["pProperties"])]);
+}
+
+#[test]
+fn test_param_directions() {
+    use vk_parse::ParamDirection::*;
+
+    let xml = r#"<registry>
+        <commands>
+            <command><proto><type>VkResult</type> <name>vkEnumerateInstanceExtensionProperties</name></proto><param optional="true" len="null-terminated">const <type>char</type>* <name>pLayerName</name></param><param optional="false,true"><type>uint32_t</type>* <name>pPropertyCount</name></param><param optional="true" len="pPropertyCount"><type>VkExtensionProperties</type>* <name>pProperties</name></param></command>
+            <command><proto><type>VkResult</type> <name>vkMapMemory</name></proto><param><type>VkDevice</type> <name>device</name></param><param><type>VkDeviceMemory</type> <name>memory</name></param><param><type>VkDeviceSize</type> <name>offset</name></param><param><type>void</type>** <name>ppData</name></param></command>
+            <command><proto><type>VkResult</type> <name>vkCreateInstance</name></proto><param>const <type>VkInstanceCreateInfo</type>* <name>pCreateInfo</name></param><param optional="true">const <type>VkAllocationCallbacks</type>* <name>pAllocator</name></param><param><type>VkInstance</type>* <name>pInstance</name></param></command>
+        </commands>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let directions: Vec<_> = registry
+        .commands()
+        .map(|command| match *command {
+            vk_parse::Command::Definition(ref def) => def.param_directions(),
+            _ => panic!(),
+        })
+        .collect();
+    assert_eq!(
+        directions,
+        [
+            vec![Input, InOut, Output],
+            vec![Input, Input, Input, Output],
+            vec![Input, Input, Output],
+        ]
+    );
+}
+
+#[test]
+fn test_two_call_enumerations() {
+    let xml = r#"<registry>
+        <commands>
+            <command><proto><type>VkResult</type> <name>vkEnumerateInstanceExtensionProperties</name></proto><param optional="true" len="null-terminated">const <type>char</type>* <name>pLayerName</name></param><param optional="false,true"><type>uint32_t</type>* <name>pPropertyCount</name></param><param optional="true" len="pPropertyCount"><type>VkExtensionProperties</type>* <name>pProperties</name></param></command>
+            <command><proto><type>VkResult</type> <name>vkEnumeratePhysicalDeviceQueueFamilyPerformanceQueryCountersKHR</name></proto><param><type>VkPhysicalDevice</type> <name>physicalDevice</name></param><param><type>uint32_t</type> <name>queueFamilyIndex</name></param><param optional="false,true"><type>uint32_t</type>* <name>pCounterCount</name></param><param optional="true" len="pCounterCount"><type>VkPerformanceCounterKHR</type>* <name>pCounters</name></param><param optional="true" len="pCounterCount"><type>VkPerformanceCounterDescriptionKHR</type>* <name>pCounterDescriptions</name></param></command>
+            <command><proto><type>VkResult</type> <name>vkAllocateCommandBuffers</name></proto><param><type>VkDevice</type> <name>device</name></param><param>const <type>VkCommandBufferAllocateInfo</type>* <name>pAllocateInfo</name></param><param len="pAllocateInfo-&gt;commandBufferCount"><type>VkCommandBuffer</type>* <name>pCommandBuffers</name></param></command>
+            <command><proto><type>void</type> <name>vkCmdBindVertexBuffers</name></proto><param><type>VkCommandBuffer</type> <name>commandBuffer</name></param><param><type>uint32_t</type> <name>bindingCount</name></param><param len="bindingCount">const <type>VkBuffer</type>* <name>pBuffers</name></param></command>
+        </commands>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let enumerations: Vec<_> = registry
+        .two_call_enumerations()
+        .iter()
+        .map(|e| {
+            let arrays: Vec<_> = e.arrays.iter().map(|a| &*a.definition.name).collect();
+            (&*e.command.proto.name, &*e.count.definition.name, arrays)
+        })
+        .collect();
+    assert_eq!(
+        enumerations,
+        [
+            (
+                "vkEnumerateInstanceExtensionProperties",
+                "pPropertyCount",
+                vec!["pProperties"]
+            ),
+            (
+                "vkEnumeratePhysicalDeviceQueueFamilyPerformanceQueryCountersKHR",
+                "pCounterCount",
+                vec!["pCounters", "pCounterDescriptions"]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_extension_interactions() {
+    let xml = r#"<registry>
+        <extensions>
+            <extension name="VK_KHR_swapchain" number="2" supported="vulkan">
+                <require>
+                    <command name="vkCreateSwapchainKHR"/>
+                </require>
+                <require feature="VK_VERSION_1_1">
+                    <command name="vkGetDeviceGroupPresentCapabilitiesKHR"/>
+                </require>
+                <require extension="VK_KHR_device_group" comment="Interaction">
+                    <command name="vkAcquireNextImage2KHR"/>
+                </require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let extension = registry.extensions().next().unwrap();
+    let interactions: Vec<_> = extension
+        .interactions()
+        .iter()
+        .map(|i| (i.extension, i.feature, i.comment, i.items.len()))
+        .collect();
+    assert_eq!(
+        interactions,
+        [
+            (None, Some("VK_VERSION_1_1"), None, 1),
+            (Some("VK_KHR_device_group"), None, Some("Interaction"), 1),
+        ]
+    );
+}
+
+#[test]
+fn test_api_differences() {
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkPipelineCacheCreateInfo" api="vulkan"><member><type>size_t</type> <name>initialDataSize</name></member></type>
+            <type category="struct" name="VkPipelineCacheCreateInfo" api="vulkansc"><member><type>size_t</type> <name>initialDataSize</name></member><member>const <type>void</type>* <name>pInitialData</name></member></type>
+        </types>
+        <enums name="VkPipelineCacheHeaderVersion" type="enum">
+            <enum value="1" name="VK_PIPELINE_CACHE_HEADER_VERSION_ONE"/>
+            <enum api="vulkansc" value="1000298001" name="VK_PIPELINE_CACHE_HEADER_VERSION_SAFETY_CRITICAL_ONE"/>
+        </enums>
+        <feature api="vulkan,vulkansc" name="VK_VERSION_1_0" number="1.0">
+            <require>
+                <type name="VkPipelineCacheCreateInfo"/>
+                <command name="vkCreatePipelineCache"/>
+                <command name="vkMergePipelineCaches"/>
+            </require>
+        </feature>
+        <feature api="vulkansc" name="VKSC_VERSION_1_0" number="1.0">
+            <remove>
+                <command name="vkMergePipelineCaches"/>
+            </remove>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_surface" number="1" supported="vulkan,vulkansc">
+                <require><command name="vkDestroySurfaceKHR"/></require>
+            </extension>
+            <extension name="VK_KHR_portability_subset" number="164" supported="vulkan">
+                <require><type name="VkPhysicalDevicePortabilitySubsetFeaturesKHR"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let differences = registry.api_differences("vulkan", "vulkansc");
+    assert_eq!(differences.only_first.commands, ["vkMergePipelineCaches"]);
+    assert_eq!(
+        differences.only_first.types,
+        ["VkPhysicalDevicePortabilitySubsetFeaturesKHR"]
+    );
+    assert_eq!(
+        differences.only_first.extensions,
+        ["VK_KHR_portability_subset"]
+    );
+    assert_eq!(
+        differences.only_second.enums,
+        ["VK_PIPELINE_CACHE_HEADER_VERSION_SAFETY_CRITICAL_ONE"]
+    );
+    assert!(differences.only_second.commands.is_empty());
+    let altered: Vec<_> = differences
+        .altered_types
+        .iter()
+        .map(|&(a, b)| (a.api.as_ref(), b.api.as_ref()))
+        .collect();
+    assert_eq!(altered, [(Some(&apis("vulkan")), Some(&apis("vulkansc")))]);
+    assert!(differences.altered_enums.is_empty());
+}
+
+#[test]
+fn test_partition_by_platform() {
+    let xml = r#"<registry>
+        <platforms>
+            <platform name="xlib" protect="VK_USE_PLATFORM_XLIB_KHR"/>
+            <platform name="win32" protect="VK_USE_PLATFORM_WIN32_KHR"/>
+        </platforms>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require><type name="VkInstance"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_win32_surface" number="10" supported="vulkan" platform="win32">
+                <require>
+                    <enum value="6" name="VK_KHR_WIN32_SURFACE_SPEC_VERSION"/>
+                    <type name="VkSurfaceKHR"/>
+                    <type name="VkWin32SurfaceCreateInfoKHR"/>
+                    <command name="vkCreateWin32SurfaceKHR"/>
+                </require>
+            </extension>
+            <extension name="VK_KHR_surface" number="1" supported="vulkan">
+                <require><type name="VkSurfaceKHR"/></require>
+            </extension>
+            <extension name="VK_EXT_beta" number="2" supported="vulkan" protect="VK_ENABLE_BETA_EXTENSIONS">
+                <require><command name="vkBetaEXT"/></require>
+            </extension>
+            <extension name="VK_EXT_disabled" number="3" supported="disabled" platform="xlib">
+                <require><command name="vkDisabledEXT"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let groups: Vec<_> = registry
+        .partition_by_platform()
+        .into_iter()
+        .map(|g| (g.platform, g.protect, g.types, g.commands, g.enums))
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            (
+                None,
+                None,
+                vec!["VkInstance", "VkSurfaceKHR"],
+                vec![],
+                vec![]
+            ),
+            (
+                Some("win32"),
+                Some("VK_USE_PLATFORM_WIN32_KHR"),
+                vec!["VkWin32SurfaceCreateInfoKHR"],
+                vec!["vkCreateWin32SurfaceKHR"],
+                vec!["VK_KHR_WIN32_SURFACE_SPEC_VERSION"]
+            ),
+            (
+                None,
+                Some("VK_ENABLE_BETA_EXTENSIONS"),
+                vec![],
+                vec!["vkBetaEXT"],
+                vec![]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_loader_table() {
+    use vk_parse::LoaderLevel;
+
+    let xml = r#"<registry>
+        <types>
+            <type category="handle"><type>VK_DEFINE_HANDLE</type>(<name>VkInstance</name>)</type>
+            <type category="handle" parent="VkInstance"><type>VK_DEFINE_HANDLE</type>(<name>VkPhysicalDevice</name>)</type>
+            <type category="handle" parent="VkPhysicalDevice"><type>VK_DEFINE_HANDLE</type>(<name>VkDevice</name>)</type>
+            <type category="handle" parent="VkDevice"><type>VK_DEFINE_NON_DISPATCHABLE_HANDLE</type>(<name>VkCommandPool</name>)</type>
+            <type category="handle" parent="VkCommandPool"><type>VK_DEFINE_HANDLE</type>(<name>VkCommandBuffer</name>)</type>
+        </types>
+        <commands>
+            <command><proto><type>PFN_vkVoidFunction</type> <name>vkGetInstanceProcAddr</name></proto><param optional="true"><type>VkInstance</type> <name>instance</name></param><param>const <type>char</type>* <name>pName</name></param></command>
+            <command><proto><type>PFN_vkVoidFunction</type> <name>vkGetDeviceProcAddr</name></proto><param><type>VkDevice</type> <name>device</name></param><param>const <type>char</type>* <name>pName</name></param></command>
+            <command><proto><type>VkResult</type> <name>vkCreateInstance</name></proto><param>const <type>VkInstanceCreateInfo</type>* <name>pCreateInfo</name></param><param><type>VkInstance</type>* <name>pInstance</name></param></command>
+            <command><proto><type>void</type> <name>vkGetPhysicalDeviceFeatures2</name></proto><param><type>VkPhysicalDevice</type> <name>physicalDevice</name></param></command>
+            <command name="vkGetPhysicalDeviceFeatures2KHR" alias="vkGetPhysicalDeviceFeatures2"/>
+            <command><proto><type>void</type> <name>vkCmdDraw</name></proto><param><type>VkCommandBuffer</type> <name>commandBuffer</name></param></command>
+            <command><proto><type>void</type> <name>vkCmdDisabledEXT</name></proto><param><type>VkCommandBuffer</type> <name>commandBuffer</name></param></command>
+        </commands>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require>
+                <command name="vkGetInstanceProcAddr"/>
+                <command name="vkGetDeviceProcAddr"/>
+                <command name="vkCreateInstance"/>
+                <command name="vkCmdDraw"/>
+            </require>
+        </feature>
+        <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+            <require><command name="vkGetPhysicalDeviceFeatures2"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_get_physical_device_properties2" number="60" supported="vulkan">
+                <require><command name="vkGetPhysicalDeviceFeatures2KHR"/></require>
+            </extension>
+            <extension name="VK_EXT_disabled" number="61" supported="disabled">
+                <require><command name="vkCmdDisabledEXT"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let table: Vec<_> = registry
+        .loader_table()
+        .iter()
+        .map(|group| {
+            let entries: Vec<_> = group
+                .entries
+                .iter()
+                .map(|e| (e.name, e.alias, e.required_by.clone()))
+                .collect();
+            (group.level, entries)
+        })
+        .collect();
+    assert_eq!(
+        table,
+        [
+            (
+                LoaderLevel::Exported,
+                vec![("vkGetInstanceProcAddr", None, vec!["VK_VERSION_1_0"])]
+            ),
+            (
+                LoaderLevel::Global,
+                vec![("vkCreateInstance", None, vec!["VK_VERSION_1_0"])]
+            ),
+            (
+                LoaderLevel::Instance,
+                vec![
+                    ("vkGetDeviceProcAddr", None, vec!["VK_VERSION_1_0"]),
+                    ("vkGetPhysicalDeviceFeatures2", None, vec!["VK_VERSION_1_1"]),
+                    (
+                        "vkGetPhysicalDeviceFeatures2KHR",
+                        Some("vkGetPhysicalDeviceFeatures2"),
+                        vec!["VK_KHR_get_physical_device_properties2"]
+                    ),
+                ]
+            ),
+            (
+                LoaderLevel::Device,
+                vec![("vkCmdDraw", None, vec!["VK_VERSION_1_0"])]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_extension_constants() {
+    let xml = r#"<registry>
+        <extensions>
+            <extension name="VK_KHR_surface" number="1" supported="vulkan">
+                <require>
+                    <enum value="25" name="VK_KHR_SURFACE_SPEC_VERSION"/>
+                    <enum value="&quot;VK_KHR_surface&quot;" name="VK_KHR_SURFACE_EXTENSION_NAME"/>
+                </require>
+            </extension>
+            <extension name="VK_KHR_maintenance1" number="70" supported="vulkan">
+                <require>
+                    <enum value="2" name="VK_KHR_MAINTENANCE_1_SPEC_VERSION"/>
+                    <enum value="&quot;VK_KHR_maintenance1&quot;" name="VK_KHR_MAINTENANCE_1_EXTENSION_NAME"/>
+                    <enum alias="VK_KHR_MAINTENANCE_1_EXTENSION_NAME" name="VK_KHR_MAINTENANCE1_EXTENSION_NAME"/>
+                </require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let constants: Vec<_> = registry
+        .extensions()
+        .map(|e| {
+            let name = e.name_constant().unwrap();
+            let version = e.spec_version().unwrap();
+            (&*name.constant.name, name.value, version.value)
+        })
+        .collect();
+    assert_eq!(
+        constants,
+        [
+            ("VK_KHR_SURFACE_EXTENSION_NAME", "VK_KHR_surface", 25),
+            (
+                "VK_KHR_MAINTENANCE_1_EXTENSION_NAME",
+                "VK_KHR_maintenance1",
+                2
+            ),
+        ]
+    );
+    let extension = registry.constant_extension("VK_KHR_MAINTENANCE_1_SPEC_VERSION");
+    assert_eq!(extension.map(|e| &*e.name), Some("VK_KHR_maintenance1"));
+    assert!(registry
+        .constant_extension("VK_KHR_MAINTENANCE1_EXTENSION_NAME")
+        .is_none());
+}
+
+#[test]
+fn test_api_variant_differences() {
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkPhysicalDeviceProperties">
+                <member><type>uint32_t</type> <name>apiVersion</name></member>
+                <member api="vulkan"><type>uint32_t</type> <name>driverVersion</name></member>
+                <member api="vulkansc"><type>uint64_t</type> <name>driverVersion</name></member>
+                <member api="vulkansc"><type>uint32_t</type> <name>maxPipelines</name></member>
+            </type>
+            <type category="struct" name="VkPipelineCacheCreateInfo" api="vulkan"><member><type>size_t</type> <name>initialDataSize</name></member></type>
+            <type category="struct" name="VkPipelineCacheCreateInfo" api="vulkansc"><member><type>size_t</type> <name>initialDataSize</name></member><member>const <type>void</type>* <name>pInitialData</name></member></type>
+            <type category="struct" name="VkExtent2D"><member><type>uint32_t</type> <name>width</name></member></type>
+        </types>
+        <commands>
+            <command api="vulkan"><proto><type>void</type> <name>vkGetDeviceQueue</name></proto><param><type>VkDevice</type> <name>device</name></param><param><type>VkQueue</type>* <name>pQueue</name></param></command>
+            <command api="vulkansc"><proto><type>void</type> <name>vkGetDeviceQueue</name></proto><param><type>VkDevice</type> <name>device</name></param><param api="vulkansc"><type>uint32_t</type> <name>queueIndex</name></param><param><type>VkQueue</type>* <name>pQueue</name></param></command>
+        </commands>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let report = registry.api_variant_differences("vulkan", "vulkansc");
+
+    let structs: Vec<_> = report
+        .structs
+        .iter()
+        .map(|d| {
+            let names = |members: &[&vk_parse::TypeMemberDefinition]| {
+                members
+                    .iter()
+                    .map(|m| m.name().unwrap().to_string())
+                    .collect::<Vec<_>>()
+            };
+            let changed: Vec<_> = d
+                .changed
+                .iter()
+                .map(|&(a, b)| (a.to_string(), b.to_string()))
+                .collect();
+            (d.name, names(&d.only_first), names(&d.only_second), changed)
+        })
+        .collect();
+    assert_eq!(
+        structs,
+        [
+            (
+                "VkPhysicalDeviceProperties",
+                vec![],
+                vec!["maxPipelines".to_string()],
+                vec![(
+                    "uint32_t driverVersion".to_string(),
+                    "uint64_t driverVersion".to_string()
+                )]
+            ),
+            (
+                "VkPipelineCacheCreateInfo",
+                vec![],
+                vec!["pInitialData".to_string()],
+                vec![]
+            ),
+        ]
+    );
+
+    let commands: Vec<_> = report
+        .commands
+        .iter()
+        .map(|d| {
+            let names: Vec<_> = d.only_second.iter().map(|p| &*p.definition.name).collect();
+            (d.name, d.only_first.len(), names, d.changed.len())
+        })
+        .collect();
+    assert_eq!(commands, [("vkGetDeviceQueue", 0, vec!["queueIndex"], 0)]);
+}
+
+#[test]
+fn test_formats_by_provenance() {
+    let xml = r#"<registry>
+        <types><type name="VkFormat" category="enum"/></types>
+        <enums name="VkFormat" type="enum">
+            <enum value="0" name="VK_FORMAT_UNDEFINED"/>
+            <enum value="37" name="VK_FORMAT_R8G8B8A8_UNORM"/>
+        </enums>
+        <formats>
+            <format name="VK_FORMAT_R8G8B8A8_UNORM" class="32-bit" blockSize="4" texelsPerBlock="1"><component name="R" bits="8" numericFormat="UNORM"/></format>
+            <format name="VK_FORMAT_A4R4G4B4_UNORM_PACK16" class="16-bit" blockSize="2" texelsPerBlock="1" packed="16"><component name="A" bits="4" numericFormat="UNORM"/></format>
+        </formats>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require><type name="VkFormat"/></require>
+        </feature>
+        <feature api="vulkan" name="VK_VERSION_1_3" number="1.3">
+            <require><enum extends="VkFormat" extnumber="341" offset="2" name="VK_FORMAT_A4R4G4B4_UNORM_PACK16"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_EXT_4444_formats" number="341" supported="vulkan">
+                <require>
+                    <enum value="1" name="VK_EXT_4444_FORMATS_SPEC_VERSION"/>
+                    <enum offset="2" extends="VkFormat" name="VK_FORMAT_A4R4G4B4_UNORM_PACK16"/>
+                    <enum extends="VkFormat" name="VK_FORMAT_A4R4G4B4_UNORM_PACK16_EXT" alias="VK_FORMAT_A4R4G4B4_UNORM_PACK16"/>
+                </require>
+            </extension>
+            <extension name="VK_EXT_disabled" number="342" supported="disabled">
+                <require><enum extends="VkFormat" offset="0" name="VK_FORMAT_DISABLED_EXT"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(registry.formats().count(), 2);
+
+    let groups: Vec<_> = registry
+        .formats_by_provenance()
+        .iter()
+        .map(|g| {
+            let formats: Vec<_> = g
+                .formats
+                .iter()
+                .map(|f| (&*f.value.name, f.format.map(|f| &*f.class)))
+                .collect();
+            (g.required_by, formats)
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            (
+                "VK_VERSION_1_0",
+                vec![
+                    ("VK_FORMAT_UNDEFINED", None),
+                    ("VK_FORMAT_R8G8B8A8_UNORM", Some("32-bit")),
+                ]
+            ),
+            (
+                "VK_VERSION_1_3",
+                vec![("VK_FORMAT_A4R4G4B4_UNORM_PACK16", Some("16-bit"))]
+            ),
+            (
+                "VK_EXT_4444_formats",
+                vec![
+                    ("VK_FORMAT_A4R4G4B4_UNORM_PACK16", Some("16-bit")),
+                    ("VK_FORMAT_A4R4G4B4_UNORM_PACK16_EXT", Some("16-bit")),
+                ]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_feature_structs() {
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkPhysicalDeviceFeatures"><member><type>VkBool32</type> <name>robustBufferAccess</name></member><member><type>VkBool32</type> <name>fullDrawIndexUint32</name></member></type>
+            <type category="struct" name="VkPhysicalDeviceFeatures2"><member values="VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2"><type>VkStructureType</type> <name>sType</name></member><member><type>void</type>* <name>pNext</name></member><member><type>VkPhysicalDeviceFeatures</type> <name>features</name></member></type>
+            <type category="struct" name="VkPhysicalDevice16BitStorageFeatures" structextends="VkPhysicalDeviceFeatures2,VkDeviceCreateInfo"><member values="VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_16BIT_STORAGE_FEATURES"><type>VkStructureType</type> <name>sType</name></member><member><type>void</type>* <name>pNext</name></member><member><type>VkBool32</type> <name>storageBuffer16BitAccess</name></member></type>
+            <type category="struct" name="VkPhysicalDevice16BitStorageFeaturesKHR" alias="VkPhysicalDevice16BitStorageFeatures"/>
+            <type category="struct" name="VkPhysicalDeviceProperties"><member><type>VkBool32</type> <name>unused</name></member></type>
+        </types>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require><type name="VkPhysicalDeviceFeatures"/></require>
+        </feature>
+        <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+            <require><type name="VkPhysicalDeviceFeatures2"/><type name="VkPhysicalDevice16BitStorageFeatures"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_16bit_storage" number="84" supported="vulkan">
+                <require><type name="VkPhysicalDevice16BitStorageFeaturesKHR"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let structs: Vec<_> = registry
+        .feature_structs()
+        .iter()
+        .map(|s| {
+            let features: Vec<_> = s.features.iter().map(|f| f.name().unwrap()).collect();
+            (
+                s.name,
+                s.aliases.clone(),
+                s.s_type,
+                s.required_by.clone(),
+                features,
+            )
+        })
+        .collect();
+    assert_eq!(
+        structs,
+        [
+            (
+                "VkPhysicalDeviceFeatures",
+                vec![],
+                None,
+                vec!["VK_VERSION_1_0"],
+                vec!["robustBufferAccess", "fullDrawIndexUint32"]
+            ),
+            (
+                "VkPhysicalDevice16BitStorageFeatures",
+                vec!["VkPhysicalDevice16BitStorageFeaturesKHR"],
+                Some("VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_16BIT_STORAGE_FEATURES"),
+                vec!["VK_VERSION_1_1", "VK_KHR_16bit_storage"],
+                vec!["storageBuffer16BitAccess"]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_property_structs() {
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkPhysicalDeviceLimits" returnedonly="true"><member limittype="max"><type>uint32_t</type> <name>maxImageDimension1D</name></member><member limittype="min,pot"><type>VkDeviceSize</type> <name>minMemoryMapAlignment</name></member><member limittype="range"><type>float</type> <name>pointSizeRange</name>[2]</member></type>
+            <type category="struct" name="VkPhysicalDeviceMaintenance3Properties" returnedonly="true" structextends="VkPhysicalDeviceProperties2"><member values="VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MAINTENANCE_3_PROPERTIES"><type>VkStructureType</type> <name>sType</name></member><member><type>void</type>* <name>pNext</name></member><member limittype="max"><type>uint32_t</type> <name>maxPerSetDescriptors</name></member><member limittype="max,future"><type>VkDeviceSize</type> <name>maxMemoryAllocationSize</name></member></type>
+            <type category="struct" name="VkPhysicalDeviceMaintenance3PropertiesKHR" alias="VkPhysicalDeviceMaintenance3Properties"/>
+            <type category="struct" name="VkPhysicalDeviceFeatures"><member><type>VkBool32</type> <name>robustBufferAccess</name></member></type>
+        </types>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require><type name="VkPhysicalDeviceLimits"/></require>
+        </feature>
+        <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+            <require><type name="VkPhysicalDeviceMaintenance3Properties"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_maintenance3" number="169" supported="vulkan">
+                <require><type name="VkPhysicalDeviceMaintenance3PropertiesKHR"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    use vk_parse::LimitType::*;
+    let structs: Vec<_> = registry
+        .property_structs()
+        .iter()
+        .map(|s| {
+            let members: Vec<_> = s
+                .members
+                .iter()
+                .map(|m| (m.definition.name().unwrap(), m.limit_types.clone()))
+                .collect();
+            (
+                s.name,
+                s.aliases.clone(),
+                s.s_type,
+                s.required_by.clone(),
+                members,
+            )
+        })
+        .collect();
+    assert_eq!(
+        structs,
+        [
+            (
+                "VkPhysicalDeviceLimits",
+                vec![],
+                None,
+                vec!["VK_VERSION_1_0"],
+                vec![
+                    ("maxImageDimension1D", vec![Max]),
+                    ("minMemoryMapAlignment", vec![Min, Pot]),
+                    ("pointSizeRange", vec![Range]),
+                ]
+            ),
+            (
+                "VkPhysicalDeviceMaintenance3Properties",
+                vec!["VkPhysicalDeviceMaintenance3PropertiesKHR"],
+                Some("VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MAINTENANCE_3_PROPERTIES"),
+                vec!["VK_VERSION_1_1", "VK_KHR_maintenance3"],
+                vec![
+                    ("maxPerSetDescriptors", vec![Max]),
+                    ("maxMemoryAllocationSize", vec![Max]),
+                ]
+            ),
+        ]
+    );
+    assert_eq!(vk_parse::LimitType::from_name("bitmask"), Some(Bitmask));
+    assert_eq!(NoAuto.name(), "noauto");
+}
+
+#[test]
+fn test_deprecations() {
+    let xml = r#"<registry>
+        <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+            <require><command name="vkGetPhysicalDeviceFeatures2"/></require>
+            <deprecate explanationlink="deprecation-gpdp2">
+                <command name="vkGetPhysicalDeviceFeatures"/>
+                <type name="VkPhysicalDeviceFeatures"/>
+            </deprecate>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_get_physical_device_properties2" number="60" supported="vulkan" promotedto="VK_VERSION_1_1"/>
+            <extension name="VK_EXT_debug_report" number="12" supported="vulkan" deprecatedby="VK_EXT_debug_utils"/>
+            <extension name="VK_NV_glsl_shader" number="13" supported="vulkan" deprecatedby=""/>
+            <extension name="VK_AMD_negative_viewport_height" number="36" supported="vulkan" obsoletedby="VK_KHR_maintenance1"/>
+            <extension name="VK_EXT_disabled" number="37" supported="disabled" deprecatedby=""/>
+            <extension name="VK_EXT_legacy" number="38" supported="vulkan" deprecated="true"/>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let deprecations = registry.deprecations();
+
+    let extensions: Vec<_> = deprecations
+        .extensions
+        .iter()
+        .map(|d| (&*d.extension.name, d.obsoleted, d.replacement))
+        .collect();
+    assert_eq!(
+        extensions,
+        [
+            ("VK_EXT_debug_report", false, Some("VK_EXT_debug_utils")),
+            ("VK_NV_glsl_shader", false, None),
+            (
+                "VK_AMD_negative_viewport_height",
+                true,
+                Some("VK_KHR_maintenance1")
+            ),
+            ("VK_EXT_legacy", false, None),
+        ]
+    );
+    assert_eq!(
+        deprecations.extensions[3].deprecated,
+        Some(vk_parse::Deprecation::Deprecated)
+    );
+
+    let items = |items: &[vk_parse::DeprecatedItem]| -> Vec<_> {
+        items
+            .iter()
+            .map(|d| {
+                let link = d.explanationlink.map(|link| link.to_string());
+                (d.name.to_string(), d.deprecated_by.to_string(), link)
+            })
+            .collect()
+    };
+    assert_eq!(
+        items(&deprecations.commands),
+        [(
+            "vkGetPhysicalDeviceFeatures".to_string(),
+            "VK_VERSION_1_1".to_string(),
+            Some("deprecation-gpdp2".to_string())
+        )]
+    );
+    assert_eq!(
+        items(&deprecations.types),
+        [(
+            "VkPhysicalDeviceFeatures".to_string(),
+            "VK_VERSION_1_1".to_string(),
+            Some("deprecation-gpdp2".to_string())
+        )]
+    );
+    assert_eq!(deprecations.enums, []);
+}
+
+#[test]
+fn test_collapse_api_variants() {
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkPipelineCacheCreateInfo" api="vulkan"><member><type>size_t</type> <name>initialDataSize</name></member><member><type>uint32_t</type> <name>flags</name></member></type>
+            <type category="struct" name="VkExtent2D"><member><type>uint32_t</type> <name>width</name></member></type>
+            <type category="struct" name="VkPipelineCacheCreateInfo" api="vulkansc"><member><type>size_t</type> <name>initialDataSize</name></member><member>const <type>void</type>* <name>pInitialData</name></member><member><type>uint64_t</type> <name>flags</name></member></type>
+            <type category="define" api="vulkan" name="VK_API_VERSION">#define <name>VK_API_VERSION</name> 1</type>
+            <type category="define" api="vulkansc" name="VK_API_VERSION">#define <name>VK_API_VERSION</name> 1</type>
+            <type category="struct" name="VkOffset2D" api="vulkan"><member><type>int32_t</type> <name>x</name></member></type>
+            <type category="struct" name="VkOffset2D" api="vulkansc" returnedonly="true"><member><type>int32_t</type> <name>x</name></member></type>
+        </types>
+        <commands>
+            <command api="vulkan"><proto><type>void</type> <name>vkGetDeviceQueue</name></proto><param><type>VkDevice</type> <name>device</name></param><param><type>VkQueue</type>* <name>pQueue</name></param></command>
+            <command api="vulkansc"><proto><type>void</type> <name>vkGetDeviceQueue</name></proto><param><type>VkDevice</type> <name>device</name></param><param><type>uint32_t</type> <name>queueIndex</name></param><param><type>VkQueue</type>* <name>pQueue</name></param></command>
+        </commands>
+        </registry>"#;
+    let (mut registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let summary = |registry: &vk_parse::Registry| {
+        let report = registry.api_variant_differences("vulkan", "vulkansc");
+        let structs: Vec<_> = report
+            .structs
+            .iter()
+            .map(|d| {
+                let only_second: Vec<_> = d.only_second.iter().map(|m| m.to_string()).collect();
+                let changed: Vec<_> = d
+                    .changed
+                    .iter()
+                    .map(|&(a, b)| (a.to_string(), b.to_string()))
+                    .collect();
+                (d.name.to_string(), d.only_first.len(), only_second, changed)
+            })
+            .collect();
+        let commands: Vec<_> = report
+            .commands
+            .iter()
+            .map(|d| {
+                let only_second: Vec<_> = d
+                    .only_second
+                    .iter()
+                    .map(|p| p.definition.name.to_string())
+                    .collect();
+                (d.name.to_string(), d.only_first.len(), only_second)
+            })
+            .collect();
+        (structs, commands)
+    };
+    let report = summary(&registry);
+    registry.collapse_api_variants();
+    assert_eq!(summary(&registry), report);
+
+    let types: Vec<_> = registry
+        .types()
+        .map(|ty| (ty.declared_name().unwrap(), ty.api.as_ref()))
+        .collect();
+    assert_eq!(
+        types,
+        [
+            ("VkPipelineCacheCreateInfo", Some(&apis("vulkan,vulkansc"))),
+            ("VkExtent2D", None),
+            ("VK_API_VERSION", Some(&apis("vulkan,vulkansc"))),
+            ("VkOffset2D", Some(&apis("vulkan"))),
+            ("VkOffset2D", Some(&apis("vulkansc"))),
+        ]
+    );
+    let members: Vec<_> = match registry.types().next().unwrap().spec {
+        vk_parse::TypeSpec::Members(ref members) => members
+            .iter()
+            .filter_map(|m| match *m {
+                vk_parse::TypeMember::Definition(ref def) => {
+                    Some((def.to_string(), def.api.as_ref()))
+                }
+                _ => None,
+            })
+            .collect(),
+        _ => panic!("expected members"),
+    };
+    assert_eq!(
+        members,
+        [
+            ("size_t initialDataSize".to_string(), None),
+            ("uint32_t flags".to_string(), Some(&apis("vulkan"))),
+            (
+                "const void* pInitialData".to_string(),
+                Some(&apis("vulkansc"))
+            ),
+            ("uint64_t flags".to_string(), Some(&apis("vulkansc"))),
+        ]
+    );
+
+    let commands: Vec<_> = registry
+        .commands()
+        .map(|command| match *command {
+            vk_parse::Command::Definition(ref def) => {
+                let params: Vec<_> = def
+                    .params
+                    .iter()
+                    .map(|p| (&*p.definition.name, p.api.as_ref()))
+                    .collect();
+                (def.api.as_ref(), params)
+            }
+            _ => panic!("expected definition"),
+        })
+        .collect();
+    assert_eq!(
+        commands,
+        [(
+            Some(&apis("vulkan,vulkansc")),
+            vec![
+                ("device", None),
+                ("queueIndex", Some(&apis("vulkansc"))),
+                ("pQueue", None),
+            ]
+        )]
+    );
+}
+
+#[test]
+fn test_requires_for() {
+    let xml = r#"<registry>
+        <feature api="vulkan,vulkansc" name="VK_VERSION_1_0" number="1.0">
+            <require comment="Header boilerplate"><type name="vk_platform"/></require>
+            <require api="vulkan"><command name="vkCreateShaderModule"/></require>
+            <require profile="core"><command name="vkCreateInstance"/></require>
+            <require profile="compatibility"><command name="vkCreateLegacy"/></require>
+            <remove api="vulkansc"><command name="vkCreateShaderModule"/></remove>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_swapchain" number="2" supported="vulkan,vulkansc">
+                <require>
+                    <comment>Swapchain</comment>
+                    <enum value="70" name="VK_KHR_SWAPCHAIN_SPEC_VERSION"/>
+                    <enum value="69" name="VK_KHR_SWAPCHAIN_SPEC_VERSION" api="vulkansc"/>
+                    <command name="vkCreateSwapchainKHR"/>
+                </require>
+                <require api="vulkansc"><command name="vkGetSwapchainStatusKHR"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    fn names<'a, I: Iterator<Item = &'a vk_parse::InterfaceItem>>(items: I) -> Vec<String> {
+        items
+            .map(|item| match *item {
+                vk_parse::InterfaceItem::Type { ref name, .. }
+                | vk_parse::InterfaceItem::Command { ref name, .. } => name.to_string(),
+                vk_parse::InterfaceItem::Enum(ref e) => {
+                    format!("{} = {:?}", e.name, e.spec)
+                }
+                _ => panic!("unexpected comment"),
+            })
+            .collect()
+    }
+
+    let feature = registry.features().next().unwrap();
+    assert_eq!(
+        names(feature.requires_for("vulkan", Some("core"))),
+        ["vk_platform", "vkCreateShaderModule", "vkCreateInstance"]
+    );
+    assert_eq!(
+        names(feature.requires_for("vulkansc", None)),
+        ["vk_platform", "vkCreateInstance", "vkCreateLegacy"]
+    );
+    assert_eq!(names(feature.removes_for("vulkan", None)).len(), 0);
+    assert_eq!(
+        names(feature.removes_for("vulkansc", None)),
+        ["vkCreateShaderModule"]
+    );
+
+    let extension = registry.extensions().next().unwrap();
+    let vulkan = names(extension.requires_for("vulkan", None));
+    assert_eq!(vulkan.len(), 2);
+    assert!(vulkan[0].contains("\"70\""));
+    assert_eq!(vulkan[1], "vkCreateSwapchainKHR");
+    let vulkansc = names(extension.requires_for("vulkansc", None));
+    assert_eq!(vulkansc.len(), 4);
+    assert!(vulkansc[0].contains("\"70\"") && vulkansc[1].contains("\"69\""));
+    assert_eq!(vulkansc[3], "vkGetSwapchainStatusKHR");
+}
+
+#[test]
+fn test_comment_groups() {
+    let xml = r#"<registry>
+        <types>
+            <type name="vk_platform" category="include">#include "vk_platform.h"</type>
+            <comment>Basic C types, pulled in via vk_platform.h</comment>
+            <type requires="vk_platform" name="void"/>
+            <type requires="vk_platform" name="char"/>
+            <comment>Unused</comment>
+            <comment>Bitmask types</comment>
+            <type category="bitmask" name="VkFramebufferCreateFlags"><type>VkFlags</type> <name>VkFramebufferCreateFlags</name></type>
+            <comment>Trailing</comment>
+        </types>
+        <enums name="VkImageLayout" type="enum">
+            <enum value="0" name="VK_IMAGE_LAYOUT_UNDEFINED"/>
+            <comment>Read-only layouts</comment>
+            <enum value="5" name="VK_IMAGE_LAYOUT_SHADER_READ_ONLY_OPTIMAL"/>
+            <unused start="6"/>
+            <enum value="7" name="VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL"/>
+        </enums>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let types = registry
+        .0
+        .iter()
+        .find_map(|child| match *child {
+            vk_parse::RegistryChild::Types(ref types) => Some(types),
+            _ => None,
+        })
+        .unwrap();
+    let groups: Vec<_> = types
+        .comment_groups()
+        .iter()
+        .map(|g| {
+            let names: Vec<_> = g
+                .items
+                .iter()
+                .map(|ty| ty.declared_name().unwrap())
+                .collect();
+            (g.comment, names)
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            (None, vec!["vk_platform"]),
+            (
+                Some("Basic C types, pulled in via vk_platform.h"),
+                vec!["void", "char"]
+            ),
+            (Some("Bitmask types"), vec!["VkFramebufferCreateFlags"]),
+        ]
+    );
+
+    let enums = registry.enums_blocks().next().unwrap();
+    let groups: Vec<_> = enums
+        .comment_groups()
+        .iter()
+        .map(|g| {
+            let names: Vec<_> = g.items.iter().map(|e| &*e.name).collect();
+            (g.comment, names)
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            (None, vec!["VK_IMAGE_LAYOUT_UNDEFINED"]),
+            (
+                Some("Read-only layouts"),
+                vec![
+                    "VK_IMAGE_LAYOUT_SHADER_READ_ONLY_OPTIMAL",
+                    "VK_IMAGE_LAYOUT_TRANSFER_DST_OPTIMAL"
+                ]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_semantic_hash() {
+    let first = r#"<registry>
+        <types>
+            <type category="struct" name="VkExtent2D" comment="Extent"><member><type>uint32_t</type>   <name>width</name></member><member><type>uint32_t</type> <name>height</name><comment>Height</comment></member></type>
+            <type category="define" name="VK_MAKE_VERSION">#define <name>VK_MAKE_VERSION</name>(major, minor)  ((major) | (minor))</type>
+        </types>
+        <enums name="VkResult" type="enum"><enum value="0" name="VK_SUCCESS" comment="Command completed successfully"/></enums>
+        <commands>
+            <command successcodes="VK_SUCCESS" errorcodes="VK_ERROR_OUT_OF_HOST_MEMORY"><proto><type>VkResult</type> <name>vkCreateInstance</name></proto><param>const <type>VkInstanceCreateInfo</type>* <name>pCreateInfo</name></param></command>
+        </commands>
+        <extensions>
+            <extension name="VK_KHR_surface" number="1" supported="vulkan" comment="Surface">
+                <require comment="Commands"><comment>Surface</comment><command name="vkDestroySurfaceKHR"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let second = r#"<registry>
+        <comment>Reordered, with different comments and whitespace</comment>
+        <commands>
+            <command errorcodes="VK_ERROR_OUT_OF_HOST_MEMORY" successcodes="VK_SUCCESS">
+                <proto><type>VkResult</type>   <name>vkCreateInstance</name></proto>
+                <param>const <type>VkInstanceCreateInfo</type>*   <name>pCreateInfo</name></param>
+            </command>
+        </commands>
+        <enums name="VkResult" type="enum"><enum name="VK_SUCCESS" value="0"/></enums>
+        <types>
+            <comment>Types</comment>
+            <type category="define" name="VK_MAKE_VERSION">#define <name>VK_MAKE_VERSION</name>(major, minor) ((major) | (minor))</type>
+            <type name="VkExtent2D" category="struct"><member><type>uint32_t</type> <name>width</name></member><member><type>uint32_t</type> <name>height</name></member></type>
+        </types>
+        <extensions>
+            <extension supported="vulkan" number="1" name="VK_KHR_surface">
+                <require><command name="vkDestroySurfaceKHR"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (first, errors) = parse(first, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let options = ParseOptions::new().raw_attributes(true).omit_code(true);
+    let (second, errors) = parse(second, &options).unwrap();
+    assert_eq!(errors, []);
+
+    fn hashes(registry: &vk_parse::Registry) -> Vec<(String, u64)> {
+        let mut hashes: Vec<_> = registry
+            .types()
+            .map(|ty| (ty.declared_name().unwrap().to_string(), ty.semantic_hash()))
+            .chain(
+                registry
+                    .commands()
+                    .map(|c| (c.name().to_string(), c.semantic_hash())),
+            )
+            .chain(registry.enums_blocks().flat_map(|enums| {
+                enums.children.iter().filter_map(|child| match *child {
+                    vk_parse::EnumsChild::Enum(ref e) => {
+                        Some((e.name.to_string(), e.semantic_hash()))
+                    }
+                    _ => None,
+                })
+            }))
+            .chain(
+                registry
+                    .extensions()
+                    .map(|e| (e.name.to_string(), e.semantic_hash())),
+            )
+            .collect();
+        hashes.sort();
+        hashes
+    }
+    let first_hashes = hashes(&first);
+    assert_eq!(first_hashes.len(), 5);
+    assert_eq!(first_hashes, hashes(&second));
+    // Hashes must only change along with the canonical form.
+    assert_eq!(
+        first_hashes[2],
+        ("VK_SUCCESS".to_string(), 0xe182_51aa_1bdb_60a2)
+    );
+
+    let mut changed = second.clone();
+    for child in &mut changed.0 {
+        if let vk_parse::RegistryChild::Commands(ref mut commands) = *child {
+            if let vk_parse::Command::Definition(ref mut def) = commands.children[0] {
+                def.successcodes = vec!["VK_SUCCESS".into(), "VK_INCOMPLETE".into()];
+            }
+        }
+    }
+    let changed: Vec<_> = first_hashes
+        .iter()
+        .zip(hashes(&changed))
+        .filter(|&(a, ref b)| a != b)
+        .map(|(a, _)| a.0.clone())
+        .collect();
+    assert_eq!(changed, ["vkCreateInstance"]);
+}
+
+#[test]
+fn test_enumerant_name() {
+    let xml = r#"<registry>
+        <tags>
+            <tag name="KHR" author="Khronos" contact="Khronos"/>
+            <tag name="EXT" author="Multivendor" contact="Khronos"/>
+        </tags>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let split = |type_name: &str, name: &'static str| {
+        let name = registry.enumerant_name(type_name, name);
+        (name.prefix, name.short, name.is_bit, name.vendor)
+    };
+    assert_eq!(
+        split("VkImageLayout", "VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL"),
+        ("VK_IMAGE_LAYOUT_", "COLOR_ATTACHMENT_OPTIMAL", false, None)
+    );
+    assert_eq!(
+        split("VkImageLayout", "VK_IMAGE_LAYOUT_PRESENT_SRC_KHR"),
+        ("VK_IMAGE_LAYOUT_", "PRESENT_SRC", false, Some("KHR"))
+    );
+    assert_eq!(
+        split("VkResult", "VK_SUCCESS"),
+        ("VK_", "SUCCESS", false, None)
+    );
+    assert_eq!(
+        split("VkImageType", "VK_IMAGE_TYPE_2D"),
+        ("VK_IMAGE_", "TYPE_2D", false, None)
+    );
+    assert_eq!(
+        split("VkSampleCountFlagBits", "VK_SAMPLE_COUNT_1_BIT"),
+        ("VK_SAMPLE_", "COUNT_1", true, None)
+    );
+    assert_eq!(
+        split("VkShaderStageFlagBits", "VK_SHADER_STAGE_RAYGEN_BIT_KHR"),
+        ("VK_SHADER_STAGE_", "RAYGEN", true, Some("KHR"))
+    );
+    assert_eq!(
+        split(
+            "VkPipelineStageFlagBits2KHR",
+            "VK_PIPELINE_STAGE_2_NONE_KHR"
+        ),
+        ("VK_PIPELINE_STAGE_2_", "NONE", false, Some("KHR"))
+    );
+    assert_eq!(
+        split(
+            "VkVideoEncodeH264RateControlFlagBitsKHR",
+            "VK_VIDEO_ENCODE_H264_RATE_CONTROL_REGULAR_GOP_BIT_KHR"
+        ),
+        (
+            "VK_VIDEO_ENCODE_H264_RATE_CONTROL_",
+            "REGULAR_GOP",
+            true,
+            Some("KHR")
+        )
+    );
+    assert_eq!(
+        split(
+            "VkDebugReportObjectTypeEXT",
+            "VK_DEBUG_REPORT_OBJECT_TYPE_INSTANCE_EXT"
+        ),
+        (
+            "VK_DEBUG_REPORT_OBJECT_TYPE_",
+            "INSTANCE",
+            false,
+            Some("EXT")
+        )
+    );
+    assert_eq!(
+        split("VkColorSpaceKHR", "VK_COLORSPACE_SRGB_NONLINEAR_KHR"),
+        ("VK_", "COLORSPACE_SRGB_NONLINEAR", false, Some("KHR"))
+    );
+    assert_eq!(
+        split("VkQueryType", "VK_QUERY_TYPE_KHR"),
+        ("VK_QUERY_TYPE_", "KHR", false, None)
+    );
+}
+
+#[test]
+fn test_bitmasks() {
+    let xml = r#"<registry>
+        <types>
+            <type requires="VkImageUsageFlagBits" category="bitmask">typedef <type>VkFlags</type> <name>VkImageUsageFlags</name>;</type>
+            <type category="bitmask">typedef <type>VkFlags</type> <name>VkDeviceCreateFlags</name>;</type>
+            <type requires="VkPipelineLayoutCreateFlagBits" category="bitmask">typedef <type>VkFlags</type> <name>VkPipelineLayoutCreateFlags</name>;</type>
+            <type bitvalues="VkAccessFlagBits2" category="bitmask">typedef <type>VkFlags64</type> <name>VkAccessFlags2</name>;</type>
+            <type category="bitmask" name="VkAccessFlags2KHR" alias="VkAccessFlags2"/>
+            <type name="VkImageUsageFlagBits" category="enum"/>
+            <type name="VkPipelineLayoutCreateFlagBits" category="enum"/>
+            <type name="VkAccessFlagBits2" category="enum"/>
+        </types>
+        <enums name="VkImageUsageFlagBits" type="bitmask"><enum bitpos="0" name="VK_IMAGE_USAGE_TRANSFER_SRC_BIT"/></enums>
+        <enums name="VkPipelineLayoutCreateFlagBits" type="bitmask"></enums>
+        <enums name="VkAccessFlagBits2" type="bitmask" bitwidth="64"><enum value="0" name="VK_ACCESS_2_NONE"/></enums>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require>
+                <type name="VkImageUsageFlags"/>
+                <type name="VkImageUsageFlagBits"/>
+                <type name="VkDeviceCreateFlags"/>
+                <type name="VkPipelineLayoutCreateFlags"/>
+            </require>
+        </feature>
+        <feature api="vulkan" name="VK_VERSION_1_3" number="1.3">
+            <require><type name="VkAccessFlags2"/><type name="VkAccessFlagBits2"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_EXT_graphics_pipeline_library" number="321" supported="vulkan">
+                <require><type name="VkPipelineLayoutCreateFlagBits"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let bitmasks: Vec<_> = registry
+        .bitmasks()
+        .iter()
+        .map(|b| (b.name, b.bits, b.is_empty(), b.gained_bits_later()))
+        .collect();
+    assert_eq!(
+        bitmasks,
+        [
+            (
+                "VkImageUsageFlags",
+                Some("VkImageUsageFlagBits"),
+                false,
+                false
+            ),
+            ("VkDeviceCreateFlags", None, true, false),
+            (
+                "VkPipelineLayoutCreateFlags",
+                Some("VkPipelineLayoutCreateFlagBits"),
+                true,
+                true
+            ),
+            ("VkAccessFlags2", Some("VkAccessFlagBits2"), false, false),
+        ]
+    );
+    let layout = &registry.bitmasks()[2];
+    assert_eq!(layout.required_by, ["VK_VERSION_1_0"]);
+    assert_eq!(
+        layout.bits_required_by,
+        ["VK_EXT_graphics_pipeline_library"]
+    );
+}
+
+#[test]
+fn test_retain() {
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkA"><member><type>int</type> <name>a</name></member><member api="vulkansc"><type>int</type> <name>sc</name></member></type>
+            <type category="struct" name="VkB" structextends="VkA"/>
+            <type category="struct" name="VkC"/>
+            <type category="struct" name="VkSC"/>
+            <type category="struct" name="VkBKHR" alias="VkB"/>
+        </types>
+        <commands>
+            <command><proto><type>void</type> <name>vkA</name></proto><param><type>int</type> <name>a</name></param><param api="vulkansc"><type>int</type> <name>sc</name></param></command>
+        </commands>
+        <feature api="vulkan,vulkansc" name="VK_VERSION_1_0" number="1.0">
+            <require><type name="VkA"/><command name="vkA"/></require>
+        </feature>
+        <feature api="vulkansc" name="VKSC_VERSION_1_0" number="1.0">
+            <require><type name="VkSC"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_EXT_b" number="1" supported="disabled">
+                <require><type name="VkB"/></require>
+            </extension>
+            <extension name="VK_KHR_b" number="2" supported="vulkan" requires="VK_EXT_b" promotedto="VK_EXT_b">
+                <require><type name="VkBKHR"/></require>
+                <require extension="VK_EXT_b"><type name="VkC"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(registry.dangling_references(), []);
+
+    let mut disabled = registry.clone();
+    let dangling: Vec<_> = disabled
+        .remove_disabled()
+        .into_iter()
+        .map(|d| (d.from.to_string(), d.via, d.to.to_string()))
+        .collect();
+    assert_eq!(
+        dangling,
+        [
+            ("VkBKHR".to_string(), "alias", "VkB".to_string()),
+            ("VK_KHR_b".to_string(), "requires", "VK_EXT_b".to_string()),
+            ("VK_KHR_b".to_string(), "promotedto", "VK_EXT_b".to_string()),
+        ]
+    );
+    let types: Vec<_> = disabled.types().filter_map(|t| t.declared_name()).collect();
+    assert_eq!(types, ["VkA", "VkSC", "VkBKHR"]);
+    let khr = disabled.extensions().next().unwrap();
+    assert_eq!(khr.name.as_ref(), "VK_KHR_b");
+    assert_eq!(khr.children.len(), 1);
+
+    let mut vulkan = registry.clone();
+    // Disabled extensions aren't supported by any API.
+    assert_eq!(vulkan.retain_api("vulkan").len(), 3);
+    let types: Vec<_> = vulkan.types().filter_map(|t| t.declared_name()).collect();
+    assert_eq!(types, ["VkA", "VkBKHR"]);
+    let features: Vec<_> = vulkan.features().map(|f| &*f.name).collect();
+    assert_eq!(features, ["VK_VERSION_1_0"]);
+    let extensions: Vec<_> = vulkan.extensions().map(|e| &*e.name).collect();
+    assert_eq!(extensions, ["VK_KHR_b"]);
+    match vulkan.types().next().unwrap().spec {
+        vk_parse::TypeSpec::Members(ref members) => assert_eq!(members.len(), 1),
+        _ => panic!("VkA should have members"),
+    }
+    match *vulkan.commands().next().unwrap() {
+        vk_parse::Command::Definition(ref def) => assert_eq!(def.params.len(), 1),
+        _ => panic!("vkA should be defined"),
+    };
+}
+
+#[test]
+fn test_edit() {
+    use vk_parse::{EditError, Enum, EnumSpec, Extension, TypeMemberDefinition, TypeMemberMarkup};
+
+    let xml = r#"<registry>
+        <types>
+            <type name="uint32_t"/>
+            <type category="struct" name="VkA"><member><type>uint32_t</type> <name>a</name></member></type>
+            <type category="enum" name="VkStructureType"/>
+            <type category="handle" name="VkDevice"/>
+        </types>
+        <enums name="VkStructureType" type="enum"><enum value="0" name="VK_STRUCTURE_TYPE_APPLICATION_INFO"/></enums>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require><type name="VkA"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_a" number="1" supported="vulkan">
+                <require><enum offset="0" extends="VkStructureType" name="VK_STRUCTURE_TYPE_A_KHR"/></require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (mut registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let original = registry.clone();
+
+    let mut extension = Extension::default();
+    extension.name = "VK_KHR_a".into();
+    assert_eq!(
+        registry.add_extension(extension.clone()),
+        Err(EditError::DuplicateName {
+            name: "VK_KHR_a".into()
+        })
+    );
+    extension.name = "VK_KHR_b".into();
+    extension.number = Some(1);
+    assert_eq!(
+        registry.add_extension(extension.clone()),
+        Err(EditError::DuplicateNumber {
+            number: 1,
+            extension: "VK_KHR_a".into()
+        })
+    );
+    extension.number = Some(2);
+    extension.requires = Some("VK_KHR_missing".into());
+    assert_eq!(
+        registry.add_extension(extension.clone()),
+        Err(EditError::MissingReference {
+            from: "VK_KHR_b".into(),
+            to: "VK_KHR_missing".into()
+        })
+    );
+    extension.requires = Some("VK_KHR_a".into());
+    assert_eq!(registry, original);
+    assert_eq!(registry.add_extension(extension), Ok(()));
+    assert_eq!(registry.extensions().count(), 2);
+
+    let mut e = Enum::default();
+    e.name = "VK_STRUCTURE_TYPE_B_KHR".into();
+    e.spec = EnumSpec::Offset {
+        offset: 0,
+        extends: "VkStructureType".into(),
+        extnumber: Some(1),
+        dir: true,
+    };
+    assert_eq!(
+        registry.add_enum("VK_KHR_b", e.clone()),
+        Err(EditError::DuplicateValue {
+            name: "VK_STRUCTURE_TYPE_B_KHR".into(),
+            existing: "VK_STRUCTURE_TYPE_A_KHR".into()
+        })
+    );
+    e.spec = EnumSpec::Offset {
+        offset: 0,
+        extends: "VkStructureTypo".into(),
+        extnumber: None,
+        dir: true,
+    };
+    assert_eq!(
+        registry.add_enum("VK_KHR_b", e.clone()),
+        Err(EditError::MissingReference {
+            from: "VK_STRUCTURE_TYPE_B_KHR".into(),
+            to: "VkStructureTypo".into()
+        })
+    );
+    e.spec = EnumSpec::Offset {
+        offset: 0,
+        extends: "VkStructureType".into(),
+        extnumber: None,
+        dir: true,
+    };
+    assert_eq!(registry.add_enum("VK_KHR_b", e), Ok(()));
+    let b = registry.extensions().nth(1).unwrap();
+    assert_eq!(b.children.len(), 1);
+
+    let mut member = TypeMemberDefinition::default();
+    member.code = "uint32_t a".into();
+    member
+        .markup
+        .push(TypeMemberMarkup::Type("uint32_t".into()));
+    member.markup.push(TypeMemberMarkup::Name("a".into()));
+    assert_eq!(
+        registry.add_member("VkA", member.clone()),
+        Err(EditError::DuplicateName { name: "a".into() })
+    );
+    assert_eq!(
+        registry.add_member("VkDevice", member.clone()),
+        Err(EditError::UnexpectedKind {
+            name: "VkDevice".into(),
+            expected: "struct or union"
+        })
+    );
+    member.markup[1] = TypeMemberMarkup::Name("b".into());
+    assert_eq!(registry.add_member("VkA", member), Ok(()));
+    match registry.types().nth(1).unwrap().spec {
+        vk_parse::TypeSpec::Members(ref members) => assert_eq!(members.len(), 2),
+        _ => panic!("VkA should have members"),
+    };
+}
+
+const WRITER_REGISTRY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<registry>
+    <comment>Copyright &amp; license</comment>
+    <vendorids comment="Vendor IDs">
+        <vendorid name="KHR" id="0x10000" comment="Khronos"/>
+    </vendorids>
+    <platforms>
+        <platform name="xlib" protect="VK_USE_PLATFORM_XLIB_KHR" comment="X Window System"/>
+    </platforms>
+    <tags>
+        <tag name="KHR" author="Khronos" contact="Someone"/>
+    </tags>
+    <types>
+        <type name="uint32_t"/>
+        <type category="define">#define <name>VK_A</name> (1 &lt;&lt; 2)</type>
+        <type category="basetype">typedef <type>uint32_t</type> <name>VkFlags</name>;</type>
+        <type category="handle" objtypeenum="VK_OBJECT_TYPE_DEVICE"><type>VK_DEFINE_HANDLE</type>(<name>VkDevice</name>)</type>
+        <comment>Structures</comment>
+        <type category="struct" name="VkA" structextends="VkB" comment="A struct">
+            <member values="VK_STRUCTURE_TYPE_A"><type>VkStructureType</type> <name>sType</name></member>
+            <member optional="true" len="count"><type>uint32_t</type>* <name>pData</name><comment>Data</comment></member>
+            <member><type>char</type> <name>name</name>[<enum>VK_MAX_NAME</enum>]</member>
+            <comment>Trailing</comment>
+        </type>
+        <type category="struct" name="VkAKHR" alias="VkA"/>
+    </types>
+    <enums name="API Constants">
+        <enum type="uint32_t" value="256" name="VK_MAX_NAME"/>
+    </enums>
+    <enums name="VkStructureType" type="enum" start="0" end="9" vendor="KHR" comment="Types">
+        <enum value="0" name="VK_STRUCTURE_TYPE_A"/>
+        <enum bitpos="1" name="VK_STRUCTURE_TYPE_B"/>
+        <unused start="2" end="3" comment="Reserved"/>
+        <comment>Aliases</comment>
+        <enum name="VK_STRUCTURE_TYPE_A_KHR" alias="VK_STRUCTURE_TYPE_A"/>
+    </enums>
+    <commands comment="Commands">
+        <command successcodes="VK_SUCCESS" errorcodes="VK_ERROR_OUT_OF_HOST_MEMORY" queues="graphics">
+            <proto><type>VkResult</type> <name>vkCreateA</name></proto>
+            <param><type>VkDevice</type> <name>device</name></param>
+            <param optional="false,true" len="count" validstructs="VkA,VkB">const <type>VkA</type>* <name>pA</name></param>
+            <implicitexternsyncparams>
+                <param>the device</param>
+            </implicitexternsyncparams>
+        </command>
+        <command>
+            <proto><type>void</type> <name>vkEmpty</name></proto>
+        </command>
+        <command name="vkCreateAKHR" alias="vkCreateA"/>
+    </commands>
+    <feature api="vulkan" name="VK_VERSION_1_0" number="1.0" comment="Core">
+        <require comment="Header">
+            <type name="VkA"/>
+            <command name="vkCreateA"/>
+            <enum extends="VkStructureType" extnumber="2" offset="1" dir="-" name="VK_ERROR_A"/>
+        </require>
+        <remove>
+            <type name="VkDevice"/>
+        </remove>
+    </feature>
+    <extensions>
+        <extension name="VK_KHR_a" number="2" type="device" requires="VK_KHR_b" platform="xlib" author="KHR" contact="Someone" supported="vulkan" provisional="true" sortorder="1">
+            <require api="vulkan" extension="VK_KHR_b">
+                <enum value="1" name="VK_KHR_A_SPEC_VERSION"/>
+                <enum value="&quot;VK_KHR_a&quot;" name="VK_KHR_A_EXTENSION_NAME"/>
+                <enum bitpos="4" extends="VkStructureType" name="VK_STRUCTURE_TYPE_C_KHR"/>
+            </require>
+        </extension>
+        <extension name="VK_KHR_b" number="3" supported="disabled"/>
+    </extensions>
+    <formats>
+        <format name="VK_FORMAT_R8_UNORM" class="8-bit" blockSize="1" texelsPerBlock="1">
+            <component name="R" bits="8" numericFormat="UNORM"/>
+            <spirvimageformat name="R8"/>
+        </format>
+    </formats>
+    <spirvextensions>
+        <spirvextension name="SPV_KHR_a">
+            <enable version="VK_VERSION_1_1"/>
+            <enable extension="VK_KHR_a"/>
+        </spirvextension>
+    </spirvextensions>
+    <spirvcapabilities>
+        <spirvcapability name="Shader">
+            <enable struct="VkFeatures" feature="shader" requires="VK_VERSION_1_0"/>
+            <enable property="VkProperties" member="ops" value="VK_A" requires="VK_VERSION_1_1"/>
+        </spirvcapability>
+    </spirvcapabilities>
+</registry>
+"#;
+
+#[test]
+fn test_write_xml() {
+    use vk_parse::{AttributeOrder, CommentPlacement, IndentStyle, WriteOptions};
+
+    let (registry, errors) = parse(WRITER_REGISTRY, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let xml = registry.to_xml(&WriteOptions::new());
+    let (reparsed, errors) = parse(&xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, registry);
+    assert_eq!(reparsed.to_xml(&WriteOptions::new()), xml);
+    assert!(xml.contains(
+        "        <type category=\"define\">#define <name>VK_A</name> (1 &lt;&lt; 2)</type>\n"
+    ));
+    assert!(xml.contains(
+        "            <member optional=\"true\" len=\"count\"><type>uint32_t</type>* <name>pData</name><comment>Data</comment></member>\n"
+    ));
+    assert!(xml.contains(
+        "            <param optional=\"false,true\" len=\"count\" validstructs=\"VkA,VkB\">const <type>VkA</type>* <name>pA</name></param>\n"
+    ));
+    // Names of enums are aligned within a block.
+    assert!(xml.contains(
+        "                <enum value=\"1\"                            name=\"VK_KHR_A_SPEC_VERSION\"/>\n"
+    ));
+    assert!(xml.contains(
+        "                <enum bitpos=\"4\" extends=\"VkStructureType\" name=\"VK_STRUCTURE_TYPE_C_KHR\"/>\n"
+    ));
+
+    // Code omitted while parsing is regenerated.
+    let (omitted, _errors) = parse(WRITER_REGISTRY, &ParseOptions::new().omit_code(true)).unwrap();
+    let (reparsed, _errors) = parse(
+        &omitted.to_xml(&WriteOptions::new()),
+        &ParseOptions::new().omit_code(true),
+    )
+    .unwrap();
+    assert_eq!(reparsed, omitted);
+
+    let options = WriteOptions::new()
+        .indent(IndentStyle::Tabs)
+        .attribute_order(AttributeOrder::Alphabetical)
+        .comment_placement(CommentPlacement::First)
+        .align_enums(false)
+        .block_line_width(Some(80))
+        .xml_declaration(false);
+    let xml = registry.to_xml(&options);
+    assert!(xml.starts_with("<registry>\n\t<comment>"));
+    assert!(xml.contains("\t<vendorids comment=\"Vendor IDs\">\n"));
+    assert!(xml.contains("\t\t<vendorid comment=\"Khronos\" id=\"0x10000\" name=\"KHR\"/>\n"));
+    assert!(xml.contains("\t\t<remove><type name=\"VkDevice\"/></remove>\n"));
+    assert!(xml.contains("\t\t\t<require api=\"vulkan\" extension=\"VK_KHR_b\">\n"));
+    let (reparsed, errors) = parse(&xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, registry);
+}
+
+#[test]
+fn test_minimized() {
+    use vk_parse::WriteOptions;
+
+    let xml = r#"<registry>
+        <comment>Copyright</comment>
+        <types>
+            <type name="uint32_t"/>
+            <type category="basetype">typedef <type>uint32_t</type> <name>VkFlags</name>;</type>
+            <comment>Bitmasks</comment>
+            <type requires="VkABits" category="bitmask">typedef <type>VkFlags</type> <name>VkAFlags</name>;</type>
+            <type category="enum" name="VkABits"/>
+            <type category="enum" name="VkStructureType"/>
+            <type category="handle"><type>VK_DEFINE_HANDLE</type>(<name>VkDevice</name>)</type>
+            <type category="struct" name="VkA"><member><type>VkAFlags</type> <name>flags</name></member><member><type>uint32_t</type> <name>ids</name>[<enum>VK_MAX</enum>]</member></type>
+            <type category="struct" name="VkB"/>
+            <type category="struct" name="VkC"/>
+        </types>
+        <enums name="API Constants">
+            <enum value="8" name="VK_MAX_BASE"/>
+            <enum name="VK_MAX" alias="VK_MAX_BASE"/>
+            <enum value="1" name="VK_UNUSED"/>
+        </enums>
+        <enums name="VkABits" type="bitmask"><enum bitpos="0" name="VK_A_0"/></enums>
+        <enums name="VkStructureType" type="enum"><enum value="0" name="VK_STRUCTURE_TYPE_0"/></enums>
+        <commands>
+            <command><proto><type>void</type> <name>vkA</name></proto><param><type>VkDevice</type> <name>device</name></param><param><type>VkA</type>* <name>pA</name></param></command>
+            <command name="vkAKHR" alias="vkA"/>
+            <command><proto><type>void</type> <name>vkC</name></proto></command>
+        </commands>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0">
+            <require><type name="VkStructureType"/></require>
+        </feature>
+        <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+            <require><type name="VkB"/></require>
+        </feature>
+        <feature api="vulkansc" name="VKSC_VERSION_1_0" number="1.0">
+            <require><type name="VkC"/></require>
+        </feature>
+        <extensions>
+            <extension name="VK_KHR_a" number="1" requires="VK_KHR_b" requiresCore="1.1" supported="vulkan">
+                <require><command name="vkAKHR"/><enum offset="0" extends="VkStructureType" name="VK_STRUCTURE_TYPE_A"/></require>
+                <require extension="VK_KHR_c"><command name="vkC"/></require>
+                <require extension="VK_KHR_b+(VK_VERSION_1_0,VK_VERSION_1_1)"><type name="VkB"/></require>
+            </extension>
+            <extension name="VK_KHR_b" number="2" supported="vulkan"/>
+            <extension name="VK_KHR_c" number="3" supported="vulkan">
+                <require><type name="VkC"/></require>
+            </extension>
+        </extensions>
+        <formats>
+            <format name="VK_STRUCTURE_TYPE_A" class="8-bit" blockSize="1" texelsPerBlock="1"><component name="R" bits="8" numericFormat="UNORM"/></format>
+            <format name="VK_FORMAT_OTHER" class="8-bit" blockSize="1" texelsPerBlock="1"><component name="R" bits="8" numericFormat="UNORM"/></format>
+        </formats>
+        <spirvextensions>
+            <spirvextension name="SPV_a"><enable extension="VK_KHR_a"/></spirvextension>
+            <spirvextension name="SPV_c"><enable extension="VK_KHR_c"/></spirvextension>
+        </spirvextensions>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let minimized = registry.minimized(vec!["VK_KHR_a"]);
+    let names = |items: Vec<&str>| items.into_iter().map(String::from).collect::<Vec<_>>();
+    assert_eq!(
+        names(minimized.features().map(|f| &*f.name).collect()),
+        ["VK_VERSION_1_0", "VK_VERSION_1_1"]
+    );
+    assert_eq!(
+        names(minimized.extensions().map(|e| &*e.name).collect()),
+        ["VK_KHR_a", "VK_KHR_b"]
+    );
+    // The parenthesized condition is satisfied by `VK_KHR_b` and either feature.
+    assert_eq!(minimized.extensions().next().unwrap().children.len(), 2);
+    assert_eq!(
+        names(
+            minimized
+                .types()
+                .filter_map(|t| t.declared_name())
+                .collect()
+        ),
+        [
+            "uint32_t",
+            "VkFlags",
+            "VkAFlags",
+            "VkABits",
+            "VkStructureType",
+            "VkDevice",
+            "VkA",
+            "VkB"
+        ]
+    );
+    assert_eq!(
+        names(minimized.commands().map(|c| c.name()).collect()),
+        ["vkA", "vkAKHR"]
+    );
+    let enums: Vec<_> = minimized
+        .enums_blocks()
+        .flat_map(|block| &block.children)
+        .filter_map(|child| match *child {
+            vk_parse::EnumsChild::Enum(ref e) => Some(e.name.to_string()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        enums,
+        ["VK_MAX_BASE", "VK_MAX", "VK_A_0", "VK_STRUCTURE_TYPE_0"]
+    );
+    assert_eq!(minimized.dangling_references(), []);
+
+    let xml = minimized.to_xml(&WriteOptions::new());
+    assert!(xml.contains("<format name=\"VK_STRUCTURE_TYPE_A\""));
+    assert!(!xml.contains("VK_FORMAT_OTHER"));
+    assert!(xml.contains("SPV_a"));
+    assert!(!xml.contains("SPV_c"));
+    assert!(!xml.contains("<comment>Bitmasks</comment>"));
+    let (reparsed, errors) = parse(&xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, minimized);
+}
+
+#[test]
+fn test_parse_stream_lossy() {
+    use vk_parse::{Error, RegistryChild};
+
+    let xml = r#"<registry>
+        <tags><tag name="KHR" author="Khronos" contact="-" bogus="1"/></tags>
+        <types><type name="a"></types>
+        <commands comment="Commands"/>
+        </registry>"#;
+    assert!(parse(xml, &ParseOptions::new()).is_ok());
+    let (registry, errors) =
+        vk_parse::parse_stream_lossy(xml.as_bytes(), &ParseOptions::new().strict(true));
+    assert_eq!(registry.0.len(), 3);
+    match registry.0[1] {
+        RegistryChild::Malformed(ref section) => {
+            assert_eq!(&*section.name, "types");
+            assert_eq!(&*section.text, r#"<types><type name="a"></types>"#);
+        }
+        ref other => panic!("{:?}", other),
+    }
+    match registry.0[2] {
+        RegistryChild::Commands(ref commands) => {
+            assert_eq!(commands.comment.as_deref(), Some("Commands"))
+        }
+        ref other => panic!("{:?}", other),
+    }
+    assert_eq!(errors.len(), 2);
+    match errors[0] {
+        Error::UnexpectedAttribute { ref name, .. } => assert_eq!(name, "bogus"),
+        ref other => panic!("{:?}", other),
+    }
+    match errors[1] {
+        Error::ReadError { ref xpath, .. } => assert_eq!(xpath, "/registry/types/type"),
+        ref other => panic!("{:?}", other),
+    }
+    // Malformed sections are written back as they were.
+    let written = registry.to_xml(&vk_parse::WriteOptions::new());
+    assert!(written.contains("\n    <types><type name=\"a\"></types>\n"));
+
+    let (registry, errors) =
+        vk_parse::parse_stream_lossy(&b"<registry><tags>"[..], &ParseOptions::new());
+    // Read before the problem.
+    assert_eq!(registry.0.len(), 1);
+    match errors[..] {
+        [Error::ReadError { ref xpath, .. }] => assert_eq!(xpath, "/registry/tags"),
+        ref other => panic!("{:?}", other),
+    }
+
+    let (registry, errors) = vk_parse::parse_stream_lossy(&b""[..], &ParseOptions::new());
+    assert_eq!(registry.0, []);
+    assert!(matches!(errors.last(), Some(Error::MissingElement { .. })));
+}
+
+#[test]
+fn test_coverage() {
+    use vk_parse::Error;
+
+    let xml = r#"<registry version="1">
+        <comment kind="license">Text</comment>
+        <tags><tag name="KHR" author="Khronos" contact="-"><note/>text</tag></tags>
+        <types>stray
+            <type category="basetype">typedef <type>uint32_t</type> <name span="1">VkFlags</name>;</type>
+            <type category="struct" name="VkA"><member><type>uint32_t</type> <name>a</name><comment>see <b>b</b></comment></member></type>
+        </types>
+        <formats comment="Formats"/>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let options = ParseOptions::new().coverage(true);
+    let (covered, errors) = parse(xml, &options).unwrap();
+    assert_eq!(covered, registry);
+    assert!(errors.iter().all(|e| e.is_coverage()));
+    let report: Vec<_> = errors
+        .iter()
+        .map(|e| match *e {
+            Error::IgnoredElement {
+                ref xpath,
+                ref name,
+            } => format!("element {} {}", xpath, name),
+            Error::IgnoredAttribute {
+                ref xpath,
+                ref name,
+            } => format!("attribute {} {}", xpath, name),
+            Error::IgnoredText { ref xpath } => format!("text {}", xpath),
+            ref other => panic!("{:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        report,
+        [
+            "attribute /registry version",
+            "attribute /registry/comment kind",
+            "element /registry/tags/tag/note note",
+            "text /registry/tags/tag",
+            "text /registry/types",
+            "attribute /registry/types/type/name span",
+            "element /registry/types/type/member/comment/b b",
+            "attribute /registry/formats comment",
+        ]
+    );
+
+    // Coverage reports don't fail strict parsing.
+    assert!(parse(xml, &options.strict(true)).is_ok());
+}
+
+#[test]
+fn test_verify_roundtrip() {
+    use vk_parse::{RegistryChild, RoundtripMismatch, TypeMember, TypeMemberMarkup, TypeSpec};
+
+    let (mut registry, _errors) = parse(WRITER_REGISTRY, &ParseOptions::new()).unwrap();
+    assert_eq!(registry.verify_roundtrip(), []);
+    let (omitted, _errors) = parse(WRITER_REGISTRY, &ParseOptions::new().omit_code(true)).unwrap();
+    assert_eq!(
+        omitted.verify_roundtrip_with_options(
+            &ParseOptions::new().omit_code(true),
+            &vk_parse::WriteOptions::new()
+        ),
+        []
+    );
+
+    // Markup which isn't part of the code, and comments of formats, can't be written.
+    for child in &mut registry.0 {
+        match *child {
+            RegistryChild::Types(ref mut types) => {
+                for child in &mut types.children {
+                    if let vk_parse::TypesChild::Type(ref mut ty) = *child {
+                        if let TypeSpec::Members(ref mut members) = ty.spec {
+                            if let TypeMember::Definition(ref mut def) = members[0] {
+                                def.markup[1] = TypeMemberMarkup::Name("sTypo".into());
+                            }
+                        }
+                    }
+                }
+            }
+            RegistryChild::Formats(ref mut formats) => formats.comment = Some("Formats".into()),
+            _ => {}
+        }
+    }
+    let mismatches = registry.verify_roundtrip();
+    assert_eq!(mismatches.len(), 2, "{:?}", mismatches);
+    match mismatches[0] {
+        RoundtripMismatch::Item {
+            kind,
+            ref name,
+            ref expected,
+            ref found,
+        } => {
+            assert_eq!((kind, &**name), ("type", "VkA"));
+            assert!(expected.is_some() && found.is_some());
+        }
+        ref other => panic!("{:?}", other),
+    }
+    match mismatches[1] {
+        RoundtripMismatch::Section { index, .. } => {
+            assert!(matches!(registry.0[index], RegistryChild::Formats(_)))
+        }
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_validation_disabled_extensions() {
+    use vk_parse::{EditError, Enum, EnumSpec, ValidationOptions};
+
+    let xml = r#"<registry>
+        <types><type category="enum" name="VkStructureType"/></types>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0"/>
+        <extensions>
+            <extension name="VK_KHR_a" number="1" supported="vulkan"/>
+            <extension name="VK_KHR_reserved" number="2" supported="disabled" promotedto="VK_KHR_never">
+                <require>
+                    <type name="VkNever"/>
+                    <enum offset="0" extends="VkStructureType" name="VK_STRUCTURE_TYPE_RESERVED"/>
+                </require>
+            </extension>
+        </extensions>
+        </registry>"#;
+    let (mut registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let audit = ValidationOptions::new().include_disabled(true);
+    assert_eq!(registry.dangling_references(), []);
+    let dangling: Vec<_> = registry
+        .dangling_references_with_options(&audit)
+        .into_iter()
+        .map(|d| (d.via, d.to.to_string()))
+        .collect();
+    assert_eq!(
+        dangling,
+        [
+            ("promotedto", "VK_KHR_never".to_string()),
+            ("require", "VkNever".to_string())
+        ]
+    );
+
+    // Same value as the enum of the disabled extension.
+    let mut e = Enum::default();
+    e.name = "VK_STRUCTURE_TYPE_A".into();
+    e.spec = EnumSpec::Offset {
+        offset: 0,
+        extends: "VkStructureType".into(),
+        extnumber: Some(2),
+        dir: true,
+    };
+    assert_eq!(
+        registry.add_enum_with_options("VK_KHR_a", e.clone(), &audit),
+        Err(EditError::DuplicateValue {
+            name: "VK_STRUCTURE_TYPE_A".into(),
+            existing: "VK_STRUCTURE_TYPE_RESERVED".into()
+        })
+    );
+    assert_eq!(registry.add_enum("VK_KHR_a", e), Ok(()));
+
+    // Contents of disabled extensions aren't checked, but their numbers are still reserved.
+    let mut extension = vk_parse::Extension::default();
+    extension.name = "VK_KHR_placeholder".into();
+    extension.number = Some(3);
+    extension.supported = Some(vk_parse::ExtensionSupport::Disabled);
+    extension.requires = Some("VK_KHR_missing".into());
+    assert!(registry
+        .clone()
+        .add_extension_with_options(extension.clone(), &audit)
+        .is_err());
+    assert_eq!(registry.add_extension(extension.clone()), Ok(()));
+    extension.name = "VK_KHR_other".into();
+    assert_eq!(
+        registry.add_extension(extension),
+        Err(EditError::DuplicateNumber {
+            number: 3,
+            extension: "VK_KHR_placeholder".into()
+        })
+    );
+}
+
+#[test]
+fn test_registry_dialect() {
+    use std::sync::{Arc, Mutex};
+    use vk_parse::{Error, RegistryDialect, RegistrySection, XmlEvent};
+
+    let xml = r#"<registry>
+        <comment>OpenXR registry</comment>
+        <tags><tag name="EXT" author="Multivendor" contact="someone"/></tags>
+        <types><type name="XrBool32" category="basetype"/></types>
+        <commands>
+            <command><proto><type>XrResult</type> <name>xrCreateInstance</name></proto></command>
+        </commands>
+        <feature api="openxr" name="XR_VERSION_1_0" number="1.0"/>
+        </registry>"#;
+
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(registry.0.len(), 5);
+
+    let options = ParseOptions::new().dialect(RegistryDialect::Core);
+    let (core, errors) = parse(xml, &options).unwrap();
+    assert_eq!(
+        errors,
+        [Error::UnexpectedElement {
+            xpath: "/registry/tags".into(),
+            name: "tags".into()
+        }]
+    );
+    let mut expected = registry.clone();
+    expected.0.remove(1);
+    assert_eq!(core, expected);
+
+    // Sections of other registries are left to hooks.
+    let gl = r#"<registry>
+        <groups><group name="Boolean"><enum name="GL_FALSE"/><enum name="GL_TRUE"/></group></groups>
+        <feature api="gl" name="GL_VERSION_1_0" number="1.0"/>
+        </registry>"#;
+    let groups = Arc::new(Mutex::new(Vec::new()));
+    let found = groups.clone();
+    let options = ParseOptions::new()
+        .dialect(RegistryDialect::Core)
+        .element_hook("groups", move |element| {
+            for event in element.events {
+                if let XmlEvent::StartElement { name, .. } = event {
+                    found.lock().unwrap().push(name.local_name);
+                }
+            }
+        });
+    let (registry, errors) = parse(gl, &options).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(registry.features().count(), 1);
+    assert_eq!(*groups.lock().unwrap(), ["group", "enum", "enum"]);
+
+    assert!(RegistryDialect::Core.has_section(RegistrySection::Feature));
+    assert!(!RegistryDialect::Core.has_section(RegistrySection::Tags));
+    assert!(RegistryDialect::Vulkan.has_section(RegistrySection::Formats));
+}
+
+#[test]
+fn test_enable_without_kind() {
+    use vk_parse::{Error, RegistryChild};
+
+    let xml = r#"<registry><spirvextensions>
+        <spirvextension name="SPV_a"><enable/><enable extension="VK_KHR_a"/></spirvextension>
+        </spirvextensions></registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(
+        errors,
+        [Error::SchemaViolation {
+            xpath: "/registry/spirvextensions/spirvextension".into(),
+            desc: "Missing version, extension, struct or property on enable.".into()
+        }]
+    );
+    match registry.0[0] {
+        RegistryChild::SpirvExtensions(ref s) => assert_eq!(s.children[0].enables.len(), 1),
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_from_events() {
+    use vk_parse::{Error, RegistryChild};
+
+    fn next_element(events: &mut vk_parse::Events<&[u8]>) -> String {
+        loop {
+            if let xml::reader::XmlEvent::StartElement { name, .. } =
+                events.next().unwrap().unwrap()
+            {
+                return name.local_name;
+            }
+        }
+    }
+
+    let xml = r#"<document>
+        <registry>
+            <types><type name="VkBool32" category="basetype" bogus=""/></types>
+        </registry>
+        <sections>
+            <comment>Loose section</comment>
+            <unknown/>
+            <commands/>
+        </sections>
+        <after/>
+        </document>"#;
+    let mut events = vk_parse::ParserConfig::new()
+        .create_reader(xml.as_bytes())
+        .into_iter();
+    assert_eq!(next_element(&mut events), "document");
+
+    let options = ParseOptions::new();
+    let (registry, errors) = vk_parse::parse_registry_from_events(&mut events, &options).unwrap();
+    assert_eq!(registry.types().count(), 1);
+    assert_eq!(
+        errors,
+        [Error::UnexpectedAttribute {
+            xpath: "/registry/types/type".into(),
+            name: "bogus".into()
+        }]
+    );
+
+    assert_eq!(next_element(&mut events), "sections");
+    let mut parse_section = || vk_parse::parse_section_from_events(&mut events, &options).unwrap();
+    assert!(matches!(parse_section(), (Some(RegistryChild::Comment(_)), ref e) if e.is_empty()));
+    assert_eq!(
+        parse_section(),
+        (
+            None,
+            vec![Error::UnexpectedElement {
+                xpath: "/registry/unknown".into(),
+                name: "unknown".into()
+            }]
+        )
+    );
+    assert!(matches!(parse_section(), (Some(RegistryChild::Commands(_)), ref e) if e.is_empty()));
+    // End of the enclosing element.
+    assert_eq!(parse_section(), (None, vec![]));
+
+    // Reading stops right after the parsed element.
+    assert_eq!(next_element(&mut events), "after");
+    assert!(matches!(
+        vk_parse::parse_registry_from_events(&mut events, &options),
+        Err(FatalError::MissingRegistryElement)
+    ));
+}
+
+#[test]
+fn test_element_hook() {
+    use std::sync::{Arc, Mutex};
+    use vk_parse::{Error, XmlEvent};
+
+    let xml = r#"<registry>
+        <types>
+            <type name="VkBool32" category="basetype"><note id="1">Hidden <b>text</b></note></type>
+            <note id="2"/>
+        </types>
+        <enums name="API Constants">
+            <enum name="VK_TRUE" value="1"><note id="3"/></enum>
+        </enums>
+        <note id="4"/>
+        <other/>
+        </registry>"#;
+
+    let notes = Arc::new(Mutex::new(Vec::new()));
+    let found = notes.clone();
+    let options = ParseOptions::new().element_hook("note", move |element| {
+        let text: String = element
+            .events
+            .filter_map(|e| match e {
+                XmlEvent::Characters(text) => Some(text),
+                _ => None,
+            })
+            .collect();
+        let id = &element.attributes["id"];
+        let note = format!("{} {} {:?}", element.xpath, id, text);
+        found.lock().unwrap().push(note);
+    });
+    let (registry, errors) = parse(xml, &options).unwrap();
+    assert_eq!(
+        errors,
+        [Error::UnexpectedElement {
+            xpath: "/registry/other".into(),
+            name: "other".into()
+        }]
+    );
+    assert_eq!(registry.types().count(), 1);
+    assert_eq!(registry.enums_blocks().count(), 1);
+    assert_eq!(
+        *notes.lock().unwrap(),
+        [
+            r#"/registry/types/type/note 1 "Hidden text""#,
+            r#"/registry/types/note 2 """#,
+            r#"/registry/enums/enum/note 3 """#,
+            r#"/registry/note 4 """#,
+        ]
+    );
+}
+
+#[test]
+fn test_platform_name() {
+    use vk_parse::{PlatformName, RegistryChild};
+
+    let xml = r#"<registry>
+        <platforms>
+            <platform name="xlib" protect="VK_USE_PLATFORM_XLIB_KHR" comment="X Window System, Xlib client library"/>
+            <platform name="future" protect="VK_USE_PLATFORM_FUTURE"/>
+        </platforms>
+        </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let platforms = match registry.0[0] {
+        RegistryChild::Platforms(ref platforms) => &platforms.children,
+        _ => panic!(),
+    };
+    assert_eq!(platforms[0].name, PlatformName::Xlib);
+    assert_eq!(
+        platforms[0].name.guard_macro(),
+        Some(&*platforms[0].protect)
+    );
+    assert_eq!(platforms[0].name.headers(), ["X11/Xlib.h"]);
+    assert_eq!(platforms[1].name, PlatformName::Other("future".into()));
+    assert_eq!(platforms[1].name.to_string(), "future");
+    assert_eq!(platforms[1].name.guard_macro(), None);
+    assert_eq!(PlatformName::from("xlib_xrandr").as_str(), "xlib_xrandr");
+    assert_eq!(
+        ron::ser::to_string(&platforms[0].name).unwrap(),
+        ron::ser::to_string(&"xlib").unwrap()
+    );
+}
+
+#[test]
+fn test_error_codes() {
+    use vk_parse::Error;
+
+    let xml = r#"<registry><tags><tag name="KHR" author="Khronos" contact="-" bogus="1"/><tag/></tags></registry>"#;
+    let (_, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    let codes: Vec<_> = errors.iter().map(Error::code).collect();
+    assert_eq!(codes, ["VKP0002", "VKP0005"]);
+
+    let error = parse("<other/>", &ParseOptions::new()).unwrap_err();
+    assert_eq!(error.code(), "VKP0101");
+    let error = parse(xml, &ParseOptions::new().strict(true)).unwrap_err();
+    assert_eq!(error.code(), "VKP0103");
+}
+
+#[test]
+fn test_diagnostics() {
+    let xml = r#"<registry>
+    <tags>
+        <tag name="KHR" author="Khronos" contact="-" bogus="1"/>
+    </tags>
+</registry>"#;
+    let (_, diagnostics) = vk_parse::parse_str_with_diagnostics(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].position, Some((3, 9)));
+    assert_eq!(
+        diagnostics[0].render(xml),
+        r#"error[VKP0002]: unexpected attribute `bogus`
+  |
+3 |         <tag name="KHR" author="Khronos" contact="-" bogus="1"/>
+  |         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+  |
+  = note: at /registry/tags/tag"#
+    );
+
+    let failure =
+        vk_parse::parse_str_with_diagnostics(xml, &ParseOptions::new().strict(true)).unwrap_err();
+    assert_eq!(failure.error.code(), "VKP0103");
+    assert_eq!(failure.position, Some((3, 9)));
+    assert_eq!(failure.diagnostics, diagnostics);
+
+    let options = ParseOptions::new().max_elements(2);
+    let failure = vk_parse::parse_str_with_diagnostics(xml, &options).unwrap_err();
+    assert_eq!(failure.error.code(), "VKP0104");
+    assert_eq!(failure.position, Some((2, 5)));
+}
+
+#[test]
+fn test_calling_convention() {
+    use vk_parse::TypeSpec;
+
+    let types = parse_types(
+        r#"<registry><types>
+        <type category="funcpointer">typedef void (VKAPI_PTR *<name>PFN_vkVoidFunction</name>)(void);</type>
+        <type category="funcpointer">typedef void (<apientry/> *<name>GLDEBUGPROC</name>)(<type>GLenum</type> source);</type>
+        <type category="funcpointer">typedef void (<apientry>XRAPI_PTR</apientry> *<name>PFN_xrVoidFunction</name>)(void);</type>
+        <type category="funcpointer">typedef void (*<name>PFN_plain</name>)(void);</type>
+        <type category="basetype">typedef <type>uint32_t</type> <name>VkFlags</name>;</type>
+        </types></registry>"#,
+    );
+    let conventions: Vec<_> = types
+        .iter()
+        .map(|ty| match ty.spec {
+            TypeSpec::Code(ref code) => code.calling_convention.as_deref(),
+            _ => panic!(),
+        })
+        .collect();
+    assert_eq!(
+        conventions,
+        [Some("VKAPI_PTR"), Some(""), Some("XRAPI_PTR"), None, None]
+    );
+
+    let (registry, errors) = parse(
+        r#"<registry><commands>
+        <command><proto><type>void</type> <apientry>APIENTRY</apientry> <name>glFlush</name></proto></command>
+        <command><proto><type>void</type> <name>vkVoid</name></proto></command>
+        </commands></registry>"#,
+        &ParseOptions::new(),
+    )
+    .unwrap();
+    assert_eq!(errors, []);
+    let protos: Vec<_> = registry
+        .commands()
+        .map(|command| match *command {
+            vk_parse::Command::Definition(ref def) => def.proto.clone(),
+            _ => panic!(),
+        })
+        .collect();
+    assert_eq!(protos[0].calling_convention.as_deref(), Some("APIENTRY"));
+    assert_eq!(protos[0].to_string(), "void glFlush");
+    assert_eq!(protos[1].calling_convention, None);
+    let xml = registry.to_xml(&vk_parse::WriteOptions::new());
+    assert_eq!(parse(&xml, &ParseOptions::new()).unwrap().0, registry);
+}
+
+#[test]
+fn test_c_helpers() {
+    use vk_parse::c;
+    use vk_parse::CLiteral;
+
+    assert!(c::is_c_identifier("_vkFoo2"));
+    assert!(!c::is_c_identifier("2D"));
+    assert!(!c::is_c_identifier(""));
+    assert!(c::is_c_keyword("const"));
+    assert!(!c::is_c_keyword("uint32_t"));
+
+    assert_eq!(
+        c::split_literal(" 0x7FFFFFFFU)"),
+        Some(("0x7FFFFFFFU", ")"))
+    );
+    assert_eq!(c::split_literal("1e-3f*2"), Some(("1e-3f", "*2")));
+    assert_eq!(c::split_literal(".5"), Some((".5", "")));
+    assert_eq!(c::split_literal(r#""a\"b" x"#), Some((r#""a\"b""#, " x")));
+    assert_eq!(c::split_literal("'\\''"), Some(("'\\''", "")));
+    assert_eq!(c::split_literal("\"unterminated"), None);
+    assert_eq!(c::split_literal("VK_TRUE"), None);
+    let (literal, _) = c::split_literal("1000UL - 1").unwrap();
+    assert!(matches!(
+        CLiteral::parse(literal),
+        Some(CLiteral::Integer { value: 1000, .. })
+    ));
+
+    assert_eq!(
+        c::split_declarator("const char* const* ppEnabledLayerNames"),
+        Some(("const char* const*", "ppEnabledLayerNames", ""))
+    );
+    assert_eq!(
+        c::split_declarator("uint32_t mask:8;"),
+        Some(("uint32_t", "mask", ":8"))
+    );
+    assert_eq!(c::split_declarator("uint32_t"), None);
+    assert_eq!(c::split_declarator("uint32_t const"), None);
+}
+
+const PROMOTIONS: &str = r#"<registry>
+    <types>
+        <type category="struct" name="VkFooProperties"><member><type>uint32_t</type> <name>x</name></member></type>
+        <type category="struct" name="VkFooPropertiesKHR" alias="VkFooProperties"/>
+        <type category="struct" name="VkBar"><member><type>VkFooPropertiesKHR</type> <name>foo</name></member></type>
+    </types>
+    <enums name="VkResult" type="enum">
+        <enum value="-1000" name="VK_ERROR_FOO"/>
+    </enums>
+    <commands>
+        <command><proto><type>void</type> <name>vkGetFoo</name></proto><param><type>VkFooProperties</type>* <name>pProperties</name></param></command>
+        <command name="vkGetFooKHR" alias="vkGetFoo"/>
+        <command errorcodes="VK_ERROR_FOO_KHR"><proto><type>void</type> <name>vkGetBar</name></proto><param><type>VkFooPropertiesKHR</type>* <name>pProperties</name></param></command>
+    </commands>
+    <feature api="vulkan" name="VK_VERSION_1_0" number="1.0"/>
+    <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+        <require>
+            <type name="VkFooProperties"/>
+            <command name="vkGetFoo"/>
+            <enum name="VK_ERROR_FOO"/>
+        </require>
+    </feature>
+    <extensions>
+        <extension name="VK_KHR_foo" number="1" supported="vulkan" promotedto="VK_VERSION_1_1">
+            <require>
+                <type name="VkFooPropertiesKHR"/>
+                <command name="vkGetFooKHR"/>
+                <enum extends="VkResult" name="VK_ERROR_FOO_KHR" alias="VK_ERROR_FOO"/>
+            </require>
+        </extension>
+        <extension name="VK_EXT_bar" number="2" supported="vulkan">
+            <require>
+                <type name="VkBar"/>
+                <command name="vkGetBar"/>
+            </require>
+        </extension>
+    </extensions>
+</registry>"#;
+
+#[test]
+fn test_apply_promotions() {
+    let (mut registry, errors) = parse(PROMOTIONS, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert!(registry
+        .clone()
+        .apply_promotions("VK_VERSION_1_0")
+        .is_empty());
+
+    let renames = registry.apply_promotions("VK_VERSION_1_1");
+    let renames: Vec<_> = renames
+        .iter()
+        .map(|(name, core_name)| (&**name, &**core_name))
+        .collect();
+    assert_eq!(
+        renames,
+        [
+            ("VK_ERROR_FOO_KHR", "VK_ERROR_FOO"),
+            ("VkFooPropertiesKHR", "VkFooProperties"),
+            ("vkGetFooKHR", "vkGetFoo"),
+        ]
+    );
+
+    let type_names: Vec<_> = registry
+        .types()
+        .filter_map(|ty| ty.declared_name())
+        .collect();
+    assert_eq!(type_names, ["VkFooProperties", "VkBar"]);
+    let command_names: Vec<_> = registry.commands().map(|c| c.name()).collect();
+    assert_eq!(command_names, ["vkGetFoo", "vkGetBar"]);
+    match *registry.commands().nth(1).unwrap() {
+        vk_parse::Command::Definition(ref def) => {
+            assert_eq!(def.errorcodes, ["VK_ERROR_FOO".into()]);
+            assert_eq!(
+                def.params[0].definition.type_name.as_deref(),
+                Some("VkFooProperties")
+            );
+            assert!(!def.code.contains("KHR"));
+        }
+        ref other => panic!("{:?}", other),
+    }
+    let extension = registry.extensions().next().unwrap();
+    match extension.children[0] {
+        vk_parse::ExtensionChild::Require { ref items, .. } => {
+            let names: Vec<_> = items
+                .iter()
+                .map(|item| match *item {
+                    vk_parse::InterfaceItem::Type { ref name, .. }
+                    | vk_parse::InterfaceItem::Command { ref name, .. } => &**name,
+                    ref other => panic!("{:?}", other),
+                })
+                .collect();
+            assert_eq!(names, ["VkFooProperties", "vkGetFoo"]);
+        }
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_video_api() {
+    use vk_parse::{RegistryChild, VideoCoding, VideoOperation, WriteOptions};
+
+    let xml = r#"<registry>
+    <types>
+        <type category="struct" name="VkVideoCapabilitiesKHR"/>
+        <type category="struct" name="VkVideoDecodeCapabilitiesKHR" structextends="VkVideoCapabilitiesKHR"/>
+        <type category="struct" name="VkVideoDecodeH264CapabilitiesKHR" structextends="VkVideoCapabilitiesKHR"/>
+    </types>
+    <commands>
+        <command videocoding="inside"><proto><type>void</type> <name>vkCmdDecodeVideoKHR</name></proto></command>
+        <command videocoding="both"><proto><type>void</type> <name>vkCmdPipelineBarrier</name></proto></command>
+        <command><proto><type>void</type> <name>vkCmdDraw</name></proto></command>
+    </commands>
+    <extensions>
+        <extension name="VK_KHR_video_queue" number="24" supported="vulkan">
+            <require>
+                <type name="VkVideoCapabilitiesKHR"/>
+                <command name="vkCmdBeginVideoCodingKHR"/>
+            </require>
+        </extension>
+        <extension name="VK_KHR_video_decode_queue" number="25" supported="vulkan">
+            <require>
+                <type name="VkVideoDecodeCapabilitiesKHR"/>
+                <command name="vkCmdDecodeVideoKHR"/>
+            </require>
+        </extension>
+        <extension name="VK_KHR_video_decode_h264" number="41" supported="vulkan">
+            <require>
+                <type name="vk_video/vulkan_video_codec_h264std_decode.h"/>
+                <type name="VkVideoDecodeH264CapabilitiesKHR"/>
+                <type name="VkVideoDecodeH264ProfileInfoKHR"/>
+                <enum extends="VkVideoCodecOperationFlagBitsKHR" bitpos="0" name="VK_VIDEO_CODEC_OPERATION_DECODE_H264_BIT_KHR"/>
+            </require>
+        </extension>
+        <extension name="VK_KHR_video_encode_h264" number="39" supported="disabled"/>
+        <extension name="VK_KHR_surface" number="1" supported="vulkan"/>
+    </extensions>
+    <videocodecs>
+        <videocodec name="Decode">
+            <videocapabilities struct="VkVideoDecodeCapabilitiesKHR"/>
+            <videoformat name="Decode Output" usage="VK_IMAGE_USAGE_VIDEO_DECODE_DST_BIT_KHR">
+                <videorequirecapabilities struct="VkVideoDecodeCapabilitiesKHR" member="flags" value="VK_VIDEO_DECODE_CAPABILITY_DPB_AND_OUTPUT_DISTINCT_BIT_KHR"/>
+            </videoformat>
+        </videocodec>
+        <videocodec name="H.264 Decode" extend="Decode" value="VK_VIDEO_CODEC_OPERATION_DECODE_H264_BIT_KHR">
+            <videoprofiles struct="VkVideoDecodeH264ProfileInfoKHR">
+                <videoprofilemember name="stdProfileIdc">
+                    <videoprofile value="STD_VIDEO_H264_PROFILE_IDC_BASELINE" name="Baseline"/>
+                    <videoprofile value="STD_VIDEO_H264_PROFILE_IDC_MAIN" name="Main"/>
+                </videoprofilemember>
+            </videoprofiles>
+            <videocapabilities struct="VkVideoDecodeH264CapabilitiesKHR"/>
+        </videocodec>
+        <videocodec name="H.264 Encode" extend="Encode" value="VK_VIDEO_CODEC_OPERATION_ENCODE_H264_BIT_KHR"/>
+    </videocodecs>
+</registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let codecs = match registry.0[3] {
+        RegistryChild::VideoCodecs(ref codecs) => codecs,
+        ref other => panic!("{:?}", other),
+    };
+    let h264 = &codecs.children[1];
+    assert_eq!(h264.extend.as_deref(), Some("Decode"));
+    let profiles = &h264.profiles[0].members[0].profiles;
+    assert_eq!(&*profiles[1].name, "Main");
+    assert_eq!(&*profiles[1].value, "STD_VIDEO_H264_PROFILE_IDC_MAIN");
+    let format = &codecs.children[0].formats[0];
+    assert_eq!(format.name.as_deref(), Some("Decode Output"));
+    assert_eq!(&*format.required_capabilities[0].member, "flags");
+
+    let groups = registry.video_api();
+    let summary: Vec<_> = groups
+        .iter()
+        .map(|g| {
+            let extensions: Vec<_> = g.extensions.iter().map(|e| &*e.name).collect();
+            (
+                g.codec.map(|c| &*c.name),
+                g.operation,
+                extensions,
+                g.commands.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                None,
+                None,
+                vec!["VK_KHR_video_queue"],
+                vec!["vkCmdBeginVideoCodingKHR", "vkCmdPipelineBarrier"]
+            ),
+            (
+                Some("Decode"),
+                Some(VideoOperation::Decode),
+                vec!["VK_KHR_video_decode_queue"],
+                vec!["vkCmdDecodeVideoKHR"]
+            ),
+            (
+                Some("H.264 Decode"),
+                Some(VideoOperation::Decode),
+                vec!["VK_KHR_video_decode_h264"],
+                vec![]
+            ),
+        ]
+    );
+    assert_eq!(groups[0].capabilities, Vec::<&str>::new());
+    assert_eq!(groups[1].capabilities, ["VkVideoDecodeCapabilitiesKHR"]);
+    assert_eq!(groups[2].types.len(), 3);
+    assert_eq!(groups[2].capabilities, ["VkVideoDecodeH264CapabilitiesKHR"]);
+
+    let video_coding: Vec<_> = registry
+        .commands()
+        .map(|c| match *c {
+            vk_parse::Command::Definition(ref def) => def.video_coding(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(
+        video_coding,
+        [VideoCoding::Inside, VideoCoding::Both, VideoCoding::Outside]
+    );
+
+    let (reparsed, errors) =
+        parse(&registry.to_xml(&WriteOptions::new()), &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, registry);
+
+    let xml = r#"<registry>
+    <commands>
+        <command videocoding="sometimes"><proto><type>void</type> <name>vkCmdFoo</name></proto></command>
+    </commands>
+</registry>"#;
+    let (_registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    match errors[..] {
+        [vk_parse::Error::UnexpectedAttributeValue {
+            ref name,
+            ref value,
+            ..
+        }] => assert_eq!((&**name, &**value), ("videocoding", "sometimes")),
+        ref other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn test_timeline() {
+    let xml = r#"<registry>
+    <commands>
+        <command><proto><type>void</type> <name>vkGetFoo</name></proto></command>
+        <command name="vkGetFooKHR" alias="vkGetFoo"/>
+        <command name="vkGetFooEXT" alias="vkGetFooKHR"/>
+    </commands>
+    <feature api="vulkan,vulkansc" name="VK_VERSION_1_0" number="1.0">
+        <require><command name="vkDestroyFoo"/></require>
+    </feature>
+    <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+        <require><command name="vkGetFoo"/></require>
+        <deprecate explanationlink="deprecation-foo"><command name="vkDestroyFoo"/></deprecate>
+    </feature>
+    <feature api="vulkansc" name="VKSC_VERSION_1_0" number="1.0">
+        <remove><command name="vkDestroyFoo"/></remove>
+    </feature>
+    <extensions>
+        <extension name="VK_EXT_foo" number="1" supported="vulkan" deprecatedby="VK_KHR_foo">
+            <require><command name="vkGetFooEXT"/></require>
+        </extension>
+        <extension name="VK_KHR_foo" number="2" supported="vulkan" promotedto="VK_VERSION_1_1">
+            <require><command name="vkGetFooKHR"/></require>
+        </extension>
+    </extensions>
+</registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert!(registry.timeline("vkUnknown").is_none());
+
+    let timeline = registry.timeline("vkGetFoo").unwrap();
+    let introduced = timeline.introduced.unwrap();
+    assert_eq!(
+        (introduced.by, introduced.is_feature, introduced.api),
+        ("VK_VERSION_1_1", true, Some(&apis("vulkan")))
+    );
+    assert!(timeline.promoted.is_none());
+
+    let timeline = registry.timeline("vkGetFooEXT").unwrap();
+    assert_eq!(timeline.introduced.unwrap().by, "VK_EXT_foo");
+    let (promoted_by, promoted_name) = timeline.promoted.unwrap();
+    assert_eq!(
+        (promoted_by.by, promoted_name),
+        ("VK_VERSION_1_1", "vkGetFoo")
+    );
+    assert_eq!(timeline.deprecated_extensions.len(), 1);
+    assert_eq!(
+        timeline.deprecated_extensions[0].replacement,
+        Some("VK_KHR_foo")
+    );
+
+    let timeline = registry.timeline("vkDestroyFoo").unwrap();
+    assert_eq!(timeline.required_by.len(), 1);
+    assert_eq!(timeline.deprecated.len(), 1);
+    assert_eq!(timeline.deprecated[0].deprecated_by, "VK_VERSION_1_1");
+    let removed: Vec<_> = timeline.removed_by.iter().map(|e| (e.by, e.api)).collect();
+    assert_eq!(removed, [("VKSC_VERSION_1_0", Some(&apis("vulkansc")))]);
+}
+
+#[test]
+fn test_fuzzing_metadata() {
+    use vk_parse::{FuzzingMetadata, ParamDirection, ValueKind};
+
+    let xml = r#"<registry>
+    <types>
+        <type category="handle" parent="VkInstance" objtypeenum="VK_OBJECT_TYPE_DEVICE"><type>VK_DEFINE_HANDLE</type>(<name>VkDevice</name>)</type>
+        <type category="handle" objtypeenum="VK_OBJECT_TYPE_BUFFER"><type>VK_DEFINE_NON_DISPATCHABLE_HANDLE</type>(<name>VkBuffer</name>)</type>
+        <type category="enum" name="VkStructureType"/>
+        <type category="enum" name="VkBufferUsageFlagBits"/>
+        <type requires="VkBufferUsageFlagBits" category="bitmask">typedef <type>VkFlags</type> <name>VkBufferUsageFlags</name>;</type>
+        <type category="struct" name="VkBufferCreateInfo">
+            <member values="VK_STRUCTURE_TYPE_BUFFER_CREATE_INFO"><type>VkStructureType</type> <name>sType</name></member>
+            <member optional="true">const <type>void</type>* <name>pNext</name></member>
+            <member><type>VkBufferUsageFlags</type> <name>usage</name></member>
+            <member optional="true"><type>uint32_t</type> <name>queueFamilyIndexCount</name></member>
+            <member len="queueFamilyIndexCount">const <type>uint32_t</type>* <name>pQueueFamilyIndices</name></member>
+        </type>
+    </types>
+    <enums name="VkStructureType" type="enum">
+        <enum value="12" name="VK_STRUCTURE_TYPE_BUFFER_CREATE_INFO"/>
+    </enums>
+    <enums name="VkBufferUsageFlagBits" type="bitmask">
+        <enum bitpos="0" name="VK_BUFFER_USAGE_TRANSFER_SRC_BIT"/>
+        <enum name="VK_BUFFER_USAGE_TRANSFER_SOURCE_BIT" alias="VK_BUFFER_USAGE_TRANSFER_SRC_BIT"/>
+    </enums>
+    <commands>
+        <command>
+            <proto><type>VkResult</type> <name>vkCreateBuffer</name></proto>
+            <param><type>VkDevice</type> <name>device</name></param>
+            <param>const <type>VkBufferCreateInfo</type>* <name>pCreateInfo</name></param>
+            <param><type>VkBuffer</type>* <name>pBuffer</name></param>
+        </command>
+    </commands>
+    <extensions>
+        <extension name="VK_KHR_foo" number="3" supported="vulkan">
+            <require>
+                <enum offset="1" extends="VkStructureType" name="VK_STRUCTURE_TYPE_FOO_KHR"/>
+                <enum bitpos="5" extends="VkBufferUsageFlagBits" name="VK_BUFFER_USAGE_FOO_BIT_KHR"/>
+            </require>
+        </extension>
+    </extensions>
+</registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let metadata = registry.fuzzing_metadata();
+    let command = &metadata.commands[0];
+    assert_eq!(&*command.name, "vkCreateBuffer");
+    let params: Vec<_> = command
+        .params
+        .iter()
+        .map(|p| (&*p.name, &*p.type_name, p.kind, p.direction))
+        .collect();
+    assert_eq!(
+        params,
+        [
+            (
+                "device",
+                "VkDevice",
+                ValueKind::Handle,
+                Some(ParamDirection::Input)
+            ),
+            (
+                "pCreateInfo",
+                "VkBufferCreateInfo",
+                ValueKind::Struct,
+                Some(ParamDirection::Input)
+            ),
+            (
+                "pBuffer",
+                "VkBuffer",
+                ValueKind::Handle,
+                Some(ParamDirection::Output)
+            ),
+        ]
+    );
+
+    let info = &metadata.structs[0];
+    assert_eq!(
+        info.s_type.as_deref(),
+        Some("VK_STRUCTURE_TYPE_BUFFER_CREATE_INFO")
+    );
+    assert_eq!(info.members[1].optional, [true]);
+    assert_eq!(info.members[2].kind, ValueKind::Bitmask);
+    assert_eq!(
+        info.members[2].domain.as_deref(),
+        Some("VkBufferUsageFlagBits")
+    );
+    assert_eq!(&*info.members[3].counts[0], "pQueueFamilyIndices");
+    assert_eq!(&*info.members[4].len[0], "queueFamilyIndexCount");
+
+    let domains: Vec<_> = metadata
+        .enums
+        .iter()
+        .map(|d| {
+            let values: Vec<_> = d.values.iter().map(|v| (&*v.name, v.value)).collect();
+            (&*d.name, d.is_bitmask, values)
+        })
+        .collect();
+    assert_eq!(
+        domains,
+        [
+            (
+                "VkStructureType",
+                false,
+                vec![
+                    ("VK_STRUCTURE_TYPE_BUFFER_CREATE_INFO", Some(12)),
+                    ("VK_STRUCTURE_TYPE_FOO_KHR", Some(1000002001)),
+                ]
+            ),
+            (
+                "VkBufferUsageFlagBits",
+                true,
+                vec![
+                    ("VK_BUFFER_USAGE_TRANSFER_SRC_BIT", Some(1)),
+                    ("VK_BUFFER_USAGE_FOO_BIT_KHR", Some(32)),
+                ]
+            ),
+        ]
+    );
+
+    let text = ron::ser::to_string(&metadata).unwrap();
+    let parsed: FuzzingMetadata = ron::de::from_str(&text).unwrap();
+    assert_eq!(parsed, metadata);
+}
+
+#[test]
+fn test_capi() {
+    use std::ffi::{CStr, CString};
+    use std::os::raw::c_int;
+    use vk_parse_capi::*;
+
+    unsafe {
+        let registry = vk_parse_bytes(PROMOTIONS.as_ptr(), PROMOTIONS.len());
+        assert!(!registry.is_null());
+        assert_eq!(vk_parse_error_count(registry), 0);
+        assert_eq!(vk_parse_count(registry, VkParseKind::Command as c_int), 3);
+        assert_eq!(vk_parse_count(registry, VkParseKind::Enum as c_int), 2);
+        assert_eq!(vk_parse_count(registry, VkParseKind::Feature as c_int), 2);
+        let name = vk_parse_name(registry, VkParseKind::Extension as c_int, 1);
+        assert_eq!(CStr::from_ptr(name).to_str(), Ok("VK_EXT_bar"));
+        assert!(vk_parse_name(registry, VkParseKind::Extension as c_int, 2).is_null());
+
+        let name = CString::new("vkGetFoo").unwrap();
+        let json = vk_parse_json(registry, VkParseKind::Command as c_int, name.as_ptr());
+        assert!(!json.is_null());
+        let text = CStr::from_ptr(json).to_str().unwrap();
+        assert!(text.starts_with('['));
+        assert!(text.contains("\"vkGetFoo\""));
+        vk_parse_string_free(json);
+        assert!(vk_parse_json(registry, VkParseKind::Type as c_int, name.as_ptr()).is_null());
+        assert_eq!(vk_parse_count(registry, 5), 0);
+        assert!(vk_parse_name(registry, -1, 0).is_null());
+        assert!(vk_parse_json(registry, 5, name.as_ptr()).is_null());
+        let error = CStr::from_ptr(vk_parse_last_error()).to_str().unwrap();
+        assert_eq!(error, "unknown kind 5");
+        vk_parse_free(registry);
+
+        let invalid = "<types/>";
+        assert!(vk_parse_bytes(invalid.as_ptr(), invalid.len()).is_null());
+        assert!(!vk_parse_last_error().is_null());
+    }
+}
+
+#[test]
+fn test_digest() {
+    let first = r#"<registry>
+        <types>
+            <type category="struct" name="VkExtent2D"><member><type>uint32_t</type>   <name>width</name></member></type>
+            <type name="VkBool32" category="basic">typedef <type>uint32_t</type> <name>VkBool32</name>;</type>
+        </types>
+        <enums name="VkResult" type="enum"><enum value="0" name="VK_SUCCESS" comment="Success"/><enum value="1" name="VK_NOT_READY"/></enums>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0"><require><type name="VkBool32"/></require></feature>
+    </registry>"#;
+    let second_xml = r#"<registry>
+        <comment>Reordered, with different comments and whitespace</comment>
+        <feature api="vulkan" name="VK_VERSION_1_0" number="1.0" comment="Core"><require><type name="VkBool32"/></require></feature>
+        <enums name="VkResult" type="enum"><enum name="VK_NOT_READY" value="1"/><enum name="VK_SUCCESS" value="0"/></enums>
+        <types><type category="basic" name="VkBool32">typedef <type>uint32_t</type>    <name>VkBool32</name>;</type></types>
+        <types><type category="struct" name="VkExtent2D"><member><type>uint32_t</type> <name>width</name></member></type></types>
+    </registry>"#;
+    let (first, errors) = parse(first, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let (second, errors) = parse(second_xml, &ParseOptions::new().raw_attributes(true)).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(first.digest(), second.digest());
+
+    let changed = second_xml.replace(r#"value="1""#, r#"value="2""#);
+    let (changed, _errors) = parse(&changed, &ParseOptions::new()).unwrap();
+    assert_ne!(changed.digest(), first.digest());
+}
+
+#[test]
+fn test_sync() {
+    use vk_parse::{RegistryChild, SyncChild, WriteOptions};
+
+    let xml = r#"<registry>
+    <sync comment="Synchronization">
+        <syncstage name="VK_PIPELINE_STAGE_2_VERTEX_SHADER_BIT">
+            <syncsupport queues="graphics"/>
+            <syncequivalent stage="VK_PIPELINE_STAGE_2_PRE_RASTERIZATION_SHADERS_BIT"/>
+        </syncstage>
+        <syncstage name="VK_PIPELINE_STAGE_2_TRANSFER_BIT_KHR" alias="VK_PIPELINE_STAGE_2_TRANSFER_BIT"/>
+        <syncaccess name="VK_ACCESS_2_SHADER_READ_BIT">
+            <comment>Read access to a shader resource</comment>
+            <syncsupport stage="VK_PIPELINE_STAGE_2_VERTEX_SHADER_BIT,VK_PIPELINE_STAGE_2_FRAGMENT_SHADER_BIT"/>
+            <syncequivalent access="VK_ACCESS_2_SHADER_SAMPLED_READ_BIT,VK_ACCESS_2_SHADER_STORAGE_READ_BIT"/>
+        </syncaccess>
+        <syncpipeline name="graphics primitive pipeline">
+            <syncpipelinestage order="None" before="VK_PIPELINE_STAGE_2_VERTEX_SHADER_BIT">VK_PIPELINE_STAGE_2_DRAW_INDIRECT_BIT</syncpipelinestage>
+            <syncpipelinestage>VK_PIPELINE_STAGE_2_VERTEX_SHADER_BIT</syncpipelinestage>
+        </syncpipeline>
+    </sync>
+</registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    let sync = match registry.0[0] {
+        RegistryChild::Sync(ref sync) => sync,
+        _ => panic!("unexpected registry child"),
+    };
+    assert_eq!(sync.comment.as_deref(), Some("Synchronization"));
+    let names: Vec<_> = sync.children.iter().map(|c| c.name()).collect();
+    assert_eq!(
+        names,
+        [
+            "VK_PIPELINE_STAGE_2_VERTEX_SHADER_BIT",
+            "VK_PIPELINE_STAGE_2_TRANSFER_BIT_KHR",
+            "VK_ACCESS_2_SHADER_READ_BIT",
+            "graphics primitive pipeline",
+        ]
+    );
+    match sync.children[0] {
+        SyncChild::Stage(ref stage) => {
+            let support = stage.support.as_ref().unwrap();
+            assert_eq!(support.queues, [vk_parse::QueueKind::Graphics]);
+            let equivalent = stage.equivalent.as_ref().unwrap();
+            assert_eq!(
+                equivalent.stage,
+                [Box::from(
+                    "VK_PIPELINE_STAGE_2_PRE_RASTERIZATION_SHADERS_BIT"
+                )]
+            );
+        }
+        _ => panic!("unexpected sync child"),
+    }
+    match sync.children[1] {
+        SyncChild::Stage(ref stage) => {
+            assert_eq!(
+                stage.alias.as_deref(),
+                Some("VK_PIPELINE_STAGE_2_TRANSFER_BIT")
+            );
+        }
+        _ => panic!("unexpected sync child"),
+    }
+    match sync.children[2] {
+        SyncChild::Access(ref access) => {
+            assert_eq!(
+                access.comment.as_deref(),
+                Some("Read access to a shader resource")
+            );
+            let support = access.support.as_ref().unwrap();
+            assert_eq!(support.stage.len(), 2);
+            let equivalent = access.equivalent.as_ref().unwrap();
+            assert_eq!(
+                equivalent.access,
+                [
+                    Box::from("VK_ACCESS_2_SHADER_SAMPLED_READ_BIT"),
+                    Box::from("VK_ACCESS_2_SHADER_STORAGE_READ_BIT")
+                ]
+            );
+        }
+        _ => panic!("unexpected sync child"),
+    }
+    match sync.children[3] {
+        SyncChild::Pipeline(ref pipeline) => {
+            assert_eq!(pipeline.stages.len(), 2);
+            assert_eq!(
+                &*pipeline.stages[0].name,
+                "VK_PIPELINE_STAGE_2_DRAW_INDIRECT_BIT"
+            );
+            assert_eq!(pipeline.stages[0].order.as_deref(), Some("None"));
+            assert_eq!(pipeline.stages[1].before, None);
+        }
+        _ => panic!("unexpected sync child"),
+    }
+
+    let (reparsed, errors) =
+        parse(&registry.to_xml(&WriteOptions::new()), &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, registry);
+
+    let xml = r#"<registry><sync>
+        <syncstage name="VK_PIPELINE_STAGE_2_NONE"><syncequivalent stage=",VK_PIPELINE_STAGE_2_NONE"/></syncstage>
+    </sync></registry>"#;
+    let (_registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert!(matches!(
+        errors[..],
+        [vk_parse::Error::UnexpectedAttributeValue { ref name, .. }] if name == "stage"
+    ));
+}
+
+#[test]
+fn test_extension_depends() {
+    use vk_parse::{DependsExpr, WriteOptions};
+
+    let xml = r#"<registry><extensions>
+        <extension name="VK_KHR_a" number="1" supported="vulkan" depends="VK_KHR_b+(VK_VERSION_1_1,VK_KHR_c)"/>
+        <extension name="VK_KHR_d" number="2" supported="vulkan" depends="VK_KHR_a"/>
+        <extension name="VK_KHR_e" number="3" supported="vulkan" depends="(VK_KHR_a"/>
+    </extensions></registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        vk_parse::Error::UnexpectedAttributeValue { ref name, .. } if name == "depends"
+    ));
+
+    let extensions: Vec<_> = registry.extensions().collect();
+    let leaf = |name: &str| DependsExpr::Leaf(name.into());
+    let depends = extensions[0].depends.as_ref().unwrap();
+    assert_eq!(
+        *depends,
+        DependsExpr::And(vec![
+            leaf("VK_KHR_b"),
+            DependsExpr::Or(vec![leaf("VK_VERSION_1_1"), leaf("VK_KHR_c")]),
+        ])
+    );
+    assert_eq!(depends.names(), ["VK_KHR_b", "VK_VERSION_1_1", "VK_KHR_c"]);
+    assert_eq!(depends.to_string(), "VK_KHR_b+(VK_VERSION_1_1,VK_KHR_c)");
+    assert!(depends.is_satisfied(|name| name == "VK_KHR_b" || name == "VK_KHR_c"));
+    assert!(!depends.is_satisfied(|name| name == "VK_KHR_b"));
+    assert_eq!(extensions[1].depends, Some(leaf("VK_KHR_a")));
+    assert_eq!(extensions[2].depends, None);
+
+    // Operators of the same kind are flattened, and `+` binds tighter than `,`.
+    assert_eq!(
+        DependsExpr::parse("a + (b + c) , d"),
+        Some(DependsExpr::Or(vec![
+            DependsExpr::And(vec![leaf("a"), leaf("b"), leaf("c")]),
+            leaf("d"),
+        ]))
+    );
+    assert_eq!(DependsExpr::parse("a,,b"), None);
+    assert_eq!(DependsExpr::parse("a)"), None);
+    // Deep nesting is rejected rather than overflowing the stack.
+    let nested = format!("{}a{}", "(".repeat(64), ")".repeat(64));
+    assert_eq!(DependsExpr::parse(&nested), Some(leaf("a")));
+    let nested = format!("{}a{}", "(".repeat(100_000), ")".repeat(100_000));
+    assert_eq!(DependsExpr::parse(&nested), None);
+
+    let xml = registry.to_xml(&WriteOptions::new());
+    assert!(xml.contains(r#"depends="VK_KHR_b+(VK_VERSION_1_1,VK_KHR_c)""#));
+}
+
+#[test]
+fn test_require_depends() {
+    use vk_parse::{DependsExpr, ExtensionChild, WriteOptions};
+
+    let xml = r#"<registry>
+    <types>
+        <type category="struct" name="VkA"/>
+        <type category="struct" name="VkB"/>
+    </types>
+    <feature api="vulkan" name="VK_VERSION_1_0" number="1.0"/>
+    <feature api="vulkan" name="VK_VERSION_1_1" number="1.1"/>
+    <extensions>
+        <extension name="VK_KHR_a" number="1" supported="vulkan">
+            <require><type name="VkA"/></require>
+            <require depends="VK_VERSION_1_1,VK_KHR_b"><type name="VkB"/></require>
+        </extension>
+        <extension name="VK_KHR_b" number="2" supported="vulkan"/>
+    </extensions>
+</registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let extension = registry.extensions().next().unwrap();
+    match extension.children[1] {
+        ExtensionChild::Require { ref depends, .. } => assert_eq!(
+            *depends,
+            Some(DependsExpr::Or(vec![
+                DependsExpr::Leaf("VK_VERSION_1_1".into()),
+                DependsExpr::Leaf("VK_KHR_b".into()),
+            ]))
+        ),
+        _ => panic!("unexpected extension child"),
+    }
+    let interactions = extension.interactions();
+    assert_eq!(interactions.len(), 1);
+    assert_eq!(
+        interactions[0].depends.map(|d| d.to_string()).as_deref(),
+        Some("VK_VERSION_1_1,VK_KHR_b")
+    );
+
+    // Blocks whose dependencies aren't included are left out.
+    let types = |registry: &vk_parse::Registry| -> Vec<String> {
+        registry
+            .types()
+            .filter_map(|ty| ty.declared_name().map(String::from))
+            .collect()
+    };
+    assert_eq!(types(&registry.minimized(["VK_KHR_a"])), ["VkA"]);
+    assert_eq!(
+        types(&registry.minimized(["VK_KHR_a", "VK_KHR_b"])),
+        ["VkA", "VkB"]
+    );
+
+    let (reparsed, errors) =
+        parse(&registry.to_xml(&WriteOptions::new()), &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, registry);
+}
+
+#[test]
+fn test_deprecated_attribute() {
+    use vk_parse::{Command, Deprecation, EnumsChild, TypeMember, TypeSpec, WriteOptions};
+
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkFoo" deprecated="true">
+                <member deprecated="ignored"><type>uint32_t</type> <name>enabledLayerCount</name></member>
+            </type>
+        </types>
+        <enums name="VkBar" type="enum">
+            <enum value="0" name="VK_BAR_A"/>
+            <enum name="VK_BAR_OLD" alias="VK_BAR_A" deprecated="aliased"/>
+        </enums>
+        <commands>
+            <command deprecated="true"><proto><type>void</type> <name>vkFoo</name></proto><param deprecated="ignored"><type>uint32_t</type> <name>flags</name></param></command>
+            <command name="vkFooKHR" alias="vkFoo" deprecated="aliased"/>
+            <command name="vkFooEXT" alias="vkFoo" deprecated="maybe"/>
+        </commands>
+        <extensions>
+            <extension name="VK_KHR_a" number="1" supported="vulkan" deprecated="true"/>
+        </extensions>
+    </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        vk_parse::Error::UnexpectedAttributeValue { ref name, ref value, .. }
+            if name == "deprecated" && value == "maybe"
+    ));
+    assert_eq!(
+        Deprecation::from_name("ignored"),
+        Some(Deprecation::Ignored)
+    );
+    assert_eq!(Deprecation::Deprecated.name(), "true");
+
+    let ty = registry.types().next().unwrap();
+    assert_eq!(ty.deprecated, Some(Deprecation::Deprecated));
+    match ty.spec {
+        TypeSpec::Members(ref members) => match members[0] {
+            TypeMember::Definition(ref def) => {
+                assert_eq!(def.deprecated, Some(Deprecation::Ignored))
+            }
+            _ => panic!("expected member definition"),
+        },
+        _ => panic!("expected members"),
+    }
+
+    let enums = registry.enums_blocks().next().unwrap();
+    let deprecations: Vec<_> = enums
+        .children
+        .iter()
+        .filter_map(|child| match *child {
+            EnumsChild::Enum(ref e) => Some(e.deprecated),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(deprecations, [None, Some(Deprecation::Aliased)]);
+
+    let commands: Vec<_> = registry.commands().map(Command::deprecated).collect();
+    assert_eq!(
+        commands,
+        [
+            Some(Deprecation::Deprecated),
+            Some(Deprecation::Aliased),
+            None
+        ]
+    );
+    match *registry.commands().next().unwrap() {
+        Command::Definition(ref def) => {
+            assert_eq!(def.params[0].deprecated, Some(Deprecation::Ignored))
+        }
+        _ => panic!("expected command definition"),
+    }
+    let extension = registry.extensions().next().unwrap();
+    assert_eq!(extension.deprecated, Some(Deprecation::Deprecated));
+
+    let xml = registry.to_xml(&WriteOptions::new());
+    assert_eq!(xml.matches(r#"deprecated="ignored""#).count(), 2);
+    let (reparsed, errors) = parse(&xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, registry);
+}
+
+#[test]
+fn test_api_set() {
+    use vk_parse::{Api, ApiSet, ExtensionChild, WriteOptions};
+
+    let xml = r#"<registry>
+        <types>
+            <type category="struct" name="VkFoo" api="vulkan,vulkansc"/>
+            <type category="struct" name="VkBar" api="vulkan,,vulkansc"/>
+        </types>
+        <feature api="vulkan,vulkansc" name="VK_VERSION_1_0" number="1.0">
+            <require api="vulkansc"><type name="VkFoo"/></require>
+        </feature>
+    </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        vk_parse::Error::UnexpectedAttributeValue { ref name, ref value, .. }
+            if name == "api" && value == "vulkan,,vulkansc"
+    ));
+
+    let types: Vec<_> = registry.types().map(|ty| ty.api.as_ref()).collect();
+    assert_eq!(types, [Some(&apis("vulkan,vulkansc")), None]);
+    let api = types[0].unwrap();
+    assert_eq!(api.apis, [Api::Vulkan, Api::VulkanSc]);
+    assert!(api.contains_api("vulkansc"));
+    assert!(!api.contains_api("opengl"));
+    assert_eq!(api.to_string(), "vulkan,vulkansc");
+
+    let feature = registry.features().next().unwrap();
+    assert_eq!(
+        feature.api.names().collect::<Vec<_>>(),
+        ["vulkan", "vulkansc"]
+    );
+    match feature.children[0] {
+        ExtensionChild::Require { ref api, .. } => assert_eq!(*api, Some(apis("vulkansc"))),
+        _ => panic!("expected require"),
+    }
+
+    // Unknown APIs are kept, and duplicates are dropped.
+    let other = apis("gles,vulkan,gles");
+    assert_eq!(other.apis, [Api::Other("gles".into()), Api::Vulkan]);
+    assert_eq!(ApiSet::parse(""), None);
+
+    let xml = registry.to_xml(&WriteOptions::new());
+    assert!(xml.contains(r#"api="vulkan,vulkansc" name="VK_VERSION_1_0""#));
+    let (reparsed, errors) = parse(&xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, registry);
+}
+
+#[test]
+fn test_feature_version() {
+    use vk_parse::{ApiVersion, WriteOptions};
+
+    let xml = r#"<registry>
+        <feature api="vulkan" name="VK_VERSION_1_10" number="1.10"/>
+        <feature api="vulkan" name="VK_VERSION_1_2" number="1.2"/>
+        <feature api="vulkan" name="VK_VERSION_X" number="1.x"/>
+    </registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        errors[0],
+        vk_parse::Error::UnexpectedAttributeValue { ref name, ref value, .. }
+            if name == "number" && value == "1.x"
+    ));
+    assert!(matches!(
+        errors[1],
+        vk_parse::Error::MissingAttribute { ref name, .. } if name == "number"
+    ));
+
+    let numbers: Vec<_> = registry.features().map(|f| f.number).collect();
+    assert_eq!(numbers, [ApiVersion::new(1, 10), ApiVersion::new(1, 2)]);
+    assert!(numbers[0] > numbers[1]);
+    assert_eq!(numbers[0].to_string(), "1.10");
+    assert_eq!(ApiVersion::parse("1.3"), Some(ApiVersion::new(1, 3)));
+    assert_eq!(ApiVersion::parse("1"), None);
+    assert_eq!(ApiVersion::parse("1.-3"), None);
+    assert_eq!(ApiVersion::parse("1.3.0"), None);
+
+    let xml = registry.to_xml(&WriteOptions::new());
+    assert!(xml.contains(r#"number="1.10""#));
+}
+
+#[test]
+fn test_extension_supported() {
+    use vk_parse::{Api, ExtensionSupport, WriteOptions};
+
+    let xml = r#"<registry><extensions>
+        <extension name="VK_KHR_a" number="1" supported="vulkan,vulkansc"/>
+        <extension name="VK_KHR_b" number="2" supported="disabled"/>
+        <extension name="VK_KHR_c" number="3"/>
+        <extension name="VK_KHR_d" number="4" supported="vulkan,disabled"/>
+    </extensions></registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        vk_parse::Error::UnexpectedAttributeValue { ref name, ref value, .. }
+            if name == "supported" && value == "vulkan,disabled"
+    ));
+
+    let extensions: Vec<_> = registry.extensions().collect();
+    assert_eq!(
+        extensions[0].supported,
+        Some(ExtensionSupport::Apis(apis("vulkan,vulkansc")))
+    );
+    assert!(extensions[0].supports("vulkansc"));
+    assert!(!extensions[0].supports("opengl"));
+    assert!(extensions[0].supports_api(&Api::VulkanSc));
+    assert!(!extensions[0].supports_api(&Api::from_name("opengl")));
+    assert!(!extensions[0].is_disabled());
+    assert_eq!(extensions[1].supported, Some(ExtensionSupport::Disabled));
+    assert!(extensions[1].is_disabled());
+    assert!(!extensions[1].supports("vulkan"));
+    assert!(!extensions[1].supports_api(&Api::Vulkan));
+    // Extensions without `supported` apply to all APIs.
+    assert!(extensions[2].supports("vulkan"));
+    assert!(extensions[2].supports_api(&Api::Vulkan));
+    assert_eq!(extensions[3].supported, None);
+    assert_eq!(ExtensionSupport::parse(""), None);
+
+    let xml = registry.to_xml(&WriteOptions::new());
+    assert!(xml.contains(r#"supported="vulkan,vulkansc""#));
+    assert!(xml.contains(r#"supported="disabled""#));
+}
+
+#[test]
+fn test_command_queues() {
+    use vk_parse::{Command, QueueKind, WriteOptions};
+
+    let xml = r#"<registry><commands>
+        <command queues="graphics,compute,sparse_binding,graphics,future_queue">
+            <proto><type>void</type> <name>vkCmdFoo</name></proto>
+        </command>
+        <command><proto><type>void</type> <name>vkBar</name></proto></command>
+        <command queues="graphics,"><proto><type>void</type> <name>vkCmdBaz</name></proto></command>
+    </commands></registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0],
+        vk_parse::Error::UnexpectedAttributeValue { ref name, ref value, .. }
+            if name == "queues" && value == "graphics,"
+    ));
+
+    let queues: Vec<_> = registry
+        .commands()
+        .map(|command| match *command {
+            Command::Definition(ref def) => def.queues.clone(),
+            _ => panic!("expected definition"),
+        })
+        .collect();
+    assert_eq!(
+        queues,
+        [
+            vec![
+                QueueKind::Graphics,
+                QueueKind::Compute,
+                QueueKind::SparseBinding,
+                QueueKind::Other("future_queue".into()),
+            ],
+            vec![],
+            vec![],
+        ]
+    );
+    assert_eq!(
+        QueueKind::join(&queues[0]),
+        "graphics,compute,sparse_binding,future_queue"
+    );
+    assert_eq!(
+        QueueKind::parse_list("transfer,opticalflow"),
+        Some(vec![QueueKind::Transfer, QueueKind::OpticalFlow])
+    );
+
+    let xml = registry.to_xml(&WriteOptions::new());
+    assert!(xml.contains(r#"queues="graphics,compute,sparse_binding,future_queue""#));
+    assert_eq!(xml.matches("queues=").count(), 1);
+}
+
+#[test]
+fn test_command_result_codes() {
+    use vk_parse::{Command, WriteOptions};
+
+    let xml = r#"<registry><commands>
+        <command successcodes="VK_SUCCESS,VK_INCOMPLETE" errorcodes="VK_ERROR_OUT_OF_HOST_MEMORY,VK_ERROR_OUT_OF_DEVICE_MEMORY">
+            <proto><type>VkResult</type> <name>vkEnumerateFoo</name></proto>
+        </command>
+        <command><proto><type>void</type> <name>vkDestroyFoo</name></proto></command>
+    </commands></registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+
+    let codes: Vec<_> = registry
+        .commands()
+        .map(|command| match *command {
+            Command::Definition(ref def) => (&def.successcodes[..], &def.errorcodes[..]),
+            _ => panic!("expected definition"),
+        })
+        .collect();
+    assert_eq!(
+        codes[0].0,
+        [Box::from("VK_SUCCESS"), Box::from("VK_INCOMPLETE")]
+    );
+    assert_eq!(
+        codes[0].1,
+        [
+            Box::from("VK_ERROR_OUT_OF_HOST_MEMORY"),
+            Box::from("VK_ERROR_OUT_OF_DEVICE_MEMORY")
+        ]
+    );
+    assert!(codes[1].0.is_empty() && codes[1].1.is_empty());
+
+    let xml = registry.to_xml(&WriteOptions::new());
+    assert!(xml.contains(r#"successcodes="VK_SUCCESS,VK_INCOMPLETE""#));
+    assert_eq!(xml.matches("codes=").count(), 2);
+    let (reparsed, errors) = parse(&xml, &ParseOptions::new()).unwrap();
+    assert_eq!(errors, []);
+    assert_eq!(reparsed, registry);
+
+    // Lists with an empty entry are reported, as for `queues`.
+    let xml = r#"<registry><commands>
+        <command successcodes="VK_SUCCESS," errorcodes="">
+            <proto><type>VkResult</type> <name>vkEnumerateFoo</name></proto>
+        </command>
+    </commands></registry>"#;
+    let (registry, errors) = parse(xml, &ParseOptions::new()).unwrap();
+    let names: Vec<_> = errors
+        .iter()
+        .map(|e| match *e {
+            vk_parse::Error::UnexpectedAttributeValue { ref name, .. } => &**name,
+            ref other => panic!("{:?}", other),
+        })
+        .collect();
+    assert_eq!(names, ["successcodes", "errorcodes"]);
+    match *registry.commands().next().unwrap() {
+        Command::Definition(ref def) => {
+            assert!(def.successcodes.is_empty() && def.errorcodes.is_empty())
+        }
+        _ => panic!("expected definition"),
+    };
+}
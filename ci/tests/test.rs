@@ -13,7 +13,7 @@ const URL_MAIN: &str = "https://raw.githubusercontent.com/KhronosGroup/Vulkan-Do
 fn download<T: std::io::Write>(dst: &mut T, url: &str) {
     let resp = minreq::get(url)
         .send()
-        .expect(&format!("Failed to GET resource: {:?}", url));
+        .unwrap_or_else(|_| panic!("Failed to GET resource: {:?}", url));
 
     let is_success = 200 <= resp.status_code && resp.status_code < 300;
     if !is_success {
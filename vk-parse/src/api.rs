@@ -0,0 +1,954 @@
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
+#[cfg(not(feature = "std"))]
+use core::{fmt, iter::FromIterator};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")]
+use std::{fmt, iter::FromIterator};
+
+use types::*;
+
+/// API which definitions can be specific to, as named in `api` attributes, e.g. `vulkan`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum Api {
+    /// `vulkan`, the Vulkan API.
+    Vulkan,
+    /// `vulkansc`, the Vulkan SC API for safety-critical systems.
+    VulkanSc,
+    /// Any other API, e.g. of a registry other than Vulkan's.
+    Other(Box<str>),
+}
+
+impl Api {
+    /// API with given name, e.g. `vulkansc`.
+    pub fn from_name(name: &str) -> Api {
+        match name {
+            "vulkan" => Api::Vulkan,
+            "vulkansc" => Api::VulkanSc,
+            _ => Api::Other(name.into()),
+        }
+    }
+
+    /// Name of the API, as used in `api`.
+    pub fn name(&self) -> &str {
+        match *self {
+            Api::Vulkan => "vulkan",
+            Api::VulkanSc => "vulkansc",
+            Api::Other(ref name) => name,
+        }
+    }
+}
+
+impl fmt::Display for Api {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// APIs a definition is specific to, given by a comma-separated `api` attribute, e.g.
+/// `vulkan,vulkansc`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct ApiSet {
+    /// APIs in order of appearance, without duplicates.
+    pub apis: Vec<Api>,
+}
+
+impl ApiSet {
+    /// Parses comma-separated list of APIs, returning `None` if it's empty or has an empty name.
+    pub fn parse(text: &str) -> Option<ApiSet> {
+        if text.split(',').any(str::is_empty) {
+            return None;
+        }
+        Some(text.split(',').map(Api::from_name).collect())
+    }
+
+    /// Whether the set contains API with given name.
+    pub fn contains_api(&self, api: &str) -> bool {
+        self.apis.iter().any(|a| a.name() == api)
+    }
+
+    /// Whether the set contains `api`.
+    pub fn contains(&self, api: &Api) -> bool {
+        self.apis.contains(api)
+    }
+
+    /// Names of the APIs, in order of appearance.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.apis.iter().map(Api::name)
+    }
+}
+
+impl FromIterator<Api> for ApiSet {
+    fn from_iter<I: IntoIterator<Item = Api>>(iter: I) -> ApiSet {
+        let mut apis = Vec::new();
+        for api in iter {
+            if !apis.contains(&api) {
+                apis.push(api);
+            }
+        }
+        ApiSet { apis }
+    }
+}
+
+/// Writes the set as in the registry, e.g. `vulkan,vulkansc`.
+impl fmt::Display for ApiSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, api) in self.apis.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            f.write_str(api.name())?;
+        }
+        Ok(())
+    }
+}
+
+/// APIs an extension is supported for, given by `supported`, e.g. `vulkan,vulkansc`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum ExtensionSupport {
+    /// `disabled`, the extension only reserves its number and isn't part of any API.
+    Disabled,
+    /// The extension is supported for the listed APIs.
+    Apis(ApiSet),
+}
+
+impl ExtensionSupport {
+    /// Parses `supported`, returning `None` if it's not a valid list of APIs or lists
+    /// `disabled` along with other names.
+    pub fn parse(text: &str) -> Option<ExtensionSupport> {
+        if text == "disabled" {
+            return Some(ExtensionSupport::Disabled);
+        }
+        let apis = ApiSet::parse(text)?;
+        if apis.contains_api("disabled") {
+            return None;
+        }
+        Some(ExtensionSupport::Apis(apis))
+    }
+
+    /// Whether the extension is supported for API with given name.
+    pub fn supports(&self, api: &str) -> bool {
+        match *self {
+            ExtensionSupport::Disabled => false,
+            ExtensionSupport::Apis(ref apis) => apis.contains_api(api),
+        }
+    }
+
+    /// Whether the extension is supported for `api`.
+    pub fn supports_api(&self, api: &Api) -> bool {
+        match *self {
+            ExtensionSupport::Disabled => false,
+            ExtensionSupport::Apis(ref apis) => apis.contains(api),
+        }
+    }
+}
+
+/// Writes the value as in the registry, e.g. `disabled`.
+impl fmt::Display for ExtensionSupport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ExtensionSupport::Disabled => f.write_str("disabled"),
+            ExtensionSupport::Apis(ref apis) => write!(f, "{}", apis),
+        }
+    }
+}
+
+/// Whether a list of APIs, such as `api` of a type, contains `api`. A missing list applies to
+/// all APIs.
+pub(crate) fn api_matches(list: Option<&ApiSet>, api: &str) -> bool {
+    list.map_or(true, |list| list.contains_api(api))
+}
+
+/// Whether a comma-separated list, such as `profile` of a block, contains `name`. A missing
+/// list applies to all names.
+pub(crate) fn list_matches(list: Option<&str>, name: &str) -> bool {
+    list.map_or(true, |list| list.split(',').any(|a| a == name))
+}
+
+impl Feature {
+    /// Items of `<require>` blocks applicable to `api` and `profile`, see
+    /// `Extension::requires_for`.
+    pub fn requires_for<'a: 'b, 'b>(
+        &'a self,
+        api: &'b str,
+        profile: Option<&'b str>,
+    ) -> impl Iterator<Item = &'a InterfaceItem> + 'b {
+        interface_items(&self.children, false, api, profile)
+    }
+
+    /// Items of `<remove>` blocks applicable to `api` and `profile`, see
+    /// `Extension::requires_for`.
+    pub fn removes_for<'a: 'b, 'b>(
+        &'a self,
+        api: &'b str,
+        profile: Option<&'b str>,
+    ) -> impl Iterator<Item = &'a InterfaceItem> + 'b {
+        interface_items(&self.children, true, api, profile)
+    }
+}
+
+impl Extension {
+    /// Items of `<require>` blocks applicable to `api` and `profile`, in order of the blocks.
+    ///
+    /// Blocks apply if their `api` lists `api`, and their `profile` is `profile`, where blocks
+    /// without the attributes apply to all APIs and profiles, and no `profile` accepts blocks
+    /// of all profiles. Enums with `api` not listing `api` are skipped, as are comments. Whether
+    /// the extension itself is supported for `api` isn't checked.
+    pub fn requires_for<'a: 'b, 'b>(
+        &'a self,
+        api: &'b str,
+        profile: Option<&'b str>,
+    ) -> impl Iterator<Item = &'a InterfaceItem> + 'b {
+        interface_items(&self.children, false, api, profile)
+    }
+
+    /// Items of `<remove>` blocks applicable to `api` and `profile`, see
+    /// `Extension::requires_for`.
+    pub fn removes_for<'a: 'b, 'b>(
+        &'a self,
+        api: &'b str,
+        profile: Option<&'b str>,
+    ) -> impl Iterator<Item = &'a InterfaceItem> + 'b {
+        interface_items(&self.children, true, api, profile)
+    }
+}
+
+/// Items of either `<require>` or `<remove>` blocks of `children` applicable to `api` and
+/// `profile`, see `Extension::requires_for`.
+fn interface_items<'a: 'b, 'b>(
+    children: &'a [ExtensionChild],
+    remove: bool,
+    api: &'b str,
+    profile: Option<&'b str>,
+) -> impl Iterator<Item = &'a InterfaceItem> + 'b {
+    children
+        .iter()
+        .flat_map(move |child| {
+            let (block_api, block_profile, items) = match *child {
+                ExtensionChild::Require {
+                    ref api,
+                    ref profile,
+                    ref items,
+                    ..
+                } if !remove => (api, profile, &items[..]),
+                ExtensionChild::Remove {
+                    ref api,
+                    ref profile,
+                    ref items,
+                    ..
+                } if remove => (api, profile, &items[..]),
+                _ => return [].iter(),
+            };
+            let applies = api_matches(block_api.as_ref(), api)
+                && profile.map_or(true, |profile| {
+                    list_matches(block_profile.as_deref(), profile)
+                });
+            if applies {
+                items.iter()
+            } else {
+                [].iter()
+            }
+        })
+        .filter(move |item| match **item {
+            InterfaceItem::Enum(ref e) => api_matches(e.api.as_ref(), api),
+            InterfaceItem::Comment(_) => false,
+            _ => true,
+        })
+}
+
+/// Names of items available for an API, in order of first appearance.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct ApiItems<'a> {
+    pub types: Vec<&'a str>,
+    pub commands: Vec<&'a str>,
+    pub enums: Vec<&'a str>,
+    pub extensions: Vec<&'a str>,
+}
+
+/// Differences between the interfaces of two APIs defined by the same registry, such as
+/// `vulkan` and `vulkansc`, see `Registry::api_differences`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct ApiDifferences<'a> {
+    /// Items available for the first API but not for the second one.
+    pub only_first: ApiItems<'a>,
+    /// Items available for the second API but not for the first one.
+    pub only_second: ApiItems<'a>,
+    /// Types defined differently for each API, as definitions for the first and second API.
+    pub altered_types: Vec<(&'a Type, &'a Type)>,
+    /// Enums defined differently for each API, as definitions for the first and second API.
+    pub altered_enums: Vec<(&'a Enum, &'a Enum)>,
+}
+
+impl Registry {
+    /// Items available for `api`, i.e. required by its features and by extensions supported
+    /// for it, minus items removed by its features, e.g. those removed by `VKSC_VERSION_1_0`.
+    /// Enums of `<enums>` blocks specific to an API are included as well.
+    pub fn api_items(&self, api: &str) -> ApiItems<'_> {
+        let mut available = Available::default();
+        for feature in self.features() {
+            if api_matches(Some(&feature.api), api) {
+                available.require(feature.requires_for(api, None));
+            }
+        }
+        for extension in self.extensions() {
+            if extension.supports(api) {
+                available.extensions.insert(&extension.name);
+                available.require(extension.requires_for(api, None));
+            }
+        }
+        for enums in self.enums_blocks() {
+            for child in &enums.children {
+                if let EnumsChild::Enum(ref e) = *child {
+                    if e.api.is_some() && api_matches(e.api.as_ref(), api) {
+                        available.enums.insert(&e.name);
+                    }
+                }
+            }
+        }
+        for feature in self.features() {
+            if api_matches(Some(&feature.api), api) {
+                available.remove(feature.removes_for(api, None));
+            }
+        }
+        for extension in self.extensions() {
+            if extension.supports(api) {
+                available.remove(extension.removes_for(api, None));
+            }
+        }
+        ApiItems {
+            types: available.types.order,
+            commands: available.commands.order,
+            enums: available.enums.order,
+            extensions: available.extensions.order,
+        }
+    }
+
+    /// Compares interfaces of two APIs, e.g. to find what differs between `vulkan` and
+    /// `vulkansc`. Items available for only one of them are found by `api_items`, while
+    /// altered items are types and enums with separate definitions for each API, given by
+    /// their `api` attributes.
+    pub fn api_differences(&self, first: &str, second: &str) -> ApiDifferences<'_> {
+        let first_items = self.api_items(first);
+        let second_items = self.api_items(second);
+        let types: Vec<_> = self
+            .types()
+            .filter(|ty| ty.api.is_some())
+            .filter_map(|ty| Some((ty.declared_name()?, ty.api.as_ref(), ty)))
+            .collect();
+        let enums: Vec<_> = self
+            .enums_blocks()
+            .flat_map(|enums| enums.children.iter())
+            .filter_map(|child| match *child {
+                EnumsChild::Enum(ref e) => Some(e),
+                _ => None,
+            })
+            .chain(self.features().flat_map(|f| required_enums(&f.children)))
+            .chain(self.extensions().flat_map(|e| required_enums(&e.children)))
+            .filter(|e| e.api.is_some())
+            .map(|e| (&*e.name, e.api.as_ref(), e))
+            .collect();
+
+        ApiDifferences {
+            only_first: only(&first_items, &second_items),
+            only_second: only(&second_items, &first_items),
+            altered_types: altered(&types, first, second),
+            altered_enums: altered(&enums, first, second),
+        }
+    }
+}
+
+/// Differences between definitions of a struct or command for two APIs, see
+/// `Registry::api_variant_differences`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct VariantDifferences<'a, T> {
+    /// Name of the struct or command.
+    pub name: &'a str,
+    /// Members or parameters defined only for the first API.
+    pub only_first: Vec<&'a T>,
+    /// Members or parameters defined only for the second API.
+    pub only_second: Vec<&'a T>,
+    /// Members or parameters with the same name but a different type, as definitions for the
+    /// first and second API.
+    pub changed: Vec<(&'a T, &'a T)>,
+}
+
+/// Differences between members of structs and parameters of commands defined differently for
+/// two APIs, see `Registry::api_variant_differences`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct ApiVariantReport<'a> {
+    pub structs: Vec<VariantDifferences<'a, TypeMemberDefinition>>,
+    pub commands: Vec<VariantDifferences<'a, CommandParam>>,
+}
+
+impl Registry {
+    /// Compares members of structs and parameters of commands between two APIs, e.g. to review
+    /// what differs in `vulkansc` when porting from `vulkan`. Variants are given by the `api`
+    /// attributes of types and commands defined repeatedly, and of individual members and
+    /// parameters. Members and parameters are identified by name, and are changed if their
+    /// `c_type` differs. When it isn't recognized, code of members and type names of parameters
+    /// are compared instead.
+    ///
+    /// Only structs, unions and commands defined for both APIs, with some differences, are
+    /// listed, in order of the registry.
+    pub fn api_variant_differences(&self, first: &str, second: &str) -> ApiVariantReport<'_> {
+        let mut report = ApiVariantReport::default();
+
+        let types: Vec<_> = self
+            .types()
+            .filter_map(|ty| match ty.spec {
+                TypeSpec::Members(ref members) => Some((ty.declared_name()?, ty, members)),
+                _ => None,
+            })
+            .collect();
+        let mut seen = BTreeSet::new();
+        for &(name, _, _) in &types {
+            if !seen.insert(name) {
+                continue;
+            }
+            let find = |api: &str| {
+                types
+                    .iter()
+                    .find(|&&(n, ty, _)| n == name && api_matches(ty.api.as_ref(), api))
+                    .map(|&(_, _, members)| members)
+            };
+            if let (Some(a), Some(b)) = (find(first), find(second)) {
+                let differences =
+                    variant_differences(name, member_items(a, first), member_items(b, second));
+                report.structs.extend(differences);
+            }
+        }
+
+        let commands: Vec<_> = self
+            .commands()
+            .filter_map(|command| match *command {
+                Command::Definition(ref def) => Some(def),
+                Command::Alias { .. } => None,
+            })
+            .collect();
+        let mut seen = BTreeSet::new();
+        for def in &commands {
+            let name = &*def.proto.name;
+            if !seen.insert(name) {
+                continue;
+            }
+            let find = |api: &str| {
+                commands
+                    .iter()
+                    .find(|d| &*d.proto.name == name && api_matches(d.api.as_ref(), api))
+                    .cloned()
+            };
+            if let (Some(a), Some(b)) = (find(first), find(second)) {
+                let differences =
+                    variant_differences(name, param_items(a, first), param_items(b, second));
+                report.commands.extend(differences);
+            }
+        }
+        report
+    }
+}
+
+impl Registry {
+    /// Collapses types and commands defined repeatedly for different APIs, such as a struct
+    /// defined once for `vulkan` and once for `vulkansc`, into a single definition whose `api`
+    /// lists all of their APIs. Members of structs and parameters of commands which aren't
+    /// shared by all of the definitions are kept, with `api` listing the APIs they're defined
+    /// for, the same way the registry marks individual members, so the differences remain
+    /// available, e.g. to `Registry::api_variant_differences`.
+    ///
+    /// The collapsed definition takes the place of the first one. Definitions are collapsed
+    /// only if they differ just in their APIs, members and parameters, and all of them have an
+    /// `api`. Code of collapsed commands is cleared unless it's the same for all of the
+    /// definitions, so that `Display` regenerates it.
+    pub fn collapse_api_variants(&mut self) {
+        let mut types: BTreeMap<&str, Vec<(usize, usize)>> = BTreeMap::new();
+        let mut commands: BTreeMap<&str, Vec<(usize, usize)>> = BTreeMap::new();
+        for (i, child) in self.0.iter().enumerate() {
+            match *child {
+                RegistryChild::Types(ref block) => {
+                    for (j, child) in block.children.iter().enumerate() {
+                        if let TypesChild::Type(ref ty) = *child {
+                            if let (Some(_), Some(name)) = (&ty.api, ty.declared_name()) {
+                                types.entry(name).or_default().push((i, j));
+                            }
+                        }
+                    }
+                }
+                RegistryChild::Commands(ref block) => {
+                    for (j, child) in block.children.iter().enumerate() {
+                        if let Command::Definition(ref def) = *child {
+                            if def.api.is_some() {
+                                commands.entry(&def.proto.name).or_default().push((i, j));
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let mut collapsed_types = Vec::new();
+        let mut collapsed_commands = Vec::new();
+        let mut removed = BTreeSet::new();
+        for positions in types.values().filter(|positions| positions.len() > 1) {
+            let variants: Vec<_> = positions
+                .iter()
+                .filter_map(|&(i, j)| match self.0[i] {
+                    RegistryChild::Types(ref block) => match block.children[j] {
+                        TypesChild::Type(ref ty) => Some(ty),
+                        TypesChild::Comment(_) => None,
+                    },
+                    _ => None,
+                })
+                .collect();
+            if let Some(ty) = collapse_types(&variants) {
+                collapsed_types.push((positions[0], ty));
+                removed.extend(positions[1..].iter().cloned());
+            }
+        }
+        for positions in commands.values().filter(|positions| positions.len() > 1) {
+            let variants: Vec<_> = positions
+                .iter()
+                .filter_map(|&(i, j)| match self.0[i] {
+                    RegistryChild::Commands(ref block) => match block.children[j] {
+                        Command::Definition(ref def) => Some(def),
+                        Command::Alias { .. } => None,
+                    },
+                    _ => None,
+                })
+                .collect();
+            if let Some(def) = collapse_commands(&variants) {
+                collapsed_commands.push((positions[0], def));
+                removed.extend(positions[1..].iter().cloned());
+            }
+        }
+
+        for ((i, j), ty) in collapsed_types {
+            if let RegistryChild::Types(ref mut block) = self.0[i] {
+                block.children[j] = TypesChild::Type(ty);
+            }
+        }
+        for ((i, j), def) in collapsed_commands {
+            if let RegistryChild::Commands(ref mut block) = self.0[i] {
+                block.children[j] = Command::Definition(def);
+            }
+        }
+        for (i, child) in self.0.iter_mut().enumerate() {
+            match *child {
+                RegistryChild::Types(ref mut block) => {
+                    let mut j = 0;
+                    block.children.retain(|_| {
+                        j += 1;
+                        !removed.contains(&(i, j - 1))
+                    });
+                }
+                RegistryChild::Commands(ref mut block) => {
+                    let mut j = 0;
+                    block.children.retain(|_| {
+                        j += 1;
+                        !removed.contains(&(i, j - 1))
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Member or parameter, which can be specific to some APIs, see
+/// `Registry::collapse_api_variants`.
+trait ApiVariant: Clone + PartialEq {
+    fn api(&self) -> Option<&ApiSet>;
+    fn set_api(&mut self, api: Option<ApiSet>);
+}
+
+impl ApiVariant for TypeMember {
+    fn api(&self) -> Option<&ApiSet> {
+        match *self {
+            TypeMember::Definition(ref def) => def.api.as_ref(),
+            TypeMember::Comment(_) => None,
+        }
+    }
+
+    fn set_api(&mut self, api: Option<ApiSet>) {
+        if let TypeMember::Definition(ref mut def) = *self {
+            def.api = api;
+        }
+    }
+}
+
+impl ApiVariant for CommandParam {
+    fn api(&self) -> Option<&ApiSet> {
+        self.api.as_ref()
+    }
+
+    fn set_api(&mut self, api: Option<ApiSet>) {
+        self.api = api;
+    }
+}
+
+fn collapse_types(variants: &[&Type]) -> Option<Type> {
+    let header = |ty: &Type| Type {
+        api: None,
+        spec: TypeSpec::None,
+        ..ty.clone()
+    };
+    let first = variants.first()?;
+    if variants.iter().any(|ty| header(ty) != header(first)) {
+        return None;
+    }
+    let spec = if variants.iter().all(|ty| ty.spec == first.spec) {
+        first.spec.clone()
+    } else {
+        let members = variants
+            .iter()
+            .map(|ty| match ty.spec {
+                TypeSpec::Members(ref members) => Some((ty.api.as_ref(), &members[..])),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        TypeSpec::Members(merge_variants(&members))
+    };
+    let apis = all_apis(variants.iter().map(|ty| ty.api.as_ref()));
+    Some(Type {
+        api: Some(api_set(&apis)),
+        spec,
+        ..header(first)
+    })
+}
+
+fn collapse_commands(variants: &[&CommandDefinition]) -> Option<CommandDefinition> {
+    let header = |def: &CommandDefinition| CommandDefinition {
+        api: None,
+        params: Vec::new(),
+        code: Box::default(),
+        ..def.clone()
+    };
+    let first = variants.first()?;
+    if variants.iter().any(|def| header(def) != header(first)) {
+        return None;
+    }
+    let params: Vec<_> = variants
+        .iter()
+        .map(|def| (def.api.as_ref(), &def.params[..]))
+        .collect();
+    let code = if variants.iter().all(|def| def.code == first.code) {
+        first.code.clone()
+    } else {
+        Box::default()
+    };
+    let apis = all_apis(variants.iter().map(|def| def.api.as_ref()));
+    Some(CommandDefinition {
+        api: Some(api_set(&apis)),
+        params: merge_variants(&params),
+        code,
+        ..header(first)
+    })
+}
+
+/// APIs of all lists, in order of first appearance.
+fn all_apis<'a, I: Iterator<Item = Option<&'a ApiSet>>>(lists: I) -> Vec<&'a str> {
+    let mut apis = Vec::new();
+    for api in lists.flatten().flat_map(ApiSet::names) {
+        if !apis.contains(&api) {
+            apis.push(api);
+        }
+    }
+    apis
+}
+
+/// Set of APIs with given names.
+fn api_set(names: &[&str]) -> ApiSet {
+    names.iter().map(|&name| Api::from_name(name)).collect()
+}
+
+/// Merges items of definitions for the given APIs, keeping the order of each, with items not
+/// defined for all of the APIs marked by the APIs they're defined for.
+fn merge_variants<T: ApiVariant>(variants: &[(Option<&ApiSet>, &[T])]) -> Vec<T> {
+    let all = all_apis(variants.iter().map(|&(api, _)| api));
+    let mut merged: Vec<(T, Vec<&str>)> = Vec::new();
+    for &(api, items) in variants {
+        let variant_apis = all_apis(core::iter::once(api));
+        let items = items
+            .iter()
+            .map(|item| {
+                let apis: Vec<_> = variant_apis
+                    .iter()
+                    .cloned()
+                    .filter(|&a| api_matches(item.api(), a))
+                    .collect();
+                let mut item = item.clone();
+                item.set_api(None);
+                (item, apis)
+            })
+            .filter(|(_, apis)| !apis.is_empty())
+            .collect();
+        merged = merge_lists(merged, items);
+    }
+    merged
+        .into_iter()
+        .map(|(mut item, apis)| {
+            if apis.len() < all.len() {
+                item.set_api(Some(api_set(&apis)));
+            }
+            item
+        })
+        .collect()
+}
+
+/// Merges two lists of items with their APIs, following their longest common subsequence, so
+/// that items in both lists appear once, with APIs of both.
+fn merge_lists<'a, T: PartialEq>(
+    first: Vec<(T, Vec<&'a str>)>,
+    second: Vec<(T, Vec<&'a str>)>,
+) -> Vec<(T, Vec<&'a str>)> {
+    let (n, m) = (first.len(), second.len());
+    // Lengths of common subsequences of suffixes starting at each pair of indices.
+    let mut lengths = vec![0usize; (n + 1) * (m + 1)];
+    let at = |i: usize, j: usize| i * (m + 1) + j;
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[at(i, j)] = if first[i].0 == second[j].0 {
+                lengths[at(i + 1, j + 1)] + 1
+            } else {
+                lengths[at(i + 1, j)].max(lengths[at(i, j + 1)])
+            };
+        }
+    }
+
+    let mut steps = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && first[i].0 == second[j].0 {
+            steps.push((true, true));
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lengths[at(i + 1, j)] >= lengths[at(i, j + 1)]) {
+            steps.push((true, false));
+            i += 1;
+        } else {
+            steps.push((false, true));
+            j += 1;
+        }
+    }
+
+    let mut first = first.into_iter();
+    let mut second = second.into_iter();
+    let mut merged = Vec::new();
+    for step in steps {
+        match step {
+            (true, true) => {
+                if let (Some((item, mut apis)), Some((_, other))) = (first.next(), second.next()) {
+                    for api in other {
+                        if !apis.contains(&api) {
+                            apis.push(api);
+                        }
+                    }
+                    merged.push((item, apis));
+                }
+            }
+            (true, false) => merged.extend(first.next()),
+            _ => merged.extend(second.next()),
+        }
+    }
+    merged
+}
+
+/// Member or parameter given by name, type, code and the item itself.
+type VariantItem<'a, T> = (&'a str, Option<&'a CType>, &'a str, &'a T);
+
+fn member_items<'a>(
+    members: &'a [TypeMember],
+    api: &str,
+) -> Vec<VariantItem<'a, TypeMemberDefinition>> {
+    members
+        .iter()
+        .filter_map(|member| match *member {
+            TypeMember::Definition(ref def) if api_matches(def.api.as_ref(), api) => {
+                Some((def.name()?, def.c_type.as_ref(), &*def.code, def))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn param_items<'a>(def: &'a CommandDefinition, api: &str) -> Vec<VariantItem<'a, CommandParam>> {
+    def.params
+        .iter()
+        .filter(|param| api_matches(param.api.as_ref(), api))
+        .map(|param| {
+            let definition = &param.definition;
+            // Code of the whole command is shared by parameters, so types are compared by name.
+            let code = definition.type_name.as_deref().unwrap_or("");
+            (&*definition.name, definition.c_type.as_ref(), code, param)
+        })
+        .collect()
+}
+
+fn variant_differences<'a, T>(
+    name: &'a str,
+    first: Vec<VariantItem<'a, T>>,
+    second: Vec<VariantItem<'a, T>>,
+) -> Option<VariantDifferences<'a, T>> {
+    let mut differences = VariantDifferences {
+        name,
+        only_first: Vec::new(),
+        only_second: Vec::new(),
+        changed: Vec::new(),
+    };
+    for &(name, c_type, code, item) in &first {
+        match second.iter().find(|&&(n, _, _, _)| n == name) {
+            None => differences.only_first.push(item),
+            Some(&(_, other_c_type, other_code, other)) => {
+                let is_changed = match (c_type, other_c_type) {
+                    (Some(a), Some(b)) => a != b,
+                    _ => code != other_code,
+                };
+                if is_changed {
+                    differences.changed.push((item, other));
+                }
+            }
+        }
+    }
+    for &(name, _, _, item) in &second {
+        if first.iter().all(|&(n, _, _, _)| n != name) {
+            differences.only_second.push(item);
+        }
+    }
+    let is_empty = differences.only_first.is_empty()
+        && differences.only_second.is_empty()
+        && differences.changed.is_empty();
+    if is_empty {
+        None
+    } else {
+        Some(differences)
+    }
+}
+
+/// Items available for an API, see `Registry::api_items`.
+#[derive(Default)]
+struct Available<'a> {
+    types: Names<'a>,
+    commands: Names<'a>,
+    enums: Names<'a>,
+    extensions: Names<'a>,
+}
+
+/// Names in order of first appearance, with fast lookup.
+#[derive(Default)]
+struct Names<'a> {
+    order: Vec<&'a str>,
+    set: BTreeSet<&'a str>,
+}
+
+impl<'a> Names<'a> {
+    fn insert(&mut self, name: &'a str) {
+        if self.set.insert(name) {
+            self.order.push(name);
+        }
+    }
+
+    fn remove_all(&mut self, names: &Names<'a>) {
+        self.order.retain(|name| !names.set.contains(name));
+        self.set.retain(|name| !names.set.contains(name));
+    }
+}
+
+impl<'a> Available<'a> {
+    fn require<I: Iterator<Item = &'a InterfaceItem>>(&mut self, items: I) {
+        for item in items {
+            self.insert(item);
+        }
+    }
+
+    fn remove<I: Iterator<Item = &'a InterfaceItem>>(&mut self, items: I) {
+        let mut removed = Available::default();
+        removed.require(items);
+        self.types.remove_all(&removed.types);
+        self.commands.remove_all(&removed.commands);
+        self.enums.remove_all(&removed.enums);
+    }
+
+    fn insert(&mut self, item: &'a InterfaceItem) {
+        match *item {
+            InterfaceItem::Type { ref name, .. } => self.types.insert(name),
+            InterfaceItem::Command { ref name, .. } => self.commands.insert(name),
+            InterfaceItem::Enum(ref e) => self.enums.insert(&e.name),
+            InterfaceItem::Comment(_) => {}
+        }
+    }
+}
+
+fn required_enums(children: &[ExtensionChild]) -> impl Iterator<Item = &Enum> {
+    children
+        .iter()
+        .flat_map(|child| match *child {
+            ExtensionChild::Require { ref items, .. } => items.iter(),
+            _ => [].iter(),
+        })
+        .filter_map(|item| match *item {
+            InterfaceItem::Enum(ref e) => Some(e),
+            _ => None,
+        })
+}
+
+/// Items of `items` which aren't in `other`.
+fn only<'a>(items: &ApiItems<'a>, other: &ApiItems<'a>) -> ApiItems<'a> {
+    fn missing<'a>(names: &[&'a str], other: &[&'a str]) -> Vec<&'a str> {
+        let other: BTreeSet<_> = other.iter().collect();
+        names
+            .iter()
+            .filter(|name| !other.contains(name))
+            .cloned()
+            .collect()
+    }
+    ApiItems {
+        types: missing(&items.types, &other.types),
+        commands: missing(&items.commands, &other.commands),
+        enums: missing(&items.enums, &other.enums),
+        extensions: missing(&items.extensions, &other.extensions),
+    }
+}
+
+/// Pairs of different definitions of the same name for each API, among definitions given by
+/// name, `api` and the item itself.
+fn altered<'a, T: PartialEq>(
+    items: &[(&'a str, Option<&'a ApiSet>, &'a T)],
+    first: &str,
+    second: &str,
+) -> Vec<(&'a T, &'a T)> {
+    let mut pairs = Vec::new();
+    let mut seen = BTreeSet::new();
+    for &(name, _, _) in items {
+        if !seen.insert(name) {
+            continue;
+        }
+        let find = |api: &str| {
+            items
+                .iter()
+                .find(|&&(n, item_api, _)| n == name && api_matches(item_api, api))
+                .map(|&(_, _, item)| item)
+        };
+        if let (Some(a), Some(b)) = (find(first), find(second)) {
+            if a != b {
+                pairs.push((a, b));
+            }
+        }
+    }
+    pairs
+}
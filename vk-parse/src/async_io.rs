@@ -0,0 +1,92 @@
+use std;
+use std::future::Future;
+use std::io::Read;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
+
+use options::ParseOptions;
+use parse::parse_stream_with_options;
+use types::*;
+
+/// Parses the Vulkan XML file from asynchronous stream into a Rust object.
+///
+/// Parsing itself runs on a blocking thread of the tokio runtime, so that it doesn't block
+/// threads driving other tasks. Returned future must be polled from within a tokio runtime.
+pub fn parse_stream_async<T>(stream: T) -> ParseFuture
+where
+    T: AsyncRead + Unpin + Send + 'static,
+{
+    parse_stream_async_with_options(stream, &ParseOptions::default())
+}
+
+/// Parses the Vulkan XML file from asynchronous stream into a Rust object, using given options.
+///
+/// See `parse_stream_async` for details.
+pub fn parse_stream_async_with_options<T>(stream: T, options: &ParseOptions) -> ParseFuture
+where
+    T: AsyncRead + Unpin + Send + 'static,
+{
+    let options = options.clone();
+    ParseFuture {
+        start: Some(Box::new(move || {
+            let reader = std::io::BufReader::new(BlockingReader {
+                runtime: Handle::current(),
+                stream,
+            });
+            tokio::task::spawn_blocking(move || parse_stream_with_options(reader, &options))
+        })),
+        task: None,
+    }
+}
+
+type ParseResult = Result<(Registry, Vec<Error>), FatalError>;
+
+/// Future returned by `parse_stream_async`, resolving to the parsed registry.
+#[must_use = "futures do nothing unless polled"]
+pub struct ParseFuture {
+    start: Option<Box<dyn FnOnce() -> JoinHandle<ParseResult> + Send>>,
+    task: Option<JoinHandle<ParseResult>>,
+}
+
+impl Future for ParseFuture {
+    type Output = ParseResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some(start) = self.start.take() {
+            self.task = Some(start());
+        }
+        let task = match self.task {
+            Some(ref mut task) => task,
+            None => panic!("ParseFuture polled after completion"),
+        };
+        match Pin::new(task).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(result) => {
+                self.task = None;
+                match result {
+                    Ok(result) => Poll::Ready(result),
+                    Err(e) => match e.try_into_panic() {
+                        Ok(payload) => std::panic::resume_unwind(payload),
+                        Err(e) => panic!("Parsing task failed: {}", e),
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Adapts asynchronous stream for use by the parser running on a blocking thread.
+struct BlockingReader<T> {
+    runtime: Handle,
+    stream: T,
+}
+
+impl<T: AsyncRead + Unpin> Read for BlockingReader<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let stream = &mut self.stream;
+        self.runtime.block_on(stream.read(buf))
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use catalog::required_types;
+use types::*;
+
+/// Bitmask type along with its bits, see `Registry::bitmasks`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Bitmask<'a> {
+    /// Name of the bitmask, e.g. `VkImageUsageFlags`.
+    pub name: &'a str,
+    pub definition: &'a Type,
+    /// Name of the type of bits, given by `requires` or `bitvalues`, e.g.
+    /// `VkImageUsageFlagBits`, `None` if the bitmask is reserved for future use.
+    pub bits: Option<&'a str>,
+    /// `<enums>` block defining the bits, if any.
+    pub bits_enums: Option<&'a Enums>,
+    /// Features and extensions requiring the bitmask, in order of the registry.
+    pub required_by: Vec<&'a str>,
+    /// Features and extensions requiring the type of bits, in order of the registry.
+    pub bits_required_by: Vec<&'a str>,
+}
+
+impl<'a> Bitmask<'a> {
+    /// Whether the bitmask has no bits defined, either because it has no type of bits, or the
+    /// type has no values, e.g. `VkPipelineLayoutCreateFlags` before
+    /// `VK_EXT_graphics_pipeline_library` added `VkPipelineLayoutCreateFlagBits`.
+    pub fn is_empty(&self) -> bool {
+        self.bits_enums.map_or(true, |enums| {
+            !enums
+                .children
+                .iter()
+                .any(|child| matches!(*child, EnumsChild::Enum(_)))
+        })
+    }
+
+    /// Whether the type of bits was introduced by another feature or extension than the
+    /// bitmask, which had no bits until then.
+    pub fn gained_bits_later(&self) -> bool {
+        match (self.required_by.first(), self.bits_required_by.first()) {
+            (Some(flags), Some(bits)) => flags != bits,
+            _ => false,
+        }
+    }
+}
+
+impl Registry {
+    /// Bitmask types which aren't aliases, in order of the registry, with their types of bits
+    /// and the features and extensions introducing each, so generators can emit placeholders
+    /// for bitmasks without bits, see `Bitmask::is_empty` and `Bitmask::gained_bits_later`.
+    pub fn bitmasks(&self) -> Vec<Bitmask<'_>> {
+        let required_by = required_types(self);
+        let required = |name: &str| -> Vec<&str> {
+            required_by
+                .get(name)
+                .into_iter()
+                .flatten()
+                .map(|&(_, name)| name)
+                .collect()
+        };
+        let enums: BTreeMap<&str, &Enums> = self
+            .enums_blocks()
+            .filter_map(|enums| Some((enums.name.as_deref()?, enums)))
+            .collect();
+
+        let mut bitmasks = Vec::new();
+        for ty in self.types() {
+            if ty.category.as_deref() != Some("bitmask") || ty.alias.is_some() {
+                continue;
+            }
+            let name = match (&ty.spec, ty.declared_name()) {
+                (&TypeSpec::BitmaskAlias { .. }, _) | (_, None) => continue,
+                (_, Some(name)) => name,
+            };
+            let bits = ty.requires.as_deref().or(ty.bitvalues.as_deref());
+            bitmasks.push(Bitmask {
+                name,
+                definition: ty,
+                bits,
+                bits_enums: bits.and_then(|bits| enums.get(bits).cloned()),
+                required_by: required(name),
+                bits_required_by: bits.map(required).unwrap_or_default(),
+            });
+        }
+        bitmasks
+    }
+}
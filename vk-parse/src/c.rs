@@ -1,491 +1,796 @@
-#![allow(dead_code)]
+//! Helpers for the C code found in the registry, e.g. in `<type>` and `<member>` elements, macro
+//! values or `c_expr` text. These are the primitives used by the parser itself, for generators
+//! processing such code on their own.
+//!
+//! ```
+//! use vk_parse::c;
+//!
+//! assert!(c::is_c_identifier("VK_API_VERSION_1_0"));
+//! assert_eq!(c::split_literal("1000.0f)"), Some(("1000.0f", ")")));
+//! assert_eq!(
+//!     c::split_declarator("const float matrix[3][4];"),
+//!     Some(("const float", "matrix", "[3][4]"))
+//! );
+//! ```
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+use types::{
+    CType, PreprocessorBranch, PreprocessorItem, TypeDefine, TypeDefineValue, TypeInclude,
+};
+
+fn is_number_start(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c == '_' || c.is_ascii_alphabetic()
+}
 
 //--------------------------------------------------------------------------------------------------
-/// Performs Phase 1 of C compilation, specifically replaces platform-specific end-of-line indicators
-/// with newlines characters and transforms trigraph sequences.
-struct IterPhase1<'a> {
+/// Splits code into identifiers, numbers and single characters, including whitespace. Sequences
+/// of identifier characters, see `is_c_identifier_char`, are kept together and each other
+/// character is a token on its own, so that the tokens always add up to the code.
+///
+/// ```
+/// let tokens: Vec<_> = vk_parse::c::TokenIter::new("VK_MAKE_API_VERSION(0, 1, 3, 0)").collect();
+/// assert_eq!(tokens[..4], ["VK_MAKE_API_VERSION", "(", "0", ","]);
+/// ```
+pub struct TokenIter<'a> {
     src: &'a str,
 }
 
-impl<'a> IterPhase1<'a> {
-    fn new(src: &'a str) -> Self {
+impl<'a> TokenIter<'a> {
+    /// Creates iterator over tokens of `src`.
+    pub fn new(src: &'a str) -> Self {
         Self { src }
     }
 }
 
-impl<'a> Iterator for IterPhase1<'a> {
-    type Item = char;
-    fn next(&mut self) -> Option<char> {
-        let mut iter = self.src.chars();
-
-        let c = if let Some(c) = iter.next() {
-            c
-        } else {
-            return None;
-        };
-
-        match c {
-            // TODO: trigraphs and digraphs (probably not important)
-            '\r' => {
-                if let Some('\n') = iter.next() {
-                    self.src = self.src.split_at(2).1;
-                    Some('\n')
-                } else {
-                    self.src = self.src.split_at(1).1;
-                    Some('\r')
+impl<'a> Iterator for TokenIter<'a> {
+    type Item = &'a str;
+    fn next(&mut self) -> Option<&'a str> {
+        let mut iter = self.src.char_indices();
+        if let Some((_, c)) = iter.next() {
+            if is_c_identifier_char(c) {
+                for (end_idx, c) in iter {
+                    if !is_c_identifier_char(c) {
+                        let split = self.src.split_at(end_idx);
+                        self.src = split.1;
+                        return Some(split.0);
+                    }
                 }
-            }
-            c => {
-                self.src = self.src.split_at(1).1;
-                Some(c)
+
+                let res = self.src;
+                self.src = "";
+                return Some(res);
+            } else {
+                let split = self.src.split_at(1);
+                self.src = split.1;
+                return Some(split.0);
             }
         }
+
+        None
     }
 }
 
-//--------------------------------------------------------------------------------------------------
-struct IterPhase2<'a> {
-    src: IterPhase1<'a>,
-    c1: Option<char>,
-    c2: Option<char>,
+/// Whether the character may be part of a C identifier, i.e. is an ASCII letter, digit or `_`.
+pub fn is_c_identifier_char(c: char) -> bool {
+    c == '_' || c.is_ascii_alphanumeric()
 }
 
-impl<'a> IterPhase2<'a> {
-    fn new(code: &'a str) -> Self {
-        let mut src = IterPhase1::new(code);
-        let c1 = src.next();
-        let c2 = src.next();
-        Self { src, c1, c2 }
-    }
+/// Whether the string is a C identifier, i.e. is non-empty, consists of characters accepted by
+/// `is_c_identifier_char` and doesn't start with a digit. Keywords are accepted as well, see
+/// `is_c_keyword`.
+pub fn is_c_identifier(s: &str) -> bool {
+    s.starts_with(is_identifier_start) && s.chars().all(is_c_identifier_char)
 }
 
-impl<'a> Iterator for IterPhase2<'a> {
-    type Item = char;
-    fn next(&mut self) -> Option<char> {
-        while let (Some('\\'), Some('\n')) = (self.c1, self.c2) {
-            self.c1 = self.src.next();
-            self.c2 = self.src.next();
-        }
-
-        let c = self.c1;
-        self.c1 = self.c2;
-        self.c2 = self.src.next();
-        c
-    }
+/// Whether the string is a keyword of C99, which can't be used as a name.
+pub fn is_c_keyword(s: &str) -> bool {
+    matches!(
+        s,
+        "auto"
+            | "break"
+            | "case"
+            | "char"
+            | "const"
+            | "continue"
+            | "default"
+            | "do"
+            | "double"
+            | "else"
+            | "enum"
+            | "extern"
+            | "float"
+            | "for"
+            | "goto"
+            | "if"
+            | "inline"
+            | "int"
+            | "long"
+            | "register"
+            | "restrict"
+            | "return"
+            | "short"
+            | "signed"
+            | "sizeof"
+            | "static"
+            | "struct"
+            | "switch"
+            | "typedef"
+            | "union"
+            | "unsigned"
+            | "void"
+            | "volatile"
+            | "while"
+            | "_Bool"
+            | "_Complex"
+            | "_Imaginary"
+    )
 }
 
 //--------------------------------------------------------------------------------------------------
-/// Part of phase 3 that deals with comments and whitespace. Does not produce tokens, just filters
-/// and transforms characters.
-struct IterPhase3a<'a> {
-    src: IterPhase2<'a>,
-    peek: Option<char>,
-    prev_space: bool,
+/// Splits a literal off the start of code, returning the literal as written and the remaining
+/// code, or `None` if code doesn't start with a literal. Leading whitespace is skipped.
+///
+/// Recognized literals are numbers, including any suffixes such as in `0x7FFFFFFFU` or
+/// `1000.0f`, and string and character literals, which may contain escape sequences. Values of
+/// numbers and strings can be obtained with `CLiteral::parse`.
+pub fn split_literal(code: &str) -> Option<(&str, &str)> {
+    let code = code.trim_start();
+    let mut chars = code.char_indices().peekable();
+    let (_, first) = chars.next()?;
+    let end = match first {
+        '"' | '\'' => loop {
+            match chars.next()? {
+                (_, '\\') => {
+                    chars.next()?;
+                }
+                (_, '\n') => return None,
+                (i, c) if c == first => break i + 1,
+                _ => {}
+            }
+        },
+        c if is_number_start(c) || (c == '.' && code[1..].starts_with(is_number_start)) => {
+            // Preprocessing number, so that invalid numbers are split off as a whole too.
+            let mut prev = c;
+            loop {
+                match chars.peek() {
+                    Some(&(_, c))
+                        if is_c_identifier_char(c)
+                            || c == '.'
+                            || ((c == '+' || c == '-') && "eEpP".contains(prev)) =>
+                    {
+                        prev = c;
+                        chars.next();
+                    }
+                    Some(&(i, _)) => break i,
+                    None => break code.len(),
+                }
+            }
+        }
+        _ => return None,
+    };
+    Some(code.split_at(end))
 }
 
-impl<'a> IterPhase3a<'a> {
-    fn new(code: &'a str) -> Self {
-        let mut src = IterPhase2::new(code);
-        let peek = src.next();
-        Self {
-            src,
-            peek,
-            prev_space: false,
-        }
+//--------------------------------------------------------------------------------------------------
+/// Splits declaration of a struct member or command parameter into the text of its type, its
+/// name and the array or bitfield suffix following the name, e.g. `const float matrix[3][4];`
+/// into `const float`, `matrix` and `[3][4]`, without interpreting the type.
+/// Returns `None` if the name can't be found. See `parse_declaration` for parsing the type.
+pub fn split_declarator(code: &str) -> Option<(&str, &str, &str)> {
+    let code = code.trim();
+    let code = code.strip_suffix(';').unwrap_or(code).trim_end();
+    let name_end = code.find(|c| matches!(c, '[' | ':')).unwrap_or(code.len());
+    let suffix = code[name_end..].trim();
+    let before_suffix = code[..name_end].trim_end();
+    let name_start = before_suffix
+        .rfind(|c: char| !is_c_identifier_char(c))
+        .map_or(0, |i| i + 1);
+    let (type_part, name) = before_suffix.split_at(name_start);
+    let type_part = type_part.trim_end();
+    if type_part.is_empty() || !is_declared_name(name) {
+        return None;
     }
+    Some((type_part, name, suffix))
+}
 
-    fn is_merged_whitespace(c: char) -> bool {
-        c == '\t' || ('\u{000B}' <= c && c <= '\u{000D}') || c == ' '
+//--------------------------------------------------------------------------------------------------
+/// Parses declaration of a struct member or command parameter, such as
+/// `const char* const* ppEnabledLayerNames`, `float matrix[3][4]` or `uint32_t mask:8`.
+/// Returns declared type and name, or `None` if the declaration isn't recognized or has more
+/// than `MAX_DECLARATOR_DEPTH` pointer levels and array dimensions.
+pub fn parse_declaration(code: &str) -> Option<(CType, &str)> {
+    let mut tokens: Vec<&str> = TokenIter::new(code)
+        .filter(|t| !t.trim().is_empty())
+        .collect();
+    if tokens.last() == Some(&";") {
+        tokens.pop();
     }
-}
 
-impl<'a> Iterator for IterPhase3a<'a> {
-    type Item = char;
-    fn next(&mut self) -> Option<char> {
-        loop {
-            let c = if let Some(c) = self.peek {
-                c
-            } else {
-                return None;
-            };
+    let name_end = tokens
+        .iter()
+        .position(|&t| t == "[" || t == ":")
+        .unwrap_or(tokens.len());
+    if name_end < 2 {
+        return None;
+    }
+    let name = tokens[name_end - 1];
+    if !is_declared_name(name) {
+        return None;
+    }
 
-            if c == '\n' {
-                self.peek = self.src.next();
-                self.prev_space = false;
-                return Some('\n');
-            } else if c == '/' {
-                // line comment, block comment or just char
-                self.peek = self.src.next();
-                let c = if let Some(c) = self.peek {
-                    c
-                } else {
-                    self.prev_space = false;
-                    return Some('/');
-                };
-
-                if c == '/' {
-                    self.peek = None;
-                    while let Some(c) = self.src.next() {
-                        if c == '\n' {
-                            self.peek = Some(c);
-                            break;
-                        }
-                    }
-                    if !self.prev_space {
-                        self.prev_space = true;
-                        return Some(' ');
-                    }
-                } else if c == '*' {
-                    while let Some(c) = self.src.next() {
-                        if c == '*' {
-                            if let Some('/') = self.src.next() {
-                                self.peek = self.src.next();
-                                break;
-                            }
-                        }
-                    }
-                    if !self.prev_space {
-                        self.prev_space = true;
-                        return Some(' ');
-                    }
-                } else {
-                    self.prev_space = false;
-                    return Some('/');
+    let mut type_name = String::new();
+    let mut is_const = false;
+    let mut is_struct = false;
+    let mut pointers: Vec<bool> = Vec::new();
+    for &token in &tokens[..name_end - 1] {
+        match token {
+            "*" => pointers.push(false),
+            "const" => match pointers.last_mut() {
+                Some(pointer_const) => *pointer_const = true,
+                None => is_const = true,
+            },
+            "struct" if type_name.is_empty() => is_struct = true,
+            t if pointers.is_empty() && is_c_identifier(t) => {
+                if !type_name.is_empty() {
+                    type_name.push(' ');
                 }
-            } else if IterPhase3a::is_merged_whitespace(c) {
-                self.peek = None;
-                while let Some(c) = self.src.next() {
-                    if !IterPhase3a::is_merged_whitespace(c) {
-                        self.peek = Some(c);
-                        break;
+                type_name.push_str(t);
+            }
+            _ => return None,
+        }
+    }
+    if type_name.is_empty() {
+        return None;
+    }
+
+    let mut array_lens = Vec::new();
+    let mut bitfield_width = None;
+    let mut suffix = tokens[name_end..].iter();
+    while let Some(&token) = suffix.next() {
+        match token {
+            "[" => {
+                let mut len = String::new();
+                loop {
+                    match suffix.next() {
+                        Some(&"]") => break,
+                        Some(t) => len.push_str(t),
+                        None => return None,
                     }
                 }
-                if !self.prev_space {
-                    self.prev_space = true;
-                    return Some(' ');
-                }
-            } else {
-                self.peek = self.src.next();
-                self.prev_space = false;
-                return Some(c);
+                array_lens.push(len);
+            }
+            ":" if bitfield_width.is_none() => {
+                bitfield_width = Some(suffix.next()?.parse::<u8>().ok()?);
             }
+            _ => return None,
         }
     }
-}
-
-//--------------------------------------------------------------------------------------------------
-/// Simplified tokens for easier dealing with most C code we care about. Not precisely according to
-/// C/C++ standards, as those are not as easy to deal with and make more sense when combined with
-/// preprocessor.
-#[derive(Clone, Eq, PartialEq, Debug)]
-enum Token<'a> {
-    PpDirective(PpDirective),
-    Punctuation,
-    HeaderName(&'a str),
-    Identifier(&'a str),
-    StringLiteral(&'a str),
-    CharacterLiteral(char),
-    NewLine,
-}
+    if pointers.len() + array_lens.len() > MAX_DECLARATOR_DEPTH {
+        return None;
+    }
+    let mut c_type = CType::pointer_to(
+        CType::Named {
+            name: type_name.into_boxed_str(),
+            is_const,
+            is_struct,
+        },
+        &pointers,
+    );
+    // `T name[A][B]` is an array of A arrays of B elements of type T.
+    for len in array_lens.into_iter().rev() {
+        c_type = CType::Array {
+            len: len.into_boxed_str(),
+            inner: Box::new(c_type),
+        };
+    }
+    if let Some(width) = bitfield_width {
+        c_type = CType::Bitfield {
+            width,
+            inner: Box::new(c_type),
+        };
+    }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-enum PpDirective {
-    Define,
-    Undef,
-    Include,
-    If,
-    Ifdef,
-    Ifndef,
-    Else,
-    Elif,
-    Endif,
-    Line,
-    Error,
-    Pragma,
+    Some((c_type, name))
 }
 
-impl PpDirective {
-    fn from_str(s: &str) -> PpDirective {
-        match s {
-            "define" => PpDirective::Define,
-            "undef" => PpDirective::Undef,
-            "include" => PpDirective::Include,
-            "if" => PpDirective::If,
-            "ifdef" => PpDirective::Ifdef,
-            "ifndef" => PpDirective::Ifndef,
-            "else" => PpDirective::Else,
-            "elif" => PpDirective::Elif,
-            "endif" => PpDirective::Endif,
-            "line" => PpDirective::Line,
-            "error" => PpDirective::Error,
-            "pragma" => PpDirective::Pragma,
-            _ => panic!("Unrecognized postprocessor directive {:?}", s),
-        }
-    }
-}
+/// Maximum number of pointer levels and array dimensions accepted by `parse_declaration`, as
+/// each of them nests the resulting `CType`, which is processed recursively. The registry never
+/// declares more than a few.
+const MAX_DECLARATOR_DEPTH: usize = 16;
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
-enum LineState {
-    /// Right after newline.
-    Start,
-    /// When first thing encountered on the line is '#'
-    PpStart,
-    /// First identifier after '#'
-    PpDirective,
-    /// Contents of postprocessor directive.
-    PpDirectiveBody,
-    /// Reached newline but not reported yet.
-    PpDirectiveEnd,
-    /// Normal line tokens.
-    Normal,
+fn is_declared_name(s: &str) -> bool {
+    is_c_identifier(s) && !is_c_keyword(s)
 }
 
-struct IterTokenInner<'src> {
-    src: IterPhase3a<'src>,
-    peek: Option<char>,
-    line: LineState,
-    buf: String,
-}
+/// Maximum nesting of conditional blocks built by `parse_preprocessor`, as the resulting items
+/// are processed recursively. Headers of the registry never nest more than a few levels.
+const MAX_CONDITIONAL_DEPTH: usize = 64;
 
-impl<'src> IterTokenInner<'src> {
-    fn new(src: &'src str) -> Self {
-        let mut src = IterPhase3a::new(src);
-        let peek = src.next();
-        Self {
-            src,
-            peek,
-            line: LineState::Start,
-            buf: String::new(),
+//--------------------------------------------------------------------------------------------------
+/// Splits code into preprocessor directives, comments and remaining C code.
+///
+/// Line continuations are joined, and contents of `#if` blocks are nested into branches
+/// according to their `#elif`, `#else` and `#endif` directives. This never fails: unbalanced
+/// `#elif`, `#else` and `#endif` directives are kept as `PreprocessorItem::Directive`, and
+/// blocks missing their `#endif` are closed at the end of the code. Blocks nested deeper than
+/// `MAX_CONDITIONAL_DEPTH` aren't nested further, their directives are kept as
+/// `PreprocessorItem::Directive` instead.
+pub fn parse_preprocessor(code: &str) -> Vec<PreprocessorItem> {
+    let mut root = Vec::new();
+    // Branches of currently open conditional blocks, innermost last.
+    let mut open: Vec<Vec<PreprocessorBranch>> = Vec::new();
+    // Number of blocks open beyond `MAX_CONDITIONAL_DEPTH`.
+    let mut excess = 0;
+    let mut pending_code = String::new();
+
+    fn current<'a>(
+        root: &'a mut Vec<PreprocessorItem>,
+        open: &'a mut [Vec<PreprocessorBranch>],
+    ) -> &'a mut Vec<PreprocessorItem> {
+        match open.last_mut().and_then(|branches| branches.last_mut()) {
+            Some(branch) => &mut branch.items,
+            None => root,
         }
     }
 
-    fn next(&mut self) -> Option<Token> {
-        self.buf.clear();
-        loop {
-            let c = if let Some(c) = self.peek {
-                c
-            } else {
-                return None;
-            };
-            match (self.line, c) {
-                (LineState::Start, ' ') => self.peek = self.src.next(),
-                (LineState::Start, '\n') => self.peek = self.src.next(),
-                (LineState::Start, '#') => {
-                    self.line = LineState::PpStart;
-                    self.peek = self.src.next();
-                }
-                (LineState::Start, c) => {
-                    self.buf.push(c);
-                    self.line = LineState::Normal;
-                    self.peek = self.src.next();
-                }
-
-                (LineState::PpStart, ' ') => self.peek = self.src.next(),
-                (LineState::PpStart, '\n') => panic!(
-                    "Unexpected combination ({:?}, {:?})",
-                    LineState::PpStart,
-                    '\n'
-                ),
-                (LineState::PpStart, c) => {
-                    self.buf.push(c);
-                    self.line = LineState::PpDirective;
-                    self.peek = self.src.next();
-                }
+    fn branch(condition: Option<String>) -> PreprocessorBranch {
+        PreprocessorBranch {
+            condition: condition.map(String::into_boxed_str),
+            items: Vec::new(),
+        }
+    }
 
-                (LineState::PpDirective, ' ') => {
-                    self.line = LineState::PpDirectiveBody;
-                    self.peek = self.src.next();
-                    return Some(Token::PpDirective(PpDirective::from_str(&self.buf)));
-                }
-                (LineState::PpDirective, '\n') => {
-                    self.line = LineState::PpDirectiveEnd;
-                    return Some(Token::PpDirective(PpDirective::from_str(&self.buf)));
-                }
-                (LineState::PpDirective, c) => {
-                    self.buf.push(c);
-                    self.peek = self.src.next();
-                }
+    for line in logical_lines(code) {
+        let text = line.code.trim();
+        if let Some(directive) = text.strip_prefix('#') {
+            if !pending_code.is_empty() {
+                let code = core::mem::take(&mut pending_code).into_boxed_str();
+                current(&mut root, &mut open).push(PreprocessorItem::Code(code));
+            }
 
-                (LineState::PpDirectiveBody, ' ') => {
-                    self.peek = self.src.next();
-                    return Some(Token::Identifier(&self.buf));
-                }
-                (LineState::PpDirectiveBody, '\n') => {
-                    self.line = LineState::PpDirectiveEnd;
-                    return Some(Token::Identifier(&self.buf));
+            let directive = directive.trim_start();
+            let name_len = directive
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(directive.len());
+            let (name, rest) = directive.split_at(name_len);
+            let rest = rest.trim();
+            match name {
+                "define" => {
+                    let item = match parse_define_directive(rest) {
+                        Some(define) => PreprocessorItem::Define(define),
+                        None => PreprocessorItem::Directive(text.into()),
+                    };
+                    current(&mut root, &mut open).push(item);
                 }
-                (LineState::PpDirectiveBody, c) => {
-                    self.buf.push(c);
-                    self.peek = self.src.next();
+                "include" => {
+                    let item = match parse_include_directive(rest) {
+                        Some(include) => PreprocessorItem::Include(include),
+                        None => PreprocessorItem::Directive(text.into()),
+                    };
+                    current(&mut root, &mut open).push(item);
                 }
-
-                (LineState::PpDirectiveEnd, '\n') => {
-                    self.peek = self.src.next();
-                    self.line = LineState::Start;
-                    return Some(Token::NewLine);
-                }
-                (LineState::PpDirectiveEnd, c) => panic!(
-                    "Unexpected combination ({:?}, {:?})",
-                    LineState::PpDirectiveEnd,
-                    c
-                ),
-
-                (LineState::Normal, ' ') => {
-                    self.peek = self.src.next();
-                    return Some(Token::Identifier(&self.buf));
+                "if" | "ifdef" | "ifndef" if open.len() == MAX_CONDITIONAL_DEPTH => {
+                    excess += 1;
+                    current(&mut root, &mut open).push(PreprocessorItem::Directive(text.into()));
                 }
-                (LineState::Normal, '\n') => {
-                    self.peek = self.src.next();
-                    self.line = LineState::Start;
-                    if self.buf.len() > 0 {
-                        return Some(Token::Identifier(&self.buf));
+                "elif" | "else" | "endif" if excess > 0 => {
+                    if name == "endif" {
+                        excess -= 1;
                     }
+                    current(&mut root, &mut open).push(PreprocessorItem::Directive(text.into()));
                 }
-                (LineState::Normal, ';') => {
-                    if self.buf.len() > 0 {
-                        return Some(Token::Identifier(&self.buf));
+                "if" => open.push(vec![branch(Some(String::from(rest)))]),
+                "ifdef" => open.push(vec![branch(Some(format!("defined({})", rest)))]),
+                "ifndef" => open.push(vec![branch(Some(format!("!defined({})", rest)))]),
+                "elif" | "else" | "endif" if !open.is_empty() => {
+                    if name == "endif" {
+                        let branches = open.pop().unwrap();
+                        current(&mut root, &mut open).push(PreprocessorItem::Conditional(branches));
                     } else {
-                        self.peek = self.src.next();
-                        return Some(Token::Punctuation);
+                        let condition = if name == "elif" {
+                            Some(String::from(rest))
+                        } else {
+                            None
+                        };
+                        open.last_mut().unwrap().push(branch(condition));
                     }
                 }
-                (LineState::Normal, c) => {
-                    self.buf.push(c);
-                    self.peek = self.src.next();
-                }
+                _ => current(&mut root, &mut open).push(PreprocessorItem::Directive(text.into())),
+            }
+        } else if !text.is_empty() {
+            if !pending_code.is_empty() {
+                pending_code.push('\n');
+            }
+            pending_code.push_str(text);
+        }
+
+        if !line.comments.is_empty() {
+            let items = current(&mut root, &mut open);
+            if !pending_code.is_empty() {
+                let code = core::mem::take(&mut pending_code).into_boxed_str();
+                items.push(PreprocessorItem::Code(code));
             }
+            items.extend(
+                line.comments
+                    .into_iter()
+                    .map(|c| PreprocessorItem::Comment(c.into_boxed_str())),
+            );
         }
     }
-}
 
-fn is_number_start(c: char) -> bool {
-    '0' <= c && c <= '9'
+    if !pending_code.is_empty() {
+        current(&mut root, &mut open).push(PreprocessorItem::Code(pending_code.into_boxed_str()));
+    }
+    while let Some(branches) = open.pop() {
+        current(&mut root, &mut open).push(PreprocessorItem::Conditional(branches));
+    }
+    root
 }
 
-fn is_number_part(c: char) -> bool {
-    c == '.'
-        || c == '+'
-        || c == '-'
-        || c == 'x'
-        || c == 'X'
-        || ('0' <= c && c <= '9')
-        || ('a' <= c && c <= 'f')
-        || ('A' <= c && c <= 'F')
+/// Parses the part of `#include` directive following the `include` keyword.
+fn parse_include_directive(text: &str) -> Option<TypeInclude> {
+    let (system, end) = match text.chars().next()? {
+        '<' => (true, '>'),
+        '"' => (false, '"'),
+        _ => return None,
+    };
+    let len = text[1..].find(end)?;
+    Some(TypeInclude {
+        path: text[1..1 + len].into(),
+        system,
+    })
 }
 
-fn is_identifier_start(c: char) -> bool {
-    c == '_' || ('a' <= c && c <= 'z') || ('A' <= c && c <= 'Z')
-}
+/// Parses the part of `#define` directive following the `define` keyword.
+fn parse_define_directive(text: &str) -> Option<TypeDefine> {
+    let name_len = text
+        .find(|c: char| !is_c_identifier_char(c))
+        .unwrap_or(text.len());
+    if name_len == 0 {
+        return None;
+    }
+    let (name, rest) = text.split_at(name_len);
+
+    // Parameter list has to follow the name immediately, otherwise it's part of the value.
+    let value = if let Some(rest) = rest.strip_prefix('(') {
+        let params_end = rest.find(')')?;
+        TypeDefineValue::Function {
+            params: rest[..params_end]
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(Box::from)
+                .collect(),
+            body: rest[params_end + 1..].trim().into(),
+        }
+    } else if rest.trim().is_empty() {
+        TypeDefineValue::Empty
+    } else {
+        TypeDefineValue::Value(rest.trim().into())
+    };
 
-fn is_identifier_part(c: char) -> bool {
-    c == '_' || ('a' <= c && c <= 'z') || ('A' <= c && c <= 'Z') || ('0' <= c && c <= '9')
+    Some(TypeDefine {
+        name: name.into(),
+        value,
+    })
 }
 
-//--------------------------------------------------------------------------------------------------
-pub struct TokenIter<'a> {
-    src: &'a str,
+/// Line of code after joining line continuations, with comments moved out of it.
+#[derive(Default)]
+struct LogicalLine {
+    code: String,
+    comments: Vec<String>,
 }
 
-impl<'a> TokenIter<'a> {
-    pub fn new(src: &'a str) -> Self {
-        Self { src }
-    }
-}
+fn logical_lines(code: &str) -> Vec<LogicalLine> {
+    let mut lines = Vec::new();
+    let mut line = LogicalLine::default();
+    let mut quote = None;
+    let mut chars = code.chars().peekable();
 
-impl<'a> Iterator for TokenIter<'a> {
-    type Item = &'a str;
-    fn next(&mut self) -> Option<&'a str> {
-        let mut iter = self.src.char_indices();
-        if let Some((_, c)) = iter.next() {
-            if is_c_identifier_char(c) {
-                for (end_idx, c) in iter {
-                    if !is_c_identifier_char(c) {
-                        let split = self.src.split_at(end_idx);
-                        self.src = split.1;
-                        return Some(split.0);
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if chars.peek() == Some(&'\r') {
+                    chars.next();
+                }
+                match chars.next() {
+                    Some('\n') => {}
+                    Some(next) => {
+                        line.code.push(c);
+                        line.code.push(next);
                     }
+                    None => line.code.push(c),
                 }
-
-                let res = self.src;
-                self.src = "";
-                return Some(res);
-            } else {
-                let split = self.src.split_at(1);
-                self.src = split.1;
-                return Some(split.0);
             }
+            '\n' => {
+                lines.push(core::mem::take(&mut line));
+                quote = None;
+            }
+            _ if quote.is_some() => {
+                line.code.push(c);
+                if quote == Some(c) {
+                    quote = None;
+                }
+            }
+            '"' | '\'' => {
+                line.code.push(c);
+                quote = Some(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                let mut comment = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    comment.push(c);
+                    chars.next();
+                }
+                line.comments.push(String::from(comment[1..].trim()));
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut comment = String::new();
+                let mut prev = None;
+                for c in chars.by_ref() {
+                    if prev == Some('*') && c == '/' {
+                        comment.pop();
+                        break;
+                    }
+                    comment.push(c);
+                    prev = Some(c);
+                }
+                line.comments.push(String::from(comment.trim()));
+                line.code.push(' ');
+            }
+            _ => line.code.push(c),
         }
-
-        None
     }
+    lines.push(line);
+    lines
 }
 
-pub fn is_c_identifier_char(c: char) -> bool {
-    if '0' <= c && c <= '9' {
-        true
-    } else if 'a' <= c && c <= 'z' {
-        true
-    } else if 'A' <= c && c <= 'Z' {
-        true
-    } else if c == '_' {
-        true
-    } else {
-        false
+//--------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::ToString;
+
+    fn named(name: &str, is_const: bool) -> CType {
+        CType::Named {
+            name: name.into(),
+            is_const,
+            is_struct: false,
+        }
     }
-}
 
-pub fn is_c_identifier(s: &str) -> bool {
-    for c in s.chars() {
-        if !is_c_identifier_char(c) {
-            return false;
+    fn pointer(is_const: bool, inner: CType) -> CType {
+        CType::Pointer {
+            is_const,
+            inner: Box::new(inner),
         }
     }
-    true
-}
 
-//--------------------------------------------------------------------------------------------------
-#[cfg(test)]
-mod test {
-    use super::*;
+    #[test]
+    fn test_parse_declaration() {
+        assert_eq!(
+            parse_declaration(" uint32_t  apiVersion  "),
+            Some((named("uint32_t", false), "apiVersion"))
+        );
+        assert_eq!(
+            parse_declaration("const char* const* ppEnabledLayerNames"),
+            Some((
+                pointer(false, pointer(true, named("char", true))),
+                "ppEnabledLayerNames"
+            ))
+        );
+        assert_eq!(
+            parse_declaration("struct wl_display* display"),
+            Some((
+                pointer(
+                    false,
+                    CType::Named {
+                        name: "wl_display".into(),
+                        is_const: false,
+                        is_struct: true,
+                    }
+                ),
+                "display"
+            ))
+        );
+        assert_eq!(
+            parse_declaration("float matrix[3][VK_SIZE];"),
+            Some((
+                CType::Array {
+                    len: "3".into(),
+                    inner: Box::new(CType::Array {
+                        len: "VK_SIZE".into(),
+                        inner: Box::new(named("float", false)),
+                    }),
+                },
+                "matrix"
+            ))
+        );
+        assert_eq!(
+            parse_declaration("uint32_t mask:8"),
+            Some((
+                CType::Bitfield {
+                    width: 8,
+                    inner: Box::new(named("uint32_t", false)),
+                },
+                "mask"
+            ))
+        );
+        assert_eq!(parse_declaration("void"), None);
+        assert_eq!(parse_declaration("int (*fn)(void)"), None);
+    }
 
     #[test]
-    fn test() {
-        println!("");
-        {
-            let code = "typedef void // some comment    \n /* some other comment     */ (VKAPI_PTR *PFN_vkInternalAllocationNotification)(\\\r\n    void*                                       pUserData = M_PI/4,\r\n    size_t                                      size,\\\n    VkInternalAllocationType                    allocationType, \n    VkSystemAllocationScope                     allocationScope);";
-
-            let post_phase_1: String = IterPhase1::new(code).collect();
-            let post_phase_1_ref = "typedef void // some comment    \n /* some other comment     */ (VKAPI_PTR *PFN_vkInternalAllocationNotification)(\\\n    void*                                       pUserData = M_PI/4,\n    size_t                                      size,\\\n    VkInternalAllocationType                    allocationType, \n    VkSystemAllocationScope                     allocationScope);";
-            assert_eq!(&post_phase_1, post_phase_1_ref);
-
-            let post_phase_2: String = IterPhase2::new(code).collect();
-            let post_phase_2_ref = "typedef void // some comment    \n /* some other comment     */ (VKAPI_PTR *PFN_vkInternalAllocationNotification)(    void*                                       pUserData = M_PI/4,\n    size_t                                      size,    VkInternalAllocationType                    allocationType, \n    VkSystemAllocationScope                     allocationScope);";
-            assert_eq!(&post_phase_2, post_phase_2_ref);
-
-            let post_phase_3: String = IterPhase3a::new(code).collect();
-            let post_phase_3_ref = "typedef void \n (VKAPI_PTR *PFN_vkInternalAllocationNotification)( void* pUserData = M_PI/4,\n size_t size, VkInternalAllocationType allocationType, \n VkSystemAllocationScope allocationScope);";
-            assert_eq!(&post_phase_3, post_phase_3_ref);
-        }
+    fn test_pointer_levels() {
+        let (c_type, _) = parse_declaration("const void* const** const* pValue").unwrap();
+        assert_eq!(c_type.pointer_depth(), 4);
+        assert_eq!(c_type.pointer_levels(), vec![true, false, true, false]);
+        assert_eq!(c_type.pointee(), &named("void", true));
+        assert_eq!(
+            CType::pointer_to(named("void", true), &c_type.pointer_levels()),
+            c_type
+        );
+
+        let (c_type, _) = parse_declaration("uint32_t count").unwrap();
+        assert_eq!(c_type.pointer_depth(), 0);
+        assert!(c_type.pointer_levels().is_empty());
+        assert_eq!(c_type.pointee(), &c_type);
+
+        let code = format!("void{} pValue", "*".repeat(MAX_DECLARATOR_DEPTH));
+        let (c_type, _) = parse_declaration(&code).unwrap();
+        assert_eq!(c_type.pointer_depth(), MAX_DECLARATOR_DEPTH);
+        let code = format!("void{} pValue", "*".repeat(200_000));
+        assert_eq!(parse_declaration(&code), None);
+        let code = format!("void* pValue{}", "[2]".repeat(MAX_DECLARATOR_DEPTH));
+        assert_eq!(parse_declaration(&code), None);
+    }
+
+    #[test]
+    fn test_array_shape() {
+        let (c_type, _) = parse_declaration("float matrix[3][VK_SIZE]").unwrap();
+        assert_eq!(&*c_type.array_shape(), &["3", "VK_SIZE"]);
+        let (c_type, _) = parse_declaration("const char* pName").unwrap();
+        assert!(c_type.array_shape().is_empty());
+    }
 
-        {
-            let code = "// DEPRECATED: This define has been removed. Specific version defines (e.g. VK_API_VERSION_1_0), or the VK_MAKE_VERSION macro, should be used instead.\n//#define VK_API_VERSION VK_MAKE_VERSION(1, 0, 0) // Patch version should always be set to 0";
-            let post_phase_3: String = IterPhase3a::new(code).collect();
-            assert_eq!(&post_phase_3, " \n ");
+    #[test]
+    fn test_c_decl() {
+        for &code in &[
+            "const char* const* ppEnabledLayerNames",
+            "float matrix[3][VK_SIZE]",
+            "uint32_t mask:8",
+            "struct wl_display* display",
+            "void* const pData",
+        ] {
+            let (c_type, name) = parse_declaration(code).unwrap();
+            assert_eq!(c_type.to_c_decl(name), code);
         }
 
-        {
-            let code = "#define x y\n //some comment here\n class Something;\n";
-            let mut token_iter = IterTokenInner::new(code);
-            assert_eq!(
-                token_iter.next(),
-                Some(Token::PpDirective(PpDirective::Define))
-            );
-            assert_eq!(token_iter.next(), Some(Token::Identifier("x")));
-            assert_eq!(token_iter.next(), Some(Token::Identifier("y")));
-            assert_eq!(token_iter.next(), Some(Token::NewLine));
-            assert_eq!(token_iter.next(), Some(Token::Identifier("class")),);
-            assert_eq!(token_iter.next(), Some(Token::Identifier("Something")),);
-            assert_eq!(token_iter.next(), Some(Token::Punctuation));
+        let type_of = |code| parse_declaration(code).unwrap().0.to_string();
+        assert_eq!(type_of("const char* const* ppNames"), "const char* const*");
+        assert_eq!(type_of("float matrix[3][4]"), "float[3][4]");
+        assert_eq!(type_of("uint32_t mask:8"), "uint32_t :8");
+    }
+
+    #[test]
+    fn test_parse_preprocessor() {
+        let code = "// DEPRECATED: This define is deprecated.\n\
+            #define VK_MAKE_VERSION(major, minor, patch) \\\n    \
+            ((((uint32_t)(major)) << 22U) | (((uint32_t)(minor)) << 12U) | ((uint32_t)(patch)))";
+        assert_eq!(
+            parse_preprocessor(code),
+            vec![
+                PreprocessorItem::Comment("DEPRECATED: This define is deprecated.".into()),
+                PreprocessorItem::Define(TypeDefine {
+                    name: "VK_MAKE_VERSION".into(),
+                    value: TypeDefineValue::Function {
+                        params: vec!["major".into(), "minor".into(), "patch".into()],
+                        body: "((((uint32_t)(major)) << 22U) | (((uint32_t)(minor)) << 12U) \
+                            | ((uint32_t)(patch)))"
+                            .into(),
+                    },
+                }),
+            ]
+        );
+
+        let code = "#define VK_API_VERSION_1_0 VK_MAKE_API_VERSION(0, 1, 0, 0)// Patch version";
+        assert_eq!(
+            parse_preprocessor(code),
+            vec![
+                PreprocessorItem::Define(TypeDefine {
+                    name: "VK_API_VERSION_1_0".into(),
+                    value: TypeDefineValue::Value("VK_MAKE_API_VERSION(0, 1, 0, 0)".into()),
+                }),
+                PreprocessorItem::Comment("Patch version".into()),
+            ]
+        );
+
+        let code = "\n#ifndef VK_USE_64_BIT_PTR_DEFINES\n\
+            \x20   #if defined(__LP64__) || defined(_WIN64)\n\
+            \x20       #define VK_USE_64_BIT_PTR_DEFINES 1\n\
+            \x20   #else\n\
+            \x20       #define VK_USE_64_BIT_PTR_DEFINES 0\n\
+            \x20   #endif\n\
+            #endif";
+        let define = |value: &str| {
+            PreprocessorItem::Define(TypeDefine {
+                name: "VK_USE_64_BIT_PTR_DEFINES".into(),
+                value: TypeDefineValue::Value(value.into()),
+            })
+        };
+        assert_eq!(
+            parse_preprocessor(code),
+            vec![PreprocessorItem::Conditional(vec![PreprocessorBranch {
+                condition: Some("!defined(VK_USE_64_BIT_PTR_DEFINES)".into()),
+                items: vec![PreprocessorItem::Conditional(vec![
+                    PreprocessorBranch {
+                        condition: Some("defined(__LP64__) || defined(_WIN64)".into()),
+                        items: vec![define("1")],
+                    },
+                    PreprocessorBranch {
+                        condition: None,
+                        items: vec![define("0")],
+                    },
+                ])],
+            }])]
+        );
+
+        let code =
+            "#ifdef __OBJC__\n@class CAMetalLayer;\n#else\ntypedef void CAMetalLayer;\n#endif";
+        assert_eq!(
+            parse_preprocessor(code),
+            vec![PreprocessorItem::Conditional(vec![
+                PreprocessorBranch {
+                    condition: Some("defined(__OBJC__)".into()),
+                    items: vec![PreprocessorItem::Code("@class CAMetalLayer;".into())],
+                },
+                PreprocessorBranch {
+                    condition: None,
+                    items: vec![PreprocessorItem::Code("typedef void CAMetalLayer;".into())],
+                },
+            ])]
+        );
+
+        // Unbalanced directives don't fail.
+        assert_eq!(
+            parse_preprocessor("#endif\n#if X\n#define Y"),
+            vec![
+                PreprocessorItem::Directive("#endif".into()),
+                PreprocessorItem::Conditional(vec![PreprocessorBranch {
+                    condition: Some("X".into()),
+                    items: vec![PreprocessorItem::Define(TypeDefine {
+                        name: "Y".into(),
+                        value: TypeDefineValue::Empty,
+                    })],
+                }]),
+            ]
+        );
+
+        // Blocks aren't nested deeper than the limit.
+        let depth = 100_000;
+        let code = format!("{}{}", "#if X\n".repeat(depth), "#endif\n".repeat(depth));
+        let mut items = parse_preprocessor(&code);
+        for _ in 0..MAX_CONDITIONAL_DEPTH {
+            assert_eq!(items.len(), 1);
+            items = match items.pop() {
+                Some(PreprocessorItem::Conditional(mut branches)) => branches.pop().unwrap().items,
+                other => panic!("{:?}", other),
+            };
         }
+        assert_eq!(items.len(), 2 * (depth - MAX_CONDITIONAL_DEPTH));
+        assert!(items
+            .iter()
+            .all(|item| matches!(*item, PreprocessorItem::Directive(_))));
     }
 }
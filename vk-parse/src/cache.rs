@@ -0,0 +1,177 @@
+use std;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::io::Read;
+
+use options::ParseOptions;
+use parse::{open_file, parse_bytes_with_options};
+use sections::{parse_section, split_sections};
+use types::*;
+
+/// Keeps parsed registry around and only re-parses it when the input changes.
+///
+/// Each top-level section of the registry is cached separately, keyed by hash of its contents,
+/// so after a small edit only the changed sections are parsed again. This is meant for tools
+/// which repeatedly load the same registry, e.g. in watch mode.
+///
+/// ```no_run
+/// extern crate vk_parse;
+/// use std::path::Path;
+///
+/// let mut cache = vk_parse::RegistryCache::new(vk_parse::ParseOptions::new());
+/// cache.update_file(Path::new("vk.xml")).unwrap();
+/// // ... vk.xml is modified ...
+/// if cache.update_file(Path::new("vk.xml")).unwrap() {
+///     println!("{:?}", cache.registry());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegistryCache {
+    options: ParseOptions,
+    document_hash: Option<u64>,
+    /// Cached sections, in document order. Empty if the document wasn't split into sections.
+    sections: Vec<CachedSection>,
+    registry: Registry,
+    errors: Vec<Error>,
+}
+
+type SectionResult = (Vec<RegistryChild>, Vec<Error>);
+
+#[derive(Debug, Clone)]
+struct CachedSection {
+    hash: u64,
+    /// Number of registry children produced by the section.
+    child_count: usize,
+    errors: Vec<Error>,
+}
+
+impl RegistryCache {
+    /// Creates empty cache, which will parse registries using given options.
+    pub fn new(options: ParseOptions) -> Self {
+        RegistryCache {
+            options,
+            document_hash: None,
+            sections: Vec::new(),
+            registry: Registry::default(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Registry parsed by the last successful update. Empty before the first one.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Recoverable errors encountered while parsing the registry returned by `registry`.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Drops all cached results, so that the next update parses the whole document.
+    pub fn invalidate(&mut self) {
+        self.document_hash = None;
+        self.sections.clear();
+        self.registry = Registry::default();
+        self.errors.clear();
+    }
+
+    /// Updates cached registry from the file, see `update`.
+    pub fn update_file(&mut self, path: &std::path::Path) -> Result<bool, FatalError> {
+        let mut bytes = Vec::new();
+        open_file(path)?.read_to_end(&mut bytes)?;
+        self.update(&bytes)
+    }
+
+    /// Updates cached registry from given document, parsing only sections which changed since
+    /// the previous update. Returns whether anything changed.
+    ///
+    /// With `ParseOptions::strict`, recoverable errors are returned as `FatalError::Strict`,
+    /// but the cache is still updated. After any other fatal error, the cache is invalidated.
+    pub fn update(&mut self, bytes: &[u8]) -> Result<bool, FatalError> {
+        let document_hash = hash_bytes(bytes);
+        if self.document_hash == Some(document_hash) {
+            return Ok(false);
+        }
+
+        let result = self.update_sections(bytes);
+        if let Err(e) = result {
+            self.invalidate();
+            return Err(e);
+        }
+        self.document_hash = Some(document_hash);
+
+        if self.options.strict && self.errors.iter().any(|e| !e.is_coverage()) {
+            return Err(FatalError::Strict(self.errors.clone()));
+        }
+        Ok(true)
+    }
+
+    fn update_sections(&mut self, bytes: &[u8]) -> Result<(), FatalError> {
+        let options = self.options.clone().strict(false);
+
+        // Element count limit applies to the whole document, so it can't be checked per section.
+        let sections = if options.max_elements.is_some() {
+            None
+        } else {
+            split_sections(bytes)
+        };
+        let sections = match sections {
+            Some(v) => v,
+            None => {
+                let (registry, errors) = parse_bytes_with_options(bytes, &options)?;
+                self.sections.clear();
+                self.registry = registry;
+                self.errors = errors;
+                return Ok(());
+            }
+        };
+
+        // Results of previous sections by their hash. The same section can appear repeatedly.
+        let mut previous: HashMap<u64, Vec<SectionResult>> = HashMap::new();
+        let mut old_children = std::mem::take(&mut self.registry.0).into_iter();
+        for section in self.sections.drain(..) {
+            let children = old_children.by_ref().take(section.child_count).collect();
+            previous
+                .entry(section.hash)
+                .or_default()
+                .push((children, section.errors));
+        }
+        for results in previous.values_mut() {
+            results.reverse();
+        }
+
+        let mut registry = Registry::default();
+        let mut errors = Vec::new();
+        for section in sections {
+            let hash = hash_bytes(&bytes[section.range.clone()]);
+            let reused = previous.get_mut(&hash).and_then(|v| v.pop());
+            let (children, section_errors) = match reused {
+                Some(v) => v,
+                None if !options.parses_section(section.name) => (Vec::new(), Vec::new()),
+                None => {
+                    let (section_registry, section_errors) =
+                        parse_section(bytes, &section, &options)?;
+                    (section_registry.0, section_errors)
+                }
+            };
+            self.sections.push(CachedSection {
+                hash,
+                child_count: children.len(),
+                errors: section_errors.clone(),
+            });
+            registry.0.extend(children);
+            errors.extend(section_errors);
+        }
+
+        self.registry = registry;
+        self.errors = errors;
+        Ok(())
+    }
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,288 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use types::*;
+
+/// Struct describing features of physical devices, see `Registry::feature_structs`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct FeatureStruct<'a> {
+    pub name: &'a str,
+    pub definition: &'a Type,
+    /// Aliases of the struct, e.g. `VkPhysicalDevice16BitStorageFeaturesKHR`.
+    pub aliases: Vec<&'a str>,
+    /// Value of `sType`, e.g. `VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_16BIT_STORAGE_FEATURES`, `None`
+    /// for `VkPhysicalDeviceFeatures`.
+    pub s_type: Option<&'a str>,
+    /// Features and extensions requiring the struct or its aliases, in order of the registry.
+    pub required_by: Vec<&'a str>,
+    /// Members of type `VkBool32`, each enabling a feature, in declaration order.
+    pub features: Vec<&'a TypeMemberDefinition>,
+}
+
+/// Struct describing properties or limits of physical devices, see
+/// `Registry::property_structs`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PropertyStruct<'a> {
+    pub name: &'a str,
+    pub definition: &'a Type,
+    /// Aliases of the struct, e.g. `VkPhysicalDeviceMaintenance3PropertiesKHR`.
+    pub aliases: Vec<&'a str>,
+    /// Value of `sType`, `None` for structs without it, e.g. `VkPhysicalDeviceLimits`.
+    pub s_type: Option<&'a str>,
+    /// Features and extensions requiring the struct or its aliases, in order of the registry.
+    pub required_by: Vec<&'a str>,
+    /// Members other than `sType` and `pNext`, in declaration order.
+    pub members: Vec<PropertyMember<'a>>,
+}
+
+/// Member of struct describing properties, see `Registry::property_structs`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PropertyMember<'a> {
+    pub definition: &'a TypeMemberDefinition,
+    /// How values of the member compare, given by `limittype`, see
+    /// `TypeMemberDefinition::limit_types`.
+    pub limit_types: Vec<LimitType>,
+}
+
+/// How a value of property or limit compares between devices, given by `limittype` of members,
+/// e.g. to merge limits of multiple devices or check them against a profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum LimitType {
+    /// `min`, smaller values are better.
+    Min,
+    /// `max`, larger values are better.
+    Max,
+    /// `pot`, the value is a power of two.
+    Pot,
+    /// `mul`, the value is a multiple of the limit.
+    Mul,
+    /// `bits`, the value is a number of bits, where larger values are better.
+    Bits,
+    /// `bitmask`, the value is a set of flags, where supersets are better.
+    Bitmask,
+    /// `range`, the value is an array of minimum and maximum, where wider ranges are better.
+    Range,
+    /// `struct`, the value is a struct whose members have their own limit types.
+    Struct,
+    /// `exact`, the value must match exactly.
+    Exact,
+    /// `noauto`, the value can't be compared automatically.
+    NoAuto,
+}
+
+impl LimitType {
+    /// Limit type with given name, as used in `limittype`, e.g. `max`.
+    pub fn from_name(name: &str) -> Option<LimitType> {
+        Some(match name {
+            "min" => LimitType::Min,
+            "max" => LimitType::Max,
+            "pot" => LimitType::Pot,
+            "mul" => LimitType::Mul,
+            "bits" => LimitType::Bits,
+            "bitmask" => LimitType::Bitmask,
+            "range" => LimitType::Range,
+            "struct" => LimitType::Struct,
+            "exact" => LimitType::Exact,
+            "noauto" => LimitType::NoAuto,
+            _ => return None,
+        })
+    }
+
+    /// Name of the limit type, as used in `limittype`.
+    pub fn name(self) -> &'static str {
+        match self {
+            LimitType::Min => "min",
+            LimitType::Max => "max",
+            LimitType::Pot => "pot",
+            LimitType::Mul => "mul",
+            LimitType::Bits => "bits",
+            LimitType::Bitmask => "bitmask",
+            LimitType::Range => "range",
+            LimitType::Struct => "struct",
+            LimitType::Exact => "exact",
+            LimitType::NoAuto => "noauto",
+        }
+    }
+}
+
+impl TypeMemberDefinition {
+    /// Limit types of the member given by comma-separated `limittype`, e.g. `[Max, Pot]` for
+    /// `max,pot`. Unrecognized limit types are skipped, but remain available in `limittype`.
+    pub fn limit_types(&self) -> Vec<LimitType> {
+        self.limittype
+            .as_deref()
+            .into_iter()
+            .flat_map(|limittype| limittype.split(','))
+            .filter_map(|name| LimitType::from_name(name.trim()))
+            .collect()
+    }
+}
+
+impl Registry {
+    /// Structs describing features of physical devices, i.e. `VkPhysicalDevice*Features*`
+    /// structs with members of type `VkBool32`, in order of the registry. Wrappers such as
+    /// `VkPhysicalDeviceFeatures2`, which have no such members, aren't included. Structs whose
+    /// API differs, e.g. for `vulkansc`, are listed per definition.
+    pub fn feature_structs(&self) -> Vec<FeatureStruct<'_>> {
+        catalog_structs(self, |name| {
+            name.strip_prefix("VkPhysicalDevice")
+                .map_or(false, |rest| rest.contains("Features"))
+        })
+        .into_iter()
+        .filter_map(|entry| {
+            let features: Vec<_> = entry.members.into_iter().filter(|&m| is_bool(m)).collect();
+            if features.is_empty() {
+                return None;
+            }
+            Some(FeatureStruct {
+                name: entry.name,
+                definition: entry.definition,
+                aliases: entry.aliases,
+                s_type: entry.s_type,
+                required_by: entry.required_by,
+                features,
+            })
+        })
+        .collect()
+    }
+
+    /// Structs describing properties and limits of physical devices, i.e.
+    /// `VkPhysicalDevice*Properties*` structs and `VkPhysicalDeviceLimits`, in order of the
+    /// registry, with limit types of their members. Structs whose API differs, e.g. for
+    /// `vulkansc`, are listed per definition.
+    pub fn property_structs(&self) -> Vec<PropertyStruct<'_>> {
+        catalog_structs(self, |name| {
+            name.strip_prefix("VkPhysicalDevice").map_or(false, |rest| {
+                rest.contains("Properties") || rest == "Limits"
+            })
+        })
+        .into_iter()
+        .map(|entry| PropertyStruct {
+            name: entry.name,
+            definition: entry.definition,
+            aliases: entry.aliases,
+            s_type: entry.s_type,
+            required_by: entry.required_by,
+            members: entry
+                .members
+                .into_iter()
+                .map(|definition| PropertyMember {
+                    definition,
+                    limit_types: definition.limit_types(),
+                })
+                .collect(),
+        })
+        .collect()
+    }
+}
+
+/// Struct of a catalog, along with the data shared by all catalogs.
+struct CatalogEntry<'a> {
+    name: &'a str,
+    definition: &'a Type,
+    aliases: Vec<&'a str>,
+    s_type: Option<&'a str>,
+    required_by: Vec<&'a str>,
+    /// Members other than `sType` and `pNext`.
+    members: Vec<&'a TypeMemberDefinition>,
+}
+
+/// Structs which aren't aliases, with names accepted by `is_included`, in order of the registry.
+fn catalog_structs<'a, F: Fn(&str) -> bool>(
+    registry: &'a Registry,
+    is_included: F,
+) -> Vec<CatalogEntry<'a>> {
+    let required_by = required_types(registry);
+    let mut aliases: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for ty in registry.types() {
+        if let (Some(name), Some(alias)) = (ty.name.as_deref(), ty.alias.as_deref()) {
+            aliases.entry(alias).or_default().push(name);
+        }
+    }
+
+    let mut entries = Vec::new();
+    for ty in registry.types() {
+        let name = match ty.name.as_deref() {
+            Some(name) if is_included(name) && ty.alias.is_none() => name,
+            _ => continue,
+        };
+        let members = match ty.spec {
+            TypeSpec::Members(ref members) => members,
+            _ => continue,
+        };
+        let definitions = members.iter().filter_map(|member| match *member {
+            TypeMember::Definition(ref def) => Some(def),
+            _ => None,
+        });
+        let mut s_type = None;
+        let mut included = Vec::new();
+        for def in definitions {
+            match def.name() {
+                Some("sType") => s_type = def.values.as_deref(),
+                Some("pNext") => {}
+                _ => included.push(def),
+            }
+        }
+
+        let aliases = aliases.get(name).cloned().unwrap_or_default();
+        let mut requiring: Vec<(usize, &str)> = Vec::new();
+        for type_name in core::iter::once(name).chain(aliases.iter().cloned()) {
+            for &entry in required_by.get(type_name).into_iter().flatten() {
+                if !requiring.contains(&entry) {
+                    requiring.push(entry);
+                }
+            }
+        }
+        requiring.sort();
+        entries.push(CatalogEntry {
+            name,
+            definition: ty,
+            aliases,
+            s_type,
+            required_by: requiring.into_iter().map(|(_, name)| name).collect(),
+            members: included,
+        });
+    }
+    entries
+}
+
+fn is_bool(def: &TypeMemberDefinition) -> bool {
+    match def.c_type {
+        Some(CType::Named { ref name, .. }) => &**name == "VkBool32",
+        _ => false,
+    }
+}
+
+/// Features and extensions which aren't disabled requiring each type, by name of the type,
+/// along with their position in the registry.
+pub(crate) fn required_types(registry: &Registry) -> BTreeMap<&str, Vec<(usize, &str)>> {
+    let features = registry.features().map(|f| (&*f.name, &f.children));
+    let extensions = registry
+        .extensions()
+        .filter(|e| !e.is_disabled())
+        .map(|e| (&*e.name, &e.children));
+    let mut required_by = BTreeMap::new();
+    for (index, (name, children)) in features.chain(extensions).enumerate() {
+        for child in children {
+            let items = match *child {
+                ExtensionChild::Require { ref items, .. } => items,
+                _ => continue,
+            };
+            for item in items {
+                if let InterfaceItem::Type { name: ref ty, .. } = *item {
+                    let names: &mut Vec<_> = required_by.entry(&**ty).or_default();
+                    if !names.contains(&(index, name)) {
+                        names.push((index, name));
+                    }
+                }
+            }
+        }
+    }
+    required_by
+}
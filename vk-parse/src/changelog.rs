@@ -0,0 +1,189 @@
+#[cfg(not(feature = "std"))]
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::fmt::Write;
+
+use diff::{diff, Change};
+use types::*;
+
+/// Summary of changes between two registries as a Markdown list for release notes, with
+/// sections for new and removed extensions, promotions and deprecations of extensions, new
+/// features, commands, types, enum values and constants. Sections without any changes are
+/// omitted, so the result is empty if there are none. See `diff` for a full comparison.
+pub fn changelog(old: &Registry, new: &Registry) -> String {
+    let diff = diff(old, new);
+    let mut out = String::new();
+
+    let added_extensions = diff.extensions.iter().filter_map(added);
+    section(&mut out, "New extensions", added_extensions.map(ext_line));
+    let removed_extensions = diff.extensions.iter().filter_map(removed);
+    section(
+        &mut out,
+        "Removed extensions",
+        removed_extensions.map(ext_line),
+    );
+
+    let mut promoted = Vec::new();
+    let mut deprecated = Vec::new();
+    for change in &diff.extensions {
+        let (old, new) = match *change {
+            Change::Changed { old, new } => (Some(old), new),
+            Change::Added(new) => (None, new),
+            Change::Removed(_) => continue,
+        };
+        let old_promotedto = old.and_then(|e| e.promotedto.as_deref());
+        if let Some(promotedto) = new.promotedto.as_deref() {
+            if old_promotedto != Some(promotedto) {
+                promoted.push(format!("`{}` to `{}`", new.name, promotedto));
+            }
+        }
+        let old_deprecated = old.map_or(false, |e| e.deprecatedby.is_some());
+        if let (false, Some(by)) = (old_deprecated, new.deprecatedby.as_deref()) {
+            deprecated.push(replaced_by(&new.name, by));
+        }
+        let old_obsoleted = old.map_or(false, |e| e.obsoletedby.is_some());
+        if let (false, Some(by)) = (old_obsoleted, new.obsoletedby.as_deref()) {
+            deprecated.push(format!("{} (obsoleted)", replaced_by(&new.name, by)));
+        }
+    }
+    section(&mut out, "Promoted extensions", promoted);
+    section(&mut out, "Deprecated extensions", deprecated);
+
+    let features = diff.features.iter().filter_map(added);
+    section(
+        &mut out,
+        "New features",
+        features.map(|f| format!("`{}` ({} {})", f.name, f.api, f.number)),
+    );
+
+    let commands = diff.commands.iter().filter_map(added);
+    section(
+        &mut out,
+        "New commands",
+        commands.map(|c| format!("`{}`", c.name())),
+    );
+    let commands = diff.commands.iter().filter_map(removed);
+    section(
+        &mut out,
+        "Removed commands",
+        commands.map(|c| format!("`{}`", c.name())),
+    );
+
+    let types = diff.types.iter().filter_map(|t| added(&t.change));
+    section(&mut out, "New types", types.map(type_line));
+    let types = diff.types.iter().filter_map(|t| removed(&t.change));
+    section(&mut out, "Removed types", types.map(type_line));
+    let changed_members = diff.types.iter().filter_map(|t| {
+        let names = |members: Vec<&TypeMemberDefinition>| {
+            let names: Vec<_> = members
+                .iter()
+                .filter_map(|m| Some(format!("`{}`", m.name()?)))
+                .collect();
+            names.join(", ")
+        };
+        let added = names(t.members.iter().filter_map(added).collect());
+        let removed = names(t.members.iter().filter_map(removed).collect());
+        let changes = match (added.is_empty(), removed.is_empty()) {
+            (true, true) => return None,
+            (false, true) => format!("added {}", added),
+            (true, false) => format!("removed {}", removed),
+            (false, false) => format!("added {}; removed {}", added, removed),
+        };
+        Some(format!("{}: {}", type_line(t.change.after()?), changes))
+    });
+    section(&mut out, "Changed struct members", changed_members);
+
+    // Types extended by enums of `<enums>` blocks, which enums don't refer to themselves.
+    let mut block_names = BTreeMap::new();
+    for enums in new.enums_blocks() {
+        for child in &enums.children {
+            if let (EnumsChild::Enum(ref e), Some(name)) = (child, enums.name.as_deref()) {
+                block_names.insert(&*e.name, name);
+            }
+        }
+    }
+    let values = diff.enums.iter().filter_map(added).map(|e| {
+        let extends = match e.spec {
+            EnumSpec::Offset { ref extends, .. } => Some(&**extends),
+            EnumSpec::Alias { ref extends, .. }
+            | EnumSpec::Bitpos { ref extends, .. }
+            | EnumSpec::Value { ref extends, .. } => extends.as_deref(),
+            EnumSpec::None => None,
+        };
+        match extends.or_else(|| block_names.get(&*e.name).cloned()) {
+            Some(extends) => format!("`{}` in `{}`", e.name, extends),
+            None => format!("`{}`", e.name),
+        }
+    });
+    section(&mut out, "New enum values", values);
+
+    let constants = diff.constants.iter().filter_map(added);
+    section(
+        &mut out,
+        "New constants",
+        constants.map(|e| format!("`{}`", e.name)),
+    );
+
+    out
+}
+
+fn added<'a, T>(change: &Change<'a, T>) -> Option<&'a T> {
+    match *change {
+        Change::Added(item) => Some(item),
+        _ => None,
+    }
+}
+
+fn removed<'a, T>(change: &Change<'a, T>) -> Option<&'a T> {
+    match *change {
+        Change::Removed(item) => Some(item),
+        _ => None,
+    }
+}
+
+/// Writes Markdown section with list of `lines`, unless there are none.
+fn section<I: IntoIterator<Item = String>>(out: &mut String, title: &str, lines: I) {
+    let mut lines = lines.into_iter().peekable();
+    if lines.peek().is_none() {
+        return;
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    let _ = writeln!(out, "## {}\n", title);
+    for line in lines {
+        let _ = writeln!(out, "- {}", line);
+    }
+}
+
+fn ext_line(ext: &Extension) -> String {
+    let mut line = format!("`{}`", ext.name);
+    if ext.provisional {
+        line.push_str(" (provisional)");
+    }
+    line
+}
+
+fn type_line(ty: &Type) -> String {
+    let name = ty.declared_name().unwrap_or("");
+    match ty.api {
+        Some(ref api) => format!("`{}` ({})", name, api),
+        None => format!("`{}`", name),
+    }
+}
+
+/// Extension along with what replaces it, given by `deprecatedby` or `obsoletedby`, which is
+/// empty if there's no replacement.
+fn replaced_by(name: &str, by: &str) -> String {
+    if by.is_empty() {
+        format!("`{}`", name)
+    } else {
+        format!("`{}` by `{}`", name, by)
+    }
+}
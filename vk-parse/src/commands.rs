@@ -0,0 +1,495 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use types::*;
+
+/// Command definition along with all names under which it's available, see
+/// `Registry::command_aliases`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CommandAliasGroup<'a> {
+    pub definition: &'a CommandDefinition,
+    /// Name of the definition first, followed by its aliases in order of the registry.
+    pub names: Vec<CommandName<'a>>,
+}
+
+/// Name of a command, along with features and extensions which require it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CommandName<'a> {
+    pub name: &'a str,
+    /// Names of features and extensions requiring the command, e.g. `VK_VERSION_1_1` for
+    /// `vkGetPhysicalDeviceFeatures2` and `VK_KHR_get_physical_device_properties2` for
+    /// `vkGetPhysicalDeviceFeatures2KHR`, with features first.
+    pub required_by: Vec<&'a str>,
+}
+
+impl Registry {
+    /// Finds the definition of a command along with all its aliases, given the name of the
+    /// command or any of its aliases. Entry points with names of the same group share the same
+    /// function pointer type, e.g. when an extension command was promoted to core.
+    ///
+    /// Returns `None` if there's no such command, or if its alias doesn't lead to a definition.
+    pub fn command_aliases(&self, name: &str) -> Option<CommandAliasGroup<'_>> {
+        let mut commands = BTreeMap::new();
+        for command in self.commands() {
+            commands.entry(command.name()).or_insert(command);
+        }
+        let definition = resolve(&commands, name)?;
+
+        let mut names = Vec::new();
+        names.push(CommandName {
+            name: &definition.proto.name,
+            required_by: Vec::new(),
+        });
+        for command in self.commands() {
+            let name = match *command {
+                Command::Alias { ref name, .. } => &**name,
+                Command::Definition(_) => continue,
+            };
+            let is_alias = resolve(&commands, name).map_or(false, |d| core::ptr::eq(d, definition));
+            if is_alias && names.iter().all(|n| n.name != name) {
+                names.push(CommandName {
+                    name,
+                    required_by: Vec::new(),
+                });
+            }
+        }
+
+        let required_by = required_by(self, self.extensions());
+        for n in &mut names {
+            n.required_by = required_by.get(n.name).cloned().unwrap_or_default();
+        }
+        Some(CommandAliasGroup { definition, names })
+    }
+}
+
+/// Kind of queue a command can be executed on, given by comma-separated `queues` of commands
+/// and of `<syncsupport>`, e.g. `graphics,compute`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum QueueKind {
+    /// `graphics`
+    Graphics,
+    /// `compute`
+    Compute,
+    /// `transfer`
+    Transfer,
+    /// `sparse_binding`
+    SparseBinding,
+    /// `decode`, for video decode queues.
+    Decode,
+    /// `encode`, for video encode queues.
+    Encode,
+    /// `opticalflow`
+    OpticalFlow,
+    /// Any other queue kind, e.g. added by a newer registry.
+    Other(Box<str>),
+}
+
+impl QueueKind {
+    /// Queue kind with given name, e.g. `sparse_binding`.
+    pub fn from_name(name: &str) -> QueueKind {
+        match name {
+            "graphics" => QueueKind::Graphics,
+            "compute" => QueueKind::Compute,
+            "transfer" => QueueKind::Transfer,
+            "sparse_binding" => QueueKind::SparseBinding,
+            "decode" => QueueKind::Decode,
+            "encode" => QueueKind::Encode,
+            "opticalflow" => QueueKind::OpticalFlow,
+            _ => QueueKind::Other(name.into()),
+        }
+    }
+
+    /// Name of the queue kind, as used in `queues`.
+    pub fn name(&self) -> &str {
+        match *self {
+            QueueKind::Graphics => "graphics",
+            QueueKind::Compute => "compute",
+            QueueKind::Transfer => "transfer",
+            QueueKind::SparseBinding => "sparse_binding",
+            QueueKind::Decode => "decode",
+            QueueKind::Encode => "encode",
+            QueueKind::OpticalFlow => "opticalflow",
+            QueueKind::Other(ref name) => name,
+        }
+    }
+
+    /// Parses comma-separated list of queue kinds, in order of appearance and without
+    /// duplicates. Returns `None` if it's empty or has an empty name.
+    pub fn parse_list(text: &str) -> Option<Vec<QueueKind>> {
+        let mut queues = Vec::new();
+        for name in text.split(',') {
+            if name.is_empty() {
+                return None;
+            }
+            let queue = QueueKind::from_name(name);
+            if !queues.contains(&queue) {
+                queues.push(queue);
+            }
+        }
+        Some(queues)
+    }
+
+    /// Comma-separated list of queue kinds, as used in `queues`.
+    pub fn join(queues: &[QueueKind]) -> String {
+        let names: Vec<_> = queues.iter().map(QueueKind::name).collect();
+        names.join(",")
+    }
+}
+
+/// How a command is loaded, see `Registry::loader_table`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum LoaderLevel {
+    /// Exported by the loader library itself, i.e. `vkGetInstanceProcAddr`.
+    Exported,
+    /// Loaded by `vkGetInstanceProcAddr` without an instance, e.g. `vkCreateInstance`.
+    Global,
+    /// Loaded by `vkGetInstanceProcAddr` with an instance, for commands dispatched by an
+    /// instance or physical device, along with `vkGetDeviceProcAddr`.
+    Instance,
+    /// Loaded by `vkGetDeviceProcAddr`, for commands dispatched by a device, queue or command
+    /// buffer.
+    Device,
+}
+
+/// Commands loaded the same way, see `Registry::loader_table`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct LoaderGroup<'a> {
+    pub level: LoaderLevel,
+    pub entries: Vec<LoaderEntry<'a>>,
+}
+
+/// Entry point to load, see `Registry::loader_table`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct LoaderEntry<'a> {
+    pub name: &'a str,
+    /// Definition of the command, which is shared by its aliases.
+    pub definition: &'a CommandDefinition,
+    /// Name of the command aliased by the entry point, if it's an alias.
+    pub alias: Option<&'a str>,
+    /// Names of features and extensions providing the entry point, with features first. Any
+    /// of them being enabled allows loading it.
+    pub required_by: Vec<&'a str>,
+}
+
+impl Registry {
+    /// Entry points to load, grouped by `LoaderLevel` in order of the levels, with entry points
+    /// in order of the registry. Aliases are listed as separate entry points, as they need to be
+    /// loaded by their own names. Commands which aren't required by any feature or extension
+    /// that isn't disabled are omitted.
+    ///
+    /// The level is given by the first parameter, which is the dispatchable handle, if any.
+    /// Commands dispatched by `VkDevice`, or by a handle whose `parent` leads to it, are device
+    /// level, while those dispatched by other handles are instance level.
+    pub fn loader_table(&self) -> Vec<LoaderGroup<'_>> {
+        let mut commands = BTreeMap::new();
+        for command in self.commands() {
+            commands.entry(command.name()).or_insert(command);
+        }
+        let mut handles = BTreeMap::new();
+        for ty in self.types() {
+            if ty.category.as_deref() == Some("handle") {
+                if let Some(name) = ty.declared_name() {
+                    handles.entry(name).or_insert(ty);
+                }
+            }
+        }
+        let required_by = required_by(self, self.extensions().filter(|e| !e.is_disabled()));
+
+        let mut groups = Vec::new();
+        for &level in &[
+            LoaderLevel::Exported,
+            LoaderLevel::Global,
+            LoaderLevel::Instance,
+            LoaderLevel::Device,
+        ] {
+            groups.push(LoaderGroup {
+                level,
+                entries: Vec::new(),
+            });
+        }
+        for command in self.commands() {
+            let name = command.name();
+            let definition = match resolve(&commands, name) {
+                Some(definition) if core::ptr::eq(commands[name], command) => definition,
+                _ => continue,
+            };
+            let required_by = match required_by.get(name) {
+                Some(required_by) => required_by.clone(),
+                None => continue,
+            };
+            let level = loader_level(definition, &handles);
+            groups[level as usize].entries.push(LoaderEntry {
+                name,
+                definition,
+                alias: match *command {
+                    Command::Alias { ref alias, .. } => Some(alias),
+                    Command::Definition(_) => None,
+                },
+                required_by,
+            });
+        }
+        groups
+    }
+}
+
+/// Level of the command, given handle types by name.
+fn loader_level(definition: &CommandDefinition, handles: &BTreeMap<&str, &Type>) -> LoaderLevel {
+    match &*definition.proto.name {
+        "vkGetInstanceProcAddr" => return LoaderLevel::Exported,
+        "vkGetDeviceProcAddr" => return LoaderLevel::Instance,
+        _ => {}
+    }
+    let first = definition.params.first();
+    let mut handle = match first.and_then(|p| p.definition.type_name.as_deref()) {
+        Some(name) if handles.get(name).map_or(false, |ty| is_dispatchable(ty)) => name,
+        _ => return LoaderLevel::Global,
+    };
+    // Limits the number of steps, in case parents form a cycle.
+    for _ in 0..=handles.len() {
+        if handle == "VkDevice" {
+            return LoaderLevel::Device;
+        }
+        handle = match handles.get(handle).and_then(|ty| ty.parent.as_deref()) {
+            Some(parent) => parent,
+            None => break,
+        };
+    }
+    LoaderLevel::Instance
+}
+
+/// Names of features and given extensions requiring each command, by name of the command.
+fn required_by<'a, I: Iterator<Item = &'a Extension>>(
+    registry: &'a Registry,
+    extensions: I,
+) -> BTreeMap<&'a str, Vec<&'a str>> {
+    let mut required_by = BTreeMap::new();
+    let features = registry.features().map(|f| (&*f.name, &f.children));
+    let extensions = extensions.map(|e| (&*e.name, &e.children));
+    for (name, children) in features.chain(extensions) {
+        for child in children {
+            let items = match *child {
+                ExtensionChild::Require { ref items, .. } => items,
+                _ => continue,
+            };
+            for item in items {
+                if let InterfaceItem::Command {
+                    name: ref command, ..
+                } = *item
+                {
+                    let names: &mut Vec<_> = required_by.entry(&**command).or_default();
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+        }
+    }
+    required_by
+}
+
+/// Follows aliases of the command to its definition.
+fn resolve<'a>(
+    commands: &BTreeMap<&str, &'a Command>,
+    name: &str,
+) -> Option<&'a CommandDefinition> {
+    let mut name = name;
+    // Limits the number of steps, in case aliases form a cycle.
+    for _ in 0..=commands.len() {
+        match **commands.get(name)? {
+            Command::Alias { ref alias, .. } => name = alias,
+            Command::Definition(ref definition) => return Some(definition),
+        }
+    }
+    None
+}
+
+/// Commands creating and destroying objects of a handle type, see
+/// `Registry::handle_lifetimes`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct HandleLifetime<'a> {
+    pub handle: &'a Type,
+    /// Name of the handle type, e.g. `VkCommandBuffer`.
+    pub name: &'a str,
+    /// Commands returning new objects of the handle type, e.g. `vkCreateBuffer` or
+    /// `vkAllocateCommandBuffers`.
+    pub create: Vec<&'a CommandDefinition>,
+    /// Commands destroying objects of the handle type, e.g. `vkDestroyBuffer` or
+    /// `vkFreeCommandBuffers`.
+    pub destroy: Vec<&'a CommandDefinition>,
+}
+
+impl Registry {
+    /// Finds commands creating and destroying each handle type, in order of the handle types.
+    /// Handle aliases are included in the types they alias.
+    ///
+    /// Commands are recognized by their parameters rather than names:
+    /// - A command creates objects of the handle type it returns through its last non-const
+    ///   pointer to a handle, if it takes `VkAllocationCallbacks`, or a struct with a member of
+    ///   the non-dispatchable parent type of the handle, such as `VkCommandBufferAllocateInfo`
+    ///   which contains the `VkCommandPool`.
+    /// - A command which doesn't return a handle destroys objects of its last handle parameter
+    ///   passed by value, if it takes `VkAllocationCallbacks`, or of handles passed through const
+    ///   pointer along with their non-dispatchable parent, as for `vkFreeCommandBuffers`.
+    ///
+    /// Parameters without a recognized `c_type` are ignored.
+    pub fn handle_lifetimes(&self) -> Vec<HandleLifetime<'_>> {
+        let mut types = BTreeMap::new();
+        let mut lifetimes = Vec::new();
+        // Index into `lifetimes` by name of the handle or its alias.
+        let mut handles = BTreeMap::new();
+        for ty in self.types() {
+            let name = match ty.declared_name() {
+                Some(name) => name,
+                None => continue,
+            };
+            types.entry(name).or_insert(ty);
+            let is_handle = ty.category.as_deref() == Some("handle")
+                && matches!(ty.spec, TypeSpec::Code(_))
+                && !handles.contains_key(name);
+            if is_handle {
+                handles.insert(name, lifetimes.len());
+                lifetimes.push(HandleLifetime {
+                    handle: ty,
+                    name,
+                    create: Vec::new(),
+                    destroy: Vec::new(),
+                });
+            }
+        }
+        for (&name, &ty) in &types {
+            if let TypeSpec::HandleAlias { ref alias } = ty.spec {
+                if let Some(&i) = handles.get(&**alias) {
+                    handles.entry(name).or_insert(i);
+                }
+            }
+        }
+
+        // Non-dispatchable parent of the handle, i.e. a pool allocating its objects.
+        let pool = |handle: usize| {
+            let parent = lifetimes[handle].handle.parent.as_deref()?;
+            let &parent = handles.get(parent)?;
+            if is_dispatchable(lifetimes[parent].handle) {
+                None
+            } else {
+                Some(parent)
+            }
+        };
+        let mut created = Vec::new();
+        let mut destroyed = Vec::new();
+        for command in self.commands() {
+            let definition = match *command {
+                Command::Definition(ref definition) => definition,
+                Command::Alias { .. } => continue,
+            };
+            let params: Vec<_> = definition
+                .params
+                .iter()
+                .filter_map(|p| p.definition.c_type.as_ref())
+                .collect();
+            let takes_allocator = params
+                .iter()
+                .any(|c_type| named(c_type.pointee()) == Some("VkAllocationCallbacks"));
+            let handle_of = |c_type: &CType| handles.get(named(c_type)?).cloned();
+
+            let output = params.iter().rev().find_map(|c_type| match **c_type {
+                CType::Pointer { ref inner, .. } if !is_const(inner) => handle_of(inner),
+                _ => None,
+            });
+            if let Some(handle) = output {
+                let takes_pool_info = pool(handle).map_or(false, |pool| {
+                    params.iter().any(|c_type| match **c_type {
+                        CType::Pointer { ref inner, .. } if is_const(inner) => named(inner)
+                            .and_then(|name| types.get(name))
+                            .map_or(false, |ty| has_member_of_type(ty, lifetimes[pool].name)),
+                        _ => false,
+                    })
+                });
+                if takes_allocator || takes_pool_info {
+                    created.push((handle, definition));
+                }
+                continue;
+            }
+
+            if takes_allocator {
+                if let Some(handle) = params.iter().rev().find_map(|c_type| handle_of(c_type)) {
+                    destroyed.push((handle, definition));
+                }
+                continue;
+            }
+            for c_type in &params {
+                let handle = match **c_type {
+                    CType::Pointer { ref inner, .. } if is_const(inner) => handle_of(inner),
+                    _ => None,
+                };
+                let handle = match handle {
+                    Some(handle) => handle,
+                    None => continue,
+                };
+                let takes_pool = pool(handle).map_or(false, |pool| {
+                    params.iter().any(|c_type| handle_of(c_type) == Some(pool))
+                });
+                if takes_pool {
+                    destroyed.push((handle, definition));
+                    break;
+                }
+            }
+        }
+        for (handle, definition) in created {
+            lifetimes[handle].create.push(definition);
+        }
+        for (handle, definition) in destroyed {
+            lifetimes[handle].destroy.push(definition);
+        }
+        lifetimes
+    }
+}
+
+/// Name of the type, unless it's a pointer or array.
+fn named(c_type: &CType) -> Option<&str> {
+    match *c_type {
+        CType::Named { ref name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+fn is_const(c_type: &CType) -> bool {
+    match *c_type {
+        CType::Named { is_const, .. } | CType::Pointer { is_const, .. } => is_const,
+        _ => false,
+    }
+}
+
+/// Whether the handle is defined by `VK_DEFINE_HANDLE`, as opposed to
+/// `VK_DEFINE_NON_DISPATCHABLE_HANDLE`.
+fn is_dispatchable(handle: &Type) -> bool {
+    match handle.spec {
+        TypeSpec::Code(ref code) => code
+            .markup
+            .iter()
+            .any(|m| matches!(*m, TypeCodeMarkup::Type(ref name) if &**name == "VK_DEFINE_HANDLE")),
+        _ => false,
+    }
+}
+
+fn has_member_of_type(ty: &Type, type_name: &str) -> bool {
+    match ty.spec {
+        TypeSpec::Members(ref members) => members.iter().any(|member| match *member {
+            TypeMember::Definition(ref def) => def
+                .markup
+                .iter()
+                .any(|m| matches!(*m, TypeMemberMarkup::Type(ref name) if &**name == type_name)),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
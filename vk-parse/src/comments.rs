@@ -0,0 +1,72 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use types::*;
+
+/// Items of a block following a free-standing `<comment>`, which usually describes them, see
+/// `Types::comment_groups` and `Enums::comment_groups`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct CommentGroup<'a, T> {
+    /// The comment, `None` for items preceding the first comment of the block.
+    pub comment: Option<&'a str>,
+    /// Items following the comment, up to the next one, in order of the block.
+    pub items: Vec<&'a T>,
+}
+
+impl Types {
+    /// Groups types of the block by the comments preceding them, e.g. types following
+    /// `<comment>Basic C types, pulled in via vk_platform.h</comment>`, in order of the block.
+    /// Comments without any types following them, e.g. when followed by another comment,
+    /// aren't included.
+    pub fn comment_groups(&self) -> Vec<CommentGroup<'_, Type>> {
+        comment_groups(self.children.iter().map(|child| match *child {
+            TypesChild::Type(ref ty) => Some(Ok(ty)),
+            TypesChild::Comment(ref comment) => Some(Err(&**comment)),
+        }))
+    }
+}
+
+impl Enums {
+    /// Groups enums of the block by the comments preceding them, in order of the block. Ranges
+    /// of unused values don't break groups, while comments without any enums following them
+    /// aren't included, see `Types::comment_groups`.
+    pub fn comment_groups(&self) -> Vec<CommentGroup<'_, Enum>> {
+        comment_groups(self.children.iter().map(|child| match *child {
+            EnumsChild::Enum(ref e) => Some(Ok(e)),
+            EnumsChild::Comment(ref comment) => Some(Err(&**comment)),
+            EnumsChild::Unused(_) => None,
+        }))
+    }
+}
+
+/// Groups children given as either items or comments, skipping other children.
+fn comment_groups<'a, T, I>(children: I) -> Vec<CommentGroup<'a, T>>
+where
+    I: Iterator<Item = Option<Result<&'a T, &'a str>>>,
+{
+    let mut groups = Vec::new();
+    let mut current = CommentGroup {
+        comment: None,
+        items: Vec::new(),
+    };
+    for child in children.flatten() {
+        match child {
+            Ok(item) => current.items.push(item),
+            Err(comment) => {
+                let next = CommentGroup {
+                    comment: Some(comment),
+                    items: Vec::new(),
+                };
+                let group = core::mem::replace(&mut current, next);
+                if !group.items.is_empty() {
+                    groups.push(group);
+                }
+            }
+        }
+    }
+    if !current.items.is_empty() {
+        groups.push(current);
+    }
+    groups
+}
@@ -1,6 +1,8 @@
 extern crate vkxml;
 
+use api::ExtensionSupport;
 use c;
+use commands::QueueKind;
 use parse::*;
 use std;
 use types::*;
@@ -63,11 +65,13 @@ impl From<Registry> for vkxml::Registry {
             match item {
                 RegistryChild::Comment(comment) => {
                     if let Some(ref mut enums) = enums {
-                        enums.elements.push(vkxml::EnumsElement::Notation(comment));
+                        enums
+                            .elements
+                            .push(vkxml::EnumsElement::Notation(comment.into()));
                     } else {
                         registry
                             .elements
-                            .push(vkxml::RegistryElement::Notation(comment));
+                            .push(vkxml::RegistryElement::Notation(comment.into()));
                     }
                 }
 
@@ -96,7 +100,7 @@ impl From<Registry> for vkxml::Registry {
                     None => {
                         flush_enums(&mut enums, &mut registry.elements);
                         let mut constants = vkxml::Constants {
-                            notation: e.comment,
+                            notation: e.comment.map(String::from),
                             elements: Vec::with_capacity(e.children.len()),
                         };
                         for item in e.children {
@@ -110,9 +114,9 @@ impl From<Registry> for vkxml::Registry {
                     }
                     Some(kind) => {
                         let enumeration = vkxml::Enumeration {
-                            name: e.name.unwrap_or(String::new()),
-                            notation: e.comment,
-                            purpose: if kind.as_str() == "bitmask" {
+                            name: e.name.map(String::from).unwrap_or_default(),
+                            notation: e.comment.map(String::from),
+                            purpose: if &*kind == "bitmask" {
                                 Some(vkxml::EnumerationPurpose::Bitmask)
                             } else {
                                 None
@@ -167,6 +171,9 @@ impl From<Registry> for vkxml::Registry {
                 RegistryChild::Formats { .. } => (),
                 RegistryChild::SpirvExtensions { .. } => (),
                 RegistryChild::SpirvCapabilities { .. } => (),
+                RegistryChild::Sync { .. } => (),
+                RegistryChild::VideoCodecs { .. } => (),
+                RegistryChild::Malformed { .. } => (),
             }
         }
 
@@ -177,58 +184,46 @@ impl From<Registry> for vkxml::Registry {
 impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
     fn from(orig: TypesChild) -> Self {
         match orig {
-            TypesChild::Comment(text) => Some(vkxml::DefinitionsElement::Notation(text)),
+            TypesChild::Comment(text) => Some(vkxml::DefinitionsElement::Notation(text.into())),
             TypesChild::Type(t) => {
                 let category = match t.category {
                     Some(c) => c,
                     None => {
-                        let name = t.name.unwrap_or(String::new());
+                        let name = t.name.map(String::from).unwrap_or_default();
                         return Some(vkxml::DefinitionsElement::Reference(vkxml::Reference {
                             name,
-                            notation: t.comment,
-                            include: t.requires,
+                            notation: t.comment.map(String::from),
+                            include: t.requires.map(String::from),
                         }));
                     }
                 };
 
-                match category.as_str() {
+                match &*category {
                     "include" => {
                         let mut include = vkxml::Include {
-                            name: t.name.unwrap_or(String::new()),
-                            notation: t.comment,
+                            name: t.name.map(String::from).unwrap_or_default(),
+                            notation: t.comment.map(String::from),
                             style: vkxml::IncludeStyle::Quote,
                             need_ext: false,
                         };
 
-                        match t.spec {
-                            TypeSpec::Code(TypeCode { code, markup }) => {
-                                let mut iter = code.split_whitespace();
-                                let token = iter.next().unwrap();
-                                if token != "#include" {
-                                    panic!("Unexpected token {:?}", token);
-                                }
-                                let token = iter.next().unwrap();
-                                if token.starts_with('<') {
+                        if let TypeSpec::Code(code) = t.spec {
+                            if let Some(directive) = code.include() {
+                                if directive.system {
                                     include.style = vkxml::IncludeStyle::Bracket;
                                 }
-                                for tag in markup {
-                                    match tag {
-                                        TypeCodeMarkup::Name(name) => include.name = name,
-                                        _ => (),
-                                    }
-                                }
+                                include.name = directive.path.clone().into();
                             }
-                            _ => (),
                         }
 
                         include.need_ext = !include.name.ends_with(".h");
-                        return Some(vkxml::DefinitionsElement::Include(include));
+                        Some(vkxml::DefinitionsElement::Include(include))
                     }
 
                     "define" => {
                         let mut define = vkxml::Define {
-                            name: t.name.unwrap_or(String::new()),
-                            notation: t.comment,
+                            name: t.name.map(String::from).unwrap_or_default(),
+                            notation: t.comment.map(String::from),
                             is_disabled: true,
                             comment: None,
                             replace: false,
@@ -238,25 +233,25 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                             value: None,
                         };
                         match t.spec {
-                            TypeSpec::Code(TypeCode { code, markup }) => {
+                            TypeSpec::Code(TypeCode { code, markup, .. }) => {
                                 for tag in markup {
                                     match tag {
-                                        TypeCodeMarkup::Type(val) => define.defref.push(val),
-                                        TypeCodeMarkup::Name(val) => define.name = val,
+                                        TypeCodeMarkup::Type(val) => define.defref.push(val.into()),
+                                        TypeCodeMarkup::Name(val) => define.name = val.into(),
                                         _ => panic!("Unexpected tag in define {:?}", tag),
                                     }
                                 }
-                                process_define_code(&mut define, code);
+                                process_define_code(&mut define, &code);
                             }
                             _ => panic!("Unexpected contents of define {:?}", t.spec),
                         }
-                        return Some(vkxml::DefinitionsElement::Define(define));
+                        Some(vkxml::DefinitionsElement::Define(define))
                     }
 
                     "basetype" => {
                         let mut typedef = vkxml::Typedef {
                             name: String::new(),
-                            notation: t.comment,
+                            notation: t.comment.map(String::from),
                             basetype: String::new(),
                         };
                         let markup = match t.spec {
@@ -265,12 +260,12 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                         };
                         for tag in markup {
                             match tag {
-                                TypeCodeMarkup::Type(val) => typedef.basetype = val,
-                                TypeCodeMarkup::Name(val) => typedef.name = val,
+                                TypeCodeMarkup::Type(val) => typedef.basetype = val.into(),
+                                TypeCodeMarkup::Name(val) => typedef.name = val.into(),
                                 _ => panic!("Unexpected tag in typedef {:?}", tag),
                             }
                         }
-                        return Some(vkxml::DefinitionsElement::Typedef(typedef));
+                        Some(vkxml::DefinitionsElement::Typedef(typedef))
                     }
 
                     "bitmask" => {
@@ -279,9 +274,9 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                         }
                         let mut bitmask = vkxml::Bitmask {
                             name: vkxml::Identifier::new(),
-                            notation: t.comment,
+                            notation: t.comment.map(String::from),
                             basetype: vkxml::Identifier::new(),
-                            enumref: t.requires,
+                            enumref: t.requires.map(String::from),
                         };
                         let markup = match t.spec {
                             TypeSpec::Code(TypeCode { markup, .. }) => markup,
@@ -289,12 +284,12 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                         };
                         for tag in markup {
                             match tag {
-                                TypeCodeMarkup::Type(val) => bitmask.basetype = val,
-                                TypeCodeMarkup::Name(val) => bitmask.name = val,
+                                TypeCodeMarkup::Type(val) => bitmask.basetype = val.into(),
+                                TypeCodeMarkup::Name(val) => bitmask.name = val.into(),
                                 _ => panic!("Unexpected tag in typedef {:?}", tag),
                             }
                         }
-                        return Some(vkxml::DefinitionsElement::Bitmask(bitmask));
+                        Some(vkxml::DefinitionsElement::Bitmask(bitmask))
                     }
 
                     "handle" => {
@@ -303,8 +298,8 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                         }
                         let mut handle = vkxml::Handle {
                             name: String::new(),
-                            notation: t.comment,
-                            parent: t.parent,
+                            notation: t.comment.map(String::from),
+                            parent: t.parent.map(String::from),
                             ty: vkxml::HandleType::Dispatch,
                         };
                         let markup = match t.spec {
@@ -313,9 +308,9 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                         };
                         for tag in markup {
                             match tag {
-                                TypeCodeMarkup::Name(val) => handle.name = val,
+                                TypeCodeMarkup::Name(val) => handle.name = val.into(),
                                 TypeCodeMarkup::Type(val) => {
-                                    handle.ty = match val.as_str() {
+                                    handle.ty = match &*val {
                                         "VK_DEFINE_HANDLE" => vkxml::HandleType::Dispatch,
                                         "VK_DEFINE_NON_DISPATCHABLE_HANDLE" => {
                                             vkxml::HandleType::NoDispatch
@@ -326,25 +321,25 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                                 _ => panic!("Unexpected tag in typedef {:?}", tag),
                             }
                         }
-                        return Some(vkxml::DefinitionsElement::Handle(handle));
+                        Some(vkxml::DefinitionsElement::Handle(handle))
                     }
 
                     "enum" => {
                         // if alias.is_some() {
                         //     return None;
                         // }
-                        return Some(vkxml::DefinitionsElement::Enumeration(
+                        Some(vkxml::DefinitionsElement::Enumeration(
                             vkxml::EnumerationDeclaration {
-                                name: t.name.unwrap_or(String::new()),
-                                notation: t.comment,
+                                name: t.name.map(String::from).unwrap_or_default(),
+                                notation: t.comment.map(String::from),
                             },
-                        ));
+                        ))
                     }
 
                     "funcpointer" => {
                         let mut fnptr = vkxml::FunctionPointer {
                             name: vkxml::Identifier::new(),
-                            notation: t.comment,
+                            notation: t.comment.map(String::from),
                             return_type: new_field(),
                             param: Vec::new(),
                         };
@@ -354,7 +349,7 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                         };
 
                         parse_type_funcptr(&mut fnptr, &code);
-                        return Some(vkxml::DefinitionsElement::FuncPtr(fnptr));
+                        Some(vkxml::DefinitionsElement::FuncPtr(fnptr))
                     }
 
                     "struct" => {
@@ -362,10 +357,10 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                             return None;
                         }
                         let mut s = vkxml::Struct {
-                            name: t.name.unwrap_or(String::new()),
-                            notation: t.comment,
-                            is_return: t.returnedonly.unwrap_or(String::new()).as_str() == "true",
-                            extends: t.structextends,
+                            name: t.name.map(String::from).unwrap_or_default(),
+                            notation: t.comment.map(String::from),
+                            is_return: t.returnedonly.as_ref().map_or(false, |v| &**v == "true"),
+                            extends: t.structextends.map(String::from),
                             elements: Vec::new(),
                         };
                         match t.spec {
@@ -377,13 +372,13 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                             _ => panic!("Unexpected contents of struct {:?}: {:?}", s.name, t.spec),
                         }
 
-                        return Some(vkxml::DefinitionsElement::Struct(s));
+                        Some(vkxml::DefinitionsElement::Struct(s))
                     }
 
                     "union" => {
                         let mut u = vkxml::Union {
-                            name: t.name.unwrap_or(String::new()),
-                            notation: t.comment,
+                            name: t.name.map(String::from).unwrap_or_default(),
+                            notation: t.comment.map(String::from),
                             elements: Vec::new(),
                         };
                         match t.spec {
@@ -392,10 +387,14 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                                     match member {
                                         TypeMember::Comment(..) => (),
                                         TypeMember::Definition(def) => {
-                                            let mut iter = def
-                                                .code
+                                            let code = if def.code.is_empty() {
+                                                def.to_string()
+                                            } else {
+                                                String::from(def.code)
+                                            };
+                                            let mut iter = code
                                                 .split_whitespace()
-                                                .flat_map(|s| c::TokenIter::new(s))
+                                                .flat_map(c::TokenIter::new)
                                                 .peekable();
 
                                             let field = parse_c_field(&mut iter).unwrap();
@@ -407,7 +406,7 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
                             _ => panic!("Unexpected contents of union {:?}: {:?}", u.name, t.spec),
                         }
 
-                        return Some(vkxml::DefinitionsElement::Union(u));
+                        Some(vkxml::DefinitionsElement::Union(u))
                     }
 
                     _ => panic!("Unexpected category of type {:?}", category),
@@ -420,41 +419,43 @@ impl From<TypesChild> for Option<vkxml::DefinitionsElement> {
 impl From<TypeMember> for vkxml::StructElement {
     fn from(orig: TypeMember) -> Self {
         match orig {
-            TypeMember::Comment(comment) => vkxml::StructElement::Notation(comment),
+            TypeMember::Comment(comment) => vkxml::StructElement::Notation(comment.into()),
             TypeMember::Definition(def) => {
-                let mut iter = def
-                    .code
+                let code = if def.code.is_empty() {
+                    def.to_string()
+                } else {
+                    String::from(&*def.code)
+                };
+                let mut iter = code
                     .split_whitespace()
-                    .flat_map(|s| c::TokenIter::new(s))
+                    .flat_map(c::TokenIter::new)
                     .peekable();
 
                 let mut field = parse_c_field(&mut iter).unwrap();
-                field.c_size = def.altlen;
-                field.sync = def.externsync;
-                field.optional = def.optional;
-                field.type_enums = def.values;
-                match def.len {
-                    Some(mut value) => {
-                        let null_terminated_part = ",null-terminated";
-                        if value.as_str().ends_with(null_terminated_part) {
-                            field.null_terminate = true;
-                            let start = value.len() - null_terminated_part.len();
-                            value.drain(start..);
-                        }
+                field.c_size = def.altlen.map(String::from);
+                field.sync = def.externsync.map(String::from);
+                field.optional = def.optional.map(String::from);
+                field.type_enums = def.values.map(String::from);
+                if let Some(value) = def.len {
+                    let mut value = String::from(value);
+                    let null_terminated_part = ",null-terminated";
+                    if value.as_str().ends_with(null_terminated_part) {
+                        field.null_terminate = true;
+                        let start = value.len() - null_terminated_part.len();
+                        value.drain(start..);
+                    }
 
-                        if value.as_str() == "null-terminated" {
-                            field.null_terminate = true;
-                        } else {
-                            field.size = Some(value);
-                        }
-                        field.array = Some(vkxml::ArrayType::Dynamic);
+                    if value.as_str() == "null-terminated" {
+                        field.null_terminate = true;
+                    } else {
+                        field.size = Some(value);
                     }
-                    None => (),
+                    field.array = Some(vkxml::ArrayType::Dynamic);
                 }
                 for tag in def.markup {
                     match tag {
-                        TypeMemberMarkup::Enum(value) => field.size_enumref = Some(value),
-                        TypeMemberMarkup::Comment(comment) => field.notation = Some(comment),
+                        TypeMemberMarkup::Enum(value) => field.size_enumref = Some(value.into()),
+                        TypeMemberMarkup::Comment(comment) => field.notation = Some(comment.into()),
                         _ => (),
                     }
                 }
@@ -465,234 +466,58 @@ impl From<TypeMember> for vkxml::StructElement {
     }
 }
 
-fn process_define_code(r: &mut vkxml::Define, code: String) {
-    fn consume_whitespace(chars: &mut std::str::Chars, mut current: Option<char>) -> Option<char> {
-        while let Some(c) = current {
-            if !c.is_whitespace() {
-                break;
+fn process_define_code(r: &mut vkxml::Define, code: &str) {
+    let mut define = None;
+    let mut has_directives = false;
+    for item in c::parse_preprocessor(code) {
+        match item {
+            PreprocessorItem::Comment(text) => {
+                if r.comment.is_none() {
+                    r.comment = Some(text.into());
+                }
+            }
+            PreprocessorItem::Define(d) if define.is_none() && *d.name == *r.name => {
+                define = Some(d);
+            }
+            // Anything more complex is copied verbatim.
+            PreprocessorItem::Code(..) => r.replace = true,
+            _ => {
+                r.replace = true;
+                has_directives = true;
             }
-            current = chars.next();
         }
-        current
     }
 
-    {
-        enum State {
-            Initial,
-            LineComment,
-            BlockComment,
-            DefineName,
-            DefineArgs,
-            DefineExpression,
-            DefineValue,
+    if r.replace {
+        if has_directives {
+            r.is_disabled = false;
         }
-        let mut state = State::Initial;
-        let mut chars = code.chars();
-        loop {
-            match state {
-                State::Initial => {
-                    let mut current = chars.next();
-                    current = consume_whitespace(&mut chars, current);
-
-                    match current {
-                        Some('/') => {
-                            current = chars.next();
-                            match current {
-                                Some('/') => state = State::LineComment,
-                                Some('*') => state = State::BlockComment,
-                                Some(c) => panic!("Unexpected symbol {:?}", c),
-                                None => panic!("Unexpected end of code."),
-                            }
-                        }
-
-                        Some('#') => {
-                            let text = chars.as_str();
-                            let mut directive_len = 0;
-                            while let Some(c) = chars.next() {
-                                if c.is_whitespace() {
-                                    break;
-                                }
-                                if 'a' <= c && c <= 'z' {
-                                    directive_len += 1;
-                                } else {
-                                    panic!("Unexpected symbol in preprocessor directive: {:?}", c);
-                                }
-                            }
-
-                            let directive = &text[..directive_len];
-                            match directive {
-                                "define" => state = State::DefineName,
-                                _ => {
-                                    // Different directive. Whole text treated as c expression and replace set to true.
-                                    r.replace = true;
-                                    r.is_disabled = false;
-                                    break;
-                                }
-                            }
-                        }
-
-                        Some('s') => {
-                            let expected = "truct ";
-
-                            let text = chars.as_str();
-                            if text.starts_with(expected) {
-                                // mk:TODO Less hacky handling of define which is actually forward declaration.
-                                r.replace = true;
-                                break;
-                            } else {
-                                println!("Unexpected code segment {:?}", code);
-                            }
-                        }
-
-                        Some(c) => panic!("Unexpected symbol {:?}", c),
-                        None => panic!("Unexpected end of code."),
-                    }
-                }
-
-                State::LineComment => {
-                    let text = chars.as_str();
-                    if let Some(idx) = text.find('\n') {
-                        let comment = text[..idx].trim();
-                        if r.comment.is_none() {
-                            r.comment = Some(String::from(comment));
-                        }
-                        chars = text[idx + 1..].chars();
-                        state = State::Initial;
-                    } else {
-                        if r.comment.is_none() {
-                            r.comment = Some(String::from(text.trim()));
-                        }
-
-                        break;
-                    }
-                }
-
-                State::BlockComment => {
-                    let text = chars.as_str();
-                    if let Some(idx) = text.find("*/") {
-                        let comment = &text[..idx];
-                        if r.comment.is_none() {
-                            r.comment = Some(String::from(comment));
-                        }
-                        chars = text[idx + 2..].chars();
-                        state = State::Initial;
-                    } else {
-                        panic!("Unterminated block comment {:?}", text);
-                    }
-                }
-
-                State::DefineName => {
-                    r.is_disabled = false;
-                    let text = chars.as_str();
-                    let mut current = chars.next();
-                    let mut whitespace_len = 0;
-                    while let Some(c) = current {
-                        if !c.is_whitespace() {
-                            break;
-                        }
-                        current = chars.next();
-                        whitespace_len += 1;
-                    }
-
-                    let mut name_len = 0;
-                    while let Some(c) = current {
-                        if !c::is_c_identifier_char(c) {
-                            break;
-                        }
-                        name_len += 1;
-                        current = chars.next();
-                    }
-
-                    let name = &text[whitespace_len..whitespace_len + name_len];
-                    if name != r.name.as_str() {
-                        panic!("#define name mismatch. {:?} vs. {:?}", name, r.name);
-                    }
-
-                    match current {
-                        Some('(') => state = State::DefineArgs,
-                        Some(c) => {
-                            if c.is_whitespace() {
-                                state = State::DefineValue;
-                            } else {
-                                panic!("Unexpected char after #define name: {:?}", c);
-                            }
-                        }
-                        None => break,
-                    }
-                }
-
-                State::DefineArgs => {
-                    let mut text = chars.as_str();
-                    let mut current = chars.next();
-                    loop {
-                        let mut whitespace_len = 0;
-                        while let Some(c) = current {
-                            if !c.is_whitespace() {
-                                break;
-                            }
-                            whitespace_len += 1;
-                            current = chars.next();
-                        }
-
-                        let mut name_len = 0;
-                        while let Some(c) = current {
-                            if !c::is_c_identifier_char(c) {
-                                break;
-                            }
-                            current = chars.next();
-                            name_len += 1;
-                        }
-                        let name = &text[whitespace_len..whitespace_len + name_len];
-                        r.parameters.push(String::from(name));
-
-                        current = consume_whitespace(&mut chars, current);
-                        match current {
-                            Some(',') => {
-                                text = chars.as_str();
-                                current = chars.next();
-                            }
-                            Some(')') => {
-                                chars.next();
-                                break;
-                            }
-                            Some(c) => {
-                                panic!("Unexpected character in #define argument list: {:?}", c)
-                            }
-                            None => {
-                                panic!("End of text while in the middle of #define argument list.")
-                            }
-                        }
-                    }
-                    state = State::DefineExpression;
-                }
-
-                State::DefineExpression => {
-                    r.c_expression = Some(String::from(chars.as_str().trim()));
-                    break;
-                }
-
-                State::DefineValue => {
-                    let v = Some(String::from(chars.as_str().trim()));
-                    if r.defref.len() > 0 {
-                        r.c_expression = v;
-                    } else {
-                        r.value = v;
-                    }
-                    break;
+        r.c_expression = Some(code.into());
+        return;
+    }
+    if let Some(define) = define {
+        r.is_disabled = false;
+        match define.value {
+            TypeDefineValue::Empty => {}
+            TypeDefineValue::Value(value) => {
+                if !r.defref.is_empty() {
+                    r.c_expression = Some(value.into());
+                } else {
+                    r.value = Some(value.into());
                 }
             }
+            TypeDefineValue::Function { params, body } => {
+                r.parameters = params.into_iter().map(String::from).collect();
+                r.c_expression = Some(body.into());
+            }
         }
     }
-
-    if r.replace {
-        r.c_expression = Some(code);
-    }
 }
 
 fn parse_type_funcptr(r: &mut vkxml::FunctionPointer, code: &str) {
     let mut iter = code
         .split_whitespace()
-        .flat_map(|s| c::TokenIter::new(s))
+        .flat_map(c::TokenIter::new)
         .peekable();
     let token = iter.next().unwrap();
     if token != "typedef" {
@@ -757,11 +582,8 @@ impl From<EnumsChild> for Option<vkxml::EnumerationElement> {
     fn from(orig: EnumsChild) -> Self {
         match orig {
             EnumsChild::Enum(e) => {
-                if let Some(constant) = e.into() {
-                    Some(vkxml::EnumerationElement::Enum(constant))
-                } else {
-                    None
-                }
+                let constant: Option<vkxml::Constant> = e.into();
+                constant.map(vkxml::EnumerationElement::Enum)
             }
             EnumsChild::Unused(unused) => {
                 Some(vkxml::EnumerationElement::UnusedRange(vkxml::Range {
@@ -769,7 +591,9 @@ impl From<EnumsChild> for Option<vkxml::EnumerationElement> {
                     range_end: unused.end.map(|v| v as i32),
                 }))
             }
-            EnumsChild::Comment(comment) => Some(vkxml::EnumerationElement::Notation(comment)),
+            EnumsChild::Comment(comment) => {
+                Some(vkxml::EnumerationElement::Notation(comment.into()))
+            }
         }
     }
 }
@@ -778,8 +602,8 @@ impl From<Enum> for Option<vkxml::Constant> {
     fn from(orig: Enum) -> Self {
         match orig.spec {
             EnumSpec::Bitpos { bitpos, .. } => Some(vkxml::Constant {
-                name: orig.name,
-                notation: orig.comment,
+                name: orig.name.into(),
+                notation: orig.comment.map(String::from),
                 number: None,
                 hex: None,
                 bitpos: Some(bitpos as u32),
@@ -787,19 +611,19 @@ impl From<Enum> for Option<vkxml::Constant> {
             }),
             EnumSpec::Value { value, .. } => {
                 let mut r = vkxml::Constant {
-                    name: orig.name,
-                    notation: orig.comment,
+                    name: orig.name.into(),
+                    notation: orig.comment.map(String::from),
                     number: None,
                     hex: None,
                     bitpos: None,
                     c_expression: None,
                 };
-                if let Ok(value) = i32::from_str_radix(&value, 10) {
+                if let Ok(value) = value.parse::<i32>() {
                     r.number = Some(value);
                 } else if value.starts_with("0x") {
                     r.hex = Some(String::from(value.split_at(2).1))
                 } else {
-                    r.c_expression = Some(value)
+                    r.c_expression = Some(value.into())
                 }
                 Some(r)
             }
@@ -839,10 +663,11 @@ fn parse_c_field<'a, I: Iterator<Item = &'a str>>(
     while let Some(&token) = iter.peek() {
         match token {
             "," | ")" | "(" | ";" => break,
-            "*" => match r.reference {
-                None => r.reference = Some(vkxml::ReferenceType::Pointer),
-                _ => (),
-            },
+            "*" => {
+                if r.reference.is_none() {
+                    r.reference = Some(vkxml::ReferenceType::Pointer);
+                }
+            }
             "const" => r.reference = Some(vkxml::ReferenceType::PointerToConstPointer),
             "[" => {
                 r.array = Some(vkxml::ArrayType::Static);
@@ -854,7 +679,7 @@ fn parse_c_field<'a, I: Iterator<Item = &'a str>>(
                 if r.array.is_some() {
                     let mut is_number = true;
                     for c in t.chars() {
-                        if c < '0' || '9' < c {
+                        if !c.is_ascii_digit() {
                             is_number = false;
                             break;
                         }
@@ -885,7 +710,7 @@ fn items_to_elements<T, U: From<T>>(items: Vec<T>) -> Vec<U> {
 impl From<VendorIds> for vkxml::VendorIds {
     fn from(orig: VendorIds) -> Self {
         Self {
-            notation: orig.comment,
+            notation: orig.comment.map(String::from),
             elements: items_to_elements(orig.children),
         }
     }
@@ -894,8 +719,8 @@ impl From<VendorIds> for vkxml::VendorIds {
 impl From<VendorId> for vkxml::VendorId {
     fn from(orig: VendorId) -> Self {
         Self {
-            name: orig.name,
-            notation: orig.comment,
+            name: orig.name.into(),
+            notation: orig.comment.map(String::from),
             id: format!("0x{:X}", orig.id),
         }
     }
@@ -904,7 +729,7 @@ impl From<VendorId> for vkxml::VendorId {
 impl From<Tags> for vkxml::Tags {
     fn from(orig: Tags) -> Self {
         Self {
-            notation: orig.comment,
+            notation: orig.comment.map(String::from),
             elements: items_to_elements(orig.children),
         }
     }
@@ -913,10 +738,10 @@ impl From<Tags> for vkxml::Tags {
 impl From<Tag> for vkxml::Tag {
     fn from(orig: Tag) -> Self {
         Self {
-            name: orig.name,
+            name: orig.name.into(),
             notation: None,
-            author: orig.author,
-            contact: orig.contact,
+            author: orig.author.into(),
+            contact: orig.contact.into(),
         }
     }
 }
@@ -924,7 +749,7 @@ impl From<Tag> for vkxml::Tag {
 impl From<Types> for vkxml::Definitions {
     fn from(orig: Types) -> Self {
         Self {
-            notation: orig.comment,
+            notation: orig.comment.map(String::from),
             elements: {
                 let mut elements = Vec::with_capacity(orig.children.len());
                 for item in orig.children {
@@ -941,7 +766,7 @@ impl From<Types> for vkxml::Definitions {
 impl From<Extensions> for vkxml::Extensions {
     fn from(orig: Extensions) -> Self {
         Self {
-            notation: orig.comment,
+            notation: orig.comment.map(String::from),
             elements: items_to_elements(orig.children),
         }
     }
@@ -953,24 +778,21 @@ impl From<Extension> for vkxml::Extension {
         let mut match_api = None;
         let supported = orig.supported.take();
         match supported {
-            Some(text) => {
-                if text == "disabled" {
-                    disabled = true;
-                } else {
-                    match_api = Some(text);
-                }
-            }
-            None => (),
+            Some(ExtensionSupport::Disabled) => disabled = true,
+            Some(ExtensionSupport::Apis(apis)) => match_api = Some(apis.to_string()),
+            None => {}
         }
 
         let mut elements = Vec::new();
         for item in orig.children {
-            elements.push(item.into());
+            if let Some(element) = item.into() {
+                elements.push(element);
+            }
         }
 
         vkxml::Extension {
-            name: orig.name,
-            notation: orig.comment,
+            name: orig.name.into(),
+            notation: orig.comment.map(String::from),
             number: match orig.number {
                 Some(val) => val as i32,
                 None => 0,
@@ -978,7 +800,7 @@ impl From<Extension> for vkxml::Extension {
             disabled,
             match_api,
             ty: match orig.ext_type {
-                Some(text) => match text.as_str() {
+                Some(text) => match &*text {
                     "instance" => Some(vkxml::ExtensionType::Instance),
                     "device" => Some(vkxml::ExtensionType::Device),
                     _ => panic!(
@@ -988,16 +810,16 @@ impl From<Extension> for vkxml::Extension {
                 },
                 None => None,
             },
-            define: orig.protect,
-            requires: orig.requires,
-            author: orig.author,
-            contact: orig.contact,
+            define: orig.protect.map(String::from),
+            requires: orig.requires.map(String::from),
+            author: orig.author.map(String::from),
+            contact: orig.contact.map(String::from),
             elements,
         }
     }
 }
 
-impl From<ExtensionChild> for vkxml::ExtensionElement {
+impl From<ExtensionChild> for Option<vkxml::ExtensionElement> {
     fn from(orig: ExtensionChild) -> Self {
         match orig {
             ExtensionChild::Remove {
@@ -1005,13 +827,16 @@ impl From<ExtensionChild> for vkxml::ExtensionElement {
                 profile,
                 comment,
                 items,
-            } => vkxml::ExtensionElement::Remove(vkxml::ExtensionSpecification {
-                profile,
-                notation: comment,
-                extension: None,
-                api,
-                elements: items.into_iter().filter_map(|i| i.into()).collect(),
-            }),
+                ..
+            } => Some(vkxml::ExtensionElement::Remove(
+                vkxml::ExtensionSpecification {
+                    profile: profile.map(String::from),
+                    notation: comment.map(String::from),
+                    extension: None,
+                    api: api.map(|api| api.to_string()),
+                    elements: items.into_iter().filter_map(|i| i.into()).collect(),
+                },
+            )),
 
             ExtensionChild::Require {
                 api,
@@ -1020,13 +845,18 @@ impl From<ExtensionChild> for vkxml::ExtensionElement {
                 comment,
                 items,
                 ..
-            } => vkxml::ExtensionElement::Require(vkxml::ExtensionSpecification {
-                profile,
-                notation: comment,
-                extension,
-                api,
-                elements: items.into_iter().filter_map(|i| i.into()).collect(),
-            }),
+            } => Some(vkxml::ExtensionElement::Require(
+                vkxml::ExtensionSpecification {
+                    profile: profile.map(String::from),
+                    notation: comment.map(String::from),
+                    extension: extension.map(String::from),
+                    api: api.map(|api| api.to_string()),
+                    elements: items.into_iter().filter_map(|i| i.into()).collect(),
+                },
+            )),
+
+            // vkxml has no representation of deprecated items.
+            ExtensionChild::Deprecate { .. } => None,
         }
     }
 }
@@ -1041,12 +871,12 @@ impl From<ExtensionChild> for Option<vkxml::FeatureSpecification> {
                 items,
                 ..
             } => Some(vkxml::FeatureSpecification {
-                profile,
-                notation: comment,
-                extension,
+                profile: profile.map(String::from),
+                notation: comment.map(String::from),
+                extension: extension.map(String::from),
                 elements: items.into_iter().map(|i| i.into()).collect(),
             }),
-            ExtensionChild::Remove { .. } => None,
+            ExtensionChild::Remove { .. } | ExtensionChild::Deprecate { .. } => None,
         }
     }
 }
@@ -1054,23 +884,23 @@ impl From<ExtensionChild> for Option<vkxml::FeatureSpecification> {
 impl From<InterfaceItem> for vkxml::FeatureReference {
     fn from(orig: InterfaceItem) -> Self {
         match orig {
-            InterfaceItem::Comment(v) => vkxml::FeatureReference::Notation(v),
+            InterfaceItem::Comment(v) => vkxml::FeatureReference::Notation(v.into()),
             InterfaceItem::Type { name, comment } => {
                 vkxml::FeatureReference::DefinitionReference(vkxml::NamedIdentifier {
-                    name,
-                    notation: comment,
+                    name: name.into(),
+                    notation: comment.map(String::from),
                 })
             }
             InterfaceItem::Enum(e) => {
                 vkxml::FeatureReference::EnumeratorReference(vkxml::NamedIdentifier {
-                    name: e.name,
-                    notation: e.comment,
+                    name: e.name.into(),
+                    notation: e.comment.map(String::from),
                 })
             }
             InterfaceItem::Command { name, comment } => {
                 vkxml::FeatureReference::CommandReference(vkxml::NamedIdentifier {
-                    name,
-                    notation: comment,
+                    name: name.into(),
+                    notation: comment.map(String::from),
                 })
             }
         }
@@ -1081,7 +911,7 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
     fn from(orig: InterfaceItem) -> Self {
         match orig {
             InterfaceItem::Comment(text) => {
-                Some(vkxml::ExtensionSpecificationElement::Notation(text))
+                Some(vkxml::ExtensionSpecificationElement::Notation(text.into()))
             }
 
             InterfaceItem::Enum(e) => match e.spec {
@@ -1094,12 +924,12 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
                     ..
                 } => Some(vkxml::ExtensionSpecificationElement::Enum(
                     vkxml::ExtensionEnum {
-                        name: e.name,
+                        name: e.name.into(),
                         number: None,
-                        notation: e.comment,
+                        notation: e.comment.map(String::from),
                         offset: Some(offset as usize),
                         negate: !dir,
-                        extends,
+                        extends: extends.into(),
                         hex: None,
                         bitpos: None,
                         c_expression: None,
@@ -1110,12 +940,12 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
                     if let Some(extends) = extends {
                         Some(vkxml::ExtensionSpecificationElement::Enum(
                             vkxml::ExtensionEnum {
-                                name: e.name,
+                                name: e.name.into(),
                                 number: None,
-                                notation: e.comment,
+                                notation: e.comment.map(String::from),
                                 offset: None,
                                 negate: false,
-                                extends,
+                                extends: extends.into(),
                                 hex: None,
                                 bitpos: Some(bitpos as u32),
                                 c_expression: None,
@@ -1124,8 +954,8 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
                     } else {
                         Some(vkxml::ExtensionSpecificationElement::Constant(
                             vkxml::ExtensionConstant {
-                                name: e.name,
-                                notation: e.comment,
+                                name: e.name.into(),
+                                notation: e.comment.map(String::from),
                                 text: None,
                                 enumref: None,
                                 number: None,
@@ -1137,11 +967,12 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
                     }
                 }
 
-                EnumSpec::Value { mut value, extends } => {
+                EnumSpec::Value { value, extends } => {
+                    let mut value = String::from(value);
                     let mut text = None;
                     let mut number = None;
                     let mut enumref = None;
-                    if let Ok(val) = i32::from_str_radix(&value, 10) {
+                    if let Ok(val) = value.parse::<i32>() {
                         number = Some(val);
                     } else if value.starts_with('"') && value.ends_with('"') {
                         let end = value.len() - 1;
@@ -1155,12 +986,12 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
                     if let Some(extends) = extends {
                         Some(vkxml::ExtensionSpecificationElement::Enum(
                             vkxml::ExtensionEnum {
-                                name: e.name,
+                                name: e.name.into(),
                                 number,
-                                notation: e.comment,
+                                notation: e.comment.map(String::from),
                                 offset: None,
                                 negate: false,
-                                extends,
+                                extends: extends.into(),
                                 hex: None,
                                 bitpos: None,
                                 c_expression: None,
@@ -1169,8 +1000,8 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
                     } else {
                         Some(vkxml::ExtensionSpecificationElement::Constant(
                             vkxml::ExtensionConstant {
-                                name: e.name,
-                                notation: e.comment,
+                                name: e.name.into(),
+                                notation: e.comment.map(String::from),
                                 text,
                                 enumref,
                                 number,
@@ -1184,23 +1015,23 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
 
                 EnumSpec::None => Some(vkxml::ExtensionSpecificationElement::EnumeratorReference(
                     vkxml::NamedIdentifier {
-                        name: e.name,
-                        notation: e.comment,
+                        name: e.name.into(),
+                        notation: e.comment.map(String::from),
                     },
                 )),
             },
 
             InterfaceItem::Command { name, comment } => Some(
                 vkxml::ExtensionSpecificationElement::CommandReference(vkxml::NamedIdentifier {
-                    name,
-                    notation: comment,
+                    name: name.into(),
+                    notation: comment.map(String::from),
                 }),
             ),
 
             InterfaceItem::Type { name, comment } => Some(
                 vkxml::ExtensionSpecificationElement::DefinitionReference(vkxml::NamedIdentifier {
-                    name,
-                    notation: comment,
+                    name: name.into(),
+                    notation: comment.map(String::from),
                 }),
             ),
         }
@@ -1210,7 +1041,7 @@ impl From<InterfaceItem> for Option<vkxml::ExtensionSpecificationElement> {
 impl From<Commands> for vkxml::Commands {
     fn from(orig: Commands) -> Self {
         Self {
-            notation: orig.comment,
+            notation: orig.comment.map(String::from),
             elements: {
                 let mut elements = Vec::with_capacity(orig.children.len());
                 for item in orig.children {
@@ -1229,28 +1060,33 @@ impl From<Command> for Option<vkxml::Command> {
         match orig {
             Command::Alias { .. } => None,
             Command::Definition(def) => {
+                let code = if def.code.is_empty() {
+                    def.to_string()
+                } else {
+                    String::from(&*def.code)
+                };
                 let mut r = vkxml::Command {
-                    name: def.proto.name,
-                    notation: def.comment,
+                    name: def.proto.name.into(),
+                    notation: def.comment.map(String::from),
                     return_type: new_field(),
                     param: Vec::new(),
                     external_sync: None,
-                    cmdbufferlevel: def.cmdbufferlevel,
+                    cmdbufferlevel: def.cmdbufferlevel.map(String::from),
                     pipeline: None,
-                    queues: def.queues,
+                    queues: Some(QueueKind::join(&def.queues)).filter(|q| !q.is_empty()),
                     renderpass: None,
                 };
-                match def.proto.type_name {
-                    Some(type_name) => r.return_type.basetype = type_name,
-                    None => (),
+                if let Some(type_name) = def.proto.type_name {
+                    r.return_type.basetype = type_name.into();
                 }
-                r.return_type.successcodes = def.successcodes;
-                r.return_type.errorcodes = def.errorcodes;
+                r.return_type.successcodes =
+                    Some(def.successcodes.join(",")).filter(|c| !c.is_empty());
+                r.return_type.errorcodes = Some(def.errorcodes.join(",")).filter(|c| !c.is_empty());
                 for text in def.implicitexternsyncparams {
-                    r.external_sync = Some(vkxml::ExternalSync { sync: text })
+                    r.external_sync = Some(vkxml::ExternalSync { sync: text.into() })
                 }
                 if let Some(renderpass) = def.renderpass {
-                    r.renderpass = match renderpass.as_str() {
+                    r.renderpass = match &*renderpass {
                         "both" => Some(vkxml::Renderpass::Both),
                         "inside" => Some(vkxml::Renderpass::Inside),
                         "outside" => Some(vkxml::Renderpass::Outside),
@@ -1258,7 +1094,7 @@ impl From<Command> for Option<vkxml::Command> {
                     };
                 }
                 if let Some(pipeline) = def.pipeline {
-                    r.pipeline = match pipeline.as_str() {
+                    r.pipeline = match &*pipeline {
                         "graphics" => Some(vkxml::Pipeline::Graphics),
                         "compute" => Some(vkxml::Pipeline::Compute),
                         "transfer" => Some(vkxml::Pipeline::Transfer),
@@ -1269,13 +1105,14 @@ impl From<Command> for Option<vkxml::Command> {
                 r.param.reserve(def.params.len());
                 for param in def.params {
                     let mut p = new_field();
-                    p.name = Some(param.definition.name);
+                    p.name = Some(param.definition.name.into());
                     if let Some(v) = param.definition.type_name {
-                        p.basetype = v;
+                        p.basetype = v.into();
                     }
-                    p.optional = param.optional;
-                    p.sync = param.externsync;
-                    if let Some(mut value) = param.len {
+                    p.optional = param.optional.map(String::from);
+                    p.sync = param.externsync.map(String::from);
+                    if let Some(value) = param.len {
+                        let mut value = String::from(value);
                         let null_terminated_part = ",null-terminated";
                         if value.as_str().ends_with(null_terminated_part) {
                             p.null_terminate = true;
@@ -1293,8 +1130,8 @@ impl From<Command> for Option<vkxml::Command> {
                     r.param.push(p);
                 }
 
-                let mut tokens = c::TokenIter::new(&def.code);
-                while let Some(token) = tokens.next() {
+                let mut tokens = c::TokenIter::new(&code);
+                for token in tokens.by_ref() {
                     if token == "(" {
                         break;
                     }
@@ -1305,7 +1142,7 @@ impl From<Command> for Option<vkxml::Command> {
                 let mut param_idx = 0;
                 let mut ptr_count = 0;
                 let mut const_count = 0;
-                while let Some(token) = tokens.next() {
+                for token in tokens {
                     match token {
                         "," | ")" => {
                             let p = &mut r.param[param_idx];
@@ -1354,11 +1191,11 @@ impl From<Feature> for vkxml::Feature {
     fn from(orig: Feature) -> Self {
         use std::str::FromStr;
         Self {
-            name: orig.name,
-            notation: orig.comment,
-            api: orig.api,
-            version: f32::from_str(&orig.number).unwrap(),
-            define: orig.protect,
+            name: orig.name.into(),
+            notation: orig.comment.map(String::from),
+            api: orig.api.to_string(),
+            version: f32::from_str(&orig.number.to_string()).unwrap(),
+            define: orig.protect.map(String::from),
             elements: {
                 let mut elements = Vec::with_capacity(orig.children.len());
                 for item in orig.children {
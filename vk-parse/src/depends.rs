@@ -0,0 +1,194 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fmt;
+
+/// Boolean expression of a `depends` attribute, e.g.
+/// `VK_KHR_get_physical_device_properties2+(VK_VERSION_1_1,VK_KHR_maintenance1)`.
+///
+/// Leaves are names of features or extensions, `+` is conjunction and `,` is disjunction. The
+/// registry parenthesizes expressions mixing the two, otherwise `+` binds tighter.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum DependsExpr {
+    /// Name of a feature or extension, e.g. `VK_VERSION_1_1` or `VK_KHR_maintenance1`.
+    Leaf(Box<str>),
+    /// All of at least two expressions, none of which is an `And`.
+    And(Vec<DependsExpr>),
+    /// Any of at least two expressions, none of which is an `Or`.
+    Or(Vec<DependsExpr>),
+}
+
+impl DependsExpr {
+    /// Parses expression, returning `None` if it's malformed, e.g. has unbalanced parentheses
+    /// or an empty operand, or if parentheses are nested deeper than `MAX_DEPTH`.
+    pub fn parse(text: &str) -> Option<DependsExpr> {
+        let mut parser = Parser {
+            rest: text,
+            depth: 0,
+        };
+        let expr = parser.or()?;
+        parser.skip_whitespace();
+        if parser.rest.is_empty() {
+            Some(expr)
+        } else {
+            None
+        }
+    }
+
+    /// Names of features and extensions in the expression, in order of appearance.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_names(&mut names);
+        names
+    }
+
+    fn collect_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match *self {
+            DependsExpr::Leaf(ref name) => names.push(name),
+            DependsExpr::And(ref operands) | DependsExpr::Or(ref operands) => {
+                for operand in operands {
+                    operand.collect_names(names);
+                }
+            }
+        }
+    }
+
+    /// Evaluates the expression, using `is_enabled` to get values of leaves.
+    pub fn is_satisfied<F: Fn(&str) -> bool>(&self, is_enabled: F) -> bool {
+        self.evaluate(&is_enabled)
+    }
+
+    fn evaluate<F: Fn(&str) -> bool>(&self, is_enabled: &F) -> bool {
+        match *self {
+            DependsExpr::Leaf(ref name) => is_enabled(name),
+            DependsExpr::And(ref operands) => operands.iter().all(|e| e.evaluate(is_enabled)),
+            DependsExpr::Or(ref operands) => operands.iter().any(|e| e.evaluate(is_enabled)),
+        }
+    }
+}
+
+/// Writes the expression as in the registry, with parentheses around disjunctions within
+/// conjunctions.
+impl fmt::Display for DependsExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DependsExpr::Leaf(ref name) => f.write_str(name),
+            DependsExpr::And(ref operands) => {
+                for (i, operand) in operands.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("+")?;
+                    }
+                    match *operand {
+                        DependsExpr::Or(_) => write!(f, "({})", operand)?,
+                        _ => write!(f, "{}", operand)?,
+                    }
+                }
+                Ok(())
+            }
+            DependsExpr::Or(ref operands) => {
+                for (i, operand) in operands.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", operand)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Maximum nesting of parentheses accepted by `DependsExpr::parse`, which recurses for each
+/// level. The registry never nests more than a few levels.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    rest: &'a str,
+    /// Number of currently open parentheses.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consumes `c` if the remaining text starts with it.
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(c) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn or(&mut self) -> Option<DependsExpr> {
+        let mut operands = Vec::new();
+        loop {
+            match self.and()? {
+                DependsExpr::Or(nested) => operands.extend(nested),
+                operand => operands.push(operand),
+            }
+            if !self.eat(',') {
+                break;
+            }
+        }
+        Some(single_or(operands, DependsExpr::Or))
+    }
+
+    fn and(&mut self) -> Option<DependsExpr> {
+        let mut operands = Vec::new();
+        loop {
+            match self.operand()? {
+                DependsExpr::And(nested) => operands.extend(nested),
+                operand => operands.push(operand),
+            }
+            if !self.eat('+') {
+                break;
+            }
+        }
+        Some(single_or(operands, DependsExpr::And))
+    }
+
+    fn operand(&mut self) -> Option<DependsExpr> {
+        if self.eat('(') {
+            if self.depth == MAX_DEPTH {
+                return None;
+            }
+            self.depth += 1;
+            let expr = self.or()?;
+            self.depth -= 1;
+            return if self.eat(')') { Some(expr) } else { None };
+        }
+        self.skip_whitespace();
+        let len = self
+            .rest
+            .find(|c: char| matches!(c, '+' | ',' | '(' | ')') || c.is_whitespace())
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return None;
+        }
+        let (name, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(DependsExpr::Leaf(name.into()))
+    }
+}
+
+/// The only operand, or else operands combined by `op`.
+fn single_or(
+    mut operands: Vec<DependsExpr>,
+    op: fn(Vec<DependsExpr>) -> DependsExpr,
+) -> DependsExpr {
+    if operands.len() == 1 {
+        operands.pop().unwrap()
+    } else {
+        op(operands)
+    }
+}
@@ -0,0 +1,144 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use api::ApiSet;
+use types::*;
+
+/// How a symbol is deprecated, given by its `deprecated` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum Deprecation {
+    /// `true`, the symbol is deprecated and shouldn't be used.
+    Deprecated,
+    /// `ignored`, the member or parameter is deprecated and ignored by implementations, see
+    /// `TypeMemberDefinition::deprecated` and `CommandParam::deprecated`.
+    Ignored,
+    /// `aliased`, the symbol is an alias kept for compatibility, its target should be used
+    /// instead.
+    Aliased,
+}
+
+impl Deprecation {
+    /// Value with given name, as used in `deprecated`, e.g. `aliased`.
+    pub fn from_name(name: &str) -> Option<Deprecation> {
+        Some(match name {
+            "true" => Deprecation::Deprecated,
+            "ignored" => Deprecation::Ignored,
+            "aliased" => Deprecation::Aliased,
+            _ => return None,
+        })
+    }
+
+    /// Name of the value, as used in `deprecated`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Deprecation::Deprecated => "true",
+            Deprecation::Ignored => "ignored",
+            Deprecation::Aliased => "aliased",
+        }
+    }
+}
+
+/// Deprecated or obsoleted extension, see `Registry::deprecations`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DeprecatedExtension<'a> {
+    pub extension: &'a Extension,
+    /// Whether the extension is obsoleted, i.e. given by `obsoletedby`, so it can't be used
+    /// along with its replacement, rather than only deprecated.
+    pub obsoleted: bool,
+    /// Feature or extension replacing the extension, e.g. `VK_VERSION_1_1` or
+    /// `VK_KHR_maintenance1`, `None` if there's no replacement.
+    pub replacement: Option<&'a str>,
+    /// `deprecated` of the extension, which may be set without naming a replacement.
+    pub deprecated: Option<Deprecation>,
+}
+
+/// Item deprecated by a `<deprecate>` block, see `Registry::deprecations`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct DeprecatedItem<'a> {
+    pub name: &'a str,
+    pub item: &'a InterfaceItem,
+    /// Feature or extension deprecating the item, e.g. `VK_VERSION_1_1`.
+    pub deprecated_by: &'a str,
+    /// APIs of the `<deprecate>` block, if it's specific to some.
+    pub api: Option<&'a ApiSet>,
+    /// Anchor of the section of the spec explaining the deprecation and what to use instead,
+    /// e.g. `deprecation-gpdp2`.
+    pub explanationlink: Option<&'a str>,
+}
+
+/// Deprecated and obsoleted parts of the API, see `Registry::deprecations`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Deprecations<'a> {
+    pub extensions: Vec<DeprecatedExtension<'a>>,
+    pub types: Vec<DeprecatedItem<'a>>,
+    pub commands: Vec<DeprecatedItem<'a>>,
+    pub enums: Vec<DeprecatedItem<'a>>,
+}
+
+impl Registry {
+    /// Reports deprecated and obsoleted extensions, given by `deprecatedby`, `obsoletedby` and
+    /// `deprecated`, along with their replacements, and items listed by `<deprecate>` blocks of
+    /// features and extensions, in order of the registry. Extensions obsoleted by another one
+    /// are reported as obsoleted even if they're deprecated too. Disabled extensions are
+    /// skipped.
+    pub fn deprecations(&self) -> Deprecations<'_> {
+        let mut deprecations = Deprecations::default();
+        for extension in self.extensions().filter(|e| !e.is_disabled()) {
+            let [_, (_, deprecatedby), (_, obsoletedby)] = extension.replacements();
+            let (obsoleted, replacement) = match (&extension.obsoletedby, &extension.deprecatedby) {
+                (Some(_), _) => (true, obsoletedby),
+                (None, Some(_)) => (false, deprecatedby),
+                (None, None) if extension.deprecated.is_some() => (false, None),
+                (None, None) => continue,
+            };
+            deprecations.extensions.push(DeprecatedExtension {
+                extension,
+                obsoleted,
+                replacement,
+                deprecated: extension.deprecated,
+            });
+        }
+
+        let features = self.features().map(|f| (&*f.name, &f.children));
+        let extensions = self
+            .extensions()
+            .filter(|e| !e.is_disabled())
+            .map(|e| (&*e.name, &e.children));
+        for (deprecated_by, children) in features.chain(extensions) {
+            for child in children {
+                let (api, explanationlink, items) = match *child {
+                    ExtensionChild::Deprecate {
+                        ref api,
+                        ref explanationlink,
+                        ref items,
+                        ..
+                    } => (api.as_ref(), explanationlink.as_deref(), items),
+                    _ => continue,
+                };
+                for item in items {
+                    let (list, name) = match *item {
+                        InterfaceItem::Type { ref name, .. } => (&mut deprecations.types, name),
+                        InterfaceItem::Command { ref name, .. } => {
+                            (&mut deprecations.commands, name)
+                        }
+                        InterfaceItem::Enum(ref e) => (&mut deprecations.enums, &e.name),
+                        InterfaceItem::Comment(_) => continue,
+                    };
+                    list.push(DeprecatedItem {
+                        name,
+                        item,
+                        deprecated_by,
+                        api,
+                        explanationlink,
+                    });
+                }
+            }
+        }
+        deprecations
+    }
+}
@@ -0,0 +1,209 @@
+use annotate_snippets::{Level, Renderer, Snippet};
+use xml::common::Position;
+use xml::reader::XmlEvent;
+
+use options::ParseOptions;
+use parse::{parse_bytes_located, strip_utf8_bom, xml_config};
+use types::*;
+
+/// Error found while parsing a document, along with its location in the document, see
+/// `parse_str_with_diagnostics`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Diagnostic {
+    pub error: Error,
+    /// Line and column, counting from 1, of the start tag of the element given by xpath of the
+    /// error. Columns count characters. `None` for errors which don't refer to any element.
+    pub position: Option<(usize, usize)>,
+}
+
+/// Fatal error of `parse_str_with_diagnostics`, along with its location in the document.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FatalDiagnostic {
+    pub error: FatalError,
+    /// Line and column of the start tag of the element at which parsing stopped, as for
+    /// `Diagnostic::position`. With `FatalError::Strict`, it's the position of the first error
+    /// which failed parsing.
+    pub position: Option<(usize, usize)>,
+    /// With `FatalError::Strict`, each of its errors along with its location. Empty for other
+    /// fatal errors.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Parses the Vulkan XML from string into a Rust object, using given options, along with
+/// location of each error in the document, so that errors can be rendered with excerpts of the
+/// document using `Diagnostic::render`. Errors which fail parsing with `ParseOptions::strict`
+/// are located as well.
+///
+/// ```no_run
+/// extern crate vk_parse;
+///
+/// let xml = std::fs::read_to_string("vk.xml").unwrap();
+/// let options = vk_parse::ParseOptions::new();
+/// let (_registry, diagnostics) = vk_parse::parse_str_with_diagnostics(&xml, &options).unwrap();
+/// for diagnostic in diagnostics {
+///     eprintln!("{}", diagnostic.render(&xml));
+/// }
+/// ```
+pub fn parse_str_with_diagnostics(
+    text: &str,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Diagnostic>), FatalDiagnostic> {
+    match parse_bytes_located(text.as_bytes(), options) {
+        Ok((registry, errors)) => Ok((registry, locate(text, options, errors))),
+        Err((error, elements)) => {
+            let (position, diagnostics) = match error {
+                FatalError::Strict(ref errors) => {
+                    let located = errors.iter().cloned().zip(elements).collect();
+                    let diagnostics = locate(text, options, located);
+                    let position = diagnostics
+                        .iter()
+                        .find(|d| !d.error.is_coverage())
+                        .and_then(|d| d.position);
+                    (position, diagnostics)
+                }
+                _ => {
+                    let element = elements.first().cloned().unwrap_or(0);
+                    let positions = element_positions(text, options, element);
+                    (position(&positions, element), Vec::new())
+                }
+            };
+            Err(FatalDiagnostic {
+                error,
+                position,
+                diagnostics,
+            })
+        }
+    }
+}
+
+/// Diagnostics of errors found at elements given by their document order.
+fn locate(text: &str, options: &ParseOptions, errors: Vec<(Error, usize)>) -> Vec<Diagnostic> {
+    let count = errors.iter().map(|&(_, element)| element).max();
+    let positions = element_positions(text, options, count.unwrap_or(0));
+    errors
+        .into_iter()
+        .map(|(error, element)| Diagnostic {
+            error,
+            position: position(&positions, element),
+        })
+        .collect()
+}
+
+/// Position of the element given by its document order, counting from 1.
+fn position(positions: &[(usize, usize)], element: usize) -> Option<(usize, usize)> {
+    element
+        .checked_sub(1)
+        .and_then(|i| positions.get(i).cloned())
+}
+
+/// Positions of start tags of the first `count` elements of the document, in document order.
+fn element_positions(text: &str, options: &ParseOptions, count: usize) -> Vec<(usize, usize)> {
+    let bytes = strip_utf8_bom(text.as_bytes());
+    let mut reader = xml_config(bytes, options).create_reader(bytes);
+    let mut positions = Vec::new();
+    while positions.len() < count {
+        match reader.next() {
+            Ok(XmlEvent::StartElement { .. }) => {
+                let position = reader.position();
+                positions.push((position.row as usize + 1, position.column as usize + 1));
+            }
+            Ok(XmlEvent::EndDocument) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+    positions
+}
+
+impl Diagnostic {
+    /// Renders the diagnostic as plain text, with the start tag of the element it refers to
+    /// underlined within `source`, which must be the parsed document, and its xpath shown.
+    pub fn render(&self, source: &str) -> String {
+        self.render_with(source, Renderer::plain())
+    }
+
+    /// Same as `render`, with ANSI colors for terminals.
+    pub fn render_styled(&self, source: &str) -> String {
+        self.render_with(source, Renderer::styled())
+    }
+
+    fn render_with(&self, source: &str, renderer: Renderer) -> String {
+        let level = if self.error.is_coverage() {
+            Level::Warning
+        } else {
+            Level::Error
+        };
+        let title = summary(&self.error);
+        let xpath = xpath(&self.error)
+            .filter(|xpath| !xpath.is_empty())
+            .map(|xpath| format!("at {}", xpath));
+        let mut message = level.title(&title).id(self.error.code());
+
+        let excerpt = self
+            .position
+            .and_then(|(line, column)| Some((line, column, source.lines().nth(line - 1)?)));
+        if let Some((line, column, text)) = excerpt {
+            let start = text
+                .char_indices()
+                .nth(column - 1)
+                .map_or(text.len(), |(i, _)| i);
+            let end = text[start..]
+                .find('>')
+                .map_or(text.len(), |i| start + i + 1);
+            message = message.snippet(
+                Snippet::source(text)
+                    .line_start(line)
+                    .annotation(level.span(start..end)),
+            );
+        }
+        if let Some(ref xpath) = xpath {
+            message = message.footer(Level::Note.title(xpath));
+        }
+        let rendered = renderer.render(message).to_string();
+        rendered
+    }
+}
+
+/// Short description of the error.
+fn summary(error: &Error) -> String {
+    match *error {
+        Error::UnexpectedElement { ref name, .. } => format!("unexpected element `{}`", name),
+        Error::UnexpectedAttribute { ref name, .. } => format!("unexpected attribute `{}`", name),
+        Error::UnexpectedAttributeValue {
+            ref name,
+            ref value,
+            ..
+        } => format!("unexpected value `{}` of attribute `{}`", value, name),
+        Error::MissingElement { ref name, .. } => format!("missing element `{}`", name),
+        Error::MissingAttribute { ref name, .. } => format!("missing attribute `{}`", name),
+        Error::SchemaViolation { ref desc, .. } | Error::ReadError { ref desc, .. } => desc.clone(),
+        Error::ParseIntError {
+            ref text,
+            radix,
+            ref error,
+            ..
+        } => format!("invalid base {} integer `{}`: {}", radix, text, error),
+        Error::Internal { desc } => String::from(desc),
+        Error::IgnoredElement { ref name, .. } => format!("ignored element `{}`", name),
+        Error::IgnoredAttribute { ref name, .. } => format!("ignored attribute `{}`", name),
+        Error::IgnoredText { .. } => String::from("ignored text"),
+    }
+}
+
+fn xpath(error: &Error) -> Option<&str> {
+    match *error {
+        Error::UnexpectedElement { ref xpath, .. }
+        | Error::UnexpectedAttribute { ref xpath, .. }
+        | Error::UnexpectedAttributeValue { ref xpath, .. }
+        | Error::MissingElement { ref xpath, .. }
+        | Error::MissingAttribute { ref xpath, .. }
+        | Error::SchemaViolation { ref xpath, .. }
+        | Error::ParseIntError { ref xpath, .. }
+        | Error::IgnoredElement { ref xpath, .. }
+        | Error::IgnoredAttribute { ref xpath, .. }
+        | Error::IgnoredText { ref xpath }
+        | Error::ReadError { ref xpath, .. } => Some(xpath),
+        Error::Internal { .. } => None,
+    }
+}
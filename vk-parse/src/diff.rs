@@ -0,0 +1,215 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use api::ApiSet;
+use types::*;
+
+/// Difference of a single item between two registries, see `diff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Change<'a, T> {
+    Added(&'a T),
+    Removed(&'a T),
+    Changed { old: &'a T, new: &'a T },
+}
+
+impl<'a, T> Change<'a, T> {
+    /// The item in the old registry, unless it was added.
+    pub fn before(&self) -> Option<&'a T> {
+        match *self {
+            Change::Added(_) => None,
+            Change::Removed(old) | Change::Changed { old, .. } => Some(old),
+        }
+    }
+
+    /// The item in the new registry, unless it was removed.
+    pub fn after(&self) -> Option<&'a T> {
+        match *self {
+            Change::Removed(_) => None,
+            Change::Added(new) | Change::Changed { new, .. } => Some(new),
+        }
+    }
+}
+
+/// Change of a type, along with changes of its members.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct TypeDiff<'a> {
+    pub change: Change<'a, Type>,
+    /// Changes of members, identified by name, if the type was changed and is a struct or
+    /// union in both registries.
+    pub members: Vec<Change<'a, TypeMemberDefinition>>,
+}
+
+/// Differences between two registries, see `diff`.
+///
+/// Items are identified by name, and types and commands also by their `api`. Removed and changed items are
+/// listed in order of the old registry, followed by added items in order of the new registry.
+/// When an item is defined repeatedly, only its first definition is compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct RegistryDiff<'a> {
+    pub types: Vec<TypeDiff<'a>>,
+    pub commands: Vec<Change<'a, Command>>,
+    /// Values of enum and bitmask types, defined either in `<enums>` blocks or by features and
+    /// extensions.
+    pub enums: Vec<Change<'a, Enum>>,
+    /// Enums not extending a type, such as those in the `API Constants` block or
+    /// `VK_KHR_SURFACE_SPEC_VERSION`.
+    pub constants: Vec<Change<'a, Enum>>,
+    pub features: Vec<Change<'a, Feature>>,
+    pub extensions: Vec<Change<'a, Extension>>,
+}
+
+impl<'a> RegistryDiff<'a> {
+    /// Whether the registries are the same, as far as compared items go.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+            && self.commands.is_empty()
+            && self.enums.is_empty()
+            && self.constants.is_empty()
+            && self.features.is_empty()
+            && self.extensions.is_empty()
+    }
+}
+
+/// Compares items of two registries, e.g. to find breaking changes when updating `vk.xml`.
+/// Any difference of an item, including its comments, is reported as a change.
+pub fn diff<'a>(old: &'a Registry, new: &'a Registry) -> RegistryDiff<'a> {
+    let types = diff_items(type_items(old), type_items(new))
+        .into_iter()
+        .map(|change| {
+            let members = match change {
+                Change::Changed { old, new } => match (&old.spec, &new.spec) {
+                    (TypeSpec::Members(old), TypeSpec::Members(new)) => {
+                        diff_items(member_items(old), member_items(new))
+                    }
+                    _ => Vec::new(),
+                },
+                _ => Vec::new(),
+            };
+            TypeDiff { change, members }
+        })
+        .collect();
+
+    let (old_enums, old_constants) = enum_items(old);
+    let (new_enums, new_constants) = enum_items(new);
+    RegistryDiff {
+        types,
+        commands: diff_items(
+            old.commands().map(|c| ((c.name(), c.api()), c)).collect(),
+            new.commands().map(|c| ((c.name(), c.api()), c)).collect(),
+        ),
+        enums: diff_items(old_enums, new_enums),
+        constants: diff_items(old_constants, new_constants),
+        features: diff_items(
+            old.features().map(|f| (&*f.name, f)).collect(),
+            new.features().map(|f| (&*f.name, f)).collect(),
+        ),
+        extensions: diff_items(
+            old.extensions().map(|e| (&*e.name, e)).collect(),
+            new.extensions().map(|e| (&*e.name, e)).collect(),
+        ),
+    }
+}
+
+fn diff_items<'a, K: Ord + Copy, T: PartialEq>(
+    old: Vec<(K, &'a T)>,
+    new: Vec<(K, &'a T)>,
+) -> Vec<Change<'a, T>> {
+    let mut old_map = BTreeMap::new();
+    for &(key, item) in &old {
+        old_map.entry(key).or_insert(item);
+    }
+    let mut new_map = BTreeMap::new();
+    for &(key, item) in &new {
+        new_map.entry(key).or_insert(item);
+    }
+
+    let mut changes = Vec::new();
+    for (key, item) in old {
+        if !core::ptr::eq(old_map[&key], item) {
+            continue;
+        }
+        match new_map.get(&key) {
+            None => changes.push(Change::Removed(item)),
+            Some(&new) if *new != *item => changes.push(Change::Changed { old: item, new }),
+            Some(_) => {}
+        }
+    }
+    for (key, item) in new {
+        if core::ptr::eq(new_map[&key], item) && !old_map.contains_key(&key) {
+            changes.push(Change::Added(item));
+        }
+    }
+    changes
+}
+
+fn type_items(registry: &Registry) -> Vec<((&str, Option<&ApiSet>), &Type)> {
+    registry
+        .types()
+        .filter_map(|ty| Some(((ty.declared_name()?, ty.api.as_ref()), ty)))
+        .collect()
+}
+
+fn member_items(members: &[TypeMember]) -> Vec<(&str, &TypeMemberDefinition)> {
+    members
+        .iter()
+        .filter_map(|member| match *member {
+            TypeMember::Definition(ref def) => Some((def.name()?, def)),
+            _ => None,
+        })
+        .collect()
+}
+
+type EnumItems<'a> = Vec<(&'a str, &'a Enum)>;
+
+/// Enums defined by the registry, split into values of types and constants.
+fn enum_items(registry: &Registry) -> (EnumItems<'_>, EnumItems<'_>) {
+    let mut values = Vec::new();
+    let mut constants = Vec::new();
+    for enums in registry.enums_blocks() {
+        let is_type = matches!(enums.kind.as_deref(), Some("enum") | Some("bitmask"));
+        for child in &enums.children {
+            if let EnumsChild::Enum(ref e) = *child {
+                if is_type {
+                    values.push((&*e.name, e));
+                } else {
+                    constants.push((&*e.name, e));
+                }
+            }
+        }
+    }
+
+    let features = registry.features().map(|f| &f.children);
+    let extensions = registry.extensions().map(|e| &e.children);
+    for children in features.chain(extensions) {
+        for child in children {
+            let items = match *child {
+                ExtensionChild::Require { ref items, .. } => items,
+                _ => continue,
+            };
+            for item in items {
+                let e = match *item {
+                    InterfaceItem::Enum(ref e) => e,
+                    _ => continue,
+                };
+                let extends = match e.spec {
+                    // Reference to an enum defined elsewhere.
+                    EnumSpec::None => continue,
+                    EnumSpec::Offset { .. } => true,
+                    EnumSpec::Alias { ref extends, .. }
+                    | EnumSpec::Bitpos { ref extends, .. }
+                    | EnumSpec::Value { ref extends, .. } => extends.is_some(),
+                };
+                if extends {
+                    values.push((&*e.name, e));
+                } else {
+                    constants.push((&*e.name, e));
+                }
+            }
+        }
+    }
+    (values, constants)
+}
@@ -0,0 +1,461 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec, vec::Vec};
+
+use api::ApiSet;
+use enums::extension_enum_value;
+use expr::CValue;
+use retain::Names;
+use types::*;
+use validation::ValidationOptions;
+
+/// Reason why `Registry::add_extension`, `Registry::add_enum` or `Registry::add_member` refused
+/// to change the registry.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum EditError {
+    /// Item with the same name is already in the registry, or in the struct for members.
+    DuplicateName { name: Box<str> },
+    /// Extension number is already used by `extension`.
+    DuplicateNumber { number: i64, extension: Box<str> },
+    /// Enum has the same value as `existing`, which extends the same type.
+    DuplicateValue { name: Box<str>, existing: Box<str> },
+    /// Enum is given by offset, but neither it nor its extension has a number.
+    MissingNumber { name: Box<str> },
+    /// Item `from` refers to `to`, which isn't in the registry.
+    MissingReference { from: Box<str>, to: Box<str> },
+    /// Item isn't what the change needs, e.g. a type which isn't a struct when adding a member.
+    UnexpectedKind {
+        name: Box<str>,
+        expected: &'static str,
+    },
+}
+
+impl Registry {
+    /// Adds `extension` to the last `<extensions>` block, after checking that its name and
+    /// number aren't used yet, that the extensions and features it refers to are in the
+    /// registry, along with types and commands it requires, and that enums it defines have
+    /// unique names and values among those extending the same type. The registry isn't changed
+    /// if any check fails.
+    ///
+    /// Enums of disabled extensions, and contents of `extension` if it's disabled, aren't
+    /// checked, see `ValidationOptions`. Their names and numbers are still reserved.
+    pub fn add_extension(&mut self, extension: Extension) -> Result<(), EditError> {
+        self.add_extension_with_options(extension, &ValidationOptions::default())
+    }
+
+    /// Adds `extension` as `Registry::add_extension` does, checking parts of the registry given
+    /// by `options`.
+    pub fn add_extension_with_options(
+        &mut self,
+        extension: Extension,
+        options: &ValidationOptions,
+    ) -> Result<(), EditError> {
+        self.check_extension(&extension, options)?;
+
+        let last = self.0.iter_mut().rev().find_map(|child| match *child {
+            RegistryChild::Extensions(ref mut extensions) => Some(extensions),
+            _ => None,
+        });
+        match last {
+            Some(extensions) => extensions.children.push(extension),
+            None => self.0.push(RegistryChild::Extensions(CommentedChildren {
+                comment: None,
+                children: vec![extension],
+            })),
+        }
+        Ok(())
+    }
+
+    /// Checks of `Registry::add_extension`.
+    fn check_extension(
+        &self,
+        extension: &Extension,
+        options: &ValidationOptions,
+    ) -> Result<(), EditError> {
+        let names = Names::new(self);
+        let from = &*extension.name;
+        if names.has_interface(from) {
+            return Err(EditError::DuplicateName { name: from.into() });
+        }
+        if let Some(number) = extension.number {
+            if let Some(existing) = self.extensions().find(|e| e.number == Some(number)) {
+                return Err(EditError::DuplicateNumber {
+                    number,
+                    extension: existing.name.clone(),
+                });
+            }
+        }
+        if !options.checks(extension) {
+            return Ok(());
+        }
+        for to in extension.requires.iter().flat_map(|r| r.split(',')) {
+            if !names.extensions.contains(to) {
+                return Err(missing(from, to));
+            }
+        }
+        for &(_, to) in &extension.replacements() {
+            if let Some(to) = to.filter(|to| !names.has_interface(to)) {
+                return Err(missing(from, to));
+            }
+        }
+
+        let mut defined = defined_enums(self, options);
+        for child in &extension.children {
+            let items = match *child {
+                ExtensionChild::Require { ref items, .. } => items,
+                _ => continue,
+            };
+            for item in items {
+                match *item {
+                    InterfaceItem::Type { ref name, .. } if !names.types.contains(&**name) => {
+                        return Err(missing(from, name))
+                    }
+                    InterfaceItem::Command { ref name, .. }
+                        if !names.commands.contains(&**name) =>
+                    {
+                        return Err(missing(from, name))
+                    }
+                    InterfaceItem::Enum(ref e) => {
+                        let added = check_enum(&names, &defined, from, extension.number, e)?;
+                        defined.extend(added);
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds enum `e` to the first unconditional `<require>` block of feature or extension
+    /// `required_by`, adding a block if there's none. The enum must extend a type, e.g. be
+    /// given by offset, bit position or value with `extends`, or be an alias of another enum.
+    ///
+    /// Checks are the same as for enums of `Registry::add_extension`: the type must be in the
+    /// registry, values given by offset need a number, and enums must have unique names and
+    /// values among those extending the same type. Enums defined again with the same value,
+    /// as when an extension is promoted, are allowed. The registry isn't changed if any check
+    /// fails. Enums of disabled extensions aren't checked, nor compared with, see
+    /// `ValidationOptions`.
+    pub fn add_enum(&mut self, required_by: &str, e: Enum) -> Result<(), EditError> {
+        self.add_enum_with_options(required_by, e, &ValidationOptions::default())
+    }
+
+    /// Adds enum `e` as `Registry::add_enum` does, checking parts of the registry given by
+    /// `options`.
+    pub fn add_enum_with_options(
+        &mut self,
+        required_by: &str,
+        e: Enum,
+        options: &ValidationOptions,
+    ) -> Result<(), EditError> {
+        let extends = match e.spec {
+            EnumSpec::Alias { ref extends, .. }
+            | EnumSpec::Bitpos { ref extends, .. }
+            | EnumSpec::Value { ref extends, .. } => extends.is_some(),
+            EnumSpec::Offset { .. } => true,
+            EnumSpec::None => false,
+        };
+        if !extends {
+            return Err(EditError::UnexpectedKind {
+                name: e.name,
+                expected: "enum extending a type",
+            });
+        }
+        let (number, checked) = match self.extensions().find(|ext| &*ext.name == required_by) {
+            Some(extension) => (extension.number, options.checks(extension)),
+            None if self.features().any(|f| &*f.name == required_by) => (None, true),
+            None => return Err(missing(&e.name, required_by)),
+        };
+        if checked {
+            check_enum(
+                &Names::new(self),
+                &defined_enums(self, options),
+                required_by,
+                number,
+                &e,
+            )?;
+        }
+
+        let children = self.0.iter_mut().find_map(|child| match *child {
+            RegistryChild::Feature(ref mut feature) if &*feature.name == required_by => {
+                Some(&mut feature.children)
+            }
+            RegistryChild::Extensions(ref mut extensions) => extensions
+                .children
+                .iter_mut()
+                .find(|ext| &*ext.name == required_by)
+                .map(|ext| &mut ext.children),
+            _ => None,
+        });
+        let children = match children {
+            Some(children) => children,
+            None => return Err(missing(&e.name, required_by)),
+        };
+        let block = children.iter_mut().find_map(|child| match *child {
+            ExtensionChild::Require {
+                api: None,
+                profile: None,
+                extension: None,
+                feature: None,
+                depends: None,
+                ref mut items,
+                ..
+            } => Some(items),
+            _ => None,
+        });
+        match block {
+            Some(items) => items.push(InterfaceItem::Enum(e)),
+            None => children.push(ExtensionChild::Require {
+                api: None,
+                profile: None,
+                extension: None,
+                feature: None,
+                depends: None,
+                comment: None,
+                items: vec![InterfaceItem::Enum(e)],
+                raw: RawAttributes::new(),
+            }),
+        }
+        Ok(())
+    }
+
+    /// Adds `member` at the end of struct or union `type_name`, to each variant of it which
+    /// applies to APIs of the member. The member must have a name which no other member of
+    /// the same APIs has, and types and enums it refers to must be in the registry. The
+    /// registry isn't changed if any check fails.
+    pub fn add_member(
+        &mut self,
+        type_name: &str,
+        member: TypeMemberDefinition,
+    ) -> Result<(), EditError> {
+        let name = match member.name() {
+            Some(name) => name,
+            None => {
+                return Err(EditError::UnexpectedKind {
+                    name: type_name.into(),
+                    expected: "named member",
+                })
+            }
+        };
+        let is_variant = |ty: &Type| {
+            ty.declared_name() == Some(type_name)
+                && apis_overlap(ty.api.as_ref(), member.api.as_ref())
+        };
+        {
+            let variants: Vec<&Type> = self.types().filter(|ty| is_variant(ty)).collect();
+            if variants.is_empty() {
+                return Err(missing(name, type_name));
+            }
+            for ty in &variants {
+                let is_struct = matches!(ty.category.as_deref(), Some("struct") | Some("union"))
+                    && matches!(ty.spec, TypeSpec::Members(_) | TypeSpec::None)
+                    && ty.alias.is_none();
+                if !is_struct {
+                    return Err(EditError::UnexpectedKind {
+                        name: type_name.into(),
+                        expected: "struct or union",
+                    });
+                }
+                let members: &[TypeMember] = match ty.spec {
+                    TypeSpec::Members(ref members) => members,
+                    _ => &[],
+                };
+                let duplicate = members.iter().any(|m| match *m {
+                    TypeMember::Definition(ref def) => {
+                        def.name() == Some(name)
+                            && apis_overlap(def.api.as_ref(), member.api.as_ref())
+                    }
+                    TypeMember::Comment(_) => false,
+                });
+                if duplicate {
+                    return Err(EditError::DuplicateName { name: name.into() });
+                }
+            }
+
+            let names = Names::new(self);
+            let defined = defined_enums(self, &ValidationOptions::default());
+            for markup in &member.markup {
+                let (to, found) = match *markup {
+                    TypeMemberMarkup::Type(ref to) => (to, names.types.contains(&**to)),
+                    TypeMemberMarkup::Enum(ref to) => (to, defined.iter().any(|d| d.name == &**to)),
+                    _ => continue,
+                };
+                if !found {
+                    return Err(missing(name, to));
+                }
+            }
+        }
+
+        for child in &mut self.0 {
+            let types = match *child {
+                RegistryChild::Types(ref mut types) => types,
+                _ => continue,
+            };
+            for child in &mut types.children {
+                let ty = match *child {
+                    TypesChild::Type(ref mut ty) if is_variant(ty) => ty,
+                    _ => continue,
+                };
+                match ty.spec {
+                    TypeSpec::Members(ref mut members) => {
+                        members.push(TypeMember::Definition(member.clone()))
+                    }
+                    _ => ty.spec = TypeSpec::Members(vec![TypeMember::Definition(member.clone())]),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn missing(from: &str, to: &str) -> EditError {
+    EditError::MissingReference {
+        from: from.into(),
+        to: to.into(),
+    }
+}
+
+/// Whether lists of APIs share any API, where a missing list applies to all.
+fn apis_overlap(first: Option<&ApiSet>, second: Option<&ApiSet>) -> bool {
+    match (first, second) {
+        (Some(first), Some(second)) => first.apis.iter().any(|a| second.apis.contains(a)),
+        _ => true,
+    }
+}
+
+/// Enum defined by the registry, with its value if it can be determined.
+struct DefinedEnum<'a> {
+    name: &'a str,
+    /// Type the enum belongs to, i.e. `extends` or name of its `<enums>` block.
+    extends: Option<&'a str>,
+    /// Value, where bits are given as values.
+    value: Option<i128>,
+}
+
+/// All enums defined by `<enums>` blocks, features and extensions checked with `options`.
+fn defined_enums<'a>(registry: &'a Registry, options: &ValidationOptions) -> Vec<DefinedEnum<'a>> {
+    let mut defined = Vec::new();
+    for enums in registry.enums_blocks() {
+        for child in &enums.children {
+            if let EnumsChild::Enum(ref e) = *child {
+                defined.push(DefinedEnum {
+                    name: &e.name,
+                    extends: enums.name.as_deref(),
+                    value: enum_value(&e.spec, None),
+                });
+            }
+        }
+    }
+    let features = registry.features().map(|f| (None, &f.children));
+    let extensions = registry
+        .extensions()
+        .filter(|e| options.checks(e))
+        .map(|e| (e.number, &e.children));
+    for (number, children) in features.chain(extensions) {
+        for child in children {
+            let items = match *child {
+                ExtensionChild::Require { ref items, .. } => items,
+                _ => continue,
+            };
+            for item in items {
+                match *item {
+                    InterfaceItem::Enum(ref e) if e.spec != EnumSpec::None => {
+                        defined.push(DefinedEnum {
+                            name: &e.name,
+                            extends: enum_extends(&e.spec),
+                            value: enum_value(&e.spec, number),
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    defined
+}
+
+fn enum_extends(spec: &EnumSpec) -> Option<&str> {
+    match *spec {
+        EnumSpec::Alias { ref extends, .. }
+        | EnumSpec::Bitpos { ref extends, .. }
+        | EnumSpec::Value { ref extends, .. } => extends.as_deref(),
+        EnumSpec::Offset { ref extends, .. } => Some(extends),
+        EnumSpec::None => None,
+    }
+}
+
+/// Value of enum defined by `spec`, where `number` is of the extension defining it.
+pub(crate) fn enum_value(spec: &EnumSpec, number: Option<i64>) -> Option<i128> {
+    match *spec {
+        EnumSpec::Offset {
+            offset,
+            extnumber,
+            dir,
+            ..
+        } => extnumber
+            .or(number)
+            .map(|n| i128::from(extension_enum_value(n, offset, dir))),
+        EnumSpec::Bitpos { bitpos, .. } if (0..127).contains(&bitpos) => Some(1 << bitpos),
+        EnumSpec::Value { ref value, .. } => CValue::evaluate(value).and_then(|v| {
+            v.as_i64()
+                .map(i128::from)
+                .or_else(|| v.as_u64().map(i128::from))
+        }),
+        _ => None,
+    }
+}
+
+/// Checks enum `e` required by `from`, given `defined` enums, returning the definition it
+/// adds, if any.
+fn check_enum<'a>(
+    names: &Names,
+    defined: &[DefinedEnum<'a>],
+    from: &str,
+    number: Option<i64>,
+    e: &'a Enum,
+) -> Result<Option<DefinedEnum<'a>>, EditError> {
+    if let EnumSpec::None = e.spec {
+        if !defined.iter().any(|d| d.name == &*e.name) {
+            return Err(missing(from, &e.name));
+        }
+        return Ok(None);
+    }
+    if let EnumSpec::Alias { ref alias, .. } = e.spec {
+        if !defined.iter().any(|d| d.name == &**alias) {
+            return Err(missing(&e.name, alias));
+        }
+    }
+    let extends = enum_extends(&e.spec);
+    if let Some(extends) = extends {
+        if !names.types.contains(extends) {
+            return Err(missing(&e.name, extends));
+        }
+    }
+    let value = enum_value(&e.spec, number);
+    if let (None, EnumSpec::Offset { .. }) = (value, &e.spec) {
+        return Err(EditError::MissingNumber {
+            name: e.name.clone(),
+        });
+    }
+
+    for d in defined {
+        if d.name == &*e.name {
+            // Defined again with the same value, e.g. by a feature the extension is promoted to.
+            if d.extends != extends || d.value != value {
+                return Err(EditError::DuplicateName {
+                    name: e.name.clone(),
+                });
+            }
+        } else if value.is_some() && d.value == value && d.extends == extends && extends.is_some() {
+            return Err(EditError::DuplicateValue {
+                name: e.name.clone(),
+                existing: d.name.into(),
+            });
+        }
+    }
+    Ok(Some(DefinedEnum {
+        name: &e.name,
+        extends,
+        value,
+    }))
+}
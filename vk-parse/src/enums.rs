@@ -0,0 +1,271 @@
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::ToString,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet};
+
+use types::*;
+
+/// Base value of enums defined by extensions, see `extension_enum_value`.
+const EXTENSION_ENUM_BASE: i64 = 1_000_000_000;
+/// Range of values reserved for each extension.
+const EXTENSION_ENUM_RANGE: i64 = 1000;
+
+impl Registry {
+    /// Moves enums extending types, e.g. `VK_STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR`, from
+    /// features and extensions to `<enums>` blocks of the types, so that each block lists all
+    /// values of its type, as generators emit them.
+    ///
+    /// Moved enums have their values given by offset resolved, and `required_by` set to the
+    /// feature or extension defining them. If the enum isn't specific to an API, but its
+    /// `<require>` is, the API of the latter is used. The enums are replaced by references, i.e.
+    /// enums with `EnumSpec::None`, as in features and extensions requiring enums of blocks.
+    ///
+    /// When an enum is defined repeatedly, e.g. by an extension and by the feature it was
+    /// promoted to, it's added to the block only once. Enums extending types without a block, or
+    /// whose offset can't be resolved due to missing extension number, are left in place.
+    pub fn materialize_enums(&mut self) {
+        let blocks: BTreeSet<Box<str>> = self
+            .enums_blocks()
+            .filter_map(|enums| enums.name.clone())
+            .collect();
+        let mut moved = BTreeMap::new();
+        for child in &mut self.0 {
+            match *child {
+                RegistryChild::Feature(ref mut feature) => take_enums(
+                    &feature.name,
+                    None,
+                    &mut feature.children,
+                    &blocks,
+                    &mut moved,
+                ),
+                RegistryChild::Extensions(ref mut extensions) => {
+                    for extension in &mut extensions.children {
+                        take_enums(
+                            &extension.name,
+                            extension.number,
+                            &mut extension.children,
+                            &blocks,
+                            &mut moved,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for child in &mut self.0 {
+            let enums = match *child {
+                RegistryChild::Enums(ref mut enums) => enums,
+                _ => continue,
+            };
+            let added = match enums.name.as_ref().and_then(|name| moved.remove(name)) {
+                Some(added) => added,
+                None => continue,
+            };
+            for e in added {
+                let exists = enums.children.iter().any(|child| match *child {
+                    EnumsChild::Enum(ref existing) => {
+                        existing.name == e.name && existing.api == e.api
+                    }
+                    _ => false,
+                });
+                if !exists {
+                    enums.children.push(EnumsChild::Enum(e));
+                }
+            }
+        }
+    }
+}
+
+/// Value of enum given by offset within the range of extension `extnumber`, which is negative
+/// unless `dir` is set.
+pub(crate) fn extension_enum_value(extnumber: i64, offset: i64, dir: bool) -> i64 {
+    let value = EXTENSION_ENUM_BASE + (extnumber - 1) * EXTENSION_ENUM_RANGE + offset;
+    if dir {
+        value
+    } else {
+        -value
+    }
+}
+
+/// Replaces enums of `children` extending types of `blocks` by references, and adds them to
+/// `moved` by name of the type.
+fn take_enums(
+    required_by: &str,
+    number: Option<i64>,
+    children: &mut [ExtensionChild],
+    blocks: &BTreeSet<Box<str>>,
+    moved: &mut BTreeMap<Box<str>, Vec<Enum>>,
+) {
+    for child in children {
+        let (api, items) = match *child {
+            ExtensionChild::Require {
+                ref api,
+                ref mut items,
+                ..
+            } => (api, items),
+            _ => continue,
+        };
+        for item in items {
+            let e = match *item {
+                InterfaceItem::Enum(ref mut e) => e,
+                _ => continue,
+            };
+            let (extends, spec) = match e.spec {
+                EnumSpec::Offset {
+                    offset,
+                    ref extends,
+                    extnumber,
+                    dir,
+                } => match extnumber.or(number) {
+                    Some(extnumber) => {
+                        let value = extension_enum_value(extnumber, offset, dir);
+                        let spec = EnumSpec::Value {
+                            value: value.to_string().into_boxed_str(),
+                            extends: Some(extends.clone()),
+                        };
+                        (extends, spec)
+                    }
+                    None => continue,
+                },
+                EnumSpec::Alias {
+                    extends: Some(ref extends),
+                    ..
+                }
+                | EnumSpec::Bitpos {
+                    extends: Some(ref extends),
+                    ..
+                }
+                | EnumSpec::Value {
+                    extends: Some(ref extends),
+                    ..
+                } => (extends, e.spec.clone()),
+                _ => continue,
+            };
+            if !blocks.contains(extends) {
+                continue;
+            }
+            let extends = extends.clone();
+            let materialized = Enum {
+                api: e.api.clone().or_else(|| api.clone()),
+                spec,
+                required_by: Some(required_by.into()),
+                ..e.clone()
+            };
+            e.spec = EnumSpec::None;
+            moved.entry(extends).or_default().push(materialized);
+        }
+    }
+}
+
+/// Name of enumerant split into parts, see `Registry::enumerant_name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct EnumerantName<'a> {
+    /// Prefix derived from the name of the type, e.g. `VK_IMAGE_LAYOUT_`.
+    pub prefix: &'a str,
+    /// Name without the prefix, the `_BIT` suffix and the vendor tag, e.g.
+    /// `COLOR_ATTACHMENT_OPTIMAL`.
+    pub short: &'a str,
+    /// Whether the name ends with `_BIT`, as bits of bitmasks do.
+    pub is_bit: bool,
+    /// Vendor tag suffix, e.g. `KHR` of `VK_IMAGE_LAYOUT_PRESENT_SRC_KHR`.
+    pub vendor: Option<&'a str>,
+}
+
+impl Registry {
+    /// Splits name of enumerant `name` of enum or bitmask type `type_name` into the prefix
+    /// derived from the type, the short name, and the suffixes, as binding generators do, e.g.
+    /// `VK_IMAGE_LAYOUT_COLOR_ATTACHMENT_OPTIMAL` of `VkImageLayout` to `VK_IMAGE_LAYOUT_` and
+    /// `COLOR_ATTACHMENT_OPTIMAL`.
+    ///
+    /// The prefix is given by words of the type name in upper case, without `FlagBits` and the
+    /// vendor tag of the type, e.g. `VK_PIPELINE_STAGE_2_` for `VkPipelineStageFlagBits2KHR`.
+    /// Only words the enumerant starts with are stripped, so e.g. `VK_SUCCESS` of `VkResult`
+    /// keeps `SUCCESS`. Suffixes are stripped only if some other word remains. Vendor tags are
+    /// those listed by `<tags>`.
+    ///
+    /// When the short name would start with a digit, the last word of the prefix is kept, so
+    /// that it's a valid identifier, e.g. `TYPE_2D` for `VK_IMAGE_TYPE_2D`, or `COUNT_1` for
+    /// `VK_SAMPLE_COUNT_1_BIT`.
+    pub fn enumerant_name<'a>(&self, type_name: &str, name: &'a str) -> EnumerantName<'a> {
+        let is_tag = |word: &str| self.tags().any(|tag| &*tag.name == word);
+
+        let mut type_name = type_name.strip_prefix("Vk").unwrap_or(type_name);
+        if let Some(tag) = self
+            .tags()
+            .find(|tag| type_name.len() > tag.name.len() && type_name.ends_with(&*tag.name))
+        {
+            type_name = &type_name[..type_name.len() - tag.name.len()];
+        }
+        let mut type_words = camel_case_words(type_name);
+        if let Some(i) = type_words
+            .windows(2)
+            .position(|pair| pair[0] == "Flag" && pair[1] == "Bits")
+        {
+            type_words.drain(i..i + 2);
+        }
+
+        let words: Vec<&str> = name.split('_').collect();
+        let mut prefix_len = core::iter::once("Vk")
+            .chain(type_words)
+            .zip(&words)
+            .take_while(|&(type_word, &word)| type_word.eq_ignore_ascii_case(word))
+            .count();
+        let mut end = words.len();
+        let mut vendor = None;
+        if end > prefix_len + 1 && is_tag(words[end - 1]) {
+            vendor = Some(words[end - 1]);
+            end -= 1;
+        }
+        let mut is_bit = false;
+        if end > prefix_len + 1 && words[end - 1] == "BIT" {
+            is_bit = true;
+            end -= 1;
+        }
+        while prefix_len > 0
+            && (prefix_len == end || words[prefix_len].starts_with(char::is_numeric))
+        {
+            prefix_len -= 1;
+        }
+
+        // Offsets of words within the name, each followed by an underscore.
+        let offset = |i: usize| words[..i].iter().map(|word| word.len() + 1).sum::<usize>();
+        let (start, short_end) = (offset(prefix_len), offset(end) - 1);
+        EnumerantName {
+            prefix: &name[..start],
+            short: &name[start..short_end],
+            is_bit,
+            vendor: vendor.map(|_| &name[offset(words.len() - 1)..]),
+        }
+    }
+}
+
+/// Words of a name in camel case, where digits are split from preceding lower case letters, e.g.
+/// `H264`, `Flag`, `Bits` and `2` for `H264FlagBits2`.
+fn camel_case_words(name: &str) -> Vec<&str> {
+    let bytes = name.as_bytes();
+    let mut words = Vec::new();
+    let mut start = 0;
+    for i in 1..bytes.len() {
+        let (prev, c) = (bytes[i - 1], bytes[i]);
+        let next_is_lower = bytes.get(i + 1).map_or(false, u8::is_ascii_lowercase);
+        let is_boundary = (prev.is_ascii_lowercase()
+            && (c.is_ascii_uppercase() || c.is_ascii_digit()))
+            || (prev.is_ascii_digit() && c.is_ascii_uppercase())
+            || (prev.is_ascii_uppercase() && c.is_ascii_uppercase() && next_is_lower);
+        if is_boundary {
+            words.push(&name[start..i]);
+            start = i;
+        }
+    }
+    if start < name.len() {
+        words.push(&name[start..]);
+    }
+    words
+}
@@ -0,0 +1,535 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use types::CLiteral;
+
+/// Typed value of a C constant expression, as found in values of `<enum>` elements and defines.
+///
+/// Types follow C rules, assuming 32-bit `int` and 64-bit `long`, so e.g. `(~0U)` is
+/// `U32(0xFFFFFFFF)` while `(~0ULL)` is `U64(0xFFFFFFFFFFFFFFFF)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum CValue {
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    U64(u64),
+    F32(f32),
+    F64(f64),
+}
+
+impl CValue {
+    /// Evaluates constant expression such as `(~0U)`, `(1 << 5)`, `-1` or `1000.0F`. Returns
+    /// `None` if the expression is invalid, refers to any names, nests unary operators or
+    /// parentheses deeper than `MAX_DEPTH`, or its evaluation fails, e.g. on division by zero.
+    pub fn evaluate(expr: &str) -> Option<CValue> {
+        CValue::evaluate_with(expr, |_| None)
+    }
+
+    /// Evaluates constant expression, using `lookup` to get values of names it refers to, such
+    /// as other enum constants.
+    pub fn evaluate_with<F: Fn(&str) -> Option<CValue>>(expr: &str, lookup: F) -> Option<CValue> {
+        let tokens = tokenize(expr)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            lookup: &lookup,
+            depth: 0,
+        };
+        let value = parser.expression(0)?;
+        if parser.tokens.is_empty() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Value as `i64`, if it's an integer which fits.
+    pub fn as_i64(self) -> Option<i64> {
+        match self {
+            CValue::F32(..) | CValue::F64(..) => None,
+            v => {
+                let v = v.to_i128();
+                if v >= i128::from(i64::MIN) && v <= i128::from(i64::MAX) {
+                    Some(v as i64)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Value as `u64`, if it's a non-negative integer.
+    pub fn as_u64(self) -> Option<u64> {
+        match self {
+            CValue::F32(..) | CValue::F64(..) => None,
+            v => {
+                let v = v.to_i128();
+                if v >= 0 {
+                    Some(v as u64)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Value as `f64`, converting integers.
+    pub fn as_f64(self) -> f64 {
+        match self {
+            CValue::F32(v) => f64::from(v),
+            CValue::F64(v) => v,
+            v => v.to_i128() as f64,
+        }
+    }
+
+    fn is_float(self) -> bool {
+        matches!(self, CValue::F32(..) | CValue::F64(..))
+    }
+
+    /// Position in the usual arithmetic conversions, values convert to the higher one.
+    fn rank(self) -> u8 {
+        match self {
+            CValue::I32(..) => 0,
+            CValue::U32(..) => 1,
+            CValue::I64(..) => 2,
+            CValue::U64(..) => 3,
+            CValue::F32(..) => 4,
+            CValue::F64(..) => 5,
+        }
+    }
+
+    fn to_i128(self) -> i128 {
+        match self {
+            CValue::I32(v) => i128::from(v),
+            CValue::U32(v) => i128::from(v),
+            CValue::I64(v) => i128::from(v),
+            CValue::U64(v) => i128::from(v),
+            CValue::F32(v) => v as i128,
+            CValue::F64(v) => v as i128,
+        }
+    }
+
+    /// Converts to type of given rank, wrapping around as C does for unsigned types.
+    fn convert(self, rank: u8) -> CValue {
+        match rank {
+            0 => CValue::I32(self.to_i128() as i32),
+            1 => CValue::U32(self.to_i128() as u32),
+            2 => CValue::I64(self.to_i128() as i64),
+            3 => CValue::U64(self.to_i128() as u64),
+            4 => CValue::F32(self.as_f64() as f32),
+            _ => CValue::F64(self.as_f64()),
+        }
+    }
+
+    /// Integer promotion, after which unary and shift operators apply.
+    fn promote(self) -> CValue {
+        self.convert(self.rank())
+    }
+
+    /// Applies integer operation to values converted to their common type. `op` works on
+    /// values widened to `i128`, and its result is wrapped to the common type.
+    fn integer_op<F>(self, other: CValue, op: F) -> Option<CValue>
+    where
+        F: Fn(i128, i128) -> Option<i128>,
+    {
+        if self.is_float() || other.is_float() {
+            return None;
+        }
+        let rank = self.rank().max(other.rank());
+        let result = op(self.convert(rank).to_i128(), other.convert(rank).to_i128())?;
+        Some(CValue::wrap(rank, result))
+    }
+
+    /// Wraps `result` to integer type of given rank.
+    fn wrap(rank: u8, result: i128) -> CValue {
+        match rank {
+            0 => CValue::I32(result as i32),
+            1 => CValue::U32(result as u32),
+            2 => CValue::I64(result as i64),
+            _ => CValue::U64(result as u64),
+        }
+    }
+
+    fn arithmetic<I, F>(self, other: CValue, int_op: I, float_op: F) -> Option<CValue>
+    where
+        I: Fn(i128, i128) -> Option<i128>,
+        F: Fn(f64, f64) -> f64,
+    {
+        if self.is_float() || other.is_float() {
+            let result = float_op(self.as_f64(), other.as_f64());
+            Some(CValue::F64(result).convert(self.rank().max(other.rank())))
+        } else {
+            self.integer_op(other, int_op)
+        }
+    }
+
+    fn compare(self, other: CValue, op: BinaryOp) -> CValue {
+        let ordering = if self.is_float() || other.is_float() {
+            self.as_f64().partial_cmp(&other.as_f64())
+        } else {
+            let rank = self.rank().max(other.rank());
+            Some(
+                self.convert(rank)
+                    .to_i128()
+                    .cmp(&other.convert(rank).to_i128()),
+            )
+        };
+        let result = match ordering {
+            Some(ordering) => match op {
+                BinaryOp::Less => ordering.is_lt(),
+                BinaryOp::Greater => ordering.is_gt(),
+                BinaryOp::LessEqual => ordering.is_le(),
+                BinaryOp::GreaterEqual => ordering.is_ge(),
+                BinaryOp::Equal => ordering.is_eq(),
+                _ => ordering.is_ne(),
+            },
+            // Comparisons with NaN.
+            None => op == BinaryOp::NotEqual,
+        };
+        CValue::I32(result as i32)
+    }
+
+    fn is_zero(self) -> bool {
+        match self {
+            CValue::F32(v) => v == 0.0,
+            CValue::F64(v) => v == 0.0,
+            v => v.to_i128() == 0,
+        }
+    }
+
+    fn from_literal(literal: &CLiteral) -> Option<CValue> {
+        match *literal {
+            CLiteral::Integer {
+                value,
+                radix,
+                ref suffix,
+            } => {
+                let suffix = suffix.to_ascii_lowercase();
+                let unsigned = suffix.contains('u');
+                let long = suffix.contains('l');
+                // Decimal literals without `U` suffix are never unsigned in C99.
+                let may_be_unsigned = unsigned || radix != 10;
+                let may_be_signed = !unsigned;
+                if !long && may_be_signed && value <= i32::MAX as u64 {
+                    Some(CValue::I32(value as i32))
+                } else if !long && may_be_unsigned && value <= u64::from(u32::MAX) {
+                    Some(CValue::U32(value as u32))
+                } else if may_be_signed && value <= i64::MAX as u64 {
+                    Some(CValue::I64(value as i64))
+                } else if may_be_unsigned {
+                    Some(CValue::U64(value))
+                } else {
+                    None
+                }
+            }
+            CLiteral::Float {
+                ref value,
+                ref suffix,
+            } => {
+                let value: f64 = value.parse().ok()?;
+                if suffix.eq_ignore_ascii_case("f") {
+                    Some(CValue::F32(value as f32))
+                } else {
+                    Some(CValue::F64(value))
+                }
+            }
+            CLiteral::String(..) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Or,
+    And,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    ShiftLeft,
+    ShiftRight,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    fn from_token(token: &str) -> Option<BinaryOp> {
+        Some(match token {
+            "||" => BinaryOp::Or,
+            "&&" => BinaryOp::And,
+            "|" => BinaryOp::BitOr,
+            "^" => BinaryOp::BitXor,
+            "&" => BinaryOp::BitAnd,
+            "==" => BinaryOp::Equal,
+            "!=" => BinaryOp::NotEqual,
+            "<" => BinaryOp::Less,
+            ">" => BinaryOp::Greater,
+            "<=" => BinaryOp::LessEqual,
+            ">=" => BinaryOp::GreaterEqual,
+            "<<" => BinaryOp::ShiftLeft,
+            ">>" => BinaryOp::ShiftRight,
+            "+" => BinaryOp::Add,
+            "-" => BinaryOp::Sub,
+            "*" => BinaryOp::Mul,
+            "/" => BinaryOp::Div,
+            "%" => BinaryOp::Rem,
+            _ => return None,
+        })
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Or => 1,
+            BinaryOp::And => 2,
+            BinaryOp::BitOr => 3,
+            BinaryOp::BitXor => 4,
+            BinaryOp::BitAnd => 5,
+            BinaryOp::Equal | BinaryOp::NotEqual => 6,
+            BinaryOp::Less | BinaryOp::Greater | BinaryOp::LessEqual | BinaryOp::GreaterEqual => 7,
+            BinaryOp::ShiftLeft | BinaryOp::ShiftRight => 8,
+            BinaryOp::Add | BinaryOp::Sub => 9,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 10,
+        }
+    }
+
+    fn apply(self, a: CValue, b: CValue) -> Option<CValue> {
+        match self {
+            BinaryOp::Or => Some(CValue::I32((!a.is_zero() || !b.is_zero()) as i32)),
+            BinaryOp::And => Some(CValue::I32((!a.is_zero() && !b.is_zero()) as i32)),
+            BinaryOp::BitOr => a.integer_op(b, |a, b| Some(a | b)),
+            BinaryOp::BitXor => a.integer_op(b, |a, b| Some(a ^ b)),
+            BinaryOp::BitAnd => a.integer_op(b, |a, b| Some(a & b)),
+            BinaryOp::Equal
+            | BinaryOp::NotEqual
+            | BinaryOp::Less
+            | BinaryOp::Greater
+            | BinaryOp::LessEqual
+            | BinaryOp::GreaterEqual => Some(a.compare(b, self)),
+            BinaryOp::ShiftLeft | BinaryOp::ShiftRight => {
+                if a.is_float() || b.is_float() {
+                    return None;
+                }
+                // Result has the type of the left operand.
+                let a = a.promote();
+                let bits = if a.rank() < 2 { 32 } else { 64 };
+                let shift = b.to_i128();
+                if shift < 0 || shift >= bits {
+                    return None;
+                }
+                a.integer_op(a, |a, _| {
+                    Some(if self == BinaryOp::ShiftLeft {
+                        a << shift
+                    } else {
+                        a >> shift
+                    })
+                })
+            }
+            BinaryOp::Add => a.arithmetic(b, |a, b| Some(a + b), |a, b| a + b),
+            BinaryOp::Sub => a.arithmetic(b, |a, b| Some(a - b), |a, b| a - b),
+            BinaryOp::Mul => a.arithmetic(b, |a, b| Some(a.wrapping_mul(b)), |a, b| a * b),
+            BinaryOp::Div => {
+                if !a.is_float() && !b.is_float() && b.is_zero() {
+                    return None;
+                }
+                a.arithmetic(b, |a, b| Some(a / b), |a, b| a / b)
+            }
+            BinaryOp::Rem => {
+                if b.is_zero() {
+                    return None;
+                }
+                a.integer_op(b, |a, b| Some(a % b))
+            }
+        }
+    }
+}
+
+fn tokenize(expr: &str) -> Option<Vec<&str>> {
+    let mut tokens = Vec::new();
+    let mut rest = expr.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_ascii_digit()
+            || (c == '.' && rest[1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            let is_hex = rest.starts_with("0x") || rest.starts_with("0X");
+            let mut len = 0;
+            let mut prev = '\0';
+            for c in rest.chars() {
+                let exponent_sign =
+                    !is_hex && (c == '+' || c == '-') && (prev == 'e' || prev == 'E');
+                if !(c.is_ascii_alphanumeric() || c == '.' || c == '_' || exponent_sign) {
+                    break;
+                }
+                len += 1;
+                prev = c;
+            }
+            len
+        } else if c == '_' || c.is_ascii_alphabetic() {
+            rest.find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len())
+        } else if ["<<", ">>", "<=", ">=", "==", "!=", "&&", "||"]
+            .iter()
+            .any(|op| rest.starts_with(op))
+        {
+            2
+        } else if "+-*/%&|^~!<>()".contains(c) {
+            1
+        } else {
+            return None;
+        };
+        tokens.push(&rest[..len]);
+        rest = rest[len..].trim_start();
+    }
+    Some(tokens)
+}
+
+/// Type of a cast, given by its type name.
+fn cast_rank(tokens: &[&str]) -> Option<u8> {
+    Some(match tokens {
+        ["int32_t"] | ["int"] | ["signed"] | ["signed", "int"] => 0,
+        ["uint32_t"] | ["unsigned"] | ["unsigned", "int"] => 1,
+        ["int64_t"] | ["long"] | ["long", "long"] | ["long", "int"] => 2,
+        ["uint64_t"] | ["unsigned", "long"] | ["unsigned", "long", "long"] | ["size_t"] => 3,
+        ["float"] => 4,
+        ["double"] => 5,
+        _ => return None,
+    })
+}
+
+/// Maximum nesting of unary operators and parentheses accepted by `CValue::evaluate`, which
+/// recurses for each level. The registry never nests more than a few levels.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a, 't, F> {
+    tokens: &'t [&'a str],
+    lookup: &'t F,
+    /// Number of currently nested unary operators and parentheses.
+    depth: usize,
+}
+
+impl<'a, 't, F: Fn(&str) -> Option<CValue>> Parser<'a, 't, F> {
+    fn next(&mut self) -> Option<&'a str> {
+        let (&token, rest) = self.tokens.split_first()?;
+        self.tokens = rest;
+        Some(token)
+    }
+
+    /// Parses binary operators with precedence higher than `min_precedence`.
+    fn expression(&mut self, min_precedence: u8) -> Option<CValue> {
+        let mut value = self.unary()?;
+        while let Some(op) = self.tokens.first().and_then(|t| BinaryOp::from_token(t)) {
+            if op.precedence() <= min_precedence {
+                break;
+            }
+            self.next();
+            let rhs = self.expression(op.precedence())?;
+            value = op.apply(value, rhs)?;
+        }
+        Some(value)
+    }
+
+    fn unary(&mut self) -> Option<CValue> {
+        if self.depth == MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let value = self.operand()?;
+        self.depth -= 1;
+        Some(value)
+    }
+
+    fn operand(&mut self) -> Option<CValue> {
+        match self.next()? {
+            "+" => Some(self.unary()?.promote()),
+            "-" => {
+                let value = self.unary()?.promote();
+                BinaryOp::Sub.apply(CValue::I32(0).convert(value.rank()), value)
+            }
+            "~" => {
+                let value = self.unary()?.promote();
+                value.integer_op(value, |a, _| Some(!a))
+            }
+            "!" => Some(CValue::I32(self.unary()?.is_zero() as i32)),
+            "(" => {
+                let type_len = self.tokens.iter().position(|&t| t == ")");
+                if let Some(rank) = type_len.and_then(|len| cast_rank(&self.tokens[..len])) {
+                    self.tokens = &self.tokens[type_len.unwrap_or(0) + 1..];
+                    return Some(self.unary()?.convert(rank));
+                }
+                let value = self.expression(0)?;
+                if self.next()? == ")" {
+                    Some(value)
+                } else {
+                    None
+                }
+            }
+            token if token.starts_with(|c: char| c.is_ascii_digit() || c == '.') => {
+                CValue::from_literal(&CLiteral::parse(token)?)
+            }
+            token if token.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic()) => {
+                (self.lookup)(token)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_evaluate() {
+        assert_eq!(CValue::evaluate("(~0U)"), Some(CValue::U32(u32::MAX)));
+        assert_eq!(CValue::evaluate("(~1U)"), Some(CValue::U32(u32::MAX - 1)));
+        assert_eq!(CValue::evaluate("(~0ULL)"), Some(CValue::U64(u64::MAX)));
+        assert_eq!(CValue::evaluate("(1 << 5)"), Some(CValue::I32(32)));
+        assert_eq!(CValue::evaluate("-1"), Some(CValue::I32(-1)));
+        assert_eq!(CValue::evaluate("-1U"), Some(CValue::U32(u32::MAX)));
+        assert_eq!(
+            CValue::evaluate("0x80000000"),
+            Some(CValue::U32(0x8000_0000))
+        );
+        assert_eq!(
+            CValue::evaluate("2147483648"),
+            Some(CValue::I64(2147483648))
+        );
+        assert_eq!(CValue::evaluate("1000.0F"), Some(CValue::F32(1000.0)));
+        assert_eq!(CValue::evaluate("1e-3"), Some(CValue::F64(0.001)));
+        assert_eq!(CValue::evaluate("1 + 2 * 3 - 4 / 2"), Some(CValue::I32(5)));
+        assert_eq!(CValue::evaluate("1U - 2"), Some(CValue::U32(u32::MAX)));
+        assert_eq!(
+            CValue::evaluate("0xFFFFFFFFFFFFFFFFULL * 0xFFFFFFFFFFFFFFFFULL"),
+            Some(CValue::U64(1))
+        );
+        assert_eq!(CValue::evaluate("0x7FFFFFFF * 2"), Some(CValue::I32(-2)));
+        assert_eq!(CValue::evaluate("1 == 1 && 2 > 3"), Some(CValue::I32(0)));
+        assert_eq!(
+            CValue::evaluate("((uint32_t)(1)) << 22U | ((uint32_t)(3)) << 12U"),
+            Some(CValue::U32((1 << 22) | (3 << 12)))
+        );
+        assert_eq!(CValue::evaluate("1 / 0"), None);
+        assert_eq!(CValue::evaluate("1 << 32"), None);
+        assert_eq!(CValue::evaluate("VK_FOO"), None);
+        assert_eq!(CValue::evaluate("(1"), None);
+        let nested = "(".repeat(MAX_DEPTH - 1) + "1" + &")".repeat(MAX_DEPTH - 1);
+        assert_eq!(CValue::evaluate(&nested), Some(CValue::I32(1)));
+        let nested = "(".repeat(200_000) + "1" + &")".repeat(200_000);
+        assert_eq!(CValue::evaluate(&nested), None);
+        assert_eq!(CValue::evaluate(&("-".repeat(200_000) + "1")), None);
+        assert_eq!(
+            CValue::evaluate_with("VK_FOO + 1", |name| match name {
+                "VK_FOO" => Some(CValue::U32(2)),
+                _ => None,
+            }),
+            Some(CValue::U32(3))
+        );
+    }
+}
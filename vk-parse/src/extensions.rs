@@ -0,0 +1,492 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+use api::ApiSet;
+use depends::DependsExpr;
+use expr::CValue;
+use types::*;
+
+impl Registry {
+    /// All platforms, from all `<platforms>` blocks.
+    pub fn platforms(&self) -> impl Iterator<Item = &Platform> {
+        self.0.iter().flat_map(|child| {
+            let children: &[Platform] = match *child {
+                RegistryChild::Platforms(ref platforms) => &platforms.children,
+                _ => &[],
+            };
+            children.iter()
+        })
+    }
+
+    /// C macro guarding definitions of the extension, given either by its `protect` attribute
+    /// or by its platform, e.g. `VK_ENABLE_BETA_EXTENSIONS` for provisional extensions.
+    pub fn extension_protect<'a>(&'a self, extension: &'a Extension) -> Option<&'a str> {
+        if let Some(ref protect) = extension.protect {
+            return Some(protect);
+        }
+        let platform = extension.platform.as_deref()?;
+        self.platforms()
+            .find(|p| p.name.as_str() == platform)
+            .map(|p| &*p.protect)
+    }
+
+    /// Extensions released provisionally, whose interfaces may still change.
+    pub fn provisional_extensions(&self) -> impl Iterator<Item = &Extension> {
+        self.extensions().filter(|e| e.provisional)
+    }
+
+    /// Removes provisional extensions, along with types and commands which are only required
+    /// by them, leaving only the stable API. Returns the removed extensions.
+    pub fn remove_provisional_extensions(&mut self) -> Vec<Extension> {
+        let mut removed = Vec::new();
+        for child in &mut self.0 {
+            if let RegistryChild::Extensions(ref mut extensions) = *child {
+                let (provisional, stable) = core::mem::take(&mut extensions.children)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|e| e.provisional);
+                extensions.children = stable;
+                removed.extend(provisional);
+            }
+        }
+
+        self.remove_required_only_by(removed.iter().map(|e| &*e.children));
+        removed
+    }
+
+    /// Removes types and commands required by `removed` children of features or extensions,
+    /// but not by any feature or extension of the registry.
+    pub(crate) fn remove_required_only_by<'a, I>(&mut self, removed: I)
+    where
+        I: IntoIterator<Item = &'a [ExtensionChild]>,
+    {
+        // Names required by the removed children, but not by anything else.
+        let (types, commands) = {
+            let mut kept = RequiredNames::default();
+            for feature in self.features() {
+                kept.add(&feature.children);
+            }
+            for extension in self.extensions() {
+                kept.add(&extension.children);
+            }
+            let mut dropped = RequiredNames::default();
+            for children in removed {
+                dropped.add(children);
+            }
+            let types: BTreeSet<Box<str>> = dropped
+                .types
+                .difference(&kept.types)
+                .map(|&name| name.into())
+                .collect();
+            let commands: BTreeSet<Box<str>> = dropped
+                .commands
+                .difference(&kept.commands)
+                .map(|&name| name.into())
+                .collect();
+            (types, commands)
+        };
+
+        for child in &mut self.0 {
+            match *child {
+                RegistryChild::Types(ref mut children) => {
+                    children.children.retain(|child| match *child {
+                        TypesChild::Type(ref ty) => ty
+                            .declared_name()
+                            .map_or(true, |name| !types.contains(name)),
+                        _ => true,
+                    })
+                }
+                RegistryChild::Commands(ref mut children) => children
+                    .children
+                    .retain(|command| !commands.contains(command.name())),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Extensions for a single platform, along with items they require, see
+/// `Registry::extensions_by_platform`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PlatformGroup<'a> {
+    /// Name of the platform, e.g. `xlib`, or `None` for extensions available on all platforms.
+    pub platform: Option<&'a str>,
+    /// C macro guarding definitions for the platform, e.g. `VK_USE_PLATFORM_XLIB_KHR`.
+    pub protect: Option<&'a str>,
+    pub extensions: Vec<&'a Extension>,
+    /// Names of types required by the extensions, in order of first appearance.
+    pub types: Vec<&'a str>,
+    /// Names of commands required by the extensions, in order of first appearance.
+    pub commands: Vec<&'a str>,
+}
+
+impl Registry {
+    /// Groups extensions by their `platform`. The group of extensions without a platform comes
+    /// first, followed by groups of platforms in the order of `<platforms>`, and then of
+    /// platforms which aren't listed there. Platforms without any extensions are omitted.
+    ///
+    /// Items required by extensions of multiple groups are listed in each of them.
+    pub fn extensions_by_platform(&self) -> Vec<PlatformGroup<'_>> {
+        let group = |platform, protect| PlatformGroup {
+            platform,
+            protect,
+            extensions: Vec::new(),
+            types: Vec::new(),
+            commands: Vec::new(),
+        };
+        let mut groups = Vec::new();
+        groups.push(group(None, None));
+        for platform in self.platforms() {
+            groups.push(group(Some(platform.name.as_str()), Some(&platform.protect)));
+        }
+
+        // Items already added to a group, by index of the group.
+        let mut seen = BTreeSet::new();
+        for extension in self.extensions() {
+            let platform = extension.platform.as_deref();
+            let index = match groups.iter().position(|g| g.platform == platform) {
+                Some(index) => index,
+                None => {
+                    groups.push(group(platform, None));
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            group.extensions.push(extension);
+            for child in &extension.children {
+                let items = match *child {
+                    ExtensionChild::Require { ref items, .. } => items,
+                    _ => continue,
+                };
+                for item in items {
+                    let (is_command, name) = match *item {
+                        InterfaceItem::Type { ref name, .. } => (false, &**name),
+                        InterfaceItem::Command { ref name, .. } => (true, &**name),
+                        _ => continue,
+                    };
+                    if !seen.insert((index, is_command, name)) {
+                        continue;
+                    }
+                    if is_command {
+                        group.commands.push(name);
+                    } else {
+                        group.types.push(name);
+                    }
+                }
+            }
+        }
+        groups.retain(|g| !g.extensions.is_empty());
+        groups
+    }
+}
+
+/// Items of the API guarded by the same C macro, see `Registry::partition_by_platform`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PlatformItems<'a> {
+    /// Name of the platform, e.g. `win32`, if the items are specific to one.
+    pub platform: Option<&'a str>,
+    /// C macro guarding the items, e.g. `VK_USE_PLATFORM_WIN32_KHR`, or `None` for items which
+    /// are platform-independent.
+    pub protect: Option<&'a str>,
+    pub types: Vec<&'a str>,
+    pub commands: Vec<&'a str>,
+    pub enums: Vec<&'a str>,
+}
+
+impl Registry {
+    /// Partitions items required by features and supported extensions by the C macro guarding
+    /// them, given by `Registry::extension_protect`. Items required by features or by any
+    /// extension without such macro are platform-independent, and come first. Groups of
+    /// platforms follow in the order of `<platforms>`, and then groups of other macros, such as
+    /// those only given by `protect` of extensions. Each item is listed once, in the first group
+    /// requiring it, and groups without any items are omitted.
+    pub fn partition_by_platform(&self) -> Vec<PlatformItems<'_>> {
+        let mut groups = Vec::new();
+        groups.push(PlatformItems::default());
+        for platform in self.platforms() {
+            groups.push(PlatformItems {
+                platform: Some(platform.name.as_str()),
+                protect: Some(&platform.protect),
+                ..PlatformItems::default()
+            });
+        }
+
+        let extensions: Vec<_> = self
+            .extensions()
+            .filter(|e| !e.is_disabled())
+            .map(|e| (self.extension_protect(e), e))
+            .collect();
+        let features = self.features().map(|f| (None, None, &f.children));
+        let independent = extensions
+            .iter()
+            .filter(|&&(protect, _)| protect.is_none())
+            .map(|&(_, e)| (None, None, &e.children));
+        let guarded = extensions
+            .iter()
+            .filter_map(|&(protect, e)| Some((Some(protect?), e.platform.as_deref(), &e.children)));
+
+        let mut seen = BTreeSet::new();
+        for (protect, platform, children) in features.chain(independent).chain(guarded) {
+            let index = match groups.iter().position(|g| g.protect == protect) {
+                Some(index) => index,
+                None => {
+                    groups.push(PlatformItems {
+                        platform,
+                        protect,
+                        ..PlatformItems::default()
+                    });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            for child in children {
+                let items = match *child {
+                    ExtensionChild::Require { ref items, .. } => items,
+                    _ => continue,
+                };
+                for item in items {
+                    let (list, name) = match *item {
+                        InterfaceItem::Type { ref name, .. } => (&mut group.types, &**name),
+                        InterfaceItem::Command { ref name, .. } => (&mut group.commands, &**name),
+                        InterfaceItem::Enum(ref e) => (&mut group.enums, &*e.name),
+                        InterfaceItem::Comment(_) => continue,
+                    };
+                    if seen.insert(name) {
+                        list.push(name);
+                    }
+                }
+            }
+        }
+        groups.retain(|g| !g.types.is_empty() || !g.commands.is_empty() || !g.enums.is_empty());
+        groups
+    }
+}
+
+/// Extensions of a single vendor, see `Registry::extensions_by_vendor`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct VendorGroup<'a> {
+    /// Author tag, e.g. `KHR` or `NV`.
+    pub tag: &'a str,
+    /// Author given by `<tags>`, e.g. `Khronos`, if the tag is listed there.
+    pub author: Option<&'a str>,
+    pub extensions: Vec<&'a Extension>,
+    /// Number of the extensions which were promoted, i.e. have `promotedto`.
+    pub promoted: usize,
+}
+
+impl Registry {
+    /// All tags, from all `<tags>` blocks.
+    pub fn tags(&self) -> impl Iterator<Item = &Tag> {
+        self.0.iter().flat_map(|child| {
+            let children: &[Tag] = match *child {
+                RegistryChild::Tags(ref tags) => &tags.children,
+                _ => &[],
+            };
+            children.iter()
+        })
+    }
+
+    /// Groups extensions by author tag, which is taken from the extension name, e.g. `KHR` for
+    /// `VK_KHR_surface`, or from its `author` attribute if the name doesn't contain a known tag.
+    /// Groups are ordered as tags in `<tags>`, followed by tags which aren't listed there.
+    /// Tags without any extensions are omitted.
+    pub fn extensions_by_vendor(&self) -> Vec<VendorGroup<'_>> {
+        let mut groups: Vec<VendorGroup> = self
+            .tags()
+            .map(|tag| VendorGroup {
+                tag: &tag.name,
+                author: Some(&tag.author),
+                extensions: Vec::new(),
+                promoted: 0,
+            })
+            .collect();
+        for extension in self.extensions() {
+            let name_tag = extension.name.split('_').nth(1);
+            let tag = match (name_tag, extension.author.as_deref()) {
+                (Some(tag), _) if groups.iter().any(|g| g.tag == tag) => tag,
+                (_, Some(author)) => author,
+                (Some(tag), None) => tag,
+                (None, None) => continue,
+            };
+            let index = match groups.iter().position(|g| g.tag == tag) {
+                Some(index) => index,
+                None => {
+                    groups.push(VendorGroup {
+                        tag,
+                        author: None,
+                        extensions: Vec::new(),
+                        promoted: 0,
+                    });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            group.extensions.push(extension);
+            if extension.promotedto.is_some() {
+                group.promoted += 1;
+            }
+        }
+        groups.retain(|g| !g.extensions.is_empty());
+        groups
+    }
+}
+
+/// Items an extension requires only when another extension or feature is supported as well,
+/// see `Extension::interactions`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Interaction<'a> {
+    /// Extension the items depend on, e.g. `VK_KHR_device_group` for
+    /// `vkGetDeviceGroupPresentCapabilitiesKHR` of `VK_KHR_swapchain`.
+    pub extension: Option<&'a str>,
+    /// Feature the items depend on, e.g. `VK_VERSION_1_1`.
+    pub feature: Option<&'a str>,
+    /// Features and extensions the items depend on, given by `depends`, which replaced
+    /// `extension` and `feature`.
+    pub depends: Option<&'a DependsExpr>,
+    /// APIs the items are required for, if they aren't required for all APIs.
+    pub api: Option<&'a ApiSet>,
+    pub comment: Option<&'a str>,
+    pub items: &'a [InterfaceItem],
+}
+
+impl Extension {
+    /// `<require>` blocks which only apply along with another extension or feature, given by
+    /// their `extension`, `feature` and `depends` attributes, in order of the extension.
+    pub fn interactions(&self) -> Vec<Interaction<'_>> {
+        self.children
+            .iter()
+            .filter_map(|child| match *child {
+                ExtensionChild::Require {
+                    ref api,
+                    ref extension,
+                    ref feature,
+                    ref depends,
+                    ref comment,
+                    ref items,
+                    ..
+                } if extension.is_some() || feature.is_some() || depends.is_some() => {
+                    Some(Interaction {
+                        extension: extension.as_deref(),
+                        feature: feature.as_deref(),
+                        depends: depends.as_ref(),
+                        api: api.as_ref(),
+                        comment: comment.as_deref(),
+                        items,
+                    })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Constant defined by an extension, such as `VK_KHR_SURFACE_SPEC_VERSION`, along with its
+/// evaluated value, see `Extension::name_constant` and `Extension::spec_version`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ExtensionConstant<'a, T> {
+    pub constant: &'a Enum,
+    pub value: T,
+}
+
+impl Extension {
+    /// Constant giving name of the extension as a string, e.g. `VK_KHR_SURFACE_EXTENSION_NAME`
+    /// with value `VK_KHR_surface`, without quotes. This is the first enum with the
+    /// `_EXTENSION_NAME` suffix and value given by a string literal, required by the extension.
+    pub fn name_constant(&self) -> Option<ExtensionConstant<'_, &str>> {
+        self.required_constants("_EXTENSION_NAME", |value| {
+            value.strip_prefix('"')?.strip_suffix('"')
+        })
+    }
+
+    /// Constant giving version of the extension specification, e.g.
+    /// `VK_KHR_SURFACE_SPEC_VERSION`. This is the first enum with the `_SPEC_VERSION` suffix and
+    /// value given by an integer constant expression, required by the extension.
+    pub fn spec_version(&self) -> Option<ExtensionConstant<'_, u32>> {
+        self.required_constants("_SPEC_VERSION", |value| {
+            let value = CValue::evaluate(value)?.as_u64()?;
+            if value <= u64::from(u32::MAX) {
+                Some(value as u32)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn required_constants<'a, T, F: Fn(&'a str) -> Option<T>>(
+        &'a self,
+        suffix: &str,
+        evaluate: F,
+    ) -> Option<ExtensionConstant<'a, T>> {
+        self.children
+            .iter()
+            .filter_map(|child| match *child {
+                ExtensionChild::Require { ref items, .. } => Some(items),
+                _ => None,
+            })
+            .flatten()
+            .find_map(|item| {
+                let constant = match *item {
+                    InterfaceItem::Enum(ref e) if e.name.ends_with(suffix) => e,
+                    _ => return None,
+                };
+                let value = match constant.spec {
+                    EnumSpec::Value {
+                        ref value,
+                        extends: None,
+                    } => evaluate(value)?,
+                    _ => return None,
+                };
+                Some(ExtensionConstant { constant, value })
+            })
+    }
+}
+
+impl Registry {
+    /// Finds the extension defining constant `name`, which is its `Extension::name_constant`
+    /// or `Extension::spec_version`, e.g. `VK_KHR_surface` for `VK_KHR_SURFACE_SPEC_VERSION`.
+    pub fn constant_extension(&self, name: &str) -> Option<&Extension> {
+        self.extensions().find(|e| {
+            let name_constant = e.name_constant().map(|c| c.constant);
+            let spec_version = e.spec_version().map(|c| c.constant);
+            name_constant
+                .into_iter()
+                .chain(spec_version)
+                .any(|c| &*c.name == name)
+        })
+    }
+}
+
+/// Names of types and commands required by features or extensions.
+#[derive(Default)]
+struct RequiredNames<'a> {
+    types: BTreeSet<&'a str>,
+    commands: BTreeSet<&'a str>,
+}
+
+impl<'a> RequiredNames<'a> {
+    fn add(&mut self, children: &'a [ExtensionChild]) {
+        for child in children {
+            let items = match *child {
+                ExtensionChild::Require { ref items, .. } => items,
+                _ => continue,
+            };
+            for item in items {
+                match *item {
+                    InterfaceItem::Type { ref name, .. } => {
+                        self.types.insert(name);
+                    }
+                    InterfaceItem::Command { ref name, .. } => {
+                        self.commands.insert(name);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use types::*;
+
+/// Value of `VkFormat` along with its description, see `Registry::formats_by_provenance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct FormatValue<'a> {
+    pub value: &'a Enum,
+    /// Description of the format from `<formats>`, if any. Aliases share the description of
+    /// the format they alias.
+    pub format: Option<&'a Format>,
+}
+
+/// Formats introduced by a feature or extension, see `Registry::formats_by_provenance`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct FormatGroup<'a> {
+    /// Name of the feature or extension, e.g. `VK_VERSION_1_1` or `VK_EXT_4444_formats`.
+    pub required_by: &'a str,
+    pub formats: Vec<FormatValue<'a>>,
+}
+
+impl Registry {
+    /// All formats, from all `<formats>` blocks.
+    pub fn formats(&self) -> impl Iterator<Item = &Format> {
+        self.0.iter().flat_map(|child| {
+            let children: &[Format] = match *child {
+                RegistryChild::Formats(ref formats) => &formats.children,
+                _ => &[],
+            };
+            children.iter()
+        })
+    }
+
+    /// Groups values of `VkFormat` by features and extensions introducing them, with groups of
+    /// features first, followed by groups of extensions which aren't disabled. Values of the
+    /// `VkFormat` block are introduced by the first feature requiring the type, while others
+    /// are introduced by each feature or extension defining them, so values promoted to core
+    /// appear in groups of both the extension and the feature. Groups without any values are
+    /// omitted.
+    pub fn formats_by_provenance(&self) -> Vec<FormatGroup<'_>> {
+        let mut descriptions = BTreeMap::new();
+        for format in self.formats() {
+            descriptions.entry(&*format.name).or_insert(format);
+        }
+        let mut values = self
+            .enums_blocks()
+            .filter(|enums| enums.name.as_deref() == Some("VkFormat"))
+            .flat_map(|enums| enums.children.iter())
+            .filter_map(|child| match *child {
+                EnumsChild::Enum(ref e) => Some(format_value(&descriptions, e)),
+                _ => None,
+            });
+
+        let features = self.features().map(|f| (&*f.name, &f.children));
+        let extensions = self
+            .extensions()
+            .filter(|e| !e.is_disabled())
+            .map(|e| (&*e.name, &e.children));
+        let mut groups = Vec::new();
+        for (required_by, children) in features.chain(extensions) {
+            let mut formats = Vec::new();
+            for child in children {
+                let items = match *child {
+                    ExtensionChild::Require { ref items, .. } => items,
+                    _ => continue,
+                };
+                for item in items {
+                    match *item {
+                        InterfaceItem::Type { ref name, .. } if &**name == "VkFormat" => {
+                            formats.extend(&mut values)
+                        }
+                        InterfaceItem::Enum(ref e) if extends_format(e) => {
+                            formats.push(format_value(&descriptions, e))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if !formats.is_empty() {
+                groups.push(FormatGroup {
+                    required_by,
+                    formats,
+                });
+            }
+        }
+        groups
+    }
+}
+
+/// Pairs `e` with its description, or that of the format it aliases.
+fn format_value<'a>(descriptions: &BTreeMap<&str, &'a Format>, e: &'a Enum) -> FormatValue<'a> {
+    let name = match e.spec {
+        EnumSpec::Alias { ref alias, .. } => alias,
+        _ => &e.name,
+    };
+    FormatValue {
+        value: e,
+        format: descriptions.get(&**name).cloned(),
+    }
+}
+
+fn extends_format(e: &Enum) -> bool {
+    let extends = match e.spec {
+        EnumSpec::Offset { ref extends, .. } => Some(extends),
+        EnumSpec::Alias { ref extends, .. }
+        | EnumSpec::Bitpos { ref extends, .. }
+        | EnumSpec::Value { ref extends, .. } => extends.as_ref(),
+        EnumSpec::None => None,
+    };
+    extends.map_or(false, |extends| &**extends == "VkFormat")
+}
@@ -0,0 +1,385 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::convert::TryFrom;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::convert::TryFrom;
+
+use edit::enum_value;
+use params::{ArrayCount, ParamDirection};
+use types::*;
+
+/// Constraints on commands, structs and enums, for API fuzzers and generators of stress tests
+/// synthesizing calls, see `Registry::fuzzing_metadata`. With the `serialize` feature, it can be
+/// exported in any format supported by serde.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct FuzzingMetadata {
+    pub commands: Vec<CommandConstraints>,
+    pub structs: Vec<StructConstraints>,
+    /// Domains of enum types and types of bits of bitmasks.
+    pub enums: Vec<EnumDomain>,
+}
+
+/// Constraints on parameters of a command, see `FuzzingMetadata`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct CommandConstraints {
+    pub name: Box<str>,
+    pub params: Vec<ValueConstraints>,
+}
+
+/// Constraints on members of a struct or union, see `FuzzingMetadata`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct StructConstraints {
+    pub name: Box<str>,
+    pub is_union: bool,
+    /// Value `sType` must have, e.g. `VK_STRUCTURE_TYPE_SUBMIT_INFO`, given by `values` of the
+    /// member. `None` for structs without `sType`.
+    pub s_type: Option<Box<str>>,
+    /// Structs whose `pNext` chain may include the struct, given by `structextends`.
+    pub extends: Vec<Box<str>>,
+    pub members: Vec<ValueConstraints>,
+}
+
+/// Constraints on a command parameter or struct member, see `FuzzingMetadata`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct ValueConstraints {
+    pub name: Box<str>,
+    /// Name of the type without pointers or arrays, e.g. `VkDevice`. Empty if not marked up.
+    pub type_name: Box<str>,
+    pub kind: ValueKind,
+    /// Structured type of the declaration, giving pointers and arrays.
+    pub c_type: Option<CType>,
+    /// Lengths of each dimension of the array, outer first, given by `len`, e.g. `bindingCount`
+    /// or `null-terminated`.
+    pub len: Vec<Box<str>>,
+    /// Names of arrays whose length is given by this value, see
+    /// `CommandDefinition::array_counts`.
+    pub counts: Vec<Box<str>>,
+    /// Whether the value may be null or zero, for the value itself and then for what each
+    /// pointer points to, given by `optional`. Empty if none is optional.
+    pub optional: Vec<bool>,
+    /// Values the member must have, given by `values`, e.g. for `sType`.
+    pub values: Vec<Box<str>>,
+    /// Name of the `EnumDomain` of valid values, i.e. of the enum type, or of the type of bits
+    /// for bitmasks, if the value has one.
+    pub domain: Option<Box<str>>,
+    /// Direction of command parameters, see `CommandDefinition::param_directions`. `None` for
+    /// struct members.
+    pub direction: Option<ParamDirection>,
+}
+
+/// Kind of the type of a value, given by `category` of the type, see `ValueConstraints`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum ValueKind {
+    Handle,
+    Enum,
+    Bitmask,
+    Struct,
+    Union,
+    FuncPointer,
+    /// Any other type, e.g. `uint32_t`, `VkDeviceSize` or a platform type.
+    Other,
+}
+
+/// Valid values of an enum type or type of bits, see `FuzzingMetadata`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct EnumDomain {
+    /// Name of the type, e.g. `VkFormat` or `VkImageUsageFlagBits`.
+    pub name: Box<str>,
+    /// Whether the values are bits, which may be combined.
+    pub is_bitmask: bool,
+    /// Values defined by the `<enums>` block of the type, followed by those added by features
+    /// and extensions, without aliases.
+    pub values: Vec<DomainValue>,
+}
+
+/// Value of `EnumDomain`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct DomainValue {
+    pub name: Box<str>,
+    /// Numeric value, `None` if it can't be determined or doesn't fit in `i64`.
+    pub value: Option<i64>,
+}
+
+impl Registry {
+    /// Collects constraints on parameters of commands and members of structs and unions, and
+    /// valid values of enums and bitmasks, in order of the registry, see `FuzzingMetadata`.
+    ///
+    /// All commands and types are included, so `Registry::retain_api` can be used first to
+    /// obtain constraints for a single API. Command aliases and struct aliases are skipped,
+    /// as are enums of disabled extensions.
+    pub fn fuzzing_metadata(&self) -> FuzzingMetadata {
+        let mut types = BTreeMap::new();
+        for ty in self.types() {
+            if let Some(name) = ty.declared_name() {
+                types.entry(name).or_insert(ty);
+            }
+        }
+        let constraints = Constraints { types };
+
+        let commands = self
+            .commands()
+            .filter_map(|command| match *command {
+                Command::Definition(ref def) => Some(def),
+                Command::Alias { .. } => None,
+            })
+            .map(|def| {
+                let directions = def.param_directions();
+                let counts = def.array_counts();
+                let params = def
+                    .params
+                    .iter()
+                    .zip(directions)
+                    .map(|(param, direction)| {
+                        let definition = &param.definition;
+                        let type_name = definition.type_name.as_deref().unwrap_or_default();
+                        let mut value = constraints.value(
+                            &definition.name,
+                            type_name,
+                            definition.c_type.as_ref(),
+                            param.len.as_deref(),
+                            param.optional.as_deref(),
+                        );
+                        value.counts = counted_by(&counts, param, |p| &p.definition.name);
+                        value.direction = Some(direction);
+                        value
+                    })
+                    .collect();
+                CommandConstraints {
+                    name: def.proto.name.clone(),
+                    params,
+                }
+            })
+            .collect();
+
+        let structs = self
+            .types()
+            .filter_map(|ty| {
+                let is_union = match ty.category.as_deref() {
+                    Some("struct") => false,
+                    Some("union") => true,
+                    _ => return None,
+                };
+                let members = match ty.spec {
+                    TypeSpec::Members(ref members) => members,
+                    _ => return None,
+                };
+                let counts = ty.array_counts();
+                let members: Vec<_> = members
+                    .iter()
+                    .filter_map(|member| match *member {
+                        TypeMember::Definition(ref def) => Some(def),
+                        TypeMember::Comment(_) => None,
+                    })
+                    .map(|def| {
+                        let type_name = def.markup.iter().find_map(|m| match *m {
+                            TypeMemberMarkup::Type(ref name) => Some(&**name),
+                            _ => None,
+                        });
+                        let name = def.name().unwrap_or_default();
+                        let mut value = constraints.value(
+                            name,
+                            type_name.unwrap_or_default(),
+                            def.c_type.as_ref(),
+                            def.len.as_deref(),
+                            def.optional.as_deref(),
+                        );
+                        value.counts = counted_by(&counts, def, |m| m.name().unwrap_or_default());
+                        value.values = list(def.values.as_deref());
+                        value
+                    })
+                    .collect();
+                let s_type = members
+                    .iter()
+                    .find(|m| &*m.name == "sType")
+                    .and_then(|m| m.values.first().cloned());
+                Some(StructConstraints {
+                    name: ty.declared_name()?.into(),
+                    is_union,
+                    s_type,
+                    extends: list(ty.structextends.as_deref()),
+                    members,
+                })
+            })
+            .collect();
+
+        FuzzingMetadata {
+            commands,
+            structs,
+            enums: self.enum_domains(),
+        }
+    }
+
+    /// Domains of all `<enums>` blocks of enum types and types of bits.
+    fn enum_domains(&self) -> Vec<EnumDomain> {
+        let mut domains: Vec<EnumDomain> = self
+            .enums_blocks()
+            .filter_map(|block| {
+                let is_bitmask = match block.kind.as_deref() {
+                    Some("enum") => false,
+                    Some("bitmask") => true,
+                    _ => return None,
+                };
+                let values = block
+                    .children
+                    .iter()
+                    .filter_map(|child| match *child {
+                        EnumsChild::Enum(ref e) => domain_value(e, None),
+                        _ => None,
+                    })
+                    .collect();
+                Some(EnumDomain {
+                    name: block.name.clone()?,
+                    is_bitmask,
+                    values,
+                })
+            })
+            .collect();
+
+        let features = self.features().map(|f| (None, &f.children));
+        let extensions = self
+            .extensions()
+            .filter(|e| !e.is_disabled())
+            .map(|e| (e.number, &e.children));
+        for (number, children) in features.chain(extensions) {
+            for child in children {
+                let items = match *child {
+                    ExtensionChild::Require { ref items, .. } => items,
+                    _ => continue,
+                };
+                for item in items {
+                    let e = match *item {
+                        InterfaceItem::Enum(ref e) => e,
+                        _ => continue,
+                    };
+                    let extends = match e.spec {
+                        EnumSpec::Offset { ref extends, .. } => Some(extends),
+                        EnumSpec::Bitpos { ref extends, .. }
+                        | EnumSpec::Value { ref extends, .. } => extends.as_ref(),
+                        _ => None,
+                    };
+                    let domain =
+                        extends.and_then(|extends| domains.iter_mut().find(|d| d.name == *extends));
+                    if let Some(domain) = domain {
+                        // Enums are defined repeatedly, e.g. by extensions promoted to core.
+                        if !domain.values.iter().any(|v| v.name == e.name) {
+                            domain.values.extend(domain_value(e, number));
+                        }
+                    }
+                }
+            }
+        }
+        domains
+    }
+}
+
+/// Type lookup for `ValueConstraints`.
+struct Constraints<'a> {
+    types: BTreeMap<&'a str, &'a Type>,
+}
+
+impl<'a> Constraints<'a> {
+    fn value(
+        &self,
+        name: &str,
+        type_name: &str,
+        c_type: Option<&CType>,
+        len: Option<&str>,
+        optional: Option<&str>,
+    ) -> ValueConstraints {
+        let ty = self.resolve(type_name);
+        let kind = match ty.and_then(|ty| ty.category.as_deref()) {
+            Some("handle") => ValueKind::Handle,
+            Some("enum") => ValueKind::Enum,
+            Some("bitmask") => ValueKind::Bitmask,
+            Some("struct") => ValueKind::Struct,
+            Some("union") => ValueKind::Union,
+            Some("funcpointer") => ValueKind::FuncPointer,
+            _ => ValueKind::Other,
+        };
+        let domain = match (kind, ty) {
+            (ValueKind::Enum, Some(ty)) => ty.declared_name(),
+            (ValueKind::Bitmask, Some(ty)) => ty.requires.as_deref().or(ty.bitvalues.as_deref()),
+            _ => None,
+        };
+        let optional: Vec<bool> = list(optional).iter().map(|o| &**o == "true").collect();
+        ValueConstraints {
+            name: name.into(),
+            type_name: type_name.into(),
+            kind,
+            c_type: c_type.cloned(),
+            len: list(len),
+            counts: Vec::new(),
+            optional: if optional.contains(&true) {
+                optional
+            } else {
+                Vec::new()
+            },
+            values: Vec::new(),
+            domain: domain.map(Box::from),
+            direction: None,
+        }
+    }
+
+    /// Type with given name, with aliases resolved.
+    fn resolve(&self, name: &str) -> Option<&'a Type> {
+        let mut ty = *self.types.get(name)?;
+        for _ in 0..self.types.len() {
+            ty = match ty.alias {
+                Some(ref alias) => *self.types.get(&**alias)?,
+                None => break,
+            };
+        }
+        Some(ty)
+    }
+}
+
+/// Names of arrays whose length is given by `item`.
+fn counted_by<'a, T, F: Fn(&'a T) -> &'a str>(
+    counts: &[ArrayCount<'a, T>],
+    item: &T,
+    name: F,
+) -> Vec<Box<str>> {
+    counts
+        .iter()
+        .filter(|c| core::ptr::eq(c.count, item))
+        .flat_map(|c| c.arrays.iter().map(|&array| name(array).into()))
+        .collect()
+}
+
+/// Items of a comma-separated attribute.
+fn list(value: Option<&str>) -> Vec<Box<str>> {
+    value
+        .into_iter()
+        .flat_map(|v| v.split(','))
+        .map(|item| item.trim().into())
+        .collect()
+}
+
+/// Value of enum `e`, where `number` is of the extension defining it, `None` for aliases and
+/// references.
+fn domain_value(e: &Enum, number: Option<i64>) -> Option<DomainValue> {
+    match e.spec {
+        EnumSpec::Alias { .. } | EnumSpec::None => None,
+        ref spec => Some(DomainValue {
+            name: e.name.clone(),
+            value: enum_value(spec, number).and_then(|v| i64::try_from(v).ok()),
+        }),
+    }
+}
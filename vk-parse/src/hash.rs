@@ -0,0 +1,445 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec, vec::Vec};
+#[cfg(feature = "digest")]
+use sha2::{Digest, Sha256};
+
+#[cfg(feature = "digest")]
+use inline::InlineVec;
+use types::*;
+use writer::{canonical_xml, CANONICAL_VERSION};
+
+impl Type {
+    /// Hash of the definition, which is stable across platforms and parse options, for
+    /// detecting which types changed between versions of the registry.
+    ///
+    /// Comments, position in the registry, order and original values of attributes, whitespace
+    /// in code, and code which can be regenerated from the parsed declarations don't affect the
+    /// hash.
+    ///
+    /// The hash is computed from the definition written as XML in a canonical form, rather than
+    /// from its representation in this crate, so it also stays the same across versions of
+    /// this crate, unless the canonical form changes, e.g. to include a newly parsed attribute.
+    pub fn semantic_hash(&self) -> u64 {
+        canonical_hash(&RegistryChild::Types(Types {
+            comment: None,
+            children: vec![TypesChild::Type(normalized_type(self))],
+        }))
+    }
+}
+
+impl Command {
+    /// Hash of the definition or alias, see `Type::semantic_hash`.
+    pub fn semantic_hash(&self) -> u64 {
+        canonical_hash(&RegistryChild::Commands(Commands {
+            comment: None,
+            children: vec![normalized_command(self)],
+        }))
+    }
+}
+
+impl Enum {
+    /// Hash of the definition, see `Type::semantic_hash`.
+    pub fn semantic_hash(&self) -> u64 {
+        canonical_hash(&RegistryChild::Enums(Enums {
+            children: vec![EnumsChild::Enum(normalized_enum(self))].into(),
+            ..Enums::default()
+        }))
+    }
+}
+
+impl Feature {
+    /// Hash of the feature, including its items, see `Type::semantic_hash`.
+    pub fn semantic_hash(&self) -> u64 {
+        canonical_hash(&RegistryChild::Feature(normalized_feature(self)))
+    }
+}
+
+impl Extension {
+    /// Hash of the extension, including its items, see `Type::semantic_hash`.
+    pub fn semantic_hash(&self) -> u64 {
+        canonical_hash(&RegistryChild::Extensions(Extensions {
+            comment: None,
+            children: vec![normalized_extension(self)],
+        }))
+    }
+}
+
+#[cfg(feature = "digest")]
+impl Registry {
+    /// SHA-256 of the whole registry, which is stable across platforms, versions of this crate
+    /// and parse options, e.g. for build systems deciding whether bindings need to be
+    /// regenerated. Requires the `digest` feature.
+    ///
+    /// Besides what doesn't affect `Type::semantic_hash`, order of top-level sections and of
+    /// items within them doesn't affect the digest, nor does splitting items among sections.
+    /// Order of items within a type, command, feature or extension does.
+    ///
+    /// Like semantic hashes, the digest is computed from the canonical XML form of each item,
+    /// along with the version of the form.
+    ///
+    /// ```
+    /// let registry = vk_parse::Registry::default();
+    /// let key: String = registry.digest().iter().map(|b| format!("{:02x}", b)).collect();
+    /// assert_eq!(key.len(), 64);
+    /// ```
+    pub fn digest(&self) -> [u8; 32] {
+        let mut digests = Vec::new();
+        for child in &self.0 {
+            match *child {
+                RegistryChild::Comment(_) => (),
+                RegistryChild::VendorIds(ref ids) => {
+                    digests.extend(ids.children.iter().map(|id| {
+                        item_digest(RegistryChild::VendorIds(section(VendorId {
+                            comment: None,
+                            ..id.clone()
+                        })))
+                    }))
+                }
+                RegistryChild::Platforms(ref platforms) => {
+                    digests.extend(platforms.children.iter().map(|platform| {
+                        item_digest(RegistryChild::Platforms(section(Platform {
+                            comment: None,
+                            ..platform.clone()
+                        })))
+                    }))
+                }
+                RegistryChild::Tags(ref tags) => digests.extend(
+                    tags.children
+                        .iter()
+                        .map(|tag| item_digest(RegistryChild::Tags(section(tag.clone())))),
+                ),
+                RegistryChild::Types(ref types) => {
+                    digests.extend(types.children.iter().filter_map(|child| match *child {
+                        TypesChild::Type(ref ty) => Some(item_digest(RegistryChild::Types(
+                            section(TypesChild::Type(normalized_type(ty))),
+                        ))),
+                        _ => None,
+                    }))
+                }
+                RegistryChild::Enums(ref enums) => {
+                    // Blocks are hashed apart from their items, each of which is written within
+                    // a block of the same name.
+                    digests.push(item_digest(RegistryChild::Enums(Enums {
+                        comment: None,
+                        children: InlineVec::new(),
+                        ..enums.clone()
+                    })));
+                    digests.extend(enums.children.iter().filter_map(|child| {
+                        let child = match *child {
+                            EnumsChild::Enum(ref e) => EnumsChild::Enum(normalized_enum(e)),
+                            EnumsChild::Unused(ref unused) => EnumsChild::Unused(Unused {
+                                comment: None,
+                                ..unused.clone()
+                            }),
+                            EnumsChild::Comment(_) => return None,
+                        };
+                        Some(item_digest(RegistryChild::Enums(Enums {
+                            name: enums.name.clone(),
+                            children: vec![child].into(),
+                            ..Enums::default()
+                        })))
+                    }))
+                }
+                RegistryChild::Commands(ref commands) => {
+                    digests.extend(commands.children.iter().map(|command| {
+                        item_digest(RegistryChild::Commands(section(normalized_command(
+                            command,
+                        ))))
+                    }))
+                }
+                RegistryChild::Feature(ref feature) => digests.push(item_digest(
+                    RegistryChild::Feature(normalized_feature(feature)),
+                )),
+                RegistryChild::Extensions(ref extensions) => {
+                    digests.extend(extensions.children.iter().map(|extension| {
+                        item_digest(RegistryChild::Extensions(section(normalized_extension(
+                            extension,
+                        ))))
+                    }))
+                }
+                RegistryChild::Formats(ref formats) => digests.extend(
+                    formats
+                        .children
+                        .iter()
+                        .map(|format| item_digest(RegistryChild::Formats(section(format.clone())))),
+                ),
+                RegistryChild::SpirvExtensions(ref extensions) => {
+                    digests.extend(extensions.children.iter().map(|extension| {
+                        item_digest(RegistryChild::SpirvExtensions(section(extension.clone())))
+                    }))
+                }
+                RegistryChild::SpirvCapabilities(ref capabilities) => {
+                    digests.extend(capabilities.children.iter().map(|capability| {
+                        item_digest(RegistryChild::SpirvCapabilities(section(
+                            capability.clone(),
+                        )))
+                    }))
+                }
+                RegistryChild::Sync(ref sync) => {
+                    digests.extend(sync.children.iter().map(|child| {
+                        let child = match *child {
+                            SyncChild::Access(ref access) => SyncChild::Access(SyncAccess {
+                                comment: None,
+                                ..access.clone()
+                            }),
+                            ref child => child.clone(),
+                        };
+                        item_digest(RegistryChild::Sync(section(child)))
+                    }))
+                }
+                RegistryChild::VideoCodecs(ref codecs) => {
+                    digests.extend(codecs.children.iter().map(|codec| {
+                        item_digest(RegistryChild::VideoCodecs(section(codec.clone())))
+                    }))
+                }
+                RegistryChild::Malformed(ref malformed) => {
+                    digests.push(item_digest(RegistryChild::Malformed(MalformedSection {
+                        text: normalized_code(&malformed.text),
+                        ..malformed.clone()
+                    })))
+                }
+            }
+        }
+        digests.sort_unstable();
+        let mut sha = Sha256::new();
+        sha.update([CANONICAL_VERSION]);
+        for digest in &digests {
+            sha.update(digest);
+        }
+        sha.finalize().into()
+    }
+}
+
+/// 64-bit FNV-1a.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+/// FNV-1a of the section in the canonical form, prefixed by the version of the form.
+fn canonical_hash(section: &RegistryChild) -> u64 {
+    let mut fnv = Fnv1a(0xcbf2_9ce4_8422_2325);
+    fnv.update(&[CANONICAL_VERSION]);
+    fnv.update(canonical_xml(section).as_bytes());
+    fnv.0
+}
+
+/// Section containing only the given item, without a comment.
+#[cfg(feature = "digest")]
+fn section<T>(item: T) -> CommentedChildren<T> {
+    CommentedChildren {
+        comment: None,
+        children: vec![item],
+    }
+}
+
+/// SHA-256 of the section in the canonical form, see `Registry::digest`.
+#[cfg(feature = "digest")]
+fn item_digest(section: RegistryChild) -> [u8; 32] {
+    Sha256::digest(canonical_xml(&section).as_bytes()).into()
+}
+
+/// Code with runs of whitespace replaced by single spaces.
+fn normalized_code(code: &str) -> Box<str> {
+    code.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into_boxed_str()
+}
+
+fn normalized_type(ty: &Type) -> Type {
+    let spec = match ty.spec {
+        TypeSpec::Code(ref code) => TypeSpec::Code(TypeCode {
+            code: normalized_code(&code.code),
+            ..code.clone()
+        }),
+        TypeSpec::Members(ref members) => TypeSpec::Members(
+            members
+                .iter()
+                .filter_map(|member| match *member {
+                    TypeMember::Definition(ref def) => {
+                        Some(TypeMember::Definition(TypeMemberDefinition {
+                            code: if def.c_type.is_some() {
+                                Box::default()
+                            } else {
+                                normalized_code(&def.code)
+                            },
+                            markup: def
+                                .markup
+                                .iter()
+                                .filter(|m| !matches!(**m, TypeMemberMarkup::Comment(_)))
+                                .cloned()
+                                .collect(),
+                            ..def.clone()
+                        }))
+                    }
+                    TypeMember::Comment(_) => None,
+                })
+                .collect(),
+        ),
+        ref spec => spec.clone(),
+    };
+    Type {
+        comment: None,
+        spec,
+        ..ty.clone()
+    }
+}
+
+fn normalized_command(command: &Command) -> Command {
+    match *command {
+        Command::Definition(ref def) => Command::Definition(CommandDefinition {
+            comment: None,
+            code: if is_regenerable(def) {
+                Box::default()
+            } else {
+                normalized_code(&def.code)
+            },
+            params: def
+                .params
+                .iter()
+                .map(|param| CommandParam {
+                    raw: RawAttributes::new(),
+                    ..param.clone()
+                })
+                .collect(),
+            ..def.clone()
+        }),
+        Command::Alias { .. } => command.clone(),
+    }
+}
+
+/// Whether code of the command can be regenerated, so it may be omitted, see
+/// `ParseOptions::omit_code`.
+fn is_regenerable(def: &CommandDefinition) -> bool {
+    def.proto.c_type.is_some() && def.params.iter().all(|p| p.definition.c_type.is_some())
+}
+
+fn normalized_feature(feature: &Feature) -> Feature {
+    Feature {
+        comment: None,
+        children: normalized_children(&feature.children),
+        ..feature.clone()
+    }
+}
+
+fn normalized_extension(extension: &Extension) -> Extension {
+    Extension {
+        comment: None,
+        children: normalized_children(&extension.children),
+        raw: RawAttributes::new(),
+        ..extension.clone()
+    }
+}
+
+fn normalized_enum(e: &Enum) -> Enum {
+    Enum {
+        comment: None,
+        raw: RawAttributes::new(),
+        ..e.clone()
+    }
+}
+
+fn normalized_children(children: &[ExtensionChild]) -> Vec<ExtensionChild> {
+    let items = |items: &[InterfaceItem]| -> Vec<InterfaceItem> {
+        items
+            .iter()
+            .filter_map(|item| match *item {
+                InterfaceItem::Comment(_) => None,
+                InterfaceItem::Type { ref name, .. } => Some(InterfaceItem::Type {
+                    name: name.clone(),
+                    comment: None,
+                }),
+                InterfaceItem::Command { ref name, .. } => Some(InterfaceItem::Command {
+                    name: name.clone(),
+                    comment: None,
+                }),
+                InterfaceItem::Enum(ref e) => Some(InterfaceItem::Enum(normalized_enum(e))),
+            })
+            .collect()
+    };
+    children
+        .iter()
+        .map(|child| match *child {
+            ExtensionChild::Require {
+                ref api,
+                ref profile,
+                ref extension,
+                ref feature,
+                ref depends,
+                items: ref require_items,
+                ..
+            } => ExtensionChild::Require {
+                api: api.clone(),
+                profile: profile.clone(),
+                extension: extension.clone(),
+                feature: feature.clone(),
+                depends: depends.clone(),
+                comment: None,
+                items: items(require_items),
+                raw: RawAttributes::new(),
+            },
+            ExtensionChild::Remove {
+                ref api,
+                ref profile,
+                items: ref remove_items,
+                ..
+            } => ExtensionChild::Remove {
+                api: api.clone(),
+                profile: profile.clone(),
+                comment: None,
+                items: items(remove_items),
+                raw: RawAttributes::new(),
+            },
+            ExtensionChild::Deprecate {
+                ref api,
+                ref profile,
+                ref explanationlink,
+                items: ref deprecate_items,
+                ..
+            } => ExtensionChild::Deprecate {
+                api: api.clone(),
+                profile: profile.clone(),
+                explanationlink: explanationlink.clone(),
+                comment: None,
+                items: items(deprecate_items),
+                raw: RawAttributes::new(),
+            },
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "digest"))]
+mod test {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::String};
+
+    fn hex(digest: [u8; 32]) -> String {
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_digest() {
+        let tag = Tag {
+            name: "KHR".into(),
+            author: "Khronos".into(),
+            contact: "-".into(),
+        };
+        let registry = Registry(vec![RegistryChild::Tags(Tags {
+            comment: Some("Tags".into()),
+            children: vec![tag],
+        })]);
+        // Digests must only change along with the canonical form.
+        assert_eq!(
+            hex(registry.digest()),
+            "fc5548b8dc94681f11f2f80cb3737e91c4189863d304c590e78f6cddfcabf547"
+        );
+    }
+}
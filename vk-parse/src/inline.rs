@@ -0,0 +1,151 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::{fmt, iter, ops, slice};
+use smallvec::{self, SmallVec};
+#[cfg(feature = "std")]
+use std::{fmt, iter, ops, slice};
+
+/// List which keeps up to two items inline, used for fields which rarely have more, such as
+/// `TypeMemberDefinition::markup` or `Enums::children`. Dereferences to a slice, and supports
+/// the usual `Vec` methods for building and editing it.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(Serialize, Deserialize),
+    serde(transparent)
+)]
+pub struct InlineVec<T>(SmallVec<[T; 2]>);
+
+impl<T> InlineVec<T> {
+    pub fn new() -> Self {
+        InlineVec(SmallVec::new())
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.0.push(item);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    pub fn insert(&mut self, index: usize, item: T) {
+        self.0.insert(index, item);
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        self.0.remove(index)
+    }
+
+    pub fn retain<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+        self.0.retain(f);
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        self.0.into_vec()
+    }
+}
+
+impl<T> Default for InlineVec<T> {
+    fn default() -> Self {
+        InlineVec::new()
+    }
+}
+
+/// Formats the same as a `Vec`.
+impl<T: fmt::Debug> fmt::Debug for InlineVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> ops::Deref for InlineVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for InlineVec<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T> From<Vec<T>> for InlineVec<T> {
+    fn from(items: Vec<T>) -> Self {
+        InlineVec(SmallVec::from_vec(items))
+    }
+}
+
+impl<T> iter::FromIterator<T> for InlineVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        InlineVec(iter.into_iter().collect())
+    }
+}
+
+impl<T> Extend<T> for InlineVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for InlineVec<T> {
+    type Item = T;
+    type IntoIter = InlineIntoIter<T>;
+
+    fn into_iter(self) -> InlineIntoIter<T> {
+        InlineIntoIter(self.0.into_iter())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a InlineVec<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> slice::Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut InlineVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> slice::IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Owning iterator over items of an `InlineVec`.
+pub struct InlineIntoIter<T>(smallvec::IntoIter<[T; 2]>);
+
+impl<T> Iterator for InlineIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for InlineIntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.0.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for InlineIntoIter<T> {}
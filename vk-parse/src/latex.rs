@@ -0,0 +1,322 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String};
+
+/// Arithmetic expression of a `latexmath` length, e.g. the `len` of a member given as
+/// `latexmath:[\lceil{\mathit{rasterizationSamples} \over 32}\rceil]`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum LatexExpr {
+    Number(u64),
+    /// Reference to a member, parameter or constant, e.g. `codeSize`, `VK_UUID_SIZE` or
+    /// `pAllocateInfo->descriptorSetCount`.
+    Name(Box<str>),
+    Add(Box<LatexExpr>, Box<LatexExpr>),
+    Sub(Box<LatexExpr>, Box<LatexExpr>),
+    Mul(Box<LatexExpr>, Box<LatexExpr>),
+    /// Exact division, written using `\over` or `/`.
+    Div(Box<LatexExpr>, Box<LatexExpr>),
+    /// `\lceil ... \rceil`
+    Ceil(Box<LatexExpr>),
+    /// `\lfloor ... \rfloor`
+    Floor(Box<LatexExpr>),
+}
+
+impl LatexExpr {
+    /// Parses expression, with or without the surrounding `latexmath:[` and `]`. Returns `None`
+    /// if the expression uses constructs other than those known to appear in the registry, or
+    /// nests groups or chains operators deeper than `MAX_DEPTH`.
+    pub fn parse(text: &str) -> Option<LatexExpr> {
+        let text = text.trim();
+        let text = match text.strip_prefix("latexmath:[") {
+            Some(text) => text.strip_suffix(']')?,
+            None => text,
+        };
+        let mut parser = Parser {
+            rest: text,
+            depth: 0,
+        };
+        let expr = parser.group()?;
+        parser.skip_whitespace();
+        if parser.rest.is_empty() {
+            Some(expr)
+        } else {
+            None
+        }
+    }
+
+    /// Evaluates the expression, using `lookup` to get values of names. Division is exact until
+    /// the result is rounded by `\lceil` or `\lfloor`, or at the end, where it's rounded down
+    /// as in C. Returns `None` if a name is unknown, on division by zero or on overflow.
+    pub fn evaluate<F: Fn(&str) -> Option<u64>>(&self, lookup: F) -> Option<u64> {
+        let (numerator, denominator) = self.evaluate_ratio(&lookup)?;
+        Some(numerator / denominator)
+    }
+
+    /// Evaluates the expression as a fraction.
+    fn evaluate_ratio<F: Fn(&str) -> Option<u64>>(&self, lookup: &F) -> Option<(u64, u64)> {
+        Some(match *self {
+            LatexExpr::Number(value) => (value, 1),
+            LatexExpr::Name(ref name) => (lookup(name)?, 1),
+            LatexExpr::Add(ref a, ref b) | LatexExpr::Sub(ref a, ref b) => {
+                let (a_num, a_den) = a.evaluate_ratio(lookup)?;
+                let (b_num, b_den) = b.evaluate_ratio(lookup)?;
+                let a_num = a_num.checked_mul(b_den)?;
+                let b_num = b_num.checked_mul(a_den)?;
+                let numerator = match *self {
+                    LatexExpr::Add(..) => a_num.checked_add(b_num)?,
+                    _ => a_num.checked_sub(b_num)?,
+                };
+                (numerator, a_den.checked_mul(b_den)?)
+            }
+            LatexExpr::Mul(ref a, ref b) => {
+                let (a_num, a_den) = a.evaluate_ratio(lookup)?;
+                let (b_num, b_den) = b.evaluate_ratio(lookup)?;
+                (a_num.checked_mul(b_num)?, a_den.checked_mul(b_den)?)
+            }
+            LatexExpr::Div(ref a, ref b) => {
+                let (a_num, a_den) = a.evaluate_ratio(lookup)?;
+                let (b_num, b_den) = b.evaluate_ratio(lookup)?;
+                if b_num == 0 {
+                    return None;
+                }
+                (a_num.checked_mul(b_den)?, a_den.checked_mul(b_num)?)
+            }
+            LatexExpr::Ceil(ref a) => {
+                let (numerator, denominator) = a.evaluate_ratio(lookup)?;
+                (numerator.checked_add(denominator - 1)? / denominator, 1)
+            }
+            LatexExpr::Floor(ref a) => {
+                let (numerator, denominator) = a.evaluate_ratio(lookup)?;
+                (numerator / denominator, 1)
+            }
+        })
+    }
+}
+
+/// Maximum nesting of groups, and length of operator chains, accepted by `LatexExpr::parse`.
+/// Both nest the resulting expression, which is parsed and processed recursively. The registry
+/// never uses more than a few levels.
+const MAX_DEPTH: usize = 64;
+
+struct Parser<'a> {
+    rest: &'a str,
+    /// Number of currently open groups.
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    /// Consumes `token` if the remaining text starts with it. Commands such as `\over` only
+    /// match whole words.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if !self.rest.starts_with(token) {
+            return false;
+        }
+        let after = &self.rest[token.len()..];
+        if token.starts_with('\\') && after.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return false;
+        }
+        self.rest = after;
+        true
+    }
+
+    /// Contents of a group, in which `\over` divides everything before it by everything after.
+    fn group(&mut self) -> Option<LatexExpr> {
+        if self.depth == MAX_DEPTH {
+            return None;
+        }
+        self.depth += 1;
+        let numerator = self.sum()?;
+        let expr = if self.eat("\\over") {
+            let denominator = self.sum()?;
+            LatexExpr::Div(Box::new(numerator), Box::new(denominator))
+        } else {
+            numerator
+        };
+        self.depth -= 1;
+        Some(expr)
+    }
+
+    fn sum(&mut self) -> Option<LatexExpr> {
+        let mut expr = self.product()?;
+        for _ in 0..=MAX_DEPTH {
+            if self.eat("+") {
+                expr = LatexExpr::Add(Box::new(expr), Box::new(self.product()?));
+            } else if self.eat("-") {
+                expr = LatexExpr::Sub(Box::new(expr), Box::new(self.product()?));
+            } else {
+                return Some(expr);
+            }
+        }
+        None
+    }
+
+    fn product(&mut self) -> Option<LatexExpr> {
+        let mut expr = self.factor()?;
+        for _ in 0..=MAX_DEPTH {
+            if self.eat("\\times") || self.eat("\\cdot") || self.eat("*") {
+                expr = LatexExpr::Mul(Box::new(expr), Box::new(self.factor()?));
+            } else if self.eat("/") {
+                expr = LatexExpr::Div(Box::new(expr), Box::new(self.factor()?));
+            } else {
+                return Some(expr);
+            }
+        }
+        None
+    }
+
+    fn factor(&mut self) -> Option<LatexExpr> {
+        if self.eat("(") {
+            let expr = self.group()?;
+            return if self.eat(")") { Some(expr) } else { None };
+        }
+        if self.eat("{") {
+            let expr = self.group()?;
+            return if self.eat("}") { Some(expr) } else { None };
+        }
+        if self.eat("\\lceil") {
+            let expr = self.group()?;
+            return if self.eat("\\rceil") {
+                Some(LatexExpr::Ceil(Box::new(expr)))
+            } else {
+                None
+            };
+        }
+        if self.eat("\\lfloor") {
+            let expr = self.group()?;
+            return if self.eat("\\rfloor") {
+                Some(LatexExpr::Floor(Box::new(expr)))
+            } else {
+                None
+            };
+        }
+        for font in &["\\mathit", "\\textrm", "\\mathtt", "\\mathrm", "\\texttt"] {
+            if self.eat(font) {
+                if !self.eat("{") {
+                    return None;
+                }
+                return self.name_until_brace();
+            }
+        }
+
+        self.skip_whitespace();
+        let len = self
+            .rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(self.rest.len());
+        if len == 0 {
+            return None;
+        }
+        let (token, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            token.parse().ok().map(LatexExpr::Number)
+        } else {
+            Some(LatexExpr::Name(token.into()))
+        }
+    }
+
+    /// Name within a font command, up to the closing brace, with `\_` and `{\rightarrow}`
+    /// replaced by `_` and `->`.
+    fn name_until_brace(&mut self) -> Option<LatexExpr> {
+        let mut name = String::new();
+        loop {
+            if self.rest.starts_with('}') {
+                self.rest = &self.rest[1..];
+                break;
+            } else if let Some(rest) = self.rest.strip_prefix("{\\rightarrow}") {
+                name.push_str("->");
+                self.rest = rest;
+            } else if let Some(rest) = self.rest.strip_prefix("\\rightarrow") {
+                name.push_str("->");
+                self.rest = rest;
+            } else if let Some(rest) = self.rest.strip_prefix("\\_") {
+                name.push('_');
+                self.rest = rest;
+            } else {
+                let c = self.rest.chars().next()?;
+                if !c.is_ascii_alphanumeric() && c != '_' && c != '.' {
+                    return None;
+                }
+                name.push(c);
+                self.rest = &self.rest[1..];
+            }
+        }
+        if name.is_empty() {
+            None
+        } else {
+            Some(LatexExpr::Name(name.into_boxed_str()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn name(name: &str) -> Box<LatexExpr> {
+        Box::new(LatexExpr::Name(name.into()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            LatexExpr::parse(r"latexmath:[\lceil{\mathit{rasterizationSamples} \over 32}\rceil]"),
+            Some(LatexExpr::Ceil(Box::new(LatexExpr::Div(
+                name("rasterizationSamples"),
+                Box::new(LatexExpr::Number(32))
+            ))))
+        );
+        assert_eq!(
+            LatexExpr::parse(r"latexmath:[2 \times \mathtt{VK\_UUID\_SIZE}]"),
+            Some(LatexExpr::Mul(
+                Box::new(LatexExpr::Number(2)),
+                name("VK_UUID_SIZE")
+            ))
+        );
+        assert_eq!(
+            LatexExpr::parse(r"latexmath:[\textrm{pAllocateInfo{\rightarrow}descriptorSetCount}]"),
+            Some(LatexExpr::Name("pAllocateInfo->descriptorSetCount".into()))
+        );
+        assert_eq!(LatexExpr::parse(r"latexmath:[\sqrt{x}]"), None);
+
+        let nested = "{".repeat(MAX_DEPTH) + "1" + &"}".repeat(MAX_DEPTH);
+        assert_eq!(LatexExpr::parse(&nested), None);
+        let nested = "(".repeat(MAX_DEPTH - 1) + "1" + &")".repeat(MAX_DEPTH - 1);
+        assert_eq!(LatexExpr::parse(&nested), Some(LatexExpr::Number(1)));
+        let nested = r"\lceil".repeat(200_000) + "1" + &r"\rceil".repeat(200_000);
+        assert_eq!(LatexExpr::parse(&nested), None);
+        assert_eq!(
+            LatexExpr::parse(&(String::from("1") + &" + 1".repeat(200_000))),
+            None
+        );
+        assert_eq!(
+            LatexExpr::parse(&(String::from("2") + &" * 2".repeat(200_000))),
+            None
+        );
+        assert!(LatexExpr::parse(&(String::from("1") + &" + 1".repeat(MAX_DEPTH))).is_some());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let lookup = |name: &str| match name {
+            "rasterizationSamples" => Some(33),
+            "codeSize" => Some(10),
+            _ => None,
+        };
+        let eval = |text| LatexExpr::parse(text).unwrap().evaluate(lookup);
+        assert_eq!(
+            eval(r"\lceil{\mathit{rasterizationSamples} \over 32}\rceil"),
+            Some(2)
+        );
+        assert_eq!(eval(r"\textrm{codeSize} \over 4"), Some(2));
+        assert_eq!(eval(r"(\mathit{codeSize} + 2) / 4 \times 2"), Some(6));
+        assert_eq!(eval(r"\mathit{unknown} \over 4"), None);
+        assert_eq!(eval(r"4 \over {2 - 2}"), None);
+    }
+}
@@ -3,7 +3,27 @@
 //! The entry point into this library is `parse_file_as_vkxml`, which will
 //! return a `Registry` object. This object contains all the information contained
 //! in the Vulkan API registry.
+//!
+//! The `std` feature is enabled by default. Without it, the crate is `no_std` and only needs
+//! the `alloc` crate. The parser then reads documents through the minimal `Read` trait, which
+//! is implemented for byte slices, using a built-in XML reader instead of `xml-rs`, which only
+//! supports documents encoded as UTF-8. Parsing files and events of `xml-rs` readers needs
+//! `std`.
+//!
+//! The parser handles the structure shared by Khronos registries separately from sections
+//! specific to Vulkan, so that registries of other APIs can be parsed as well, see
+//! `RegistryDialect`. Sections specific to those are left to hooks, see
+//! `ParseOptions::element_hook`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// Char array patterns need a newer compiler than the MSRV, so closures are used instead.
+#![allow(clippy::manual_pattern_char_comparison)]
 
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+extern crate smallvec;
+#[cfg(feature = "std")]
 extern crate xml;
 
 #[cfg(feature = "serialize")]
@@ -15,17 +35,148 @@ extern crate serde;
 #[cfg(feature = "vkxml-convert")]
 extern crate vkxml;
 
+#[cfg(feature = "rayon")]
+extern crate rayon_dep as rayon;
+
+#[cfg(feature = "gzip")]
+extern crate flate2;
+#[cfg(feature = "zstd")]
+extern crate zstd_dep as zstd;
+
+#[cfg(feature = "tokio")]
+extern crate tokio_dep as tokio;
+
+#[cfg(feature = "diagnostics")]
+extern crate annotate_snippets;
+
+#[cfg(feature = "digest")]
+extern crate sha2;
+
 #[macro_use]
 mod parse;
-mod c;
+mod api;
+#[cfg(feature = "tokio")]
+mod async_io;
+mod bitmasks;
+pub mod c;
+#[cfg(feature = "std")]
+mod cache;
+mod catalog;
+mod changelog;
+mod commands;
+mod comments;
 #[cfg(feature = "vkxml-convert")]
 mod convert;
+mod depends;
+mod deprecation;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+mod diff;
+mod edit;
+mod enums;
+mod expr;
+mod extensions;
+mod formats;
+mod fuzzing;
+mod hash;
+mod inline;
+mod latex;
+#[cfg(feature = "std")]
+mod lossy;
+mod merge;
+mod options;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod params;
+mod pretty;
+#[cfg(feature = "std")]
+mod promotions;
+#[cfg(not(feature = "std"))]
+mod reader;
+mod retain;
+#[cfg(feature = "std")]
+mod roundtrip;
+#[cfg(feature = "std")]
+mod sections;
+#[cfg(feature = "std")]
+mod set;
+#[cfg(feature = "std")]
+mod shared;
+mod subset;
+mod timeline;
 mod types;
+mod validation;
+mod version;
+mod video;
+mod visit;
+mod vulkan;
+mod writer;
 
+pub use api::{
+    Api, ApiDifferences, ApiItems, ApiSet, ApiVariantReport, ExtensionSupport, VariantDifferences,
+};
+#[cfg(feature = "tokio")]
+pub use async_io::{parse_stream_async, parse_stream_async_with_options, ParseFuture};
+pub use bitmasks::Bitmask;
+#[cfg(feature = "std")]
+pub use cache::RegistryCache;
+pub use catalog::{FeatureStruct, LimitType, PropertyMember, PropertyStruct};
+pub use changelog::changelog;
+pub use commands::{
+    CommandAliasGroup, CommandName, HandleLifetime, LoaderEntry, LoaderGroup, LoaderLevel,
+    QueueKind,
+};
+pub use comments::CommentGroup;
 #[cfg(feature = "vkxml-convert")]
 pub use convert::parse_file_as_vkxml;
 #[cfg(feature = "vkxml-convert")]
 pub use convert::parse_stream_as_vkxml;
-pub use parse::parse_file;
-pub use parse::parse_stream;
+pub use depends::DependsExpr;
+pub use deprecation::{DeprecatedExtension, DeprecatedItem, Deprecation, Deprecations};
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::{parse_str_with_diagnostics, Diagnostic, FatalDiagnostic};
+pub use diff::{diff, Change, RegistryDiff, TypeDiff};
+pub use edit::EditError;
+pub use enums::EnumerantName;
+pub use expr::CValue;
+pub use extensions::{ExtensionConstant, Interaction, PlatformGroup, PlatformItems, VendorGroup};
+pub use formats::{FormatGroup, FormatValue};
+pub use fuzzing::{
+    CommandConstraints, DomainValue, EnumDomain, FuzzingMetadata, StructConstraints,
+    ValueConstraints, ValueKind,
+};
+pub use inline::{InlineIntoIter, InlineVec};
+pub use latex::LatexExpr;
+#[cfg(feature = "std")]
+pub use lossy::parse_stream_lossy;
+pub use merge::{MergeConflict, MergeItemKind, MergePolicy};
+pub use options::{ParseOptions, RegistryDialect, RegistrySection, ResourceLimit, UnknownElement};
+#[cfg(feature = "rayon")]
+pub use parallel::{parse_bytes_parallel, parse_file_parallel, parse_str_parallel};
+pub use params::{ArrayCount, ParamDirection, TwoCallEnumeration};
+pub use parse::{
+    parse_bytes, parse_bytes_with_options, parse_str, parse_str_with_options, parse_stream,
+    parse_stream_with_options,
+};
+#[cfg(feature = "std")]
+pub use parse::{
+    parse_file, parse_file_with_options, parse_registry_from_events, parse_section_from_events,
+};
+#[cfg(not(feature = "std"))]
+pub use reader::{OwnedAttribute, OwnedName, Read, XmlEvent};
+pub use retain::DanglingReference;
+#[cfg(feature = "std")]
+pub use roundtrip::RoundtripMismatch;
+#[cfg(feature = "std")]
+pub use set::{parse_dir, parse_dir_with_options, RegistryFile, RegistrySet};
+#[cfg(feature = "std")]
+pub use shared::SharedRegistry;
+pub use timeline::{Timeline, TimelineEntry};
 pub use types::*;
+pub use validation::ValidationOptions;
+pub use version::{ApiVersion, PackedVersion};
+pub use video::{VideoCoding, VideoGroup, VideoOperation};
+pub use visit::{walk, RegistryVisitor};
+pub use writer::{AttributeOrder, CommentPlacement, IndentStyle, WriteOptions};
+#[cfg(feature = "std")]
+pub use xml::reader::{Events, ParserConfig, ParserConfig2, XmlEvent};
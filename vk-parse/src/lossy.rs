@@ -0,0 +1,94 @@
+use std::io::Read;
+
+use options::ParseOptions;
+use parse::parse_bytes_with_options;
+use sections::{parse_section, split_sections_lossy};
+use types::*;
+
+/// Parses the Vulkan XML file from stream into a Rust object, making the best of malformed
+/// documents instead of failing, e.g. for tools inspecting registries while they are edited.
+///
+/// Each top-level section of the registry is read on its own. Sections which can't be read,
+/// e.g. because of malformed XML or exceeded resource limits, are kept as
+/// `RegistryChild::Malformed` placeholders holding their source text, with the problem reported
+/// as `Error::ReadError` along with any errors found before it, and parsing continues with the
+/// next section. Other errors are returned as usual, even with `ParseOptions::strict`.
+///
+/// If the top-level structure of the document can't be determined, or
+/// `ParseOptions::max_elements` is set, the document is parsed as a whole, and only what was
+/// read before the first problem is returned.
+pub fn parse_stream_lossy<T: Read>(
+    mut stream: T,
+    options: &ParseOptions,
+) -> (Registry, Vec<Error>) {
+    let mut options = options.clone().strict(false);
+    options.lossy = true;
+
+    let mut errors = Vec::new();
+    let mut bytes = Vec::new();
+    if let Err(e) = stream.read_to_end(&mut bytes) {
+        // Whatever was read before the failure is still parsed.
+        errors.push(Error::ReadError {
+            xpath: String::new(),
+            desc: e.to_string(),
+        });
+    }
+
+    let sections = match split_sections_lossy(&bytes) {
+        Some(v) if options.max_elements.is_none() => v,
+        _ => {
+            let (registry, document_errors) = match parse_bytes_with_options(&bytes, &options) {
+                Ok(v) => v,
+                Err(e) => (Registry::default(), vec![fatal_error(e)]),
+            };
+            errors.extend(document_errors);
+            return (registry, errors);
+        }
+    };
+
+    let mut registry = Registry(Vec::new());
+    for section in sections {
+        if !options.parses_section(section.name) {
+            continue;
+        }
+        let (children, section_errors) = match parse_section(&bytes, &section, &options) {
+            Ok((section_registry, section_errors)) => (section_registry.0, section_errors),
+            Err(e) => (Vec::new(), vec![fatal_error(e)]),
+        };
+        let read_error = section_errors.iter().find_map(|e| match *e {
+            Error::ReadError { ref desc, .. } => Some(desc.clone()),
+            _ => None,
+        });
+        match read_error {
+            Some(desc) => registry.0.push(RegistryChild::Malformed(MalformedSection {
+                name: section.name.into(),
+                text: String::from_utf8_lossy(&bytes[section.range.clone()]).into(),
+                desc: desc.into_boxed_str(),
+            })),
+            None => registry.0.extend(children),
+        }
+        errors.extend(section_errors);
+    }
+    (registry, errors)
+}
+
+/// Reports error which would make regular parsing fail.
+fn fatal_error(e: FatalError) -> Error {
+    match e {
+        FatalError::MissingRegistryElement => Error::MissingElement {
+            xpath: String::new(),
+            name: String::from("registry"),
+        },
+        FatalError::LimitExceeded { limit, xpath } => Error::ReadError {
+            xpath,
+            desc: format!("resource limit {:?} exceeded", limit),
+        },
+        FatalError::IoError(e) => Error::ReadError {
+            xpath: String::new(),
+            desc: e.to_string(),
+        },
+        FatalError::Strict(_) => Error::Internal {
+            desc: "Strict mode is disabled for lossy parsing.",
+        },
+    }
+}
@@ -0,0 +1,365 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+use api::ApiSet;
+use types::*;
+
+/// How `Registry::merge` resolves conflicts, i.e. items with the same name but different
+/// definitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MergePolicy {
+    /// Keep the item of the registry being merged into and drop the other one.
+    KeepExisting,
+    /// Replace the item of the registry being merged into, in place.
+    Replace,
+    /// Keep both items, with the other one added after existing items.
+    KeepBoth,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        MergePolicy::KeepExisting
+    }
+}
+
+/// Kind of item identified by name, as reported in `MergeConflict`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum MergeItemKind {
+    VendorId,
+    Platform,
+    Tag,
+    /// Type, identified by its name along with its `api`.
+    Type,
+    /// `<enums>` block.
+    Enums,
+    /// Command, identified by its name along with its `api`.
+    Command,
+    Feature,
+    Extension,
+    Format,
+    SpirvExtension,
+    SpirvCapability,
+    /// Pipeline stage, access flag or pipeline of the `<sync>` section.
+    Sync,
+    VideoCodec,
+}
+
+/// Items of the same kind and name, which are defined differently in merged registries.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct MergeConflict {
+    pub kind: MergeItemKind,
+    pub name: Box<str>,
+    /// `api` of the conflicting types or commands, e.g. `vulkansc`. `None` for other kinds of
+    /// items.
+    pub api: Option<ApiSet>,
+}
+
+impl Registry {
+    /// Adds items of `other` to this registry. Items which are already present with the same
+    /// definition are skipped, while those with the same name but a different definition are
+    /// resolved according to `policy` and reported as conflicts.
+    ///
+    /// Items are added to new blocks at the end of the registry, e.g. types missing from this
+    /// registry are added in a `<types>` block containing only them, along with its comments.
+    /// Enums of features and extensions are part of those, so they aren't merged separately.
+    pub fn merge(&mut self, other: Registry, policy: MergePolicy) -> Vec<MergeConflict> {
+        let mut index = BTreeMap::new();
+        for (i, child) in self.0.iter().enumerate() {
+            let index = &mut index;
+            match *child {
+                RegistryChild::VendorIds(ref c) => register(index, MergeItemKind::VendorId, i, c),
+                RegistryChild::Platforms(ref c) => register(index, MergeItemKind::Platform, i, c),
+                RegistryChild::Tags(ref c) => register(index, MergeItemKind::Tag, i, c),
+                RegistryChild::Types(ref c) => register(index, MergeItemKind::Type, i, c),
+                RegistryChild::Commands(ref c) => register(index, MergeItemKind::Command, i, c),
+                RegistryChild::Extensions(ref c) => register(index, MergeItemKind::Extension, i, c),
+                RegistryChild::Formats(ref c) => register(index, MergeItemKind::Format, i, c),
+                RegistryChild::SpirvExtensions(ref c) => {
+                    register(index, MergeItemKind::SpirvExtension, i, c)
+                }
+                RegistryChild::SpirvCapabilities(ref c) => {
+                    register(index, MergeItemKind::SpirvCapability, i, c)
+                }
+                RegistryChild::Sync(ref c) => register(index, MergeItemKind::Sync, i, c),
+                RegistryChild::VideoCodecs(ref c) => {
+                    register(index, MergeItemKind::VideoCodec, i, c)
+                }
+                RegistryChild::Enums(ref e) => register_single(index, MergeItemKind::Enums, i, e),
+                RegistryChild::Feature(ref f) => {
+                    register_single(index, MergeItemKind::Feature, i, f)
+                }
+                _ => {}
+            }
+        }
+
+        let mut merger = Merger {
+            registry: self,
+            index,
+            conflicts: Vec::new(),
+            policy,
+        };
+        for child in other.0 {
+            match child {
+                RegistryChild::VendorIds(c) => {
+                    merger.merge_container(MergeItemKind::VendorId, c, RegistryChild::VendorIds)
+                }
+                RegistryChild::Platforms(c) => {
+                    merger.merge_container(MergeItemKind::Platform, c, RegistryChild::Platforms)
+                }
+                RegistryChild::Tags(c) => {
+                    merger.merge_container(MergeItemKind::Tag, c, RegistryChild::Tags)
+                }
+                RegistryChild::Types(c) => {
+                    merger.merge_container(MergeItemKind::Type, c, RegistryChild::Types)
+                }
+                RegistryChild::Commands(c) => {
+                    merger.merge_container(MergeItemKind::Command, c, RegistryChild::Commands)
+                }
+                RegistryChild::Extensions(c) => {
+                    merger.merge_container(MergeItemKind::Extension, c, RegistryChild::Extensions)
+                }
+                RegistryChild::Formats(c) => {
+                    merger.merge_container(MergeItemKind::Format, c, RegistryChild::Formats)
+                }
+                RegistryChild::SpirvExtensions(c) => merger.merge_container(
+                    MergeItemKind::SpirvExtension,
+                    c,
+                    RegistryChild::SpirvExtensions,
+                ),
+                RegistryChild::SpirvCapabilities(c) => merger.merge_container(
+                    MergeItemKind::SpirvCapability,
+                    c,
+                    RegistryChild::SpirvCapabilities,
+                ),
+                RegistryChild::Sync(c) => {
+                    merger.merge_container(MergeItemKind::Sync, c, RegistryChild::Sync)
+                }
+                RegistryChild::VideoCodecs(c) => {
+                    merger.merge_container(MergeItemKind::VideoCodec, c, RegistryChild::VideoCodecs)
+                }
+                RegistryChild::Enums(e) => {
+                    merger.merge_single(MergeItemKind::Enums, e, RegistryChild::Enums)
+                }
+                RegistryChild::Feature(f) => {
+                    merger.merge_single(MergeItemKind::Feature, f, RegistryChild::Feature)
+                }
+                child => {
+                    if !merger.registry.0.contains(&child) {
+                        merger.registry.0.push(child);
+                    }
+                }
+            }
+        }
+        merger.conflicts
+    }
+}
+
+type Key = (MergeItemKind, Box<str>, Option<ApiSet>);
+
+/// Position of an item, as index of the registry child and index of the item within it, if
+/// the child is a block of items.
+type Location = (usize, Option<usize>);
+
+/// Item which can be merged, identified by name.
+trait MergeItem: PartialEq + Sized {
+    /// Name and `api` of the item, `None` for comments.
+    fn name(&self) -> Option<(&str, Option<&ApiSet>)>;
+
+    /// Item at given location, if `child` contains one of this type.
+    fn get_mut(child: &mut RegistryChild, index: Option<usize>) -> Option<&mut Self>;
+
+    fn key(&self, kind: MergeItemKind) -> Option<Key> {
+        self.name()
+            .map(|(name, api)| (kind, name.into(), api.cloned()))
+    }
+}
+
+macro_rules! impl_merge_item {
+    ($ty:ty, $($variant:ident)|+, |$item:ident| $name:expr) => {
+        impl MergeItem for $ty {
+            fn name(&self) -> Option<(&str, Option<&ApiSet>)> {
+                let $item = self;
+                Some((&$name, None))
+            }
+
+            fn get_mut(child: &mut RegistryChild, index: Option<usize>) -> Option<&mut Self> {
+                match *child {
+                    $(RegistryChild::$variant(ref mut c))|+ => c.children.get_mut(index?),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_merge_item!(VendorId, VendorIds, |v| v.name);
+impl_merge_item!(Platform, Platforms, |p| p.name.as_str());
+impl_merge_item!(Tag, Tags, |t| t.name);
+impl_merge_item!(Extension, Extensions, |e| e.name);
+impl_merge_item!(Format, Formats, |f| f.name);
+// Used for both SPIR-V extensions and capabilities, as they have the same type.
+impl_merge_item!(SpirvExtOrCap, SpirvExtensions | SpirvCapabilities, |s| s
+    .name);
+impl_merge_item!(SyncChild, Sync, |s| s.name());
+impl_merge_item!(VideoCodec, VideoCodecs, |c| c.name);
+
+impl MergeItem for Command {
+    fn name(&self) -> Option<(&str, Option<&ApiSet>)> {
+        Some((self.name(), self.api()))
+    }
+
+    fn get_mut(child: &mut RegistryChild, index: Option<usize>) -> Option<&mut Self> {
+        match *child {
+            RegistryChild::Commands(ref mut c) => c.children.get_mut(index?),
+            _ => None,
+        }
+    }
+}
+
+impl MergeItem for TypesChild {
+    fn name(&self) -> Option<(&str, Option<&ApiSet>)> {
+        match *self {
+            TypesChild::Type(ref ty) => ty.declared_name().map(|name| (name, ty.api.as_ref())),
+            _ => None,
+        }
+    }
+
+    fn get_mut(child: &mut RegistryChild, index: Option<usize>) -> Option<&mut Self> {
+        match *child {
+            RegistryChild::Types(ref mut c) => c.children.get_mut(index?),
+            _ => None,
+        }
+    }
+}
+
+impl MergeItem for Enums {
+    fn name(&self) -> Option<(&str, Option<&ApiSet>)> {
+        self.name.as_deref().map(|name| (name, None))
+    }
+
+    fn get_mut(child: &mut RegistryChild, index: Option<usize>) -> Option<&mut Self> {
+        match *child {
+            RegistryChild::Enums(ref mut e) if index.is_none() => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl MergeItem for Feature {
+    fn name(&self) -> Option<(&str, Option<&ApiSet>)> {
+        Some((&self.name, None))
+    }
+
+    fn get_mut(child: &mut RegistryChild, index: Option<usize>) -> Option<&mut Self> {
+        match *child {
+            RegistryChild::Feature(ref mut f) if index.is_none() => Some(f),
+            _ => None,
+        }
+    }
+}
+
+fn register<T: MergeItem>(
+    index: &mut BTreeMap<Key, Location>,
+    kind: MergeItemKind,
+    child: usize,
+    items: &CommentedChildren<T>,
+) {
+    for (i, item) in items.children.iter().enumerate() {
+        if let Some(key) = item.key(kind) {
+            index.entry(key).or_insert((child, Some(i)));
+        }
+    }
+}
+
+fn register_single<T: MergeItem>(
+    index: &mut BTreeMap<Key, Location>,
+    kind: MergeItemKind,
+    child: usize,
+    item: &T,
+) {
+    if let Some(key) = item.key(kind) {
+        index.entry(key).or_insert((child, None));
+    }
+}
+
+struct Merger<'a> {
+    registry: &'a mut Registry,
+    /// Location of the first item with given key.
+    index: BTreeMap<Key, Location>,
+    conflicts: Vec<MergeConflict>,
+    policy: MergePolicy,
+}
+
+impl<'a> Merger<'a> {
+    /// Resolves items against existing ones and returns those which should be added.
+    fn merge_items<T: MergeItem, I: IntoIterator<Item = T>>(
+        &mut self,
+        kind: MergeItemKind,
+        items: I,
+    ) -> Vec<T> {
+        let mut added = Vec::new();
+        for item in items {
+            let key = match item.key(kind) {
+                Some(key) => key,
+                None => {
+                    added.push(item);
+                    continue;
+                }
+            };
+            let existing = match self.index.get(&key) {
+                Some(&(child, index)) => T::get_mut(&mut self.registry.0[child], index),
+                None => None,
+            };
+            match existing {
+                None => added.push(item),
+                Some(existing) if *existing == item => {}
+                Some(existing) => {
+                    let (kind, name, api) = key;
+                    self.conflicts.push(MergeConflict { kind, name, api });
+                    match self.policy {
+                        MergePolicy::KeepExisting => {}
+                        MergePolicy::Replace => *existing = item,
+                        MergePolicy::KeepBoth => added.push(item),
+                    }
+                }
+            }
+        }
+        added
+    }
+
+    fn merge_container<T: MergeItem>(
+        &mut self,
+        kind: MergeItemKind,
+        items: CommentedChildren<T>,
+        wrap: fn(CommentedChildren<T>) -> RegistryChild,
+    ) {
+        let added = self.merge_items(kind, items.children);
+        // Comments alone aren't worth a new block.
+        if added.iter().all(|item| item.name().is_none()) {
+            return;
+        }
+        let container = CommentedChildren {
+            comment: items.comment,
+            children: added,
+        };
+        register(&mut self.index, kind, self.registry.0.len(), &container);
+        self.registry.0.push(wrap(container));
+    }
+
+    fn merge_single<T: MergeItem>(
+        &mut self,
+        kind: MergeItemKind,
+        item: T,
+        wrap: fn(T) -> RegistryChild,
+    ) {
+        if let Some(item) = self.merge_items(kind, Some(item)).pop() {
+            register_single(&mut self.index, kind, self.registry.0.len(), &item);
+            self.registry.0.push(wrap(item));
+        }
+    }
+}
@@ -0,0 +1,340 @@
+#[cfg(not(feature = "std"))]
+use alloc::{string::String, sync::Arc, vec::Vec};
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(not(feature = "std"))]
+use reader::XmlEvent;
+#[cfg(feature = "std")]
+use std::fmt;
+#[cfg(feature = "std")]
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use xml::reader::{ParserConfig2, XmlEvent};
+
+use types::RawAttributes;
+
+/// Options controlling the behavior of the registry parser.
+///
+/// Options are constructed using builder-style methods, starting from
+/// `ParseOptions::new()` (or `Default::default()`), which gives the same behavior as
+/// `parse_file` and `parse_stream`.
+///
+/// ```
+/// extern crate vk_parse;
+///
+/// let options = vk_parse::ParseOptions::new().strict(true);
+/// let (registry, _errors) =
+///     vk_parse::parse_str_with_options("<registry></registry>", &options).unwrap();
+/// assert!(registry.0.is_empty());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    pub(crate) strict: bool,
+    pub(crate) strip_comments: bool,
+    pub(crate) raw_attributes: bool,
+    pub(crate) omit_code: bool,
+    pub(crate) skip_disabled_extensions: bool,
+    pub(crate) only_sections: Option<Vec<RegistrySection>>,
+    pub(crate) skipped_sections: Vec<RegistrySection>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) max_elements: Option<usize>,
+    pub(crate) max_text_len: Option<usize>,
+    pub(crate) coverage: bool,
+    pub(crate) dialect: RegistryDialect,
+    pub(crate) element_hooks: Vec<(String, ElementHook)>,
+    #[cfg(feature = "std")]
+    pub(crate) xml_config: ParserConfig2,
+    /// Reports errors of the XML reader as `Error::ReadError`, set by `parse_stream_lossy`.
+    pub(crate) lossy: bool,
+}
+
+impl ParseOptions {
+    /// Creates options with default behavior.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// In strict mode, any recoverable error encountered while parsing makes the whole parse
+    /// fail with `FatalError::Strict`, instead of being returned alongside the registry.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Drops all comments while parsing: `comment` attributes, `<comment>` elements and
+    /// comments within markup of struct members. Other contents stay unaffected, including
+    /// code of types and members.
+    pub fn strip_comments(mut self, strip: bool) -> Self {
+        self.strip_comments = strip;
+        self
+    }
+
+    /// Stores original values of all attributes in the `raw` field of elements whose attributes
+    /// are interpreted by the parser, e.g. parsed as numbers. Useful when exact text of the
+    /// registry is needed, or when checking the interpretation itself.
+    pub fn raw_attributes(mut self, raw: bool) -> Self {
+        self.raw_attributes = raw;
+        self
+    }
+
+    /// Leaves the `code` of command definitions and struct members empty, if it can be
+    /// regenerated from the parsed declarations using `Display`. Code of other types, e.g.
+    /// defines and function pointers, is always kept.
+    pub fn omit_code(mut self, omit: bool) -> Self {
+        self.omit_code = omit;
+        self
+    }
+
+    /// Skips contents of extensions with `supported="disabled"`, which are only placeholders
+    /// reserving extension numbers. Such extensions are still returned, with their attributes
+    /// but without any children, see `Extension::is_disabled`.
+    pub fn skip_disabled_extensions(mut self, skip: bool) -> Self {
+        self.skip_disabled_extensions = skip;
+        self
+    }
+
+    /// Parses only the given top-level sections of the registry, skipping contents of all
+    /// other sections without building any objects for them.
+    pub fn only_sections(mut self, sections: &[RegistrySection]) -> Self {
+        self.only_sections = Some(sections.to_vec());
+        self
+    }
+
+    /// Skips contents of given top-level sections of the registry.
+    pub fn skip_sections(mut self, sections: &[RegistrySection]) -> Self {
+        self.skipped_sections.extend_from_slice(sections);
+        self
+    }
+
+    /// Reports elements, attributes and text which the parser encountered but ignored, e.g.
+    /// attributes of text-only elements or children of elements which only have attributes,
+    /// as `Error::IgnoredElement`, `Error::IgnoredAttribute` and `Error::IgnoredText` alongside
+    /// other errors. Useful for finding registry additions which aren't modeled yet. These don't
+    /// make parsing fail in strict mode, see `Error::is_coverage`.
+    pub fn coverage(mut self, coverage: bool) -> Self {
+        self.coverage = coverage;
+        self
+    }
+
+    /// Schema of the registry being parsed. Defaults to `RegistryDialect::Vulkan`, while
+    /// `RegistryDialect::Core` parses only the structure shared by Khronos registries, e.g. for
+    /// registries of OpenXR, OpenCL or OpenGL.
+    pub fn dialect(mut self, dialect: RegistryDialect) -> Self {
+        self.dialect = dialect;
+        self
+    }
+
+    /// Registers hook for elements with given name which the parser doesn't know, e.g. vendor
+    /// annotations in patched registries. Such elements are passed to the hook instead of
+    /// being reported as `Error::UnexpectedElement`, or as `Error::IgnoredElement` with
+    /// `ParseOptions::coverage`, and the hook can read their contents, see `UnknownElement`.
+    /// Hooks for names which the parser knows at the position of the element aren't called.
+    ///
+    /// Hooks are called in document order, except with `parse_bytes_parallel`, where sections
+    /// are parsed on multiple threads. `RegistryCache` only calls them for sections which are
+    /// parsed again.
+    pub fn element_hook<F>(mut self, name: &str, hook: F) -> Self
+    where
+        F: Fn(UnknownElement) + Send + Sync + 'static,
+    {
+        self.element_hooks
+            .push((String::from(name), ElementHook(Arc::new(hook))));
+        self
+    }
+
+    /// Limits how deeply elements can be nested, with the `<registry>` element being at depth 1.
+    /// Exceeding the limit fails with `FatalError::LimitExceeded`.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Limits total number of elements in the document, including skipped ones.
+    /// Exceeding the limit fails with `FatalError::LimitExceeded`.
+    pub fn max_elements(mut self, count: usize) -> Self {
+        self.max_elements = Some(count);
+        self
+    }
+
+    /// Limits length in bytes of each continuous piece of text in the document, and of each
+    /// attribute value, comment and processing instruction, which the XML reader checks while
+    /// reading them. Exceeding the limit fails with `FatalError::LimitExceeded`.
+    pub fn max_text_len(mut self, len: usize) -> Self {
+        self.max_text_len = Some(len);
+        self
+    }
+
+    /// Configuration of the underlying XML reader, e.g. for custom entities or stricter limits
+    /// on entity expansion. Accepts both `ParserConfig` and `ParserConfig2`.
+    ///
+    /// Options which change what events are produced, such as `trim_whitespace` or
+    /// `ignore_comments`, also change the resulting `Registry`, since text content including
+    /// whitespace is preserved.
+    #[cfg(feature = "std")]
+    pub fn xml_config<C: Into<ParserConfig2>>(mut self, config: C) -> Self {
+        self.xml_config = config.into();
+        self
+    }
+
+    /// Returns whether top-level element with given name should be parsed. Unknown elements
+    /// are never filtered out, so they are still reported as errors.
+    pub(crate) fn parses_section(&self, element_name: &str) -> bool {
+        let section = match RegistrySection::from_element_name(element_name) {
+            Some(v) => v,
+            None => return true,
+        };
+        if self.skipped_sections.contains(&section) {
+            return false;
+        }
+        match self.only_sections {
+            Some(ref sections) => sections.contains(&section),
+            None => true,
+        }
+    }
+}
+
+/// Kinds of top-level elements of the registry, corresponding to variants of `RegistryChild`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RegistrySection {
+    Comment,
+    VendorIds,
+    Platforms,
+    Tags,
+    Types,
+    Enums,
+    Commands,
+    Feature,
+    Extensions,
+    Formats,
+    SpirvExtensions,
+    SpirvCapabilities,
+    Sync,
+    VideoCodecs,
+}
+
+impl RegistrySection {
+    /// Name of the XML element which represents this section.
+    pub fn element_name(self) -> &'static str {
+        match self {
+            RegistrySection::Comment => "comment",
+            RegistrySection::VendorIds => "vendorids",
+            RegistrySection::Platforms => "platforms",
+            RegistrySection::Tags => "tags",
+            RegistrySection::Types => "types",
+            RegistrySection::Enums => "enums",
+            RegistrySection::Commands => "commands",
+            RegistrySection::Feature => "feature",
+            RegistrySection::Extensions => "extensions",
+            RegistrySection::Formats => "formats",
+            RegistrySection::SpirvExtensions => "spirvextensions",
+            RegistrySection::SpirvCapabilities => "spirvcapabilities",
+            RegistrySection::Sync => "sync",
+            RegistrySection::VideoCodecs => "videocodecs",
+        }
+    }
+
+    /// Whether the section is part of the structure shared by Khronos registries: `<comment>`,
+    /// `<types>`, `<enums>`, `<commands>`, `<feature>` and `<extensions>`. Other sections are
+    /// specific to the Vulkan registry.
+    pub fn is_core(self) -> bool {
+        matches!(
+            self,
+            RegistrySection::Comment
+                | RegistrySection::Types
+                | RegistrySection::Enums
+                | RegistrySection::Commands
+                | RegistrySection::Feature
+                | RegistrySection::Extensions
+        )
+    }
+
+    /// Section represented by XML element with given name, if any.
+    pub fn from_element_name(name: &str) -> Option<RegistrySection> {
+        match name {
+            "comment" => Some(RegistrySection::Comment),
+            "vendorids" => Some(RegistrySection::VendorIds),
+            "platforms" => Some(RegistrySection::Platforms),
+            "tags" => Some(RegistrySection::Tags),
+            "types" => Some(RegistrySection::Types),
+            "enums" => Some(RegistrySection::Enums),
+            "commands" => Some(RegistrySection::Commands),
+            "feature" => Some(RegistrySection::Feature),
+            "extensions" => Some(RegistrySection::Extensions),
+            "formats" => Some(RegistrySection::Formats),
+            "spirvextensions" => Some(RegistrySection::SpirvExtensions),
+            "spirvcapabilities" => Some(RegistrySection::SpirvCapabilities),
+            "sync" => Some(RegistrySection::Sync),
+            "videocodecs" => Some(RegistrySection::VideoCodecs),
+            _ => None,
+        }
+    }
+}
+
+/// Unknown element passed to hook registered using `ParseOptions::element_hook`.
+#[non_exhaustive]
+pub struct UnknownElement<'a> {
+    pub name: &'a str,
+    /// Xpath of the element, as reported in errors.
+    pub xpath: String,
+    /// Attributes of the element, with their original values.
+    pub attributes: RawAttributes,
+    /// Events of contents of the element, ending before its end tag. Events which the hook
+    /// doesn't read are skipped, while resource limits apply to all of them.
+    pub events: &'a mut dyn Iterator<Item = XmlEvent>,
+}
+
+/// Hook registered using `ParseOptions::element_hook`.
+#[derive(Clone)]
+pub(crate) struct ElementHook(pub(crate) Arc<dyn Fn(UnknownElement) + Send + Sync>);
+
+impl fmt::Debug for ElementHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ElementHook")
+    }
+}
+
+/// Schemas of Khronos registries understood by the parser, see `ParseOptions::dialect`.
+///
+/// The parser is split into a core layer for the structure which Khronos registries share, and
+/// a layer for top-level sections specific to the Vulkan registry. Elements which a dialect
+/// doesn't know are reported as `Error::UnexpectedElement` and skipped. There are no layers for
+/// sections of other registries, such as `<groups>` of the OpenGL registry, but these can be
+/// read by hooks registered using `ParseOptions::element_hook`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RegistryDialect {
+    /// The Vulkan registry, `vk.xml`, with all sections of `RegistrySection`.
+    Vulkan,
+    /// Sections shared by Khronos registries, those for which `RegistrySection::is_core` holds.
+    Core,
+}
+
+impl Default for RegistryDialect {
+    fn default() -> Self {
+        RegistryDialect::Vulkan
+    }
+}
+
+impl RegistryDialect {
+    /// Whether registries of this dialect contain given top-level section.
+    pub fn has_section(self, section: RegistrySection) -> bool {
+        match self {
+            RegistryDialect::Vulkan => true,
+            RegistryDialect::Core => section.is_core(),
+        }
+    }
+}
+
+/// Resource limits which can be set using `ParseOptions`, to protect against excessively large
+/// or deeply nested documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ResourceLimit {
+    /// `ParseOptions::max_depth`
+    Depth,
+    /// `ParseOptions::max_elements`
+    Elements,
+    /// `ParseOptions::max_text_len`
+    TextLength,
+}
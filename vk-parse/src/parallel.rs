@@ -0,0 +1,68 @@
+use rayon::prelude::*;
+use std;
+use std::io::Read;
+
+use options::ParseOptions;
+use parse::{open_file, parse_bytes_with_options};
+use sections::{parse_section, split_sections};
+use types::*;
+
+/// Parses the Vulkan XML from bytes into a Rust object, parsing top-level sections of the
+/// registry in parallel.
+///
+/// Result is the same as from `parse_bytes_with_options`, including order of errors. If
+/// top-level structure of the document cannot be determined, or `ParseOptions::max_elements`
+/// is set, which needs to count elements of the whole document, the document is parsed
+/// sequentially instead.
+pub fn parse_bytes_parallel(
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    if options.max_elements.is_some() {
+        return parse_bytes_with_options(bytes, options);
+    }
+    let sections = match split_sections(bytes) {
+        Some(v) => v,
+        None => return parse_bytes_with_options(bytes, options),
+    };
+
+    let section_options = options.clone().strict(false);
+    let results: Vec<_> = sections
+        .into_par_iter()
+        .filter(|section| options.parses_section(section.name))
+        .map(|section| parse_section(bytes, &section, &section_options))
+        .collect();
+
+    let mut registry = Registry(Vec::new());
+    let mut errors = Vec::new();
+    for result in results {
+        let (section_registry, section_errors) = result?;
+        registry.0.extend(section_registry.0);
+        errors.extend(section_errors);
+    }
+
+    if options.strict && errors.iter().any(|e| !e.is_coverage()) {
+        return Err(FatalError::Strict(errors));
+    }
+    Ok((registry, errors))
+}
+
+/// Parses the Vulkan XML from string into a Rust object, parsing top-level sections of the
+/// registry in parallel.
+pub fn parse_str_parallel(
+    text: &str,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    parse_bytes_parallel(text.as_bytes(), options)
+}
+
+/// Parses the Vulkan XML file into a Rust object, parsing top-level sections of the registry
+/// in parallel. The whole file is read into memory first.
+pub fn parse_file_parallel(
+    path: &std::path::Path,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    let mut bytes = Vec::new();
+    open_file(path)?.read_to_end(&mut bytes)?;
+    parse_bytes_parallel(&bytes, options)
+}
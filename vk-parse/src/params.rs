@@ -0,0 +1,208 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use inline::InlineVec;
+use types::*;
+
+/// Parameter or struct member giving the length of arrays, along with those arrays, see
+/// `CommandDefinition::array_counts` and `Type::array_counts`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ArrayCount<'a, T> {
+    /// The count, e.g. `bindingCount` of `vkCmdBindVertexBuffers`, or `pPropertyCount` of
+    /// `vkEnumerateInstanceExtensionProperties` when passed by pointer.
+    pub count: &'a T,
+    /// Arrays whose `len` refers to the count, in declaration order, e.g. `pBuffers` and
+    /// `pOffsets` sharing `bindingCount`.
+    pub arrays: Vec<&'a T>,
+}
+
+impl CommandDefinition {
+    /// Pairs parameters giving lengths of arrays with the arrays, in order of the counts.
+    /// Only arrays with `len` referring to another parameter by name are included, so e.g.
+    /// `null-terminated` strings or lengths given by members of structs aren't.
+    pub fn array_counts(&self) -> Vec<ArrayCount<'_, CommandParam>> {
+        let params: Vec<_> = self
+            .params
+            .iter()
+            .map(|p| (&*p.definition.name, p.len.as_deref(), p))
+            .collect();
+        array_counts(&params)
+    }
+}
+
+/// Whether a command parameter passes data to the command or from it, see
+/// `CommandDefinition::param_directions`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum ParamDirection {
+    /// Parameter passed by value or through const pointer.
+    Input,
+    /// Non-const pointer the command writes to, e.g. `VkInstance* pInstance`.
+    Output,
+    /// Non-const pointer the command both reads and writes, which is a count of an array, e.g.
+    /// `uint32_t* pPropertyCount`.
+    InOut,
+}
+
+impl CommandDefinition {
+    /// Classifies each parameter as input or output, in order of parameters.
+    ///
+    /// Parameters passed by value or through pointer to const are inputs. Non-const pointers
+    /// are outputs, except for counts of arrays given by `len` of other parameters, which are
+    /// in/out, as their value is read to find how many elements the array can hold. Structs
+    /// returned through pointers are outputs, even though their `sType` and `pNext` are set by
+    /// the caller. Pointers to platform objects, such as `Display* dpy`, are outputs too, as
+    /// they aren't const in C. Parameters without a recognized `c_type` are inputs.
+    pub fn param_directions(&self) -> Vec<ParamDirection> {
+        let counts = self.array_counts();
+        self.params
+            .iter()
+            .map(|param| {
+                let is_output = match param.definition.c_type {
+                    Some(CType::Pointer { ref inner, .. }) => match **inner {
+                        CType::Named { is_const, .. } | CType::Pointer { is_const, .. } => {
+                            !is_const
+                        }
+                        _ => false,
+                    },
+                    _ => false,
+                };
+                if !is_output {
+                    ParamDirection::Input
+                } else if counts.iter().any(|c| core::ptr::eq(c.count, param)) {
+                    ParamDirection::InOut
+                } else {
+                    ParamDirection::Output
+                }
+            })
+            .collect()
+    }
+}
+
+impl Type {
+    /// Pairs members giving lengths of arrays with the arrays, in order of the counts, e.g.
+    /// `waitSemaphoreCount` of `VkSubmitInfo` with `pWaitSemaphores` and `pWaitDstStageMask`.
+    /// Only arrays with `len` referring to another member by name are included. Empty for types
+    /// other than structs and unions.
+    pub fn array_counts(&self) -> Vec<ArrayCount<'_, TypeMemberDefinition>> {
+        let members: Vec<_> = match self.spec {
+            TypeSpec::Members(ref members) => members
+                .iter()
+                .filter_map(|member| match *member {
+                    TypeMember::Definition(ref def) => Some((def.name()?, def.len.as_deref(), def)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        array_counts(&members)
+    }
+}
+
+impl CommandParam {
+    /// Values of the comma-separated `optional` attribute, which has one for each pointer
+    /// level, outermost first, e.g. `[false, true]` for `false,true`. Empty if it isn't given.
+    pub fn optional_levels(&self) -> InlineVec<bool> {
+        optional_levels(self.optional.as_deref())
+    }
+}
+
+impl TypeMemberDefinition {
+    /// Values of the comma-separated `optional` attribute, see `CommandParam::optional_levels`.
+    pub fn optional_levels(&self) -> InlineVec<bool> {
+        optional_levels(self.optional.as_deref())
+    }
+}
+
+fn optional_levels(optional: Option<&str>) -> InlineVec<bool> {
+    match optional {
+        Some(optional) => optional.split(',').map(|value| value == "true").collect(),
+        None => InlineVec::new(),
+    }
+}
+
+/// Command following the two-call idiom, which is called first to get the number of elements,
+/// and then again to fill arrays of that size, see `CommandDefinition::two_call_enumeration`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct TwoCallEnumeration<'a> {
+    pub command: &'a CommandDefinition,
+    /// Count passed by pointer, e.g. `pPropertyCount`.
+    pub count: &'a CommandParam,
+    /// Optional arrays filled by the command, usually one, e.g. `pProperties`.
+    pub arrays: Vec<&'a CommandParam>,
+}
+
+impl CommandDefinition {
+    /// Recognizes the two-call idiom, used e.g. by `vkEnumerateInstanceExtensionProperties`,
+    /// where the count is passed by non-const pointer, and arrays whose `len` refers to it are
+    /// outputs which are `optional`, so they can be null when querying the count.
+    pub fn two_call_enumeration(&self) -> Option<TwoCallEnumeration<'_>> {
+        let directions = self.param_directions();
+        let direction = |param: &CommandParam| {
+            let index = self.params.iter().position(|p| core::ptr::eq(p, param))?;
+            Some(directions[index])
+        };
+        let is_optional = |param: &CommandParam| param.optional_levels().first() == Some(&true);
+        self.array_counts().into_iter().find_map(|count| {
+            let is_enumeration = direction(count.count) == Some(ParamDirection::InOut)
+                && count.arrays.iter().all(|&array| {
+                    direction(array) == Some(ParamDirection::Output) && is_optional(array)
+                });
+            if is_enumeration {
+                Some(TwoCallEnumeration {
+                    command: self,
+                    count: count.count,
+                    arrays: count.arrays,
+                })
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl Registry {
+    /// Commands following the two-call idiom, in order of the registry, see
+    /// `CommandDefinition::two_call_enumeration`.
+    pub fn two_call_enumerations(&self) -> Vec<TwoCallEnumeration<'_>> {
+        self.commands()
+            .filter_map(|command| match *command {
+                Command::Definition(ref definition) => definition.two_call_enumeration(),
+                Command::Alias { .. } => None,
+            })
+            .collect()
+    }
+}
+
+/// Pairs counts and arrays of items given by name, `len` and the item itself.
+fn array_counts<'a, T>(items: &[(&'a str, Option<&'a str>, &'a T)]) -> Vec<ArrayCount<'a, T>> {
+    let mut counts: Vec<(usize, ArrayCount<T>)> = Vec::new();
+    for &(_, len, array) in items {
+        // Multi-dimensional arrays list the length of each dimension, starting with the outer.
+        let len = match len.and_then(|len| len.split(',').next()) {
+            Some(len) => len.trim(),
+            None => continue,
+        };
+        let index = match items.iter().position(|&(name, _, _)| name == len) {
+            Some(index) => index,
+            None => continue,
+        };
+        let position = match counts.iter().position(|&(i, _)| i == index) {
+            Some(position) => position,
+            None => {
+                let count = ArrayCount {
+                    count: items[index].2,
+                    arrays: Vec::new(),
+                };
+                counts.push((index, count));
+                counts.len() - 1
+            }
+        };
+        counts[position].1.arrays.push(array);
+    }
+    counts.sort_by_key(|&(index, _)| index);
+    counts.into_iter().map(|(_, count)| count).collect()
+}
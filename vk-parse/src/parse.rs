@@ -1,45 +1,352 @@
+#[cfg(feature = "std")]
 extern crate xml;
 
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(not(feature = "std"))]
+use reader::{self, Read, XmlEvent};
+#[cfg(feature = "std")]
 use std;
+#[cfg(feature = "std")]
 use std::io::Read;
-use std::str::FromStr;
-use xml::reader::XmlEvent;
-
+#[cfg(feature = "std")]
+use xml::reader::{ParserConfig2, XmlEvent};
+
+use api::{ApiSet, ExtensionSupport};
+use c;
+use commands::QueueKind;
+use depends::DependsExpr;
+use deprecation::Deprecation;
+use inline::InlineVec;
+use options::{ParseOptions, RegistryDialect, ResourceLimit, UnknownElement};
 use types::*;
-
-type XmlEvents<R> = xml::reader::Events<R>;
-type XmlAttribute = xml::attribute::OwnedAttribute;
+use version::ApiVersion;
+use video::VideoCoding;
+use vulkan;
+
+#[cfg(feature = "std")]
+pub(crate) type XmlEvents<R> = xml::reader::Events<R>;
+#[cfg(feature = "std")]
+pub(crate) type XmlAttribute = xml::attribute::OwnedAttribute;
+#[cfg(not(feature = "std"))]
+pub(crate) type XmlEvents<R> = reader::Events<R>;
+#[cfg(not(feature = "std"))]
+pub(crate) type XmlAttribute = reader::OwnedAttribute;
+/// Fatal error along with document order of the elements it was found at, see
+/// `ParseCtx::finish_located`.
+#[cfg(feature = "diagnostics")]
+pub(crate) type LocatedFatalError = (FatalError, Vec<usize>);
 
 //--------------------------------------------------------------------------------------------------
-struct ParseCtx<R: Read> {
-    events: XmlEvents<R>,
-    xpath: String,
-    errors: Vec<Error>,
+pub(crate) struct ParseCtx<'e, 'o, R: Read> {
+    events: &'e mut XmlEvents<R>,
+    /// Names of currently open elements, concatenated without separators.
+    element_names: String,
+    /// Offsets into `element_names` where each currently open element's name starts.
+    element_starts: Vec<usize>,
+    pub(crate) errors: Vec<Error>,
+    pub(crate) options: &'o ParseOptions,
+    element_count: usize,
+    /// Set when one of resource limits is exceeded, after which no more events are read.
+    limit_exceeded: Option<FatalError>,
+    /// Set when the XML reader fails, after which no more events are read.
+    read_failed: bool,
+    /// Document order of currently open elements, counting from 1.
+    #[cfg(feature = "diagnostics")]
+    element_ordinals: Vec<usize>,
+    /// Document order of the element at which each error was found, filled in whenever the
+    /// current element changes, see `locate_errors`.
+    #[cfg(feature = "diagnostics")]
+    error_elements: Vec<usize>,
+    /// Document order of the current element when one of resource limits was exceeded.
+    #[cfg(feature = "diagnostics")]
+    limit_element: usize,
 }
 
-impl<R: Read> ParseCtx<R> {
-    fn push_element(&mut self, name: &str) {
-        self.xpath.push('/');
-        self.xpath.push_str(name);
+impl<'e, 'o, R: Read> ParseCtx<'e, 'o, R> {
+    fn new(events: &'e mut XmlEvents<R>, options: &'o ParseOptions) -> Self {
+        ParseCtx {
+            events,
+            element_names: String::new(),
+            element_starts: Vec::new(),
+            errors: Vec::new(),
+            options,
+            element_count: 0,
+            limit_exceeded: None,
+            read_failed: false,
+            #[cfg(feature = "diagnostics")]
+            element_ordinals: Vec::new(),
+            #[cfg(feature = "diagnostics")]
+            error_elements: Vec::new(),
+            #[cfg(feature = "diagnostics")]
+            limit_element: 0,
+        }
+    }
+
+    /// Returns result of parsing along with the errors found, unless parsing failed.
+    fn finish<T>(self, result: Result<T, FatalError>) -> Result<(T, Vec<Error>), FatalError> {
+        if let Some(e) = self.limit_exceeded {
+            return Err(e);
+        }
+        let value = result?;
+        if self.options.strict && self.errors.iter().any(|e| !e.is_coverage()) {
+            return Err(FatalError::Strict(self.errors));
+        }
+        Ok((value, self.errors))
+    }
+
+    /// Same as `finish`, along with document order of the element at which each error was found.
+    /// If parsing fails, the fatal error is returned along with document order of the element
+    /// at which each of errors of `FatalError::Strict` was found, or of the element at which
+    /// parsing stopped for other fatal errors.
+    #[cfg(feature = "diagnostics")]
+    fn finish_located<T>(
+        mut self,
+        result: Result<T, FatalError>,
+    ) -> Result<(T, Vec<(Error, usize)>), LocatedFatalError> {
+        self.locate_errors();
+        let elements = core::mem::take(&mut self.error_elements);
+        let stopped_at = if self.limit_exceeded.is_some() {
+            self.limit_element
+        } else {
+            self.element_ordinals.last().cloned().unwrap_or(0)
+        };
+        match self.finish(result) {
+            Ok((value, errors)) => Ok((value, errors.into_iter().zip(elements).collect())),
+            Err(e @ FatalError::Strict(_)) => Err((e, elements)),
+            Err(e) => Err((e, vec![stopped_at])),
+        }
+    }
+
+    /// Attributes errors reported since the current element last changed to that element.
+    /// Errors are reported while the element they refer to is current, as for their xpath.
+    #[cfg(feature = "diagnostics")]
+    fn locate_errors(&mut self) {
+        let current = self.element_ordinals.last().cloned().unwrap_or(0);
+        self.error_elements.resize(self.errors.len(), current);
+    }
+
+    /// Reads next event from the document, checking it against resource limits.
+    pub(crate) fn next_event(&mut self) -> Option<XmlEvent> {
+        if self.limit_exceeded.is_some() || self.read_failed {
+            return None;
+        }
+        let event = match self.events.next() {
+            Some(Ok(v)) => v,
+            Some(Err(ref e)) if is_reader_limit(self.options, e) => {
+                self.exceed(ResourceLimit::TextLength);
+                return None;
+            }
+            Some(Err(e)) => {
+                self.read_failed = true;
+                if self.options.lossy {
+                    self.errors.push(Error::ReadError {
+                        xpath: self.xpath(),
+                        desc: e.to_string(),
+                    });
+                }
+                return None;
+            }
+            None => return None,
+        };
+        let exceeded = match event {
+            XmlEvent::StartElement { .. } => {
+                self.element_count += 1;
+                if exceeds(self.element_starts.len() + 1, self.options.max_depth) {
+                    Some(ResourceLimit::Depth)
+                } else if exceeds(self.element_count, self.options.max_elements) {
+                    Some(ResourceLimit::Elements)
+                } else {
+                    None
+                }
+            }
+            XmlEvent::Characters(ref text) | XmlEvent::Whitespace(ref text) => {
+                if exceeds(text.len(), self.options.max_text_len) {
+                    Some(ResourceLimit::TextLength)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        if let Some(limit) = exceeded {
+            self.exceed(limit);
+            return None;
+        }
+        Some(event)
+    }
+
+    /// Stops parsing at the current element, which exceeds given limit.
+    fn exceed(&mut self, limit: ResourceLimit) {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.limit_element = self.element_ordinals.last().cloned().unwrap_or(0);
+        }
+        self.limit_exceeded = Some(FatalError::LimitExceeded {
+            limit,
+            xpath: self.xpath(),
+        });
+    }
+
+    /// Reports element which is ignored, with `ParseOptions::coverage`. Called after the
+    /// element is pushed, so that `xpath` refers to the element itself.
+    pub(crate) fn ignored_element(&mut self, name: &str) {
+        if self.options.coverage {
+            self.errors.push(Error::IgnoredElement {
+                xpath: self.xpath(),
+                name: String::from(name),
+            });
+        }
+    }
+
+    /// Reports attributes which are ignored, with `ParseOptions::coverage`.
+    pub(crate) fn ignored_attributes(&mut self, attributes: &[XmlAttribute]) {
+        if self.options.coverage {
+            for a in attributes {
+                self.errors.push(Error::IgnoredAttribute {
+                    xpath: self.xpath(),
+                    name: a.name.local_name.clone(),
+                });
+            }
+        }
+    }
+
+    /// Reports text content of the current element which is ignored, with
+    /// `ParseOptions::coverage`.
+    pub(crate) fn ignored_text(&mut self) {
+        if self.options.coverage {
+            self.errors.push(Error::IgnoredText {
+                xpath: self.xpath(),
+            });
+        }
+    }
+
+    /// Passes unknown element to hook registered for its name, if any, which consumes the
+    /// element. Called after the element is pushed. Returns whether there was such hook.
+    pub(crate) fn element_hook(&mut self, name: &str, attributes: &[XmlAttribute]) -> bool {
+        let options = self.options;
+        let hook = match options.element_hooks.iter().find(|h| h.0 == name) {
+            Some((_, hook)) => hook,
+            None => return false,
+        };
+        let xpath = self.xpath();
+        let attributes = attributes
+            .iter()
+            .map(|a| {
+                (
+                    Box::from(a.name.local_name.as_str()),
+                    Box::from(a.value.as_str()),
+                )
+            })
+            .collect();
+        let mut events = ElementEvents {
+            ctx: self,
+            depth: 1,
+        };
+        (hook.0)(UnknownElement {
+            name,
+            xpath,
+            attributes,
+            events: &mut events,
+        });
+        events.for_each(drop);
+        true
+    }
+
+    pub(crate) fn push_element(&mut self, name: &str) {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.locate_errors();
+            self.element_ordinals.push(self.element_count);
+        }
+        self.element_starts.push(self.element_names.len());
+        self.element_names.push_str(name);
     }
 
-    fn pop_element(&mut self) {
-        if let Some(separator_pos) = self.xpath.rfind('/') {
-            self.xpath.truncate(separator_pos);
+    pub(crate) fn pop_element(&mut self) {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.locate_errors();
+            self.element_ordinals.pop();
+        }
+        if let Some(start) = self.element_starts.pop() {
+            self.element_names.truncate(start);
         } else {
             self.errors.push(Error::Internal {
                 desc: "ParseCtx push_element/pop_element mismatch.",
             });
         }
     }
+
+    /// Builds xpath of the current element. Only called when an error is reported, so that
+    /// the common path does not need to maintain the string.
+    pub(crate) fn xpath(&self) -> String {
+        let mut xpath = String::with_capacity(self.element_names.len() + self.element_starts.len());
+        let ends = self.element_starts.iter().skip(1).cloned();
+        for (start, end) in self
+            .element_starts
+            .iter()
+            .cloned()
+            .zip(ends.chain(Some(self.element_names.len())))
+        {
+            xpath.push('/');
+            xpath.push_str(&self.element_names[start..end]);
+        }
+        xpath
+    }
+
+    pub(crate) fn xpath_attribute(&self, attribute_name: &str) -> String {
+        let mut xpath = self.xpath();
+        xpath.push_str("[@");
+        xpath.push_str(attribute_name);
+        xpath.push(']');
+        xpath
+    }
+}
+
+/// Events of the rest of the current element, up to its end tag, which is consumed but not
+/// returned.
+struct ElementEvents<'c, 'e, 'o, R: Read> {
+    ctx: &'c mut ParseCtx<'e, 'o, R>,
+    depth: usize,
+}
+
+impl<'c, 'e, 'o, R: Read> Iterator for ElementEvents<'c, 'e, 'o, R> {
+    type Item = XmlEvent;
+
+    fn next(&mut self) -> Option<XmlEvent> {
+        if self.depth == 0 {
+            return None;
+        }
+        let event = self.ctx.next_event()?;
+        match event {
+            XmlEvent::StartElement { ref name, .. } => {
+                self.ctx.push_element(&name.local_name);
+                self.depth += 1;
+            }
+            XmlEvent::EndElement { .. } => {
+                self.ctx.pop_element();
+                self.depth -= 1;
+                if self.depth == 0 {
+                    return None;
+                }
+            }
+            _ => {}
+        }
+        Some(event)
+    }
 }
 
-fn xpath_attribute(xpath: &str, attribute_name: &str) -> String {
-    let mut xpath = String::from(xpath);
-    xpath.push_str("[@");
-    xpath.push_str(attribute_name);
-    xpath.push(']');
-    xpath
+fn exceeds(value: usize, limit: Option<usize>) -> bool {
+    match limit {
+        Some(limit) => value > limit,
+        None => false,
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -49,7 +356,7 @@ macro_rules! unwrap_attribute (
             Some(val) => val,
             None => {
                 $ctx.errors.push(Error::MissingAttribute {
-                    xpath: $ctx.xpath.clone(),
+                    xpath: $ctx.xpath(),
                     name: String::from(stringify!($attribute)),
                 });
                 return None;
@@ -63,11 +370,12 @@ macro_rules! match_attributes {
         for $a in $attributes {
             let n = $a.name.local_name.as_str();
             match n {
+                "comment" if $ctx.options.strip_comments => {}
                 $(
                     $p => $e,
                 )+
                 _ => $ctx.errors.push(Error::UnexpectedAttribute {
-                    xpath: $ctx.xpath.clone(),
+                    xpath: $ctx.xpath(),
                     name: String::from(n),
                 })
             }
@@ -76,19 +384,25 @@ macro_rules! match_attributes {
 }
 
 macro_rules! match_elements {
-    ($ctx:expr, $($p:pat => $e:expr),+) => {
-        while let Some(Ok(e)) = $ctx.events.next() {
+    ($ctx:expr, $($p:pat $(if $g:expr)? => $e:expr),+) => {
+        while let Some(e) = $ctx.next_event() {
             match e {
-                XmlEvent::StartElement { name, .. } => {
+                XmlEvent::StartElement { name, attributes, .. } => {
                     let name = name.local_name.as_str();
                     $ctx.push_element(name);
                     match name {
+                        "comment" if $ctx.options.strip_comments => consume_current_element($ctx),
                         $(
-                            $p => $e,
+                            $p $(if $g)? => {
+                                // Children parsed here can't have any attributes.
+                                $ctx.ignored_attributes(&attributes);
+                                $e
+                            }
                         )+
+                        _ if $ctx.element_hook(name, &attributes) => {}
                         _ => {
                             $ctx.errors.push(Error::UnexpectedElement {
-                                xpath: $ctx.xpath.clone(),
+                                xpath: $ctx.xpath(),
                                 name: String::from(name),
                             });
                             consume_current_element($ctx);
@@ -99,24 +413,27 @@ macro_rules! match_elements {
                     $ctx.pop_element();
                     break;
                 }
+                XmlEvent::Characters(_) => $ctx.ignored_text(),
                 _ => {}
             }
         }
     };
 
-    ( $ctx:expr, $attributes:ident, $($p:pat => $e:expr),+) => {
-        while let Some(Ok(e)) = $ctx.events.next() {
+    ( $ctx:expr, $attributes:ident, $($p:pat $(if $g:expr)? => $e:expr),+) => {
+        while let Some(e) = $ctx.next_event() {
             match e {
                 XmlEvent::StartElement { name, $attributes, .. } => {
                     let name = name.local_name.as_str();
                     $ctx.push_element(name);
                     match name {
+                        "comment" if $ctx.options.strip_comments => consume_current_element($ctx),
                         $(
-                            $p => $e,
+                            $p $(if $g)? => $e,
                         )+
+                        _ if $ctx.element_hook(name, &$attributes) => {}
                         _ => {
                             $ctx.errors.push(Error::UnexpectedElement {
-                                xpath: $ctx.xpath.clone(),
+                                xpath: $ctx.xpath(),
                                 name: String::from(name),
                             });
                             consume_current_element($ctx);
@@ -127,6 +444,7 @@ macro_rules! match_elements {
                     $ctx.pop_element();
                     break;
                 }
+                XmlEvent::Characters(_) => $ctx.ignored_text(),
                 _ => {}
             }
         }
@@ -135,21 +453,27 @@ macro_rules! match_elements {
 
 macro_rules! match_elements_combine_text {
     ( $ctx:expr, $buffer:ident, $($p:pat => $e:expr),+) => {
-        while let Some(Ok(e)) = $ctx.events.next() {
+        while let Some(e) = $ctx.next_event() {
             match e {
                 XmlEvent::Characters(text) => $buffer.push_str(&text),
                 XmlEvent::Whitespace(text) => $buffer.push_str(&text),
-                XmlEvent::StartElement { name, .. } => {
+                XmlEvent::StartElement { name, attributes, .. } => {
                     $buffer.push(' ');
                     let name = name.local_name.as_str();
                     $ctx.push_element(name);
                     match name {
+                        "comment" if $ctx.options.strip_comments => consume_current_element($ctx),
                         $(
-                            $p => $e,
+                            $p => {
+                                // Children parsed here can't have any attributes.
+                                $ctx.ignored_attributes(&attributes);
+                                $e
+                            }
                         )+
+                        _ if $ctx.element_hook(name, &attributes) => {}
                         _ => {
                             $ctx.errors.push(Error::UnexpectedElement {
-                                xpath: $ctx.xpath.clone(),
+                                xpath: $ctx.xpath(),
                                 name: String::from(name),
                             });
                             consume_current_element($ctx);
@@ -167,7 +491,7 @@ macro_rules! match_elements_combine_text {
     };
 
     ( $ctx:expr, $attributes:ident, $buffer:ident, $($p:pat => $e:expr),+) => {
-        while let Some(Ok(e)) = $ctx.events.next() {
+        while let Some(e) = $ctx.next_event() {
             match e {
                 XmlEvent::Characters(text) => $buffer.push_str(&text),
                 XmlEvent::Whitespace(text) => $buffer.push_str(&text),
@@ -175,12 +499,14 @@ macro_rules! match_elements_combine_text {
                     let name = name.local_name.as_str();
                     $ctx.push_element(name);
                     match name {
+                        "comment" if $ctx.options.strip_comments => consume_current_element($ctx),
                         $(
                             $p => $e,
                         )+
+                        _ if $ctx.element_hook(name, &$attributes) => {}
                         _ => {
                             $ctx.errors.push(Error::UnexpectedElement {
-                                xpath: $ctx.xpath.clone(),
+                                xpath: $ctx.xpath(),
                                 name: String::from(name),
                             });
                             consume_current_element($ctx);
@@ -199,69 +525,337 @@ macro_rules! match_elements_combine_text {
 
 //--------------------------------------------------------------------------------------------------
 /// Parses the Vulkan XML file into a Rust object.
+#[cfg(feature = "std")]
 pub fn parse_file(path: &std::path::Path) -> Result<(Registry, Vec<Error>), FatalError> {
-    let file = std::io::BufReader::new(std::fs::File::open(path)?);
-    let parser = xml::reader::ParserConfig::new().create_reader(file);
-    parse_xml(parser.into_iter())
+    parse_file_with_options(path, &ParseOptions::default())
 }
 
 /// Parses the Vulkan XML file from stream into a Rust object.
-pub fn parse_stream<T: std::io::Read>(stream: T) -> Result<(Registry, Vec<Error>), FatalError> {
-    let parser = xml::reader::ParserConfig::new().create_reader(stream);
-    parse_xml(parser.into_iter())
+pub fn parse_stream<T: Read>(stream: T) -> Result<(Registry, Vec<Error>), FatalError> {
+    parse_stream_with_options(stream, &ParseOptions::default())
 }
 
-fn parse_xml<R: Read>(events: XmlEvents<R>) -> Result<(Registry, Vec<Error>), FatalError> {
-    let mut ctx = ParseCtx {
-        events,
-        xpath: String::from(""),
-        errors: Vec::new(),
-    };
+/// Parses the Vulkan XML file into a Rust object, using given options.
+///
+/// Files with `.gz` or `.zst` extension are decompressed while parsing, if the `gzip` or `zstd`
+/// feature respectively is enabled.
+#[cfg(feature = "std")]
+pub fn parse_file_with_options(
+    path: &std::path::Path,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    parse_stream_with_options(open_file(path)?, options)
+}
+
+/// Opens file for reading, decompressing its contents based on file extension.
+#[cfg(feature = "std")]
+pub(crate) fn open_file(path: &std::path::Path) -> std::io::Result<Box<dyn Read>> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    Ok(match path.extension().and_then(|e| e.to_str()) {
+        #[cfg(feature = "gzip")]
+        Some("gz") => Box::new(std::io::BufReader::new(flate2::bufread::GzDecoder::new(
+            file,
+        ))),
+        #[cfg(feature = "zstd")]
+        Some("zst") => Box::new(std::io::BufReader::new(zstd::Decoder::with_buffer(file)?)),
+        _ => Box::new(file),
+    })
+}
+
+/// Parses the Vulkan XML file from stream into a Rust object, using given options.
+///
+/// Documents encoded as UTF-16 are recognized by byte order mark, or by their first character
+/// if there is none, and decoded transparently.
+#[cfg(feature = "std")]
+pub fn parse_stream_with_options<T: Read>(
+    mut stream: T,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    let mut head = [0; 2];
+    let mut head_len = 0;
+    while head_len < head.len() {
+        match stream.read(&mut head[head_len..]) {
+            Ok(0) => break,
+            Ok(n) => head_len += n,
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let head = &head[..head_len];
+    let parser = xml_config(head, options).create_reader(head.chain(stream));
+    parse_xml(parser.into_iter(), options)
+}
+
+/// Parses the Vulkan XML file from stream into a Rust object, using given options.
+///
+/// Without the `std` feature, the document is read by a built-in XML reader, which only
+/// supports documents encoded as UTF-8, and no entities other than those declared within the
+/// document.
+#[cfg(not(feature = "std"))]
+pub fn parse_stream_with_options<T: Read>(
+    stream: T,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    parse_xml(XmlEvents::new(stream, options.max_text_len), options)
+}
 
+/// Parses the Vulkan XML from string into a Rust object.
+pub fn parse_str(text: &str) -> Result<(Registry, Vec<Error>), FatalError> {
+    parse_bytes(text.as_bytes())
+}
+
+/// Parses the Vulkan XML from bytes into a Rust object.
+pub fn parse_bytes(bytes: &[u8]) -> Result<(Registry, Vec<Error>), FatalError> {
+    parse_bytes_with_options(bytes, &ParseOptions::default())
+}
+
+/// Parses the Vulkan XML from string into a Rust object, using given options.
+pub fn parse_str_with_options(
+    text: &str,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    parse_bytes_with_options(text.as_bytes(), options)
+}
+
+/// Parses the Vulkan XML from bytes into a Rust object, using given options.
+///
+/// Byte order mark, if present, is skipped before handing the data to the XML reader.
+pub fn parse_bytes_with_options(
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    let bytes = strip_utf8_bom(bytes);
+    #[cfg(feature = "std")]
+    let events = xml_config(bytes, options).create_reader(bytes).into_iter();
+    #[cfg(not(feature = "std"))]
+    let events = XmlEvents::new(bytes, options.max_text_len);
+    parse_xml(events, options)
+}
+
+/// Configuration of XML reader for document starting with given bytes.
+#[cfg(feature = "std")]
+pub(crate) fn xml_config(head: &[u8], options: &ParseOptions) -> ParserConfig2 {
+    let mut config = options.xml_config.clone();
+    if config.override_encoding.is_none() {
+        config.override_encoding = detect_utf16_without_bom(head);
+    }
+    // The reader buffers whole pieces of text and attribute values, so it has to enforce the
+    // limit on their length itself, before they're read into memory.
+    if let Some(len) = options.max_text_len {
+        config.max_data_length = config.max_data_length.min(len);
+        config.max_attribute_length = config.max_attribute_length.min(len);
+    }
+    config
+}
+
+/// Whether the reader failed because of exceeding `ParseOptions::max_text_len`, see
+/// `xml_config`. The reader doesn't tell which of its limits was exceeded, only its message.
+#[cfg(feature = "std")]
+fn is_reader_limit(options: &ParseOptions, error: &xml::reader::Error) -> bool {
+    const LIMIT_EXCEEDED: &str =
+        "This document is larger/more complex than allowed by the parser's configuration";
+    match *error.kind() {
+        xml::reader::ErrorKind::Syntax(ref message) => {
+            options.max_text_len.is_some() && message == LIMIT_EXCEEDED
+        }
+        _ => false,
+    }
+}
+
+/// Whether the reader failed because of exceeding `ParseOptions::max_text_len`.
+#[cfg(not(feature = "std"))]
+fn is_reader_limit(_: &ParseOptions, error: &reader::Error) -> bool {
+    error.is_limit_exceeded()
+}
+
+/// Documents without byte order mark must start with `<`, either of the XML declaration or of
+/// the root element, which reveals UTF-16 and its byte order (see appendix F of the XML
+/// specification). The XML reader itself only recognizes UTF-16 by byte order mark.
+#[cfg(feature = "std")]
+fn detect_utf16_without_bom(head: &[u8]) -> Option<xml::Encoding> {
+    match head {
+        [b'<', 0, ..] => Some(xml::Encoding::Utf16Le),
+        [0, b'<', ..] => Some(xml::Encoding::Utf16Be),
+        _ => None,
+    }
+}
+
+pub(crate) fn strip_utf8_bom(bytes: &[u8]) -> &[u8] {
+    const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+    if bytes.starts_with(UTF8_BOM) {
+        &bytes[UTF8_BOM.len()..]
+    } else {
+        bytes
+    }
+}
+
+fn parse_xml<R: Read>(
+    mut events: XmlEvents<R>,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    let mut ctx = ParseCtx::new(&mut events, options);
+    let result = parse_document(&mut ctx);
+    ctx.finish(result)
+}
+
+/// Parses the document as `parse_bytes_with_options` does, along with document order of the
+/// element at which each error was found, counting from 1, or 0 for errors outside of any, see
+/// `ParseCtx::finish_located`.
+#[cfg(feature = "diagnostics")]
+pub(crate) fn parse_bytes_located(
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<(Error, usize)>), LocatedFatalError> {
+    let bytes = strip_utf8_bom(bytes);
+    let mut events = xml_config(bytes, options).create_reader(bytes).into_iter();
+    let mut ctx = ParseCtx::new(&mut events, options);
+    let result = parse_document(&mut ctx);
+    ctx.finish_located(result)
+}
+
+fn parse_document<R: Read>(ctx: &mut ParseCtx<R>) -> Result<Registry, FatalError> {
     let mut result = Err(FatalError::MissingRegistryElement);
+    match_elements! {ctx,
+        "registry" => result = parse_registry(ctx)
+    }
+    result
+}
 
-    {
-        let ctx = &mut ctx;
-        match_elements! {ctx,
-            "registry" => result = parse_registry(ctx)
+/// Parses the `<registry>` element from events of an XML reader, e.g. when the registry is
+/// embedded within a larger document, or when the application reads the document itself.
+///
+/// Events up to the start of the next element are skipped, e.g. start of the document or
+/// whitespace. Reading stops right after the end of the `<registry>` element, so the rest of
+/// the document can still be read from `events`. If another element starts first, it's skipped
+/// and parsing fails with `FatalError::MissingRegistryElement`, as it does if an enclosing
+/// element ends first. Xpaths of errors start at the `<registry>` element.
+///
+/// `ParseOptions::xml_config` doesn't apply, since the reader is already configured.
+#[cfg(feature = "std")]
+pub fn parse_registry_from_events<R: Read>(
+    events: &mut XmlEvents<R>,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    let mut ctx = ParseCtx::new(events, options);
+    let result = match next_element(&mut ctx) {
+        Some((ref name, ref attributes)) if name == "registry" => {
+            ctx.ignored_attributes(attributes);
+            parse_registry(&mut ctx)
+        }
+        Some(_) => {
+            consume_current_element(&mut ctx);
+            Err(FatalError::MissingRegistryElement)
+        }
+        None => Err(FatalError::MissingRegistryElement),
+    };
+    ctx.finish(result)
+}
+
+/// Parses a single top-level element of the registry, e.g. `<types>` or `<extensions>`, from
+/// events of an XML reader, in the same way as `parse_registry_from_events`. Useful for reading
+/// sections one at a time, or sections which aren't within a `<registry>` element.
+///
+/// Returns `None` if the element is skipped by `ParseOptions::only_sections` or
+/// `ParseOptions::skip_sections`, if it isn't a section of the registry, in which case it's
+/// reported as `Error::UnexpectedElement`, or if an enclosing element ends first. Xpaths of
+/// errors are the same as when the element is within `<registry>`.
+#[cfg(feature = "std")]
+pub fn parse_section_from_events<R: Read>(
+    events: &mut XmlEvents<R>,
+    options: &ParseOptions,
+) -> Result<(Option<RegistryChild>, Vec<Error>), FatalError> {
+    let mut ctx = ParseCtx::new(events, options);
+    ctx.push_element("registry");
+    let mut child = None;
+    if let Some((name, attributes)) = next_element(&mut ctx) {
+        let stripped = name == "comment" && options.strip_comments;
+        if stripped || !options.parses_section(&name) {
+            consume_current_element(&mut ctx);
+        } else if is_registry_child(options, &name) {
+            let mut registry = Registry(Vec::new());
+            parse_registry_child(&mut ctx, &mut registry, &name, attributes);
+            child = registry.0.pop();
+        } else if !ctx.element_hook(&name, &attributes) {
+            ctx.errors.push(Error::UnexpectedElement {
+                xpath: ctx.xpath(),
+                name,
+            });
+            consume_current_element(&mut ctx);
         }
     }
+    ctx.finish(Ok(child))
+}
 
-    result.map(|r| (r, ctx.errors))
+/// Reads events up to the start of the next element, returning its name and attributes, or
+/// `None` if an enclosing element or the document ends first.
+#[cfg(feature = "std")]
+fn next_element<R: Read>(ctx: &mut ParseCtx<R>) -> Option<(String, Vec<XmlAttribute>)> {
+    while let Some(e) = ctx.next_event() {
+        match e {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                ctx.push_element(&name.local_name);
+                return Some((name.local_name, attributes));
+            }
+            XmlEvent::EndElement { .. } => break,
+            XmlEvent::Characters(_) => ctx.ignored_text(),
+            _ => {}
+        }
+    }
+    None
 }
 
+/// Parses the `<registry>` element.
 fn parse_registry<R: Read>(ctx: &mut ParseCtx<R>) -> Result<Registry, FatalError> {
     let mut registry = Registry(Vec::new());
 
     match_elements! {ctx, attributes,
-        "comment" => registry.0.push(RegistryChild::Comment(parse_text_element(ctx))),
-        "vendorids" => registry.0.push(parse_vendorids(ctx, attributes)),
-        "platforms" => {
-            let mut comment = None;
-            let mut children = Vec::new();
+        name if !ctx.options.parses_section(name) => consume_current_element(ctx),
+        name if is_registry_child(ctx.options, name) => {
+            parse_registry_child(ctx, &mut registry, name, attributes)
+        }
+    }
 
-            match_attributes!{ctx, a in attributes,
-                "comment" => comment = Some(a.value)
-            }
+    Ok(registry)
+}
 
-            match_elements!{ctx, attributes,
-                "platform" => if let Some(v) = parse_platform(ctx, attributes) {
-                    children.push(v);
-                }
-            }
+/// Whether top-level element with given name is a section of registries of
+/// `ParseOptions::dialect`.
+fn is_registry_child(options: &ParseOptions, name: &str) -> bool {
+    match name {
+        "comment" | "types" | "enums" | "commands" | "feature" | "extensions" => true,
+        name => options.dialect == RegistryDialect::Vulkan && vulkan::is_section(name),
+    }
+}
 
-            registry.0.push(RegistryChild::Platforms(Platforms { comment, children }));
+/// Parses top-level element for which `is_registry_child` holds into `registry`. Elements shared
+/// by Khronos registries are parsed here, while those specific to Vulkan are left to the
+/// `vulkan` module.
+// Arms are kept in the layout used within `match_elements!`, which rustfmt leaves alone.
+#[rustfmt::skip]
+fn parse_registry_child<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    registry: &mut Registry,
+    name: &str,
+    attributes: Vec<XmlAttribute>,
+) {
+    match name {
+        "comment" => {
+            ctx.ignored_attributes(&attributes);
+            registry.0.push(RegistryChild::Comment(parse_text_element(ctx)))
         },
-
-        "tags" => registry.0.push(parse_tags(ctx, attributes)),
         "types" => {
             let mut comment = None;
             let mut children = Vec::new();
             match_attributes!{ctx, a in attributes,
-                "comment" => comment = Some(a.value)
+                "comment" => comment = Some(a.value.into_boxed_str())
             }
             match_elements!{ctx, attributes,
-                "comment" => children.push(TypesChild::Comment(parse_text_element(ctx))),
+                "comment" => {
+                    ctx.ignored_attributes(&attributes);
+                    children.push(TypesChild::Comment(parse_text_element(ctx)))
+                },
                 "type" => children.push(parse_type(ctx, attributes))
             }
             registry.0.push(RegistryChild::Types(Types{
@@ -277,15 +871,16 @@ fn parse_registry<R: Read>(ctx: &mut ParseCtx<R>) -> Result<Registry, FatalError
             let mut vendor = None;
             let mut comment = None;
             let mut bitwidth = None;
-            let mut children = Vec::new();
+            let mut children = InlineVec::new();
+            let raw = raw_attributes(ctx, &attributes);
             match_attributes!{ctx, a in attributes,
-                "name"     => name     = Some(a.value),
-                "type"     => kind     = Some(a.value),
-                "start"    => start    = Some(a.value),
-                "end"      => end      = Some(a.value),
-                "vendor"   => vendor   = Some(a.value),
-                "comment"  => comment  = Some(a.value),
-                "bitwidth" => bitwidth = Some(a.value)
+                "name"     => name     = Some(a.value.into_boxed_str()),
+                "type"     => kind     = Some(a.value.into_boxed_str()),
+                "start"    => start    = Some(a.value.into_boxed_str()),
+                "end"      => end      = Some(a.value.into_boxed_str()),
+                "vendor"   => vendor   = Some(a.value.into_boxed_str()),
+                "comment"  => comment  = Some(a.value.into_boxed_str()),
+                "bitwidth" => bitwidth = Some(a.value.into_boxed_str())
             }
             match_elements!{ctx, attributes,
                 "enum" => if let Some(v) = parse_enum(ctx, attributes) {
@@ -294,21 +889,24 @@ fn parse_registry<R: Read>(ctx: &mut ParseCtx<R>) -> Result<Registry, FatalError
                 "unused" => if let Some(v) = parse_enums_child_unused(ctx, attributes) {
                     children.push(v);
                 },
-                "comment" => children.push(EnumsChild::Comment(parse_text_element(ctx)))
+                "comment" => {
+                    ctx.ignored_attributes(&attributes);
+                    children.push(EnumsChild::Comment(parse_text_element(ctx)))
+                }
             }
 
             let start = start.and_then(|val| parse_integer(ctx, &val));
             let end = end.and_then(|val| parse_integer(ctx, &val));
             let bitwidth = bitwidth.and_then(|val| parse_integer(ctx, &val)).map(|val| val as u32);
 
-            registry.0.push(RegistryChild::Enums(Enums{ name, kind, start, end, vendor, comment, children, bitwidth }));
+            registry.0.push(RegistryChild::Enums(Enums{ name, kind, start, end, vendor, comment, children, bitwidth, raw }));
         },
         "commands" => {
             let mut comment = None;
             let mut children = Vec::new();
 
             match_attributes!{ctx, a in attributes,
-                "comment" => comment = Some(a.value)
+                "comment" => comment = Some(a.value.into_boxed_str())
             }
 
             match_elements!{ctx, attributes,
@@ -323,133 +921,10 @@ fn parse_registry<R: Read>(ctx: &mut ParseCtx<R>) -> Result<Registry, FatalError
             registry.0.push(v);
         },
         "extensions" => registry.0.push(parse_extensions(ctx, attributes)),
-        "formats" => registry.0.push(parse_formats(ctx)),
-        "spirvextensions" => registry.0.push(parse_spirvextensions(ctx, attributes)),
-        "spirvcapabilities" => registry.0.push(parse_spirvcapabilities(ctx, attributes))
-    }
-
-    Ok(registry)
-}
-
-fn parse_vendorids<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> RegistryChild {
-    let mut comment = None;
-    let mut children = Vec::new();
-
-    match_attributes! {ctx, a in attributes,
-        "comment" => comment = Some(a.value)
-    }
-
-    match_elements! {ctx, attributes,
-        "vendorid" => if let Some(v) = parse_vendorid(ctx, attributes) {
-            children.push(v);
-        }
-    }
-
-    RegistryChild::VendorIds(VendorIds { comment, children })
-}
-
-fn parse_vendorid<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> Option<VendorId> {
-    let mut name = None;
-    let mut comment = None;
-    let mut id = None;
-
-    match_attributes! {ctx, a in attributes,
-        "name" => name = Some(a.value),
-        "comment" => comment = Some(a.value),
-        "id" => {
-            let mut v = None;
-            if a.value.starts_with("0x") {
-                v = u32::from_str_radix(&a.value.split_at(2).1, 16).ok();
-            }
-
-            if let Some(v) = v {
-                id = Some(v);
-            } else {
-                ctx.errors.push(Error::UnexpectedAttributeValue {
-                    xpath: ctx.xpath.clone(),
-                    name: String::from("id"),
-                    value: a.value.clone(),
-                });
-            }
-        }
-    }
-
-    consume_current_element(ctx);
-
-    unwrap_attribute!(ctx, vendorid, name);
-    unwrap_attribute!(ctx, vendorid, id);
-
-    Some(VendorId { name, comment, id })
-}
-
-fn parse_platform<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> Option<Platform> {
-    let mut name = None;
-    let mut comment = None;
-    let mut protect = None;
-
-    match_attributes! {ctx, a in attributes,
-        "name"    => name    = Some(a.value),
-        "comment" => comment = Some(a.value),
-        "protect" => protect = Some(a.value)
-    }
-
-    consume_current_element(ctx);
-
-    unwrap_attribute!(ctx, platform, name);
-    unwrap_attribute!(ctx, platform, protect);
-
-    Some(Platform {
-        name,
-        comment,
-        protect,
-    })
-}
-
-fn parse_tags<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> RegistryChild {
-    let mut comment = None;
-    let mut children = Vec::new();
-
-    match_attributes! {ctx, a in attributes,
-        "comment" => comment = Some(a.value)
-    }
-
-    match_elements! {ctx, attributes,
-        "tag" => if let Some(v) = parse_tag(ctx, attributes) {
-            children.push(v);
+        _ => {
+            registry.0.push(vulkan::parse_section(ctx, name, attributes))
         }
     }
-
-    RegistryChild::Tags(Tags { comment, children })
-}
-
-fn parse_tag<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> Option<Tag> {
-    let mut name = None;
-    let mut author = None;
-    let mut contact = None;
-
-    match_attributes! {ctx, a in attributes,
-        "name"    => name    = Some(a.value),
-        "author"  => author  = Some(a.value),
-        "contact" => contact = Some(a.value)
-    }
-
-    consume_current_element(ctx);
-
-    unwrap_attribute!(ctx, tag, name);
-    unwrap_attribute!(ctx, tag, author);
-    unwrap_attribute!(ctx, tag, contact);
-
-    Some(Tag {
-        name,
-        author,
-        contact,
-    })
 }
 
 fn parse_type<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> TypesChild {
@@ -464,29 +939,35 @@ fn parse_type<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
     let mut allowduplicate = None;
     let mut objtypeenum = None;
     let mut bitvalues = None;
+    let mut deprecated = None;
     let mut comment = None;
 
     let mut code = String::new();
-    let mut markup = Vec::new();
+    let mut markup = InlineVec::new();
     let mut members = Vec::new();
+    // Reused for all members, so that allocation is only made for the final string.
+    let mut member_code = String::new();
 
+    let raw = raw_attributes(ctx, &attributes);
     match_attributes! {ctx, a in attributes,
-        "api"            => api            = Some(a.value),
-        "alias"          => alias          = Some(a.value),
-        "requires"       => requires       = Some(a.value),
-        "name"           => name           = Some(a.value),
-        "category"       => category       = Some(a.value),
-        "parent"         => parent         = Some(a.value),
-        "returnedonly"   => returnedonly   = Some(a.value),
-        "structextends"  => structextends  = Some(a.value),
-        "allowduplicate" => allowduplicate = Some(a.value),
-        "objtypeenum"    => objtypeenum    = Some(a.value),
-        "bitvalues"      => bitvalues      = Some(a.value),
-        "comment"        => comment        = Some(a.value)
+        "api"            => api            = parse_api(ctx, a.value),
+        "alias"          => alias          = Some(a.value.into_boxed_str()),
+        "requires"       => requires       = Some(a.value.into_boxed_str()),
+        "name"           => name           = Some(a.value.into_boxed_str()),
+        "category"       => category       = Some(a.value.into_boxed_str()),
+        "parent"         => parent         = Some(a.value.into_boxed_str()),
+        "returnedonly"   => returnedonly   = Some(a.value.into_boxed_str()),
+        "structextends"  => structextends  = Some(a.value.into_boxed_str()),
+        "allowduplicate" => allowduplicate = Some(a.value.into_boxed_str()),
+        "objtypeenum"    => objtypeenum    = Some(a.value.into_boxed_str()),
+        "bitvalues"      => bitvalues      = Some(a.value.into_boxed_str()),
+        "deprecated"     => deprecated     = parse_deprecation(ctx, a.value),
+        "comment"        => comment        = Some(a.value.into_boxed_str())
     }
 
     match_elements_combine_text! {ctx, attributes, code,
         "member" => {
+            let mut api = None;
             let mut len = None;
             let mut altlen = None;
             let mut externsync = None;
@@ -498,35 +979,39 @@ fn parse_type<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
             let mut values = None;
             let mut limittype = None;
             let mut objecttype = None;
-            let mut code = String::new();
-            let mut markup = Vec::new();
+            let mut deprecated = None;
+            member_code.clear();
+            let mut markup = InlineVec::new();
+            let raw = raw_attributes(ctx, &attributes);
             match_attributes!{ctx, a in attributes,
-                "len"                   => len                   = Some(a.value),
-                "altlen"                => altlen                = Some(a.value),
-                "externsync"            => externsync            = Some(a.value),
-                "optional"              => optional              = Some(a.value),
-                "selector"              => selector              = Some(a.value),
-                "selection"             => selection             = Some(a.value),
-                "noautovalidity"        => noautovalidity        = Some(a.value),
-                "validextensionstructs" => validextensionstructs = Some(a.value),
-                "values"                => values                = Some(a.value),
-                "limittype"             => limittype             = Some(a.value),
-                "objecttype"            => objecttype            = Some(a.value)
+                "api"                   => api                   = parse_api(ctx, a.value),
+                "len"                   => len                   = Some(a.value.into_boxed_str()),
+                "altlen"                => altlen                = Some(a.value.into_boxed_str()),
+                "externsync"            => externsync            = Some(a.value.into_boxed_str()),
+                "optional"              => optional              = Some(a.value.into_boxed_str()),
+                "selector"              => selector              = Some(a.value.into_boxed_str()),
+                "selection"             => selection             = Some(a.value.into_boxed_str()),
+                "noautovalidity"        => noautovalidity        = Some(a.value.into_boxed_str()),
+                "validextensionstructs" => validextensionstructs = Some(a.value.into_boxed_str()),
+                "values"                => values                = Some(a.value.into_boxed_str()),
+                "limittype"             => limittype             = Some(a.value.into_boxed_str()),
+                "objecttype"            => objecttype            = Some(a.value.into_boxed_str()),
+                "deprecated"            => deprecated            = parse_deprecation(ctx, a.value)
             }
-            match_elements_combine_text!{ctx, code,
+            match_elements_combine_text!{ctx, member_code,
                 "type" => {
                     let text = parse_text_element(ctx);
-                    code.push_str(&text);
+                    member_code.push_str(&text);
                     markup.push(TypeMemberMarkup::Type(text));
                 },
                 "name" => {
                     let text = parse_text_element(ctx);
-                    code.push_str(&text);
+                    member_code.push_str(&text);
                     markup.push(TypeMemberMarkup::Name(text));
                 },
                 "enum" => {
                     let text = parse_text_element(ctx);
-                    code.push_str(&text);
+                    member_code.push_str(&text);
                     markup.push(TypeMemberMarkup::Enum(text));
                 },
                 "comment" => {
@@ -534,7 +1019,9 @@ fn parse_type<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
                     markup.push(TypeMemberMarkup::Comment(text));
                 }
             }
-            members.push(TypeMember::Definition(TypeMemberDefinition {
+            let mut definition = TypeMemberDefinition {
+                api,
+                deprecated,
                 len,
                 altlen,
                 externsync,
@@ -546,33 +1033,81 @@ fn parse_type<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
                 values,
                 limittype,
                 objecttype,
-                code,
+                c_type: c::parse_declaration(&member_code).map(|(c_type, _)| c_type),
+                code: Box::from(&*member_code),
                 markup,
-            }))
+                raw,
+            };
+            if ctx.options.omit_code && definition.c_type.is_some() {
+                definition.code = Box::default();
+            }
+            members.push(TypeMember::Definition(definition))
+        },
+        "comment" => {
+            ctx.ignored_attributes(&attributes);
+            members.push(TypeMember::Comment(parse_text_element(ctx)))
         },
-        "comment" => members.push(TypeMember::Comment(parse_text_element(ctx))),
         "name" => {
+            ctx.ignored_attributes(&attributes);
             let text = parse_text_element(ctx);
             code.push_str(&text);
             markup.push(TypeCodeMarkup::Name(text));
         },
         "type" => {
+            ctx.ignored_attributes(&attributes);
             let text = parse_text_element(ctx);
             code.push_str(&text);
             markup.push(TypeCodeMarkup::Type(text));
         },
         "apientry" => {
+            ctx.ignored_attributes(&attributes);
             let text = parse_text_element(ctx);
             code.push_str(&text);
             markup.push(TypeCodeMarkup::ApiEntry(text));
         }
     }
 
-    TypesChild::Type(Type {
-        api,
-        alias,
-        requires,
-        name,
+    let spec = if !members.is_empty() {
+        TypeSpec::Members(members)
+    } else if !code.is_empty() {
+        let has_directives = code.lines().any(|l| l.trim_start().starts_with('#'));
+        TypeSpec::Code(TypeCode {
+            directives: if has_directives {
+                c::parse_preprocessor(&code)
+            } else {
+                Vec::new()
+            },
+            calling_convention: match category.as_deref() {
+                Some("funcpointer") => funcpointer_calling_convention(&code, &markup),
+                _ => None,
+            },
+            code: code.into_boxed_str(),
+            markup,
+        })
+    } else {
+        match (category.as_deref(), &alias) {
+            (Some("handle"), Some(alias)) => TypeSpec::HandleAlias {
+                alias: alias.clone(),
+            },
+            (Some("bitmask"), Some(alias)) => TypeSpec::BitmaskAlias {
+                alias: alias.clone(),
+            },
+            (Some("struct"), Some(alias)) => TypeSpec::StructAlias {
+                alias: alias.clone(),
+            },
+            (Some("union"), Some(alias)) => TypeSpec::UnionAlias {
+                alias: alias.clone(),
+            },
+            _ => TypeSpec::None,
+        }
+    };
+
+    TypesChild::Type(Type {
+        api,
+        alias,
+        deprecated,
+        requires,
+        name,
         category,
         parent,
         returnedonly,
@@ -581,45 +1116,70 @@ fn parse_type<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
         objtypeenum,
         bitvalues,
         comment,
-        spec: if members.len() > 0 {
-            TypeSpec::Members(members)
-        } else if code.len() > 0 {
-            TypeSpec::Code(TypeCode { code, markup })
-        } else {
-            TypeSpec::None
-        },
+        spec,
+        raw,
     })
 }
 
+/// Calling convention of a function pointer type, see `TypeCode::calling_convention`.
+fn funcpointer_calling_convention(code: &str, markup: &[TypeCodeMarkup]) -> Option<Box<str>> {
+    let api_entry = markup.iter().find_map(|m| match *m {
+        TypeCodeMarkup::ApiEntry(ref text) => Some(text),
+        _ => None,
+    });
+    if let Some(text) = api_entry {
+        return Some(text.clone());
+    }
+    let mut tokens = code
+        .split_whitespace()
+        .flat_map(c::TokenIter::new)
+        .skip_while(|&token| token != "(")
+        .skip(1);
+    match (tokens.next(), tokens.next()) {
+        (Some(convention), Some("*")) if c::is_c_identifier(convention) => Some(convention.into()),
+        _ => None,
+    }
+}
+
 fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> Option<Command> {
     let mut name = None;
     let mut alias = None;
-    let mut queues = None;
-    let mut successcodes = None;
-    let mut errorcodes = None;
+    let mut api = None;
+    let mut queues = Vec::new();
+    let mut successcodes = Vec::new();
+    let mut errorcodes = Vec::new();
     let mut renderpass = None;
     let mut videocoding = None;
     let mut cmdbufferlevel = None;
     let mut pipeline = None;
+    let mut deprecated = None;
     let mut comment = None;
 
+    let raw = raw_attributes(ctx, &attributes);
     match_attributes! {ctx, a in attributes,
-        "name" => name = Some(a.value),
-        "alias" => alias = Some(a.value),
-        "queues" => queues = Some(a.value),
-        "successcodes" => successcodes = Some(a.value),
-        "errorcodes" => errorcodes = Some(a.value),
-        "renderpass" => renderpass = Some(a.value),
-        "videocoding" => videocoding = Some(a.value),
-        "cmdbufferlevel" => cmdbufferlevel = Some(a.value),
-        "pipeline" => pipeline = Some(a.value),
-        "comment" => comment = Some(a.value)
+        "name" => name = Some(a.value.into_boxed_str()),
+        "alias" => alias = Some(a.value.into_boxed_str()),
+        "api" => api = parse_api(ctx, a.value),
+        "queues" => queues = parse_queues(ctx, a.value),
+        "successcodes" => successcodes = parse_name_list(ctx, "successcodes", a.value),
+        "errorcodes" => errorcodes = parse_name_list(ctx, "errorcodes", a.value),
+        "renderpass" => renderpass = Some(a.value.into_boxed_str()),
+        "videocoding" => videocoding = parse_video_coding(ctx, a.value),
+        "cmdbufferlevel" => cmdbufferlevel = Some(a.value.into_boxed_str()),
+        "pipeline" => pipeline = Some(a.value.into_boxed_str()),
+        "deprecated" => deprecated = parse_deprecation(ctx, a.value),
+        "comment" => comment = Some(a.value.into_boxed_str())
     }
 
     if let Some(alias) = alias {
         unwrap_attribute!(ctx, command, name);
-        consume_current_element(ctx);
-        Some(Command::Alias { alias, name })
+        consume_leaf_element(ctx);
+        Some(Command::Alias {
+            alias,
+            name,
+            deprecated,
+            raw,
+        })
     } else {
         let mut code = String::new();
         let mut proto = None;
@@ -631,8 +1191,12 @@ fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>)
             ctx: &mut ParseCtx<R>,
             buffer: &mut String,
         ) -> Option<NameWithType> {
+            let start = buffer.len();
             let mut name = None;
             let mut type_name = None;
+            let mut calling_convention = None;
+            // Code of the calling convention, which isn't part of the declared type.
+            let mut convention_code = start..start;
             match_elements_combine_text! {ctx, buffer,
                 "type" => {
                     let text = parse_text_element(ctx);
@@ -643,19 +1207,39 @@ fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>)
                     let text = parse_text_element(ctx);
                     buffer.push_str(&text);
                     name = Some(text);
+                },
+                "apientry" => {
+                    let text = parse_text_element(ctx);
+                    convention_code = buffer.len()..buffer.len() + text.len();
+                    buffer.push_str(&text);
+                    calling_convention = Some(text);
                 }
             }
             let name = if let Some(v) = name {
                 v
             } else {
                 ctx.errors.push(Error::MissingElement {
-                    xpath: ctx.xpath.clone(),
+                    xpath: ctx.xpath(),
                     name: String::from("name"),
                 });
                 return None;
             };
 
-            Some(NameWithType { name, type_name })
+            // Only prototypes with `<apientry>` text, as in the OpenGL registry, need a copy
+            // without it, so parameters are parsed straight from the buffer.
+            let c_type = if convention_code.is_empty() {
+                c::parse_declaration(&buffer[start..]).map(|(c_type, _)| c_type)
+            } else {
+                let mut declaration = String::from(&buffer[start..convention_code.start]);
+                declaration.push_str(&buffer[convention_code.end..]);
+                c::parse_declaration(&declaration).map(|(c_type, _)| c_type)
+            };
+            Some(NameWithType {
+                c_type,
+                name,
+                type_name,
+                calling_convention,
+            })
         }
 
         match_elements! {ctx, attributes,
@@ -665,6 +1249,7 @@ fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>)
             },
 
             "param" => {
+                let mut api = None;
                 let mut len = None;
                 let mut altlen = None;
                 let mut externsync = None;
@@ -672,20 +1257,24 @@ fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>)
                 let mut noautovalidity = None;
                 let mut objecttype = None;
                 let mut validstructs = None;
+                let mut deprecated = None;
 
+                let raw = raw_attributes(ctx, &attributes);
                 match_attributes!{ctx, a in attributes,
-                    "len"            => len            = Some(a.value),
-                    "altlen"         => altlen         = Some(a.value),
-                    "externsync"     => externsync     = Some(a.value),
-                    "optional"       => optional       = Some(a.value),
-                    "noautovalidity" => noautovalidity = Some(a.value),
-                    "objecttype"     => objecttype     = Some(a.value),
-                    "validstructs"   => validstructs   = Some(a.value),
+                    "api"            => api            = parse_api(ctx, a.value),
+                    "deprecated"     => deprecated     = parse_deprecation(ctx, a.value),
+                    "len"            => len            = Some(a.value.into_boxed_str()),
+                    "altlen"         => altlen         = Some(a.value.into_boxed_str()),
+                    "externsync"     => externsync     = Some(a.value.into_boxed_str()),
+                    "optional"       => optional       = Some(a.value.into_boxed_str()),
+                    "noautovalidity" => noautovalidity = Some(a.value.into_boxed_str()),
+                    "objecttype"     => objecttype     = Some(a.value.into_boxed_str()),
+                    "validstructs"   => validstructs   = Some(a.value.into_boxed_str()),
                 }
 
                 let validstructs = validstructs.map_or(
                     Default::default(),
-                    |structs| structs.split(',').map(|s| s.to_owned()).collect()
+                    |structs| structs.split(',').map(Box::from).collect()
                 );
 
                 if !params.is_empty() {
@@ -693,6 +1282,8 @@ fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>)
                 }
                 if let Some(definition) = parse_name_with_type(ctx, &mut code) {
                     params.push(CommandParam {
+                        api,
+                        deprecated,
                         len,
                         altlen,
                         externsync,
@@ -701,18 +1292,22 @@ fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>)
                         objecttype,
                         definition,
                         validstructs,
+                        raw,
                     });
                 }
             },
 
             "alias" => {
                 match_attributes!{ctx, a in attributes,
-                    "name" => alias = Some(a.value)
+                    "name" => alias = Some(a.value.into_boxed_str())
                 }
-                consume_current_element(ctx);
+                consume_leaf_element(ctx);
             },
 
-            "description" => description = Some(parse_text_element(ctx)),
+            "description" => {
+                ctx.ignored_attributes(&attributes);
+                description = Some(parse_text_element(ctx))
+            },
             "implicitexternsyncparams" => {
                 match_elements!{ctx,
                     "param" => implicitexternsyncparams.push(parse_text_element(ctx))
@@ -725,13 +1320,15 @@ fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>)
             v
         } else {
             ctx.errors.push(Error::MissingElement {
-                xpath: ctx.xpath.clone(),
+                xpath: ctx.xpath(),
                 name: String::from("proto"),
             });
             return None;
         };
 
-        Some(Command::Definition(CommandDefinition {
+        let mut definition = CommandDefinition {
+            api,
+            deprecated,
             queues,
             successcodes,
             errorcodes,
@@ -745,8 +1342,19 @@ fn parse_command<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>)
             alias,
             description,
             implicitexternsyncparams,
-            code,
-        }))
+            code: code.into_boxed_str(),
+            raw,
+        };
+        if ctx.options.omit_code
+            && definition.proto.c_type.is_some()
+            && definition
+                .params
+                .iter()
+                .all(|p| p.definition.c_type.is_some())
+        {
+            definition.code = Box::default();
+        }
+        Some(Command::Definition(definition))
     }
 }
 
@@ -763,30 +1371,33 @@ fn parse_enum<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
     let mut positive = true;
     let mut protect = None;
     let mut alias = None;
+    let mut deprecated = None;
 
+    let raw = raw_attributes(ctx, &attributes);
     match_attributes! {ctx, a in attributes,
-        "name" => name = Some(a.value),
-        "comment" => comment = Some(a.value),
-        "type" => type_suffix = Some(a.value),
-        "api" => api = Some(a.value),
-        "extends" => extends = Some(a.value),
-        "value" => value = Some(a.value),
-        "offset" => offset = Some(a.value),
+        "name" => name = Some(a.value.into_boxed_str()),
+        "comment" => comment = Some(a.value.into_boxed_str()),
+        "type" => type_suffix = Some(a.value.into_boxed_str()),
+        "api" => api = parse_api(ctx, a.value),
+        "extends" => extends = Some(a.value.into_boxed_str()),
+        "value" => value = Some(a.value.into_boxed_str()),
+        "offset" => offset = Some(a.value.into_boxed_str()),
         "dir" => {
             if a.value.as_str() == "-" {
                 positive = false;
             } else {
                 ctx.errors.push(Error::UnexpectedAttributeValue {
-                    xpath: ctx.xpath.clone(),
+                    xpath: ctx.xpath(),
                     name: String::from("dir"),
                     value: a.value
                 });
             }
         },
-        "bitpos" => bitpos = Some(a.value),
-        "extnumber" => extnumber = Some(a.value),
-        "protect" => protect = Some(a.value),
-        "alias" => alias = Some(a.value)
+        "bitpos" => bitpos = Some(a.value.into_boxed_str()),
+        "extnumber" => extnumber = Some(a.value.into_boxed_str()),
+        "protect" => protect = Some(a.value.into_boxed_str()),
+        "alias" => alias = Some(a.value.into_boxed_str()),
+        "deprecated" => deprecated = parse_deprecation(ctx, a.value)
     }
 
     unwrap_attribute!(ctx, enum, name);
@@ -806,7 +1417,7 @@ fn parse_enum<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
     }
     if count > 1 {
         ctx.errors.push(Error::SchemaViolation {
-            xpath: ctx.xpath.clone(),
+            xpath: ctx.xpath(),
             desc: format!(
                 "Unable to determine correct specification of enum: offset={:?}, bitpos={:?}, value={:?}, alias={:?}",
                 offset, bitpos, value, alias
@@ -838,7 +1449,7 @@ fn parse_enum<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
             }
         } else {
             ctx.errors.push(Error::SchemaViolation {
-                xpath: ctx.xpath.clone(),
+                xpath: ctx.xpath(),
                 desc: String::from("Missing extends on enum with offset spec."),
             });
             consume_current_element(ctx);
@@ -859,15 +1470,18 @@ fn parse_enum<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) ->
         EnumSpec::None
     };
 
-    consume_current_element(ctx);
+    consume_leaf_element(ctx);
 
     Some(Enum {
         name,
         comment,
         type_suffix,
         api,
+        deprecated,
         protect,
         spec,
+        required_by: None,
+        raw,
     })
 }
 
@@ -879,24 +1493,23 @@ fn parse_enums_child_unused<R: Read>(
     let mut end = None;
     let mut vendor = None;
     let mut comment = None;
+    let raw = raw_attributes(ctx, &attributes);
     match_attributes! {ctx, a in attributes,
-        "start"   => start   = Some(a.value),
-        "end"     => end     = Some(a.value),
-        "vendor"  => vendor  = Some(a.value),
-        "comment" => comment = Some(a.value)
+        "start"   => start   = Some(a.value.into_boxed_str()),
+        "end"     => end     = Some(a.value.into_boxed_str()),
+        "vendor"  => vendor  = Some(a.value.into_boxed_str()),
+        "comment" => comment = Some(a.value.into_boxed_str())
     }
-    consume_current_element(ctx);
+    consume_leaf_element(ctx);
     unwrap_attribute!(ctx, unused, start);
-    let start = match parse_integer(ctx, &start) {
-        Some(v) => v,
-        None => return None,
-    };
+    let start = parse_integer(ctx, &start)?;
     let end = end.and_then(|val| parse_integer(ctx, &val));
     Some(EnumsChild::Unused(Unused {
         start,
         end,
         vendor,
         comment,
+        raw,
     }))
 }
 
@@ -911,17 +1524,19 @@ fn parse_feature<R: Read>(
     let mut comment = None;
     let mut children = Vec::new();
 
+    let raw = raw_attributes(ctx, &attributes);
     match_attributes! {ctx, a in attributes,
-        "api"     => api     = Some(a.value),
-        "name"    => name    = Some(a.value),
-        "number"  => number  = Some(a.value),
-        "protect" => protect = Some(a.value),
-        "comment" => comment = Some(a.value)
+        "api"     => api     = parse_api(ctx, a.value),
+        "name"    => name    = Some(a.value.into_boxed_str()),
+        "number"  => number  = parse_api_version(ctx, a.value),
+        "protect" => protect = Some(a.value.into_boxed_str()),
+        "comment" => comment = Some(a.value.into_boxed_str())
     }
 
     match_elements! {ctx, attributes,
-        "require" => children.push(parse_extension_item_require(ctx, attributes)),
-        "remove"  => children.push(parse_extension_item_remove(ctx, attributes))
+        "require"   => children.push(parse_extension_item_require(ctx, attributes)),
+        "remove"    => children.push(parse_extension_item_remove(ctx, attributes)),
+        "deprecate" => children.push(parse_extension_item_deprecate(ctx, attributes))
     }
 
     unwrap_attribute!(ctx, feature, api);
@@ -935,6 +1550,7 @@ fn parse_feature<R: Read>(
         protect,
         comment,
         children,
+        raw,
     }))
 }
 
@@ -946,7 +1562,7 @@ fn parse_extensions<R: Read>(
     let mut children = Vec::new();
 
     match_attributes! {ctx, a in attributes,
-        "comment" => comment = Some(a.value)
+        "comment" => comment = Some(a.value.into_boxed_str())
     }
 
     match_elements! {ctx, attributes,
@@ -972,8 +1588,10 @@ fn parse_extension<R: Read>(
     let mut ext_type = None;
     let mut requires = None;
     let mut requires_core = None;
+    let mut depends = None;
     let mut supported = None;
     let mut deprecatedby = None;
+    let mut deprecated = None;
     let mut promotedto = None;
     let mut obsoletedby = None;
     let mut provisional = None;
@@ -981,24 +1599,27 @@ fn parse_extension<R: Read>(
     let mut sortorder = None;
     let mut children = Vec::new();
 
+    let raw = raw_attributes(ctx, &attributes);
     match_attributes! {ctx, a in attributes,
-        "name"         => name          = Some(a.value),
-        "comment"      => comment       = Some(a.value),
-        "number"       => number        = Some(a.value),
-        "protect"      => protect       = Some(a.value),
-        "platform"     => platform      = Some(a.value),
-        "author"       => author        = Some(a.value),
-        "contact"      => contact       = Some(a.value),
-        "type"         => ext_type      = Some(a.value),
-        "requires"     => requires      = Some(a.value),
-        "requiresCore" => requires_core = Some(a.value),
-        "supported"    => supported     = Some(a.value),
-        "deprecatedby" => deprecatedby  = Some(a.value),
-        "promotedto"   => promotedto    = Some(a.value),
-        "provisional"  => provisional   = Some(a.value),
-        "obsoletedby"  => obsoletedby   = Some(a.value),
-        "specialuse"   => specialuse    = Some(a.value),
-        "sortorder"    => sortorder     = Some(a.value)
+        "name"         => name          = Some(a.value.into_boxed_str()),
+        "comment"      => comment       = Some(a.value.into_boxed_str()),
+        "number"       => number        = Some(a.value.into_boxed_str()),
+        "protect"      => protect       = Some(a.value.into_boxed_str()),
+        "platform"     => platform      = Some(a.value.into_boxed_str()),
+        "author"       => author        = Some(a.value.into_boxed_str()),
+        "contact"      => contact       = Some(a.value.into_boxed_str()),
+        "type"         => ext_type      = Some(a.value.into_boxed_str()),
+        "requires"     => requires      = Some(a.value.into_boxed_str()),
+        "requiresCore" => requires_core = Some(a.value.into_boxed_str()),
+        "depends"      => depends       = parse_depends(ctx, a.value),
+        "supported"    => supported     = parse_supported(ctx, a.value),
+        "deprecatedby" => deprecatedby  = Some(a.value.into_boxed_str()),
+        "deprecated"   => deprecated    = parse_deprecation(ctx, a.value),
+        "promotedto"   => promotedto    = Some(a.value.into_boxed_str()),
+        "provisional"  => provisional   = Some(a.value.into_boxed_str()),
+        "obsoletedby"  => obsoletedby   = Some(a.value.into_boxed_str()),
+        "specialuse"   => specialuse    = Some(a.value.into_boxed_str()),
+        "sortorder"    => sortorder     = Some(a.value.into_boxed_str())
     }
 
     let number = match number {
@@ -1008,11 +1629,11 @@ fn parse_extension<R: Read>(
 
     let provisional = match provisional {
         Some(value) => {
-            if value == "true" {
+            if &*value == "true" {
                 true
             } else {
                 ctx.errors.push(Error::SchemaViolation {
-                    xpath: ctx.xpath.clone(),
+                    xpath: ctx.xpath(),
                     desc: format!("Unexpected value of 'provisional' attribute: {}", value),
                 });
                 false
@@ -1028,9 +1649,14 @@ fn parse_extension<R: Read>(
 
     unwrap_attribute!(ctx, extension, name);
 
-    match_elements! {ctx, attributes,
-        "require" => children.push(parse_extension_item_require(ctx, attributes)),
-        "remove" => children.push(parse_extension_item_remove(ctx, attributes))
+    if ctx.options.skip_disabled_extensions && supported == Some(ExtensionSupport::Disabled) {
+        consume_current_element(ctx);
+    } else {
+        match_elements! {ctx, attributes,
+            "require" => children.push(parse_extension_item_require(ctx, attributes)),
+            "remove" => children.push(parse_extension_item_remove(ctx, attributes)),
+            "deprecate" => children.push(parse_extension_item_deprecate(ctx, attributes))
+        }
     }
 
     Some(Extension {
@@ -1044,17 +1670,125 @@ fn parse_extension<R: Read>(
         ext_type,
         requires,
         requires_core,
+        depends,
         supported,
         deprecatedby,
+        deprecated,
         promotedto,
         obsoletedby,
         provisional,
         specialuse,
         sortorder,
         children,
+        raw,
     })
 }
 
+/// Parses value of a `depends` attribute, reporting malformed expressions.
+fn parse_depends<R: Read>(ctx: &mut ParseCtx<R>, value: String) -> Option<DependsExpr> {
+    let expr = DependsExpr::parse(&value);
+    if expr.is_none() {
+        ctx.errors.push(Error::UnexpectedAttributeValue {
+            xpath: ctx.xpath(),
+            name: String::from("depends"),
+            value,
+        });
+    }
+    expr
+}
+
+fn parse_api<R: Read>(ctx: &mut ParseCtx<R>, value: String) -> Option<ApiSet> {
+    let apis = ApiSet::parse(&value);
+    if apis.is_none() {
+        ctx.errors.push(Error::UnexpectedAttributeValue {
+            xpath: ctx.xpath(),
+            name: String::from("api"),
+            value,
+        });
+    }
+    apis
+}
+
+fn parse_api_version<R: Read>(ctx: &mut ParseCtx<R>, value: String) -> Option<ApiVersion> {
+    let version = ApiVersion::parse(&value);
+    if version.is_none() {
+        ctx.errors.push(Error::UnexpectedAttributeValue {
+            xpath: ctx.xpath(),
+            name: String::from("number"),
+            value,
+        });
+    }
+    version
+}
+
+fn parse_supported<R: Read>(ctx: &mut ParseCtx<R>, value: String) -> Option<ExtensionSupport> {
+    let supported = ExtensionSupport::parse(&value);
+    if supported.is_none() {
+        ctx.errors.push(Error::UnexpectedAttributeValue {
+            xpath: ctx.xpath(),
+            name: String::from("supported"),
+            value,
+        });
+    }
+    supported
+}
+
+pub(crate) fn parse_queues<R: Read>(ctx: &mut ParseCtx<R>, value: String) -> Vec<QueueKind> {
+    match QueueKind::parse_list(&value) {
+        Some(queues) => queues,
+        None => {
+            ctx.errors.push(Error::UnexpectedAttributeValue {
+                xpath: ctx.xpath(),
+                name: String::from("queues"),
+                value,
+            });
+            Vec::new()
+        }
+    }
+}
+
+/// Comma-separated names, e.g. result codes of `successcodes`, reporting lists with an empty
+/// entry, like `parse_queues`.
+pub(crate) fn parse_name_list<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    name: &str,
+    value: String,
+) -> Vec<Box<str>> {
+    if value.split(',').any(str::is_empty) {
+        ctx.errors.push(Error::UnexpectedAttributeValue {
+            xpath: ctx.xpath(),
+            name: String::from(name),
+            value,
+        });
+        return Vec::new();
+    }
+    value.split(',').map(Box::from).collect()
+}
+
+fn parse_video_coding<R: Read>(ctx: &mut ParseCtx<R>, value: String) -> Option<VideoCoding> {
+    let video_coding = VideoCoding::from_name(&value);
+    if video_coding.is_none() {
+        ctx.errors.push(Error::UnexpectedAttributeValue {
+            xpath: ctx.xpath(),
+            name: String::from("videocoding"),
+            value,
+        });
+    }
+    video_coding
+}
+
+fn parse_deprecation<R: Read>(ctx: &mut ParseCtx<R>, value: String) -> Option<Deprecation> {
+    let deprecation = Deprecation::from_name(&value);
+    if deprecation.is_none() {
+        ctx.errors.push(Error::UnexpectedAttributeValue {
+            xpath: ctx.xpath(),
+            name: String::from("deprecated"),
+            value,
+        });
+    }
+    deprecation
+}
+
 fn parse_extension_item_require<R: Read>(
     ctx: &mut ParseCtx<R>,
     attributes: Vec<XmlAttribute>,
@@ -1063,18 +1797,21 @@ fn parse_extension_item_require<R: Read>(
     let mut profile = None;
     let mut extension = None;
     let mut feature = None;
+    let mut depends = None;
     let mut comment = None;
     let mut items = Vec::new();
 
+    let raw = raw_attributes(ctx, &attributes);
     match_attributes! {ctx, a in attributes,
-        "api"       => api       = Some(a.value),
-        "profile"   => profile   = Some(a.value),
-        "extension" => extension = Some(a.value),
-        "feature"   => feature   = Some(a.value),
-        "comment"   => comment   = Some(a.value)
+        "api"       => api       = parse_api(ctx, a.value),
+        "profile"   => profile   = Some(a.value.into_boxed_str()),
+        "extension" => extension = Some(a.value.into_boxed_str()),
+        "feature"   => feature   = Some(a.value.into_boxed_str()),
+        "depends"   => depends   = parse_depends(ctx, a.value),
+        "comment"   => comment   = Some(a.value.into_boxed_str())
     }
 
-    while let Some(Ok(e)) = ctx.events.next() {
+    while let Some(e) = ctx.next_event() {
         match e {
             XmlEvent::StartElement {
                 name, attributes, ..
@@ -1098,8 +1835,10 @@ fn parse_extension_item_require<R: Read>(
         profile,
         extension,
         feature,
+        depends,
         comment,
         items,
+        raw,
     }
 }
 
@@ -1112,13 +1851,14 @@ fn parse_extension_item_remove<R: Read>(
     let mut comment = None;
     let mut items = Vec::new();
 
+    let raw = raw_attributes(ctx, &attributes);
     match_attributes! {ctx, a in attributes,
-        "api"     => api     = Some(a.value),
-        "profile" => profile = Some(a.value),
-        "comment" => comment = Some(a.value)
+        "api"     => api     = parse_api(ctx, a.value),
+        "profile" => profile = Some(a.value.into_boxed_str()),
+        "comment" => comment = Some(a.value.into_boxed_str())
     }
 
-    while let Some(Ok(e)) = ctx.events.next() {
+    while let Some(e) = ctx.next_event() {
         match e {
             XmlEvent::StartElement {
                 name, attributes, ..
@@ -1142,6 +1882,54 @@ fn parse_extension_item_remove<R: Read>(
         profile,
         comment,
         items,
+        raw,
+    }
+}
+
+fn parse_extension_item_deprecate<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> ExtensionChild {
+    let mut api = None;
+    let mut profile = None;
+    let mut explanationlink = None;
+    let mut comment = None;
+    let mut items = Vec::new();
+
+    let raw = raw_attributes(ctx, &attributes);
+    match_attributes! {ctx, a in attributes,
+        "api"             => api             = parse_api(ctx, a.value),
+        "profile"         => profile         = Some(a.value.into_boxed_str()),
+        "explanationlink" => explanationlink = Some(a.value.into_boxed_str()),
+        "comment"         => comment         = Some(a.value.into_boxed_str())
+    }
+
+    while let Some(e) = ctx.next_event() {
+        match e {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let name = name.local_name.as_str();
+                ctx.push_element(name);
+                if let Some(v) = parse_interface_item(ctx, name, attributes) {
+                    items.push(v);
+                }
+            }
+            XmlEvent::EndElement { .. } => {
+                ctx.pop_element();
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    ExtensionChild::Deprecate {
+        api,
+        profile,
+        explanationlink,
+        comment,
+        items,
+        raw,
     }
 }
 
@@ -1151,394 +1939,125 @@ fn parse_interface_item<R: Read>(
     attributes: Vec<XmlAttribute>,
 ) -> Option<InterfaceItem> {
     match name {
-        "comment" => Some(InterfaceItem::Comment(parse_text_element(ctx))),
+        "comment" if ctx.options.strip_comments => {
+            consume_current_element(ctx);
+            None
+        }
+        "comment" => {
+            ctx.ignored_attributes(&attributes);
+            Some(InterfaceItem::Comment(parse_text_element(ctx)))
+        }
         "type" => {
             let mut name = None;
             let mut comment = None;
             match_attributes! {ctx, a in attributes,
-                "name"    => name    = Some(a.value),
-                "comment" => comment = Some(a.value)
+                "name"    => name    = Some(a.value.into_boxed_str()),
+                "comment" => comment = Some(a.value.into_boxed_str())
             }
             unwrap_attribute!(ctx, type, name);
-            consume_current_element(ctx);
+            consume_leaf_element(ctx);
             Some(InterfaceItem::Type { name, comment })
         }
-        "enum" => parse_enum(ctx, attributes).map(|v| InterfaceItem::Enum(v)),
+        "enum" => parse_enum(ctx, attributes).map(InterfaceItem::Enum),
         "command" => {
             let mut name = None;
             let mut comment = None;
             match_attributes! {ctx, a in attributes,
-                "name"    => name    = Some(a.value),
-                "comment" => comment = Some(a.value)
+                "name"    => name    = Some(a.value.into_boxed_str()),
+                "comment" => comment = Some(a.value.into_boxed_str())
             }
             unwrap_attribute!(ctx, type, name);
-            consume_current_element(ctx);
+            consume_leaf_element(ctx);
             Some(InterfaceItem::Command { name, comment })
         }
         _ => {
             ctx.errors.push(Error::UnexpectedElement {
-                xpath: ctx.xpath.clone(),
+                xpath: ctx.xpath(),
                 name: String::from(name),
             });
-            return None;
-        }
-    }
-}
-
-fn parse_formats<R: Read>(ctx: &mut ParseCtx<R>) -> RegistryChild {
-    let mut children = Vec::new();
-
-    match_elements! {ctx, attributes,
-        "format" => if let Some(v) = parse_format(ctx, attributes) {
-            children.push(v);
+            None
         }
     }
-
-    RegistryChild::Formats(Formats {
-        comment: None,
-        children,
-    })
-}
-
-#[allow(non_snake_case)]
-fn parse_format<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> Option<Format> {
-    let mut name = None;
-    let mut class = None;
-    let mut blockSize = None;
-    let mut texelsPerBlock = None;
-    let mut blockExtent = None;
-    let mut packed = None;
-    let mut compressed = None;
-    let mut chroma = None;
-    let mut children = Vec::new();
-
-    match_attributes! {ctx, a in attributes,
-        "name"           => name           = Some(a.value),
-        "class"          => class          = Some(a.value),
-        "blockSize"      => blockSize      = Some(a.value),
-        "texelsPerBlock" => texelsPerBlock = Some(a.value),
-        "blockExtent"    => blockExtent    = Some(a.value),
-        "packed"         => packed         = Some(a.value),
-        "compressed"     => compressed     = Some(a.value),
-        "chroma"         => chroma         = Some(a.value)
-    }
-
-    unwrap_attribute!(ctx, extension, name);
-    unwrap_attribute!(ctx, extension, class);
-    unwrap_attribute!(ctx, extension, blockSize);
-    unwrap_attribute!(ctx, extension, texelsPerBlock);
-
-    match_elements! {ctx, attributes,
-        "component"        => if let Some(v) = parse_format_component(ctx, attributes) { children.push(v); },
-        "plane"            => if let Some(v) = parse_format_plane(ctx, attributes) { children.push(v); },
-        "spirvimageformat" => if let Some(v) = parse_format_spirvimageformat(ctx, attributes) { children.push(v); }
-    }
-
-    let blockSize: Option<u8> = parse_int_attribute(ctx, blockSize, "blockSize");
-    let texelsPerBlock: Option<u8> = parse_int_attribute(ctx, texelsPerBlock, "texelsPerBlock");
-    let packed = packed.map(|v| -> Option<u8> { parse_int_attribute(ctx, v, "packed") });
-
-    let blockSize = match blockSize {
-        Some(v) => v,
-        None => return None,
-    };
-
-    let texelsPerBlock = match texelsPerBlock {
-        Some(v) => v,
-        None => return None,
-    };
-
-    let packed = match packed {
-        Some(Some(v)) => Some(v),
-        Some(None) => return None, // Attribute present, but parse error occurred.
-        None => None,
-    };
-
-    Some(Format {
-        name,
-        class,
-        blockSize,
-        texelsPerBlock,
-        blockExtent,
-        packed,
-        compressed,
-        chroma,
-        children,
-    })
-}
-
-#[allow(non_snake_case)]
-fn parse_format_component<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> Option<FormatChild> {
-    let mut name = None;
-    let mut bits = None;
-    let mut numericFormat = None;
-    let mut planeIndex = None;
-
-    match_attributes! {ctx, a in attributes,
-        "name"          => name          = Some(a.value),
-        "bits"          => bits          = Some(a.value),
-        "numericFormat" => numericFormat = Some(a.value),
-        "planeIndex"    => planeIndex    = Some(a.value)
-    }
-
-    unwrap_attribute!(ctx, extension, name);
-    unwrap_attribute!(ctx, extension, bits);
-    unwrap_attribute!(ctx, extension, numericFormat);
-
-    consume_current_element(ctx);
-
-    let planeIndex =
-        planeIndex.map(|v| -> Option<u8> { parse_int_attribute(ctx, v, "planeIndex") });
-
-    let planeIndex = match planeIndex {
-        Some(Some(v)) => Some(v),
-        Some(None) => return None, // Attribute present, but parse error occurred.
-        None => None,
-    };
-
-    Some(FormatChild::Component {
-        name,
-        bits,
-        numericFormat,
-        planeIndex,
-    })
 }
 
-#[allow(non_snake_case)]
-fn parse_format_plane<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> Option<FormatChild> {
-    let mut index = None;
-    let mut widthDivisor = None;
-    let mut heightDivisor = None;
-    let mut compatible = None;
-
-    match_attributes! {ctx, a in attributes,
-        "index"         => index         = Some(a.value),
-        "widthDivisor"  => widthDivisor  = Some(a.value),
-        "heightDivisor" => heightDivisor = Some(a.value),
-        "compatible"    => compatible    = Some(a.value)
-    }
-
-    unwrap_attribute!(ctx, extension, index);
-    unwrap_attribute!(ctx, extension, widthDivisor);
-    unwrap_attribute!(ctx, extension, heightDivisor);
-    unwrap_attribute!(ctx, extension, compatible);
-
-    consume_current_element(ctx);
-
-    let index: Option<u8> = parse_int_attribute(ctx, index, "index");
-    let widthDivisor: Option<u8> = parse_int_attribute(ctx, widthDivisor, "widthDivisor");
-    let heightDivisor: Option<u8> = parse_int_attribute(ctx, heightDivisor, "heightDivisor");
-
-    let index = match index {
-        Some(v) => v,
-        None => return None,
-    };
-
-    let widthDivisor = match widthDivisor {
-        Some(v) => v,
-        None => return None,
-    };
-
-    let heightDivisor = match heightDivisor {
-        Some(v) => v,
-        None => return None,
+/// Splits integer literal into its sign, digits and radix. Octal literals start with `0` and
+/// hexadecimal ones with `0x`, as in C. Integer suffixes such as `U` or `ULL` are ignored.
+fn split_integer(text: &str) -> (bool, &str, u32) {
+    let text = text.trim();
+    let (negative, text) = if let Some(rest) = text.strip_prefix('-') {
+        (true, rest)
+    } else {
+        (false, text.strip_prefix('+').unwrap_or(text))
     };
-
-    Some(FormatChild::Plane {
-        index,
-        widthDivisor,
-        heightDivisor,
-        compatible,
-    })
-}
-
-fn parse_format_spirvimageformat<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> Option<FormatChild> {
-    let mut name = None;
-
-    match_attributes! {ctx, a in attributes,
-        "name" => name = Some(a.value)
-    }
-
-    unwrap_attribute!(ctx, extension, name);
-
-    consume_current_element(ctx);
-
-    Some(FormatChild::SpirvImageFormat { name })
-}
-
-fn parse_spirvextensions<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> RegistryChild {
-    let mut comment = None;
-    let mut children = Vec::new();
-
-    match_attributes! {ctx, a in attributes,
-        "comment" => comment = Some(a.value)
-    }
-
-    match_elements! {ctx, attributes,
-        "spirvextension" => if let Some(v) = parse_spirvextension(ctx, attributes) {
-            children.push(v);
-        }
-    }
-
-    RegistryChild::SpirvExtensions(SpirvExtensions { comment, children })
-}
-
-fn parse_spirvextension<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> Option<SpirvExtension> {
-    let mut name = None;
-    let mut enables = Vec::new();
-
-    match_attributes! {ctx, a in attributes,
-        "name" => name = Some(a.value)
-    }
-
-    match_elements! {ctx, attributes,
-        "enable" => if let Some(v) = parse_enable(ctx, attributes) {
-            enables.push(v);
-        }
-    }
-
-    unwrap_attribute!(ctx, spirvextension, name);
-
-    Some(SpirvExtension { name, enables })
-}
-
-fn parse_spirvcapabilities<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> RegistryChild {
-    let mut comment = None;
-    let mut children = Vec::new();
-
-    match_attributes! {ctx, a in attributes,
-        "comment" => comment = Some(a.value)
-    }
-
-    match_elements! {ctx, attributes,
-        "spirvcapability" => if let Some(v) = parse_spirvcapability(ctx, attributes) {
-            children.push(v);
-        }
-    }
-
-    RegistryChild::SpirvCapabilities(SpirvCapabilities { comment, children })
-}
-
-fn parse_spirvcapability<R: Read>(
-    ctx: &mut ParseCtx<R>,
-    attributes: Vec<XmlAttribute>,
-) -> Option<SpirvCapability> {
-    let mut name = None;
-    let mut enables = Vec::new();
-
-    match_attributes! {ctx, a in attributes,
-        "name" => name = Some(a.value)
-    }
-
-    match_elements! {ctx, attributes,
-        "enable" => if let Some(v) = parse_enable(ctx, attributes) {
-            enables.push(v);
-        }
-    }
-
-    unwrap_attribute!(ctx, spirvcapability, name);
-
-    Some(SpirvCapability { name, enables })
-}
-
-fn parse_enable<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> Option<Enable> {
-    let mut version = None;
-    let mut extension = None;
-    let mut struct_ = None;
-    let mut feature = None;
-    let mut requires = None;
-    let mut alias = None;
-    let mut property = None;
-    let mut member = None;
-    let mut value = None;
-
-    match_attributes! {ctx, a in attributes,
-        "version" => version = Some(a.value),
-        "extension" => extension = Some(a.value),
-        "struct" => struct_ = Some(a.value),
-        "feature" => feature = Some(a.value),
-        "requires" => requires = Some(a.value),
-        "alias" => alias = Some(a.value),
-        "property" => property = Some(a.value),
-        "member" => member = Some(a.value),
-        "value" => value = Some(a.value)
-    }
-
-    consume_current_element(ctx);
-
-    if let Some(version) = version {
-        Some(Enable::Version(version))
-    } else if let Some(extension) = extension {
-        Some(Enable::Extension(extension))
-    } else if let Some(struct_) = struct_ {
-        unwrap_attribute!(ctx, enable, feature);
-        Some(Enable::Feature(FeatureEnable {
-            struct_,
-            feature,
-            requires,
-            alias,
-        }))
-    } else if let Some(property) = property {
-        unwrap_attribute!(ctx, enable, member);
-        unwrap_attribute!(ctx, enable, value);
-        Some(Enable::Property(PropertyEnable {
-            property,
-            member,
-            value,
-            requires,
-        }))
+    let text = text.trim_end_matches(|c| matches!(c, 'u' | 'U' | 'l' | 'L'));
+    if let Some(digits) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        (negative, digits, 16)
+    } else if text.len() > 1 && text.starts_with('0') {
+        (negative, &text[1..], 8)
     } else {
-        unimplemented!();
+        (negative, text, 10)
     }
 }
 
 fn parse_integer<R: Read>(ctx: &mut ParseCtx<R>, text: &str) -> Option<i64> {
-    let parse_res = if text.starts_with("0x") {
-        i64::from_str_radix(text.split_at(2).1, 16)
-    } else {
-        i64::from_str_radix(text, 10)
-    };
+    let (negative, digits, radix) = split_integer(text);
+    let value = u64::from_str_radix(digits, radix)
+        .ok()
+        .map(|v| {
+            if negative {
+                -i128::from(v)
+            } else {
+                i128::from(v)
+            }
+        })
+        .filter(|&v| v >= i128::from(i64::MIN) && v <= i128::from(i64::MAX));
 
-    if let Ok(v) = parse_res {
-        Some(v)
+    if let Some(v) = value {
+        Some(v as i64)
     } else {
         ctx.errors.push(Error::SchemaViolation {
-            xpath: ctx.xpath.clone(),
-            desc: format!("Value '{}' is not valid base 10 or 16 integer.", text),
+            xpath: ctx.xpath(),
+            desc: format!("Value '{}' is not a valid base {} integer.", text, radix),
         });
         None
     }
 }
 
-fn parse_int_attribute<I: FromStr<Err = std::num::ParseIntError>, R: Read>(
+/// Integer types which attributes are parsed into.
+pub(crate) trait FromStrRadix: Sized {
+    fn from_str_radix(text: &str, radix: u32) -> Result<Self, core::num::ParseIntError>;
+}
+
+macro_rules! impl_from_str_radix {
+    ($($ty:ty),*) => {$(
+        impl FromStrRadix for $ty {
+            fn from_str_radix(text: &str, radix: u32) -> Result<Self, core::num::ParseIntError> {
+                <$ty>::from_str_radix(text, radix)
+            }
+        }
+    )*};
+}
+
+impl_from_str_radix!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+pub(crate) fn parse_int_attribute<I: FromStrRadix, R: Read>(
     ctx: &mut ParseCtx<R>,
-    text: String,
+    text: Box<str>,
     attribute_name: &str,
 ) -> Option<I> {
-    match I::from_str(&text) {
+    let (negative, digits, radix) = split_integer(&text);
+    let result = if negative {
+        I::from_str_radix(&format!("-{}", digits), radix)
+    } else {
+        I::from_str_radix(digits, radix)
+    };
+    match result {
         Ok(v) => Some(v),
         Err(e) => {
             ctx.errors.push(Error::ParseIntError {
-                xpath: xpath_attribute(&ctx.xpath, attribute_name),
-                text: text,
+                xpath: ctx.xpath_attribute(attribute_name),
+                text: String::from(text),
+                radix,
                 error: e,
             });
             None
@@ -1546,9 +2065,9 @@ fn parse_int_attribute<I: FromStr<Err = std::num::ParseIntError>, R: Read>(
     }
 }
 
-fn consume_current_element<R: Read>(ctx: &mut ParseCtx<R>) {
+pub(crate) fn consume_current_element<R: Read>(ctx: &mut ParseCtx<R>) {
     let mut depth = 1;
-    while let Some(Ok(e)) = ctx.events.next() {
+    while let Some(e) = ctx.next_event() {
         match e {
             XmlEvent::StartElement { name, .. } => {
                 ctx.push_element(name.local_name.as_str());
@@ -1566,15 +2085,71 @@ fn consume_current_element<R: Read>(ctx: &mut ParseCtx<R>) {
     }
 }
 
-fn parse_text_element<R: Read>(ctx: &mut ParseCtx<R>) -> String {
+/// Skips rest of the current element, whose children and text aren't part of the schema,
+/// reporting them with `ParseOptions::coverage`. Children are passed to hooks registered by
+/// `ParseOptions::element_hook`.
+pub(crate) fn consume_leaf_element<R: Read>(ctx: &mut ParseCtx<R>) {
+    if !ctx.options.coverage && ctx.options.element_hooks.is_empty() {
+        return consume_current_element(ctx);
+    }
+    while let Some(e) = ctx.next_event() {
+        match e {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let name = name.local_name.as_str();
+                ctx.push_element(name);
+                if !ctx.element_hook(name, &attributes) {
+                    ctx.ignored_element(name);
+                    consume_current_element(ctx);
+                }
+            }
+            XmlEvent::Characters(_) => ctx.ignored_text(),
+            XmlEvent::EndElement { .. } => {
+                ctx.pop_element();
+                break;
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Original values of attributes, if requested by `ParseOptions::raw_attributes`.
+pub(crate) fn raw_attributes<R: Read>(
+    ctx: &ParseCtx<R>,
+    attributes: &[XmlAttribute],
+) -> RawAttributes {
+    if !ctx.options.raw_attributes {
+        return RawAttributes::new();
+    }
+    attributes
+        .iter()
+        .map(|a| {
+            (
+                Box::from(a.name.local_name.as_str()),
+                Box::from(a.value.as_str()),
+            )
+        })
+        .collect()
+}
+
+pub(crate) fn parse_text_element<R: Read>(ctx: &mut ParseCtx<R>) -> Box<str> {
     let mut result = String::new();
     let mut depth = 1;
-    while let Some(Ok(e)) = ctx.events.next() {
+    while let Some(e) = ctx.next_event() {
         match e {
             XmlEvent::StartElement { name, .. } => {
-                ctx.push_element(name.local_name.as_str());
+                let name = name.local_name.as_str();
+                ctx.push_element(name);
+                // Markup is dropped, while its text is kept.
+                if depth == 1 {
+                    ctx.ignored_element(name);
+                }
                 depth += 1;
             }
+            // Text elements almost always contain a single piece of text, which can be reused
+            // as is instead of being copied.
+            XmlEvent::Characters(text) if result.is_empty() => result = text,
             XmlEvent::Characters(text) => result.push_str(&text),
             XmlEvent::EndElement { .. } => {
                 depth -= 1;
@@ -1586,5 +2161,5 @@ fn parse_text_element<R: Read>(ctx: &mut ParseCtx<R>) -> String {
             _ => (),
         }
     }
-    result
+    result.into_boxed_str()
 }
@@ -0,0 +1,330 @@
+#[cfg(not(feature = "std"))]
+use alloc::format;
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::fmt::Write;
+#[cfg(feature = "std")]
+use std::fmt::Write;
+
+use types::*;
+
+impl Registry {
+    /// Renders the registry as an indented tree, with one line per type, member, enum, command
+    /// and interface item. Meant for debugging and exploring the registry, where `Debug` output
+    /// is too verbose.
+    ///
+    /// Only items for which `filter` returns `true` when given their name are printed, e.g.
+    /// `|_| true` prints everything. Children of printed types, enum groups, features and
+    /// extensions are always printed, and enum groups are also printed with those of their
+    /// enums which match. Comments are omitted.
+    pub fn pretty_print<F: Fn(&str) -> bool>(&self, filter: F) -> String {
+        let mut printer = Printer {
+            filter,
+            out: String::new(),
+        };
+        for child in &self.0 {
+            printer.registry_child(child);
+        }
+        printer.out
+    }
+}
+
+struct Printer<F> {
+    filter: F,
+    out: String,
+}
+
+impl<F: Fn(&str) -> bool> Printer<F> {
+    /// Writes `header` followed by lines written by `body`, unless `body` doesn't write any.
+    fn section<B: FnOnce(&mut Self)>(&mut self, header: &str, body: B) {
+        let start = self.out.len();
+        body(self);
+        if self.out.len() != start {
+            self.out.insert_str(start, &format!("{}\n", header));
+        }
+    }
+
+    fn line(&mut self, depth: usize, text: &str) {
+        for _ in 0..depth {
+            self.out.push_str("  ");
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    fn registry_child(&mut self, child: &RegistryChild) {
+        match *child {
+            RegistryChild::VendorIds(ref vendor_ids) => self.section("vendorids", |p| {
+                for vendor in &vendor_ids.children {
+                    if (p.filter)(&vendor.name) {
+                        p.line(1, &format!("{} = {:#x}", vendor.name, vendor.id));
+                    }
+                }
+            }),
+            RegistryChild::Platforms(ref platforms) => self.section("platforms", |p| {
+                for platform in &platforms.children {
+                    if (p.filter)(platform.name.as_str()) {
+                        p.line(1, &format!("{} ({})", platform.name, platform.protect));
+                    }
+                }
+            }),
+            RegistryChild::Tags(ref tags) => self.section("tags", |p| {
+                for tag in &tags.children {
+                    if (p.filter)(&tag.name) {
+                        p.line(1, &format!("{} ({})", tag.name, tag.author));
+                    }
+                }
+            }),
+            RegistryChild::Types(ref types) => self.section("types", |p| {
+                for child in &types.children {
+                    if let TypesChild::Type(ref ty) = *child {
+                        p.type_(ty);
+                    }
+                }
+            }),
+            RegistryChild::Enums(ref enums) => self.enums(enums),
+            RegistryChild::Commands(ref commands) => self.section("commands", |p| {
+                for command in &commands.children {
+                    p.command(command);
+                }
+            }),
+            RegistryChild::Feature(ref feature) if (self.filter)(&feature.name) => {
+                let header = format!(
+                    "feature {} ({} {})",
+                    feature.name, feature.api, feature.number
+                );
+                self.line(0, &header);
+                self.interface(1, &feature.children);
+            }
+            RegistryChild::Extensions(ref extensions) => self.section("extensions", |p| {
+                for ext in &extensions.children {
+                    if (p.filter)(&ext.name) {
+                        let mut header = String::from(&*ext.name);
+                        if let Some(number) = ext.number {
+                            let _ = write!(header, " #{}", number);
+                        }
+                        if let Some(ref ext_type) = ext.ext_type {
+                            let _ = write!(header, " {}", ext_type);
+                        }
+                        if ext.is_disabled() {
+                            header.push_str(" (disabled)");
+                        }
+                        p.line(1, &header);
+                        p.interface(2, &ext.children);
+                    }
+                }
+            }),
+            RegistryChild::Formats(ref formats) => self.section("formats", |p| {
+                for format in &formats.children {
+                    if (p.filter)(&format.name) {
+                        p.line(1, &format!("{} ({})", format.name, format.class));
+                    }
+                }
+            }),
+            RegistryChild::SpirvExtensions(ref spirv) => self.section("spirvextensions", |p| {
+                for ext in &spirv.children {
+                    if (p.filter)(&ext.name) {
+                        p.line(1, &ext.name);
+                    }
+                }
+            }),
+            RegistryChild::SpirvCapabilities(ref spirv) => self.section("spirvcapabilities", |p| {
+                for cap in &spirv.children {
+                    if (p.filter)(&cap.name) {
+                        p.line(1, &cap.name);
+                    }
+                }
+            }),
+            RegistryChild::Sync(ref sync) => self.section("sync", |p| {
+                for child in &sync.children {
+                    let (name, alias) = match *child {
+                        SyncChild::Stage(ref stage) => (&stage.name, &stage.alias),
+                        SyncChild::Access(ref access) => (&access.name, &access.alias),
+                        SyncChild::Pipeline(ref pipeline) => (&pipeline.name, &None),
+                    };
+                    if (p.filter)(name) {
+                        match *alias {
+                            Some(ref alias) => p.line(1, &format!("{} -> {}", name, alias)),
+                            None => p.line(1, name),
+                        }
+                    }
+                }
+            }),
+            RegistryChild::VideoCodecs(ref codecs) => self.section("videocodecs", |p| {
+                for codec in &codecs.children {
+                    if (p.filter)(&codec.name) {
+                        match codec.extend {
+                            Some(ref extend) => p.line(1, &format!("{} : {}", codec.name, extend)),
+                            None => p.line(1, &codec.name),
+                        }
+                    }
+                }
+            }),
+            _ => {}
+        }
+    }
+
+    fn type_(&mut self, ty: &Type) {
+        let name = match ty.declared_name() {
+            Some(name) => name,
+            None => return,
+        };
+        if !(self.filter)(name) {
+            return;
+        }
+        let mut header = String::from(name);
+        if let Some(ref category) = ty.category {
+            let _ = write!(header, " ({})", category);
+        }
+        if let Some(ref alias) = ty.alias {
+            let _ = write!(header, " -> {}", alias);
+        }
+        match ty.spec {
+            TypeSpec::Code(ref code) => {
+                let code = code.code.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !code.is_empty() {
+                    let _ = write!(header, ": {}", code);
+                }
+            }
+            TypeSpec::HandleAlias { ref alias }
+            | TypeSpec::BitmaskAlias { ref alias }
+            | TypeSpec::StructAlias { ref alias }
+            | TypeSpec::UnionAlias { ref alias }
+                if ty.alias.is_none() =>
+            {
+                let _ = write!(header, " -> {}", alias);
+            }
+            _ => {}
+        }
+        self.line(1, &header);
+
+        if let TypeSpec::Members(ref members) = ty.spec {
+            for member in members {
+                if let TypeMember::Definition(ref def) = *member {
+                    let mut line = def
+                        .to_string()
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    if let Some(ref len) = def.len {
+                        let _ = write!(line, " [len: {}]", len);
+                    }
+                    self.line(2, &line);
+                }
+            }
+        }
+    }
+
+    fn enums(&mut self, enums: &Enums) {
+        let name = enums.name.as_deref().unwrap_or("");
+        let show_all = (self.filter)(name);
+        let mut header = format!("enums {}", name);
+        if let Some(ref kind) = enums.kind {
+            let _ = write!(header, " ({})", kind);
+        }
+        self.section(&header, |p| {
+            for child in &enums.children {
+                if let EnumsChild::Enum(ref e) = *child {
+                    if show_all || (p.filter)(&e.name) {
+                        p.line(1, &enum_line(e));
+                    }
+                }
+            }
+        });
+    }
+
+    fn command(&mut self, command: &Command) {
+        match *command {
+            Command::Alias {
+                ref name,
+                ref alias,
+                ..
+            } => {
+                if (self.filter)(name) {
+                    self.line(1, &format!("{} -> {}", name, alias));
+                }
+            }
+            Command::Definition(ref def) => {
+                if (self.filter)(&def.proto.name) {
+                    self.line(1, &def.to_string());
+                }
+            }
+        }
+    }
+
+    fn interface(&mut self, depth: usize, children: &[ExtensionChild]) {
+        for child in children {
+            let (kind, api, items) = match *child {
+                ExtensionChild::Require {
+                    ref api, ref items, ..
+                } => ("require", api, items),
+                ExtensionChild::Remove {
+                    ref api, ref items, ..
+                } => ("remove", api, items),
+                ExtensionChild::Deprecate {
+                    ref api, ref items, ..
+                } => ("deprecate", api, items),
+            };
+            match *api {
+                Some(ref api) => self.line(depth, &format!("{} ({})", kind, api)),
+                None => self.line(depth, kind),
+            }
+            for item in items {
+                let line = match *item {
+                    InterfaceItem::Type { ref name, .. } => format!("type {}", name),
+                    InterfaceItem::Command { ref name, .. } => format!("command {}", name),
+                    InterfaceItem::Enum(ref e) => format!("enum {}", enum_line(e)),
+                    _ => continue,
+                };
+                self.line(depth + 1, &line);
+            }
+        }
+    }
+}
+
+fn enum_line(e: &Enum) -> String {
+    let mut line = String::from(&*e.name);
+    let extends = match e.spec {
+        EnumSpec::None => None,
+        EnumSpec::Alias {
+            ref alias,
+            ref extends,
+        } => {
+            let _ = write!(line, " -> {}", alias);
+            extends.as_deref()
+        }
+        EnumSpec::Offset {
+            offset,
+            ref extends,
+            extnumber,
+            dir,
+        } => {
+            let _ = write!(line, " = {}offset {}", if dir { "" } else { "-" }, offset);
+            if let Some(extnumber) = extnumber {
+                let _ = write!(line, " of extension {}", extnumber);
+            }
+            Some(&**extends)
+        }
+        EnumSpec::Bitpos {
+            bitpos,
+            ref extends,
+        } => {
+            let _ = write!(line, " = 1 << {}", bitpos);
+            extends.as_deref()
+        }
+        EnumSpec::Value {
+            ref value,
+            ref extends,
+        } => {
+            let _ = write!(line, " = {}", value);
+            extends.as_deref()
+        }
+    };
+    if let Some(extends) = extends {
+        let _ = write!(line, " (extends {})", extends);
+    }
+    line
+}
@@ -0,0 +1,363 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use c::TokenIter;
+use timeline::{alias_chain, item_name};
+use types::*;
+
+type Renames = BTreeMap<Box<str>, Box<str>>;
+
+impl Registry {
+    /// Rewrites names of types, commands and enums promoted to core up to the feature named
+    /// `version`, e.g. `VK_VERSION_1_2`, to their core names, so that code generated for core
+    /// doesn't need to chase aliases. Returns the renamed items with their core names.
+    ///
+    /// An item is promoted if it's required by an extension and is an alias of an item required
+    /// by `version` or an earlier feature of the same API, possibly through other aliases, such
+    /// as `vkGetPhysicalDeviceFeatures2KHR` of `vkGetPhysicalDeviceFeatures2`. Definitions of
+    /// these aliases are removed, while references to them are rewritten throughout the registry:
+    /// in `<require>`, `<remove>` and `<deprecate>` blocks, in attributes of types, struct
+    /// members, commands and enums referring to other items, and in their C code. Nothing is
+    /// changed if there's no such feature.
+    ///
+    /// Can be combined with `Registry::retain_api` or `Registry::minimized` to obtain a view of
+    /// the registry for generating core-only bindings.
+    pub fn apply_promotions(&mut self, version: &str) -> BTreeMap<Box<str>, Box<str>> {
+        let renames = self.promotions(version);
+        if renames.is_empty() {
+            return renames;
+        }
+        let is_renamed = |name: Option<&str>| name.map_or(false, |n| renames.contains_key(n));
+
+        for child in &mut self.0 {
+            match *child {
+                RegistryChild::Types(ref mut block) => {
+                    block.children.retain(|child| match *child {
+                        TypesChild::Type(ref ty) => {
+                            ty.alias.is_none() || !is_renamed(ty.declared_name())
+                        }
+                        TypesChild::Comment(_) => true,
+                    });
+                    for child in &mut block.children {
+                        if let TypesChild::Type(ref mut ty) = *child {
+                            rename_type(&renames, ty);
+                        }
+                    }
+                }
+                RegistryChild::Enums(ref mut block) => {
+                    block.children.retain(|child| match *child {
+                        EnumsChild::Enum(ref e) => !is_promoted_enum(&renames, e),
+                        _ => true,
+                    });
+                    rename_opt(&renames, &mut block.name);
+                    for child in &mut block.children {
+                        if let EnumsChild::Enum(ref mut e) = *child {
+                            rename_enum_spec(&renames, &mut e.spec);
+                        }
+                    }
+                }
+                RegistryChild::Commands(ref mut block) => {
+                    block.children.retain(|command| match *command {
+                        Command::Alias { ref name, .. } => !renames.contains_key(name),
+                        Command::Definition(_) => true,
+                    });
+                    for command in &mut block.children {
+                        rename_command(&renames, command);
+                    }
+                }
+                RegistryChild::Feature(ref mut feature) => {
+                    rename_blocks(&renames, &mut feature.children);
+                }
+                RegistryChild::Extensions(ref mut block) => {
+                    for extension in &mut block.children {
+                        rename_blocks(&renames, &mut extension.children);
+                    }
+                }
+                _ => {}
+            }
+        }
+        renames
+    }
+
+    /// Names of items promoted up to the feature named `version` along with their core names,
+    /// see `Registry::apply_promotions`.
+    fn promotions(&self, version: &str) -> Renames {
+        let features: Vec<&Feature> = self.features().collect();
+        let i = match features.iter().position(|f| &*f.name == version) {
+            Some(i) => i,
+            None => return Renames::new(),
+        };
+        let apis = &features[i].api.apis;
+        let core: BTreeSet<&str> = features[..=i]
+            .iter()
+            .filter(|f| f.api.apis.iter().any(|api| apis.contains(api)))
+            .flat_map(|f| required_items(&f.children))
+            .map(item_name)
+            .collect();
+        let extension_items: Vec<&InterfaceItem> = self
+            .extensions()
+            .flat_map(|e| required_items(&e.children))
+            .collect();
+
+        let mut aliases = BTreeMap::new();
+        for ty in self.types() {
+            if let (Some(name), Some(alias)) = (ty.declared_name(), ty.alias.as_deref()) {
+                aliases.insert(name, alias);
+            }
+        }
+        for command in self.commands() {
+            if let Command::Alias {
+                ref name,
+                ref alias,
+                ..
+            } = *command
+            {
+                aliases.insert(&**name, &**alias);
+            }
+        }
+        let block_enums = self
+            .enums_blocks()
+            .flat_map(|block| &block.children)
+            .filter_map(|child| match *child {
+                EnumsChild::Enum(ref e) => Some(e),
+                _ => None,
+            });
+        let item_enums = self
+            .features()
+            .flat_map(|f| required_items(&f.children))
+            .chain(extension_items.iter().cloned())
+            .filter_map(|item| match *item {
+                InterfaceItem::Enum(ref e) => Some(e),
+                _ => None,
+            });
+        for e in block_enums.chain(item_enums) {
+            if let EnumSpec::Alias { ref alias, .. } = e.spec {
+                aliases.insert(&*e.name, &**alias);
+            }
+        }
+
+        let mut renames = Renames::new();
+        for name in extension_items.into_iter().map(item_name) {
+            if core.contains(name) || renames.contains_key(name) {
+                continue;
+            }
+            let target = alias_chain(name, |target| {
+                if core.contains(target) {
+                    None
+                } else {
+                    aliases.get(target).cloned()
+                }
+            })
+            .last()
+            .unwrap_or(name);
+            if target != name && core.contains(target) {
+                renames.insert(name.into(), target.into());
+            }
+        }
+        renames
+    }
+}
+
+/// Items of `<require>` blocks.
+fn required_items(children: &[ExtensionChild]) -> impl Iterator<Item = &InterfaceItem> {
+    children.iter().flat_map(|child| match *child {
+        ExtensionChild::Require { ref items, .. } => &items[..],
+        _ => &[],
+    })
+}
+
+/// Whether the enum is an alias which is replaced by its core name.
+fn is_promoted_enum(renames: &Renames, e: &Enum) -> bool {
+    matches!(e.spec, EnumSpec::Alias { .. }) && renames.contains_key(&e.name)
+}
+
+fn rename(renames: &Renames, name: &mut Box<str>) {
+    if let Some(core_name) = renames.get(name) {
+        *name = core_name.clone();
+    }
+}
+
+fn rename_opt(renames: &Renames, name: &mut Option<Box<str>>) {
+    if let Some(ref mut name) = *name {
+        rename(renames, name);
+    }
+}
+
+/// Renames items of a comma-separated list.
+fn rename_list(renames: &Renames, list: &mut Option<Box<str>>) {
+    if let Some(ref mut list) = *list {
+        if list.split(',').any(|name| renames.contains_key(name)) {
+            let names: Vec<&str> = list
+                .split(',')
+                .map(|name| renames.get(name).map_or(name, |n| &**n))
+                .collect();
+            *list = names.join(",").into_boxed_str();
+        }
+    }
+}
+
+/// Renames identifiers in C code.
+fn rename_code(renames: &Renames, code: &mut Box<str>) {
+    if TokenIter::new(code).any(|token| renames.contains_key(token)) {
+        let renamed: String = TokenIter::new(code)
+            .map(|token| renames.get(token).map_or(token, |n| &**n))
+            .collect();
+        *code = renamed.into_boxed_str();
+    }
+}
+
+fn rename_c_type(renames: &Renames, c_type: &mut CType) {
+    match *c_type {
+        CType::Named { ref mut name, .. } => rename(renames, name),
+        CType::Pointer { ref mut inner, .. }
+        | CType::Array { ref mut inner, .. }
+        | CType::Bitfield { ref mut inner, .. } => rename_c_type(renames, inner),
+    }
+}
+
+fn rename_declaration(renames: &Renames, declaration: &mut NameWithType) {
+    rename_opt(renames, &mut declaration.type_name);
+    if let Some(ref mut c_type) = declaration.c_type {
+        rename_c_type(renames, c_type);
+    }
+}
+
+fn rename_preprocessor(renames: &Renames, items: &mut [PreprocessorItem]) {
+    for item in items {
+        match *item {
+            PreprocessorItem::Define(TypeDefine {
+                value: TypeDefineValue::Value(ref mut code),
+                ..
+            })
+            | PreprocessorItem::Define(TypeDefine {
+                value:
+                    TypeDefineValue::Function {
+                        body: ref mut code, ..
+                    },
+                ..
+            })
+            | PreprocessorItem::Code(ref mut code) => rename_code(renames, code),
+            PreprocessorItem::Conditional(ref mut branches) => {
+                for branch in branches {
+                    rename_preprocessor(renames, &mut branch.items);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn rename_type(renames: &Renames, ty: &mut Type) {
+    rename_opt(renames, &mut ty.alias);
+    rename_list(renames, &mut ty.requires);
+    rename_list(renames, &mut ty.parent);
+    rename_list(renames, &mut ty.structextends);
+    rename_opt(renames, &mut ty.bitvalues);
+    match ty.spec {
+        TypeSpec::Code(ref mut code) => {
+            rename_code(renames, &mut code.code);
+            for markup in &mut code.markup {
+                if let TypeCodeMarkup::Type(ref mut name) = *markup {
+                    rename(renames, name);
+                }
+            }
+            rename_preprocessor(renames, &mut code.directives);
+        }
+        TypeSpec::Members(ref mut members) => {
+            for member in members {
+                let def = match *member {
+                    TypeMember::Definition(ref mut def) => def,
+                    TypeMember::Comment(_) => continue,
+                };
+                rename_code(renames, &mut def.code);
+                for markup in &mut def.markup {
+                    match *markup {
+                        TypeMemberMarkup::Type(ref mut name)
+                        | TypeMemberMarkup::Enum(ref mut name) => rename(renames, name),
+                        _ => {}
+                    }
+                }
+                if let Some(ref mut c_type) = def.c_type {
+                    rename_c_type(renames, c_type);
+                }
+                rename_list(renames, &mut def.values);
+                rename_list(renames, &mut def.validextensionstructs);
+            }
+        }
+        TypeSpec::HandleAlias { ref mut alias }
+        | TypeSpec::BitmaskAlias { ref mut alias }
+        | TypeSpec::StructAlias { ref mut alias }
+        | TypeSpec::UnionAlias { ref mut alias } => rename(renames, alias),
+        TypeSpec::None => {}
+    }
+}
+
+fn rename_enum_spec(renames: &Renames, spec: &mut EnumSpec) {
+    match *spec {
+        EnumSpec::Alias {
+            ref mut alias,
+            ref mut extends,
+        } => {
+            rename(renames, alias);
+            rename_opt(renames, extends);
+        }
+        EnumSpec::Offset {
+            ref mut extends, ..
+        } => rename(renames, extends),
+        EnumSpec::Bitpos {
+            ref mut extends, ..
+        }
+        | EnumSpec::Value {
+            ref mut extends, ..
+        } => rename_opt(renames, extends),
+        EnumSpec::None => {}
+    }
+}
+
+fn rename_command(renames: &Renames, command: &mut Command) {
+    match *command {
+        Command::Alias { ref mut alias, .. } => rename(renames, alias),
+        Command::Definition(ref mut def) => {
+            rename_declaration(renames, &mut def.proto);
+            for param in &mut def.params {
+                rename_declaration(renames, &mut param.definition);
+            }
+            rename_opt(renames, &mut def.alias);
+            for code in def.successcodes.iter_mut().chain(&mut def.errorcodes) {
+                rename(renames, code);
+            }
+            rename_code(renames, &mut def.code);
+        }
+    }
+}
+
+/// Renames items of `<require>`, `<remove>` and `<deprecate>` blocks, removing definitions of
+/// promoted enums and items which then appear twice in a block.
+fn rename_blocks(renames: &Renames, children: &mut [ExtensionChild]) {
+    for child in children {
+        let items = match *child {
+            ExtensionChild::Require { ref mut items, .. }
+            | ExtensionChild::Remove { ref mut items, .. }
+            | ExtensionChild::Deprecate { ref mut items, .. } => items,
+        };
+        let mut renamed = Vec::with_capacity(items.len());
+        for mut item in items.drain(..) {
+            match item {
+                InterfaceItem::Type { ref mut name, .. }
+                | InterfaceItem::Command { ref mut name, .. } => rename(renames, name),
+                InterfaceItem::Enum(ref e) if is_promoted_enum(renames, e) => continue,
+                InterfaceItem::Enum(ref mut e) => rename_enum_spec(renames, &mut e.spec),
+                InterfaceItem::Comment(_) => {}
+            }
+            let is_duplicate = match item {
+                InterfaceItem::Type { .. } | InterfaceItem::Command { .. } => renamed
+                    .iter()
+                    .any(|other| item_name(other) == item_name(&item)),
+                _ => false,
+            };
+            if !is_duplicate {
+                renamed.push(item);
+            }
+        }
+        *items = renamed;
+    }
+}
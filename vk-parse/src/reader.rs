@@ -0,0 +1,779 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{convert::Infallible, fmt, str};
+
+/// Source of the document for the parser without the `std` feature, a minimal counterpart of
+/// `std::io::Read`. With the `std` feature, the parser reads from `std::io::Read` instead.
+///
+/// It's implemented for byte slices, so that documents in memory can be passed to
+/// `parse_stream` as well as to `parse_bytes`.
+pub trait Read {
+    type Error: fmt::Display;
+
+    /// Reads bytes into `buf`, returning how many were read. Returns 0 only at the end of the
+    /// document, unless `buf` is empty.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+impl Read for &[u8] {
+    type Error = Infallible;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Infallible> {
+        let len = buf.len().min(self.len());
+        let (head, rest) = self.split_at(len);
+        buf[..len].copy_from_slice(head);
+        *self = rest;
+        Ok(len)
+    }
+}
+
+impl<R: Read + ?Sized> Read for &mut R {
+    type Error = R::Error;
+
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, R::Error> {
+        (**self).read(buf)
+    }
+}
+
+/// Event of the XML reader used without the `std` feature, with the same shape as the
+/// corresponding events of `xml-rs`, which the reader is used instead of. The XML declaration,
+/// document type declaration, comments and processing instructions are skipped, and CDATA
+/// sections are reported as text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum XmlEvent {
+    StartElement {
+        name: OwnedName,
+        /// Attributes other than namespace declarations, with references replaced.
+        attributes: Vec<OwnedAttribute>,
+    },
+    EndElement {
+        name: OwnedName,
+    },
+    /// Continuous piece of text, with references replaced. Line endings are kept as is, as
+    /// `xml-rs` does.
+    Characters(String),
+    /// Same as `Characters`, for text consisting only of whitespace.
+    Whitespace(String),
+    /// End of the document, after which there are no more events.
+    EndDocument,
+}
+
+/// Name of an element or attribute, e.g. `type` or `xmlns:xsi`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedName {
+    pub local_name: String,
+    /// Namespace prefix, if any. Prefixes aren't resolved to namespaces.
+    pub prefix: Option<String>,
+}
+
+impl OwnedName {
+    fn new(mut name: String) -> OwnedName {
+        match name.find(':') {
+            Some(i) => {
+                let local_name = name[i + 1..].to_string();
+                name.truncate(i);
+                OwnedName {
+                    local_name,
+                    prefix: Some(name),
+                }
+            }
+            None => OwnedName {
+                local_name: name,
+                prefix: None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedAttribute {
+    pub name: OwnedName,
+    pub value: String,
+}
+
+/// Error of the XML reader, after which no more events are read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Error {
+    /// Zero-based line and column, in characters, at which the error was found.
+    row: u64,
+    column: u64,
+    message: String,
+    /// Whether the document exceeds the limit on length of text, see `Events::new`.
+    limit_exceeded: bool,
+}
+
+impl Error {
+    pub(crate) fn is_limit_exceeded(&self) -> bool {
+        self.limit_exceeded
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{} {}", self.row + 1, self.column + 1, self.message)
+    }
+}
+
+/// Number of bytes requested from the source at once.
+const CHUNK_LEN: usize = 8 * 1024;
+
+/// XML reader used without the `std` feature. It only supports documents encoded as UTF-8, and
+/// only the predefined entities and internal entities declared in the document. Well-formedness
+/// is checked as far as the structure of elements and syntax of markup go, but e.g. characters
+/// allowed in names aren't.
+pub(crate) struct Events<R> {
+    source: R,
+    /// Bytes read from the source, of which those starting at `pos` aren't consumed yet.
+    buf: Vec<u8>,
+    pos: usize,
+    /// Set when the source has no more bytes.
+    eof: bool,
+    /// Position of the next byte, for errors.
+    row: u64,
+    column: u64,
+    /// Full names of currently open elements.
+    open: Vec<String>,
+    /// End of an empty-element tag, returned right after its start.
+    pending_end: Option<OwnedName>,
+    /// Names and replacement text of internal entities declared by the document type
+    /// declaration. Replacement text is inserted as is, without replacing references within.
+    entities: Vec<(String, String)>,
+    started: bool,
+    root_seen: bool,
+    /// Set at the end of the document or after an error.
+    finished: bool,
+    /// Limit on length of each piece of text, attribute value, comment and processing
+    /// instruction.
+    max_len: Option<usize>,
+}
+
+impl<R: Read> Events<R> {
+    /// Creates reader of the document from `source`. If `max_len` is given, reading fails once
+    /// a piece of text, attribute value, comment or processing instruction exceeds it in bytes,
+    /// before it's read into memory as a whole.
+    pub(crate) fn new(source: R, max_len: Option<usize>) -> Self {
+        Events {
+            source,
+            buf: Vec::new(),
+            pos: 0,
+            eof: false,
+            row: 0,
+            column: 0,
+            open: Vec::new(),
+            pending_end: None,
+            entities: Vec::new(),
+            started: false,
+            root_seen: false,
+            finished: false,
+            max_len,
+        }
+    }
+
+    fn error<M: Into<String>>(&self, message: M) -> Error {
+        Error {
+            row: self.row,
+            column: self.column,
+            message: message.into(),
+            limit_exceeded: false,
+        }
+    }
+
+    /// Reads from the source until at least `len` unconsumed bytes are buffered, returning
+    /// whether there are as many.
+    fn fill(&mut self, len: usize) -> Result<bool, Error> {
+        while self.buf.len() - self.pos < len && !self.eof {
+            if self.pos >= CHUNK_LEN {
+                self.buf.drain(..self.pos);
+                self.pos = 0;
+            }
+            let filled = self.buf.len();
+            self.buf.resize(filled + CHUNK_LEN, 0);
+            let result = self.source.read(&mut self.buf[filled..]);
+            let read = *result.as_ref().unwrap_or(&0);
+            self.buf.truncate(filled + read);
+            match result {
+                Ok(0) => self.eof = true,
+                Ok(_) => {}
+                Err(e) => return Err(self.error(format!("Failed to read the document: {}", e))),
+            }
+        }
+        Ok(self.buf.len() - self.pos >= len)
+    }
+
+    fn peek(&mut self) -> Result<Option<u8>, Error> {
+        self.fill(1)?;
+        Ok(self.buf.get(self.pos).cloned())
+    }
+
+    fn starts_with(&mut self, s: &[u8]) -> Result<bool, Error> {
+        Ok(self.fill(s.len())? && self.buf[self.pos..].starts_with(s))
+    }
+
+    /// Consumes `s` if the document continues with it.
+    fn eat(&mut self, s: &[u8]) -> Result<bool, Error> {
+        if self.starts_with(s)? {
+            self.skip(s.len());
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn expect(&mut self, s: &str) -> Result<(), Error> {
+        if self.eat(s.as_bytes())? {
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected `{}`", s)))
+        }
+    }
+
+    /// Consumes `len` buffered bytes.
+    fn skip(&mut self, len: usize) {
+        for &b in &self.buf[self.pos..self.pos + len] {
+            if b == b'\n' {
+                self.row += 1;
+                self.column = 0;
+            } else if b & 0xC0 != 0x80 {
+                self.column += 1;
+            }
+        }
+        self.pos += len;
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), Error> {
+        while let Some(b) = self.peek()? {
+            if !is_whitespace(b) {
+                break;
+            }
+            self.skip(1);
+        }
+        Ok(())
+    }
+
+    fn check_len(&self, len: usize) -> Result<(), Error> {
+        match self.max_len {
+            Some(max_len) if len > max_len => Err(Error {
+                limit_exceeded: true,
+                ..self.error("Document is larger than allowed by the limit on text length")
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reads bytes up to `end`, which is consumed but not included.
+    fn take_until(&mut self, end: &[u8], out: &mut Vec<u8>) -> Result<(), Error> {
+        loop {
+            if self.eat(end)? {
+                return Ok(());
+            }
+            match self.peek()? {
+                Some(b) => out.push(b),
+                None => return Err(self.error("Unexpected end of document")),
+            }
+            self.skip(1);
+            self.check_len(out.len())?;
+        }
+    }
+
+    fn name(&mut self) -> Result<String, Error> {
+        let mut name = Vec::new();
+        while let Some(b) = self.peek()? {
+            if is_whitespace(b) || b"/>=<&'\"?!".contains(&b) {
+                break;
+            }
+            name.push(b);
+            self.skip(1);
+        }
+        if name.is_empty() {
+            return Err(self.error("Expected a name"));
+        }
+        self.utf8(name)
+    }
+
+    fn utf8(&self, bytes: Vec<u8>) -> Result<String, Error> {
+        String::from_utf8(bytes).map_err(|_| self.error("Invalid UTF-8"))
+    }
+
+    /// Replaces reference following `&`.
+    fn reference(&mut self, out: &mut Vec<u8>) -> Result<(), Error> {
+        let mut name = Vec::new();
+        loop {
+            match self.peek()? {
+                Some(b';') => break,
+                Some(b) if name.len() < 64 && !is_whitespace(b) && b != b'<' => name.push(b),
+                _ => return Err(self.error("Malformed reference")),
+            }
+            self.skip(1);
+        }
+        self.skip(1);
+        let c = match &name[..] {
+            b"lt" => Some('<'),
+            b"gt" => Some('>'),
+            b"amp" => Some('&'),
+            b"apos" => Some('\''),
+            b"quot" => Some('"'),
+            [b'#', b'x', digits @ ..] => char_reference(digits, 16),
+            [b'#', digits @ ..] => char_reference(digits, 10),
+            _ => None,
+        };
+        if let Some(c) = c {
+            out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            return Ok(());
+        }
+        match self.entities.iter().find(|e| e.0.as_bytes() == &name[..]) {
+            Some(entity) => out.extend_from_slice(entity.1.as_bytes()),
+            None => {
+                let name = String::from_utf8_lossy(&name);
+                return Err(self.error(format!("Unknown reference `&{};`", name)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the start of the document for encodings other than UTF-8, skipping byte order
+    /// mark if there is one.
+    fn start(&mut self) -> Result<(), Error> {
+        self.fill(3)?;
+        match self.buf[..] {
+            [0xEF, 0xBB, 0xBF, ..] => self.pos += 3,
+            [0xFE, 0xFF, ..] | [0xFF, 0xFE, ..] | [b'<', 0, ..] | [0, b'<', ..] => {
+                return Err(self.error("Unsupported encoding: UTF-16"));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Skips processing instruction following `<?`, checking encoding given by the XML
+    /// declaration.
+    fn processing_instruction(&mut self) -> Result<(), Error> {
+        let mut content = Vec::new();
+        self.take_until(b"?>", &mut content)?;
+        let content = self.utf8(content)?;
+        let declaration = match content.strip_prefix("xml") {
+            Some(rest) if rest.starts_with(|c: char| c.is_ascii_whitespace()) => rest,
+            _ => return Ok(()),
+        };
+        if let Some((_, rest)) = declaration.split_once("encoding") {
+            let rest = rest.trim_start().trim_start_matches('=').trim_start();
+            let encoding = rest
+                .get(1..)
+                .and_then(|v| v.split(|c| c == '"' || c == '\'').next())
+                .unwrap_or("");
+            let supported = ["utf-8", "utf8", "us-ascii", "ascii"];
+            if !supported.iter().any(|e| e.eq_ignore_ascii_case(encoding)) {
+                return Err(self.error(format!("Unsupported encoding: {}", encoding)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads document type declaration following `<!DOCTYPE`, keeping internal entities
+    /// declared in its internal subset.
+    fn doctype(&mut self) -> Result<(), Error> {
+        let mut declaration = Vec::new();
+        let mut brackets = 0;
+        let mut quote = None;
+        let mut comment = Vec::new();
+        loop {
+            if quote.is_none() && self.eat(b"<!--")? {
+                self.take_until(b"-->", &mut comment)?;
+                comment.clear();
+                continue;
+            }
+            let b = match self.peek()? {
+                Some(b) => b,
+                None => return Err(self.error("Unexpected end of document")),
+            };
+            self.skip(1);
+            match (quote, b) {
+                (Some(q), _) if b == q => quote = None,
+                (Some(_), _) => {}
+                (None, b'"') | (None, b'\'') => quote = Some(b),
+                (None, b'[') => brackets += 1,
+                (None, b']') => brackets -= 1,
+                (None, b'>') if brackets == 0 => break,
+                _ => {}
+            }
+            declaration.push(b);
+            self.check_len(declaration.len())?;
+        }
+        let declaration = self.utf8(declaration)?;
+        let mut rest = &declaration[..];
+        while let Some((_, after)) = rest.split_once("<!ENTITY") {
+            rest = after;
+            let name_end = after.trim_start().find(|c: char| c.is_ascii_whitespace());
+            let (name, after) = after.trim_start().split_at(name_end.unwrap_or(0));
+            let after = after.trim_start();
+            // Parameter entities and external entities, which start with `SYSTEM` or
+            // `PUBLIC` instead of a quoted value, aren't supported.
+            let quote = match after.chars().next() {
+                Some(c) if c == '"' || c == '\'' => c,
+                _ => continue,
+            };
+            if let Some((value, after)) = after[1..].split_once(quote) {
+                if !name.is_empty() {
+                    self.entities
+                        .push((String::from(name), String::from(value)));
+                }
+                rest = after;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads start tag following `<`.
+    fn start_element(&mut self) -> Result<XmlEvent, Error> {
+        if self.root_seen && self.open.is_empty() {
+            return Err(self.error("Unexpected element after the root element"));
+        }
+        let name = self.name()?;
+        let mut attributes: Vec<OwnedAttribute> = Vec::new();
+        let mut names = Vec::new();
+        let empty = loop {
+            self.skip_whitespace()?;
+            if self.eat(b">")? {
+                break false;
+            }
+            if self.eat(b"/>")? {
+                break true;
+            }
+            let start = self.error("");
+            let attribute_name = self.name()?;
+            if names.contains(&attribute_name) {
+                let message = format!("Duplicate attribute `{}`", attribute_name);
+                return Err(Error { message, ..start });
+            }
+            self.skip_whitespace()?;
+            self.expect("=")?;
+            self.skip_whitespace()?;
+            let value = self.attribute_value()?;
+            let attribute_name = OwnedName::new(attribute_name);
+            names.push(attribute_name_string(&attribute_name));
+            let is_namespace = match attribute_name.prefix {
+                Some(ref prefix) => prefix == "xmlns",
+                None => attribute_name.local_name == "xmlns",
+            };
+            if !is_namespace {
+                attributes.push(OwnedAttribute {
+                    name: attribute_name,
+                    value,
+                });
+            }
+        };
+        self.root_seen = true;
+        if empty {
+            self.pending_end = Some(OwnedName::new(name.clone()));
+        } else {
+            self.open.push(name.clone());
+        }
+        Ok(XmlEvent::StartElement {
+            name: OwnedName::new(name),
+            attributes,
+        })
+    }
+
+    /// Reads quoted attribute value, with references replaced. Whitespace is kept as is, as
+    /// `xml-rs` does.
+    fn attribute_value(&mut self) -> Result<String, Error> {
+        let quote = match self.peek()? {
+            Some(b) if b == b'"' || b == b'\'' => b,
+            _ => return Err(self.error("Expected quoted attribute value")),
+        };
+        self.skip(1);
+        let mut value = Vec::new();
+        loop {
+            match self.peek()? {
+                Some(b) if b == quote => break,
+                Some(b'<') => return Err(self.error("Unexpected `<` in attribute value")),
+                Some(b'&') => {
+                    self.skip(1);
+                    self.reference(&mut value)?;
+                }
+                Some(b) => {
+                    self.skip(1);
+                    value.push(b);
+                }
+                None => return Err(self.error("Unexpected end of document")),
+            }
+            self.check_len(value.len())?;
+        }
+        self.skip(1);
+        self.utf8(value)
+    }
+
+    /// Reads end tag following `</`. Errors are reported at `start`, the start of the tag.
+    fn end_element(&mut self, start: Error) -> Result<XmlEvent, Error> {
+        let name = self.name()?;
+        self.skip_whitespace()?;
+        self.expect(">")?;
+        let message = match self.open.pop() {
+            Some(ref open) if *open == name => {
+                return Ok(XmlEvent::EndElement {
+                    name: OwnedName::new(name),
+                })
+            }
+            Some(open) => format!("Unexpected end tag `</{}>`, expected `</{}>`", name, open),
+            None => format!("Unexpected end tag `</{}>`", name),
+        };
+        Err(Error { message, ..start })
+    }
+
+    /// Reads continuous piece of text, including CDATA sections and skipping comments within.
+    /// Returns `None` for whitespace outside of the root element.
+    fn text(&mut self) -> Result<Option<XmlEvent>, Error> {
+        let mut text = Vec::new();
+        let mut comment = Vec::new();
+        // Text including CDATA sections is never reported as whitespace.
+        let mut has_cdata = false;
+        while let Some(b) = self.peek()? {
+            match b {
+                b'<' => {
+                    if self.eat(b"<![CDATA[")? {
+                        has_cdata = true;
+                        self.take_until(b"]]>", &mut text)?;
+                    } else if self.eat(b"<!--")? {
+                        self.take_until(b"-->", &mut comment)?;
+                        comment.clear();
+                    } else {
+                        break;
+                    }
+                }
+                b'&' => {
+                    self.skip(1);
+                    self.reference(&mut text)?;
+                }
+                b']' if self.starts_with(b"]]>")? => {
+                    return Err(self.error("Unexpected `]]>` in text"));
+                }
+                b => {
+                    self.skip(1);
+                    text.push(b);
+                }
+            }
+            self.check_len(text.len())?;
+        }
+        let is_whitespace = !has_cdata && text.iter().all(|&b| is_whitespace(b));
+        if self.open.is_empty() {
+            return if is_whitespace {
+                Ok(None)
+            } else {
+                Err(self.error("Unexpected text outside of the root element"))
+            };
+        }
+        if text.is_empty() {
+            return Ok(None);
+        }
+        let text = self.utf8(text)?;
+        Ok(Some(if is_whitespace {
+            XmlEvent::Whitespace(text)
+        } else {
+            XmlEvent::Characters(text)
+        }))
+    }
+
+    fn next_event(&mut self) -> Result<XmlEvent, Error> {
+        if let Some(name) = self.pending_end.take() {
+            return Ok(XmlEvent::EndElement { name });
+        }
+        if !self.started {
+            self.started = true;
+            self.start()?;
+        }
+        loop {
+            match self.peek()? {
+                None if !self.open.is_empty() => {
+                    return Err(self.error("Unexpected end of document"));
+                }
+                None if !self.root_seen => return Err(self.error("Missing root element")),
+                None => {
+                    self.finished = true;
+                    return Ok(XmlEvent::EndDocument);
+                }
+                Some(b'<') => {}
+                Some(_) => match self.text()? {
+                    Some(event) => return Ok(event),
+                    None => continue,
+                },
+            }
+            let mut comment = Vec::new();
+            if self.eat(b"<?")? {
+                self.processing_instruction()?;
+            } else if self.eat(b"<!--")? {
+                self.take_until(b"-->", &mut comment)?;
+            } else if self.starts_with(b"<![CDATA[")? && !self.open.is_empty() {
+                if let Some(event) = self.text()? {
+                    return Ok(event);
+                }
+            } else if !self.root_seen && self.eat(b"<!DOCTYPE")? {
+                self.doctype()?;
+            } else if self.starts_with(b"</")? {
+                let start = self.error("");
+                self.skip(2);
+                return self.end_element(start);
+            } else if self.starts_with(b"<!")? {
+                return Err(self.error("Unexpected markup"));
+            } else {
+                self.skip(1);
+                return self.start_element();
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for Events<R> {
+    type Item = Result<XmlEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.next_event();
+        if result.is_err() {
+            self.finished = true;
+        }
+        Some(result)
+    }
+}
+
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn char_reference(digits: &[u8], radix: u32) -> Option<char> {
+    let digits = str::from_utf8(digits).ok()?;
+    let value = u32::from_str_radix(digits, radix).ok()?;
+    core::char::from_u32(value).filter(|&c| c != '\0')
+}
+
+fn attribute_name_string(name: &OwnedName) -> String {
+    match name.prefix {
+        Some(ref prefix) => format!("{}:{}", prefix, name.local_name),
+        None => name.local_name.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    fn events(text: &str) -> Result<Vec<XmlEvent>, Error> {
+        Events::new(text.as_bytes(), None).collect()
+    }
+
+    fn name(name: &str) -> OwnedName {
+        OwnedName::new(String::from(name))
+    }
+
+    /// Source which reads one byte at a time, and fails after `.1` bytes.
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl<'a> Read for Trickle<'a> {
+        type Error = &'static str;
+
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize, &'static str> {
+            if self.1 == 0 {
+                return Err("broken");
+            }
+            self.1 -= 1;
+            let len = buf.len().min(1);
+            self.0.read(&mut buf[..len]).map_err(|_| "unreachable")
+        }
+    }
+
+    #[test]
+    fn test_events() {
+        assert_eq!(
+            events(
+                "\u{FEFF}<?xml version=\"1.0\" encoding=\"UTF-8\"?>\r\n<!DOCTYPE r [<!ELEMENT r \
+                 ANY>]>\n<!-- c --><r xmlns='urn:x' a=\"1&amp;&#x32;\"\r\nb='\t'>\r\n \
+                 <t>x<!-- c -->&lt;<![CDATA[<y>]]></t><e/></r>\n"
+            ),
+            Ok(vec![
+                XmlEvent::StartElement {
+                    name: name("r"),
+                    attributes: vec![
+                        OwnedAttribute {
+                            name: name("a"),
+                            value: String::from("1&2"),
+                        },
+                        OwnedAttribute {
+                            name: name("b"),
+                            value: String::from("\t"),
+                        },
+                    ],
+                },
+                XmlEvent::Whitespace(String::from("\r\n ")),
+                XmlEvent::StartElement {
+                    name: name("t"),
+                    attributes: Vec::new(),
+                },
+                XmlEvent::Characters(String::from("x<<y>")),
+                XmlEvent::EndElement { name: name("t") },
+                XmlEvent::StartElement {
+                    name: name("e"),
+                    attributes: Vec::new(),
+                },
+                XmlEvent::EndElement { name: name("e") },
+                XmlEvent::EndElement { name: name("r") },
+                XmlEvent::EndDocument,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let message = |text: &str| events(text).unwrap_err().to_string();
+        assert_eq!(
+            message("<r>\n  <a></b></r>"),
+            "2:6 Unexpected end tag `</b>`, expected `</a>`"
+        );
+        assert_eq!(message("<r><a>"), "1:7 Unexpected end of document");
+        assert_eq!(message("<r a='1' a='2'/>"), "1:10 Duplicate attribute `a`");
+        assert_eq!(
+            message("<r>&unknown;</r>"),
+            "1:13 Unknown reference `&unknown;`"
+        );
+        assert_eq!(
+            message("<r/><r/>"),
+            "1:6 Unexpected element after the root element"
+        );
+        assert_eq!(message(" "), "1:2 Missing root element");
+        assert_eq!(
+            message("<?xml version='1.0' encoding='ISO-8859-1'?><r/>"),
+            "1:44 Unsupported encoding: ISO-8859-1"
+        );
+        assert_eq!(message("<\0r\0/\0>\0"), "1:1 Unsupported encoding: UTF-16");
+
+        let mut events = Events::new(Trickle(b"<r>                    </r>", 16), None);
+        assert!(events.next().unwrap().is_ok());
+        assert_eq!(
+            events.next().unwrap().unwrap_err().to_string(),
+            "1:17 Failed to read the document: broken"
+        );
+        assert_eq!(events.next(), None);
+
+        let mut events = Events::new(&b"<r>0123456789</r>"[..], Some(4));
+        assert!(events.next().unwrap().is_ok());
+        assert!(events.next().unwrap().unwrap_err().is_limit_exceeded());
+        assert_eq!(events.next(), None);
+    }
+
+    #[test]
+    fn test_parse_stream() {
+        let text = r#"<registry>
+            <tags><tag name="KHR" author="Khronos" contact="-"/></tags>
+            <types><type category="define">#define <name>VK_FOO</name> 1</type></types>
+        </registry>"#;
+        let (registry, errors) = ::parse_str(text).unwrap();
+        assert_eq!(errors, Vec::new());
+        assert_eq!(registry.0.len(), 2);
+        let (streamed, _) = ::parse_stream(Trickle(text.as_bytes(), usize::MAX)).unwrap();
+        assert_eq!(streamed, registry);
+    }
+}
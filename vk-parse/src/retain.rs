@@ -0,0 +1,383 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+use api::{api_matches, ApiSet};
+use types::*;
+use validation::ValidationOptions;
+
+/// Reference to a feature, extension, type or command which isn't in the registry, see
+/// `Registry::dangling_references`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct DanglingReference {
+    /// Name of the feature, extension, type or command holding the reference.
+    pub from: Box<str>,
+    /// Attribute or element holding the reference, e.g. `promotedto`, `structextends` or
+    /// `require`.
+    pub via: &'static str,
+    /// Name of the missing item.
+    pub to: Box<str>,
+}
+
+impl Registry {
+    /// Removes extensions for which `f` returns `false`, along with types and commands which
+    /// are only required by them, and `<require>` blocks of other features and extensions which
+    /// only apply along with a removed extension. Returns references to removed items which
+    /// remain in the registry, see `Registry::dangling_references`.
+    pub fn retain_extensions<F: FnMut(&Extension) -> bool>(
+        &mut self,
+        f: F,
+    ) -> Vec<DanglingReference> {
+        let removed = self.take_extensions(f);
+        self.prune(removed)
+    }
+
+    /// Removes disabled extensions, see `Extension::is_disabled` and
+    /// `Registry::retain_extensions`.
+    pub fn remove_disabled(&mut self) -> Vec<DanglingReference> {
+        self.retain_extensions(|e| !e.is_disabled())
+    }
+
+    /// Removes everything which isn't part of `api`, e.g. `vulkan`: features and `<require>`,
+    /// `<remove>` and `<deprecate>` blocks for other APIs, extensions which aren't supported
+    /// by it, and variants of types, members, commands, parameters and enums specific to other
+    /// APIs. Types and commands which are then no longer required are removed too, as by
+    /// `Registry::retain_extensions`. Items which are kept retain their `api` attributes.
+    pub fn retain_api(&mut self, api: &str) -> Vec<DanglingReference> {
+        let mut removed = self.take_extensions(|e| e.supports(api));
+        let retain_blocks = |children: &mut Vec<ExtensionChild>, removed: &mut Vec<_>| {
+            for mut child in core::mem::take(children) {
+                if !api_matches(block_api(&child), api) {
+                    removed.push(child);
+                    continue;
+                }
+                match child {
+                    ExtensionChild::Require { ref mut items, .. }
+                    | ExtensionChild::Remove { ref mut items, .. }
+                    | ExtensionChild::Deprecate { ref mut items, .. } => {
+                        items.retain(|item| match *item {
+                            InterfaceItem::Enum(ref e) => api_matches(e.api.as_ref(), api),
+                            _ => true,
+                        })
+                    }
+                }
+                children.push(child);
+            }
+        };
+
+        for child in core::mem::take(&mut self.0) {
+            let child = match child {
+                RegistryChild::Feature(mut feature) => {
+                    if !api_matches(Some(&feature.api), api) {
+                        removed.extend(feature.children);
+                        continue;
+                    }
+                    retain_blocks(&mut feature.children, &mut removed);
+                    RegistryChild::Feature(feature)
+                }
+                RegistryChild::Extensions(mut extensions) => {
+                    for extension in &mut extensions.children {
+                        retain_blocks(&mut extension.children, &mut removed);
+                    }
+                    RegistryChild::Extensions(extensions)
+                }
+                RegistryChild::Types(mut types) => {
+                    types.children.retain(|child| match *child {
+                        TypesChild::Type(ref ty) => api_matches(ty.api.as_ref(), api),
+                        TypesChild::Comment(_) => true,
+                    });
+                    for child in &mut types.children {
+                        if let TypesChild::Type(Type {
+                            spec: TypeSpec::Members(ref mut members),
+                            ..
+                        }) = *child
+                        {
+                            members.retain(|member| match *member {
+                                TypeMember::Definition(ref def) => {
+                                    api_matches(def.api.as_ref(), api)
+                                }
+                                TypeMember::Comment(_) => true,
+                            });
+                        }
+                    }
+                    RegistryChild::Types(types)
+                }
+                RegistryChild::Enums(mut enums) => {
+                    enums.children.retain(|child| match *child {
+                        EnumsChild::Enum(ref e) => api_matches(e.api.as_ref(), api),
+                        _ => true,
+                    });
+                    RegistryChild::Enums(enums)
+                }
+                RegistryChild::Commands(mut commands) => {
+                    commands.children.retain(|command| match *command {
+                        Command::Definition(ref def) => api_matches(def.api.as_ref(), api),
+                        Command::Alias { .. } => true,
+                    });
+                    for command in &mut commands.children {
+                        if let Command::Definition(ref mut def) = *command {
+                            def.params
+                                .retain(|param| api_matches(param.api.as_ref(), api));
+                        }
+                    }
+                    RegistryChild::Commands(commands)
+                }
+                child => child,
+            };
+            self.0.push(child);
+        }
+        self.prune(removed)
+    }
+
+    /// Finds references to features, extensions, types and commands which aren't in the
+    /// registry, e.g. left after removing some of them, in order of the registry.
+    ///
+    /// These are `requires`, `promotedto`, `deprecatedby` and `obsoletedby` of extensions,
+    /// items and `extension` or `feature` conditions of `<require>`, `<remove>` and
+    /// `<deprecate>` blocks, `alias`, `requires`, `parent` and `structextends` of types, and
+    /// aliases of commands. References of disabled extensions aren't checked, see
+    /// `ValidationOptions`.
+    pub fn dangling_references(&self) -> Vec<DanglingReference> {
+        self.dangling_references_with_options(&ValidationOptions::default())
+    }
+
+    /// Finds references to items which aren't in the registry, see
+    /// `Registry::dangling_references`, checking parts of the registry given by `options`.
+    pub fn dangling_references_with_options(
+        &self,
+        options: &ValidationOptions,
+    ) -> Vec<DanglingReference> {
+        let names = Names::new(self);
+        let mut dangling = Vec::new();
+        let mut check = |from: &str, via: &'static str, to: &str, found: bool| {
+            if !found {
+                dangling.push(DanglingReference {
+                    from: from.into(),
+                    via,
+                    to: to.into(),
+                });
+            }
+        };
+
+        for child in &self.0 {
+            match *child {
+                RegistryChild::Types(ref types) => {
+                    for child in &types.children {
+                        let ty = match *child {
+                            TypesChild::Type(ref ty) => ty,
+                            TypesChild::Comment(_) => continue,
+                        };
+                        let from = match ty.declared_name() {
+                            Some(name) => name,
+                            None => continue,
+                        };
+                        for to in listed(&ty.alias) {
+                            check(from, "alias", to, names.types.contains(to));
+                        }
+                        for to in listed(&ty.requires) {
+                            check(from, "requires", to, names.types.contains(to));
+                        }
+                        for to in listed(&ty.parent) {
+                            check(from, "parent", to, names.types.contains(to));
+                        }
+                        for to in listed(&ty.structextends) {
+                            check(from, "structextends", to, names.types.contains(to));
+                        }
+                    }
+                }
+                RegistryChild::Commands(ref commands) => {
+                    for command in &commands.children {
+                        if let Command::Alias {
+                            ref name,
+                            ref alias,
+                            ..
+                        } = *command
+                        {
+                            check(name, "alias", alias, names.commands.contains(&**alias));
+                        }
+                    }
+                }
+                RegistryChild::Feature(ref feature) => {
+                    names.check_blocks(&feature.name, &feature.children, &mut check);
+                }
+                RegistryChild::Extensions(ref extensions) => {
+                    for extension in &extensions.children {
+                        if !options.checks(extension) {
+                            continue;
+                        }
+                        let from = &*extension.name;
+                        for to in listed(&extension.requires) {
+                            check(from, "requires", to, names.extensions.contains(to));
+                        }
+                        for &(via, to) in &extension.replacements() {
+                            if let Some(to) = to {
+                                check(from, via, to, names.has_interface(to));
+                            }
+                        }
+                        names.check_blocks(from, &extension.children, &mut check);
+                    }
+                }
+                _ => {}
+            }
+        }
+        dangling
+    }
+
+    /// Removes extensions for which `f` returns `false`, returning their children.
+    fn take_extensions<F: FnMut(&Extension) -> bool>(&mut self, mut f: F) -> Vec<ExtensionChild> {
+        let mut removed = Vec::new();
+        for child in &mut self.0 {
+            if let RegistryChild::Extensions(ref mut extensions) = *child {
+                let (kept, dropped) = core::mem::take(&mut extensions.children)
+                    .into_iter()
+                    .partition::<Vec<_>, _>(|e| f(e));
+                extensions.children = kept;
+                removed.extend(dropped.into_iter().flat_map(|e| e.children));
+            }
+        }
+        removed
+    }
+
+    /// Removes `<require>` blocks applying only along with a missing feature or extension, and
+    /// types and commands required only by those or by `removed`, then checks references.
+    fn prune(&mut self, mut removed: Vec<ExtensionChild>) -> Vec<DanglingReference> {
+        let (features, extensions) = {
+            let names = Names::new(self);
+            let owned = |names: BTreeSet<&str>| -> BTreeSet<Box<str>> {
+                names.into_iter().map(Box::from).collect()
+            };
+            (owned(names.features), owned(names.extensions))
+        };
+        // Only plain names are handled, other conditions are reported as dangling instead.
+        let missing = |condition: &Option<Box<str>>, names: &BTreeSet<Box<str>>| {
+            condition
+                .as_deref()
+                .filter(|c| c.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+                .map_or(false, |c| !names.contains(c))
+        };
+        let is_conditional_on_missing = |child: &ExtensionChild| match *child {
+            ExtensionChild::Require {
+                ref extension,
+                ref feature,
+                ref depends,
+                ..
+            } => {
+                missing(extension, &extensions)
+                    || missing(feature, &features)
+                    || depends.as_ref().map_or(false, |d| {
+                        !d.is_satisfied(|name| features.contains(name) || extensions.contains(name))
+                    })
+            }
+            _ => false,
+        };
+
+        for child in &mut self.0 {
+            let children = match *child {
+                RegistryChild::Feature(ref mut feature) => &mut feature.children,
+                RegistryChild::Extensions(ref mut extensions) => {
+                    for extension in &mut extensions.children {
+                        let (conditional, kept) = core::mem::take(&mut extension.children)
+                            .into_iter()
+                            .partition::<Vec<_>, _>(is_conditional_on_missing);
+                        extension.children = kept;
+                        removed.extend(conditional);
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+            let (conditional, kept) = core::mem::take(children)
+                .into_iter()
+                .partition::<Vec<_>, _>(is_conditional_on_missing);
+            *children = kept;
+            removed.extend(conditional);
+        }
+
+        self.remove_required_only_by(core::iter::once(&*removed));
+        self.dangling_references()
+    }
+}
+
+/// Names listed by a comma-separated attribute.
+fn listed(value: &Option<Box<str>>) -> impl Iterator<Item = &str> {
+    value.iter().flat_map(|v| v.split(','))
+}
+
+/// `api` of a `<require>`, `<remove>` or `<deprecate>` block.
+fn block_api(child: &ExtensionChild) -> Option<&ApiSet> {
+    match *child {
+        ExtensionChild::Require { ref api, .. }
+        | ExtensionChild::Remove { ref api, .. }
+        | ExtensionChild::Deprecate { ref api, .. } => api.as_ref(),
+    }
+}
+
+/// Names of items defined by the registry.
+pub(crate) struct Names<'a> {
+    pub(crate) features: BTreeSet<&'a str>,
+    pub(crate) extensions: BTreeSet<&'a str>,
+    pub(crate) types: BTreeSet<&'a str>,
+    pub(crate) commands: BTreeSet<&'a str>,
+}
+
+impl<'a> Names<'a> {
+    pub(crate) fn new(registry: &'a Registry) -> Self {
+        Names {
+            features: registry.features().map(|f| &*f.name).collect(),
+            extensions: registry.extensions().map(|e| &*e.name).collect(),
+            types: registry
+                .types()
+                .filter_map(|ty| ty.declared_name())
+                .collect(),
+            commands: registry.commands().map(|c| c.name()).collect(),
+        }
+    }
+
+    /// Whether `name` is a feature or an extension.
+    pub(crate) fn has_interface(&self, name: &str) -> bool {
+        self.features.contains(name) || self.extensions.contains(name)
+    }
+
+    fn check_blocks<F>(&self, from: &str, children: &[ExtensionChild], check: &mut F)
+    where
+        F: FnMut(&str, &'static str, &str, bool),
+    {
+        for child in children {
+            let (via, items) = match *child {
+                ExtensionChild::Require {
+                    ref extension,
+                    ref feature,
+                    ref depends,
+                    ref items,
+                    ..
+                } => {
+                    // Conditions may be expressions, e.g. `VK_KHR_a,VK_KHR_b`.
+                    let conditions = extension.iter().chain(feature).flat_map(|c| {
+                        c.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                            .filter(|name| !name.is_empty())
+                    });
+                    let depends = depends.iter().flat_map(|d| d.names());
+                    for to in conditions.chain(depends) {
+                        check(from, "require", to, self.has_interface(to));
+                    }
+                    ("require", items)
+                }
+                ExtensionChild::Remove { ref items, .. } => ("remove", items),
+                ExtensionChild::Deprecate { ref items, .. } => ("deprecate", items),
+            };
+            for item in items {
+                match *item {
+                    InterfaceItem::Type { ref name, .. } => {
+                        check(from, via, name, self.types.contains(&**name))
+                    }
+                    InterfaceItem::Command { ref name, .. } => {
+                        check(from, via, name, self.commands.contains(&**name))
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt::Debug;
+
+use diff::{diff, Change};
+use options::ParseOptions;
+use parse::parse_str_with_options;
+use types::*;
+use writer::WriteOptions;
+
+/// Difference between a registry and the result of writing it as XML and parsing it again,
+/// see `Registry::verify_roundtrip`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RoundtripMismatch {
+    /// Written document couldn't be parsed at all.
+    Unreadable { desc: String },
+    /// Error reported while parsing the written document.
+    ParseError(Error),
+    /// Item which was lost, added or changed, formatted with `Debug`.
+    Item {
+        /// Kind of the item: `type`, `command`, `enum`, `constant`, `feature` or `extension`,
+        /// as compared by `diff`.
+        kind: &'static str,
+        name: String,
+        /// The item in the original registry, unless it was added.
+        expected: Option<String>,
+        /// The item in the parsed registry, unless it was lost.
+        found: Option<String>,
+    },
+    /// Top-level element of the registry which differs, formatted with `Debug`. Only reported
+    /// for elements without items compared by `diff`, e.g. `<platforms>` or `<formats>`, or
+    /// when no item differs, e.g. when only order or comments changed.
+    Section {
+        /// Index within `Registry::0` of the element.
+        index: usize,
+        expected: Option<String>,
+        found: Option<String>,
+    },
+}
+
+impl Registry {
+    /// Writes the registry as XML with default options, parses the result again and compares
+    /// it with the original, returning all differences, see `Registry::to_xml`. No differences
+    /// are expected for registries parsed with default options.
+    pub fn verify_roundtrip(&self) -> Vec<RoundtripMismatch> {
+        self.verify_roundtrip_with_options(&ParseOptions::default(), &WriteOptions::default())
+    }
+
+    /// Writes the registry as XML using `write_options` and compares the result of parsing it
+    /// using `parse_options` with the original, see `Registry::verify_roundtrip`. Parse options
+    /// should match those used to obtain the registry, e.g. `ParseOptions::omit_code`.
+    pub fn verify_roundtrip_with_options(
+        &self,
+        parse_options: &ParseOptions,
+        write_options: &WriteOptions,
+    ) -> Vec<RoundtripMismatch> {
+        let xml = self.to_xml(write_options);
+        let (parsed, errors) = match parse_str_with_options(&xml, parse_options) {
+            Ok(v) => v,
+            Err(e) => {
+                return vec![RoundtripMismatch::Unreadable {
+                    desc: format!("{:?}", e),
+                }]
+            }
+        };
+        let mut mismatches: Vec<_> = errors
+            .into_iter()
+            .map(RoundtripMismatch::ParseError)
+            .collect();
+        if parsed == *self {
+            return mismatches;
+        }
+
+        let differences = diff(self, &parsed);
+        let types = differences.types.iter().map(|t| {
+            let name = t.change.before().or(t.change.after());
+            let name = name.and_then(|ty| ty.declared_name()).unwrap_or_default();
+            item_mismatch("type", name, &t.change)
+        });
+        let commands = differences
+            .commands
+            .iter()
+            .map(|c| item_mismatch("command", name_of(c, |c| c.name()), c));
+        let enums = differences
+            .enums
+            .iter()
+            .map(|e| item_mismatch("enum", name_of(e, |e| &e.name), e));
+        let constants = differences
+            .constants
+            .iter()
+            .map(|e| item_mismatch("constant", name_of(e, |e| &e.name), e));
+        let features = differences
+            .features
+            .iter()
+            .map(|f| item_mismatch("feature", name_of(f, |f| &f.name), f));
+        let extensions = differences
+            .extensions
+            .iter()
+            .map(|e| item_mismatch("extension", name_of(e, |e| &e.name), e));
+        let items: Vec<_> = types
+            .chain(commands)
+            .chain(enums)
+            .chain(constants)
+            .chain(features)
+            .chain(extensions)
+            .collect();
+
+        let only_sections = items.is_empty();
+        mismatches.extend(items);
+        let count = self.0.len().max(parsed.0.len());
+        for index in 0..count {
+            let (expected, found) = (self.0.get(index), parsed.0.get(index));
+            if expected == found || !(only_sections || has_no_items(expected.or(found))) {
+                continue;
+            }
+            mismatches.push(RoundtripMismatch::Section {
+                index,
+                expected: expected.map(|v| format!("{:?}", v)),
+                found: found.map(|v| format!("{:?}", v)),
+            });
+        }
+        mismatches
+    }
+}
+
+fn name_of<'a, T, F: Fn(&'a T) -> &'a str>(change: &Change<'a, T>, name: F) -> &'a str {
+    name(change.before().or(change.after()).unwrap())
+}
+
+fn item_mismatch<T: Debug>(
+    kind: &'static str,
+    name: &str,
+    change: &Change<T>,
+) -> RoundtripMismatch {
+    RoundtripMismatch::Item {
+        kind,
+        name: String::from(name),
+        expected: change.before().map(|v| format!("{:?}", v)),
+        found: change.after().map(|v| format!("{:?}", v)),
+    }
+}
+
+/// Whether the element doesn't contain any items compared by `diff`.
+fn has_no_items(child: Option<&RegistryChild>) -> bool {
+    !matches!(
+        child,
+        Some(RegistryChild::Types(_))
+            | Some(RegistryChild::Enums(_))
+            | Some(RegistryChild::Commands(_))
+            | Some(RegistryChild::Feature(_))
+            | Some(RegistryChild::Extensions(_))
+    )
+}
@@ -0,0 +1,229 @@
+use std;
+use std::io::Read;
+use std::ops::Range;
+
+use options::ParseOptions;
+use parse::parse_stream_with_options;
+use types::*;
+
+/// Top-level child element of the `<registry>` element, located by byte range within the
+/// document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SectionRange<'a> {
+    /// Name of the element, e.g. `types` or `extensions`.
+    pub name: &'a str,
+    /// Byte range of the whole element, from `<` of its start tag to `>` of its end tag.
+    pub range: Range<usize>,
+}
+
+/// Splits document into byte ranges of top-level registry sections, without building any
+/// objects for their contents.
+///
+/// This is only a lightweight scan. Input is not validated beyond what's needed to find element
+/// boundaries, and `None` is returned if the structure isn't understood, in which case callers
+/// are expected to fall back to regular parsing, which reports problems properly.
+pub(crate) fn split_sections(bytes: &[u8]) -> Option<Vec<SectionRange<'_>>> {
+    let mut scanner = Scanner { bytes, pos: 0 };
+    if scanner.skip_prolog()? {
+        return Some(Vec::new());
+    }
+
+    let mut sections = Vec::new();
+    loop {
+        match scanner.next_markup()? {
+            Markup::Other => {}
+            Markup::End { .. } => return Some(sections),
+            Markup::Start { name, start, empty } => {
+                let mut depth = if empty { 0 } else { 1 };
+                while depth > 0 {
+                    match scanner.next_markup()? {
+                        Markup::Other => {}
+                        Markup::Start { empty, .. } => {
+                            if !empty {
+                                depth += 1;
+                            }
+                        }
+                        Markup::End { .. } => depth -= 1,
+                    }
+                }
+                sections.push(SectionRange {
+                    name,
+                    range: start..scanner.pos,
+                });
+            }
+        }
+    }
+}
+
+/// Like `split_sections`, but tolerates sections which aren't well-formed: each section ends
+/// at the first end tag with its name, which is correct for well-formed documents, since
+/// elements of top-level sections don't contain elements with the same name.
+pub(crate) fn split_sections_lossy(bytes: &[u8]) -> Option<Vec<SectionRange<'_>>> {
+    let mut scanner = Scanner { bytes, pos: 0 };
+    if scanner.skip_prolog()? {
+        return Some(Vec::new());
+    }
+
+    let mut sections = Vec::new();
+    loop {
+        match scanner.next_markup()? {
+            Markup::Other => {}
+            Markup::End { .. } => return Some(sections),
+            Markup::Start { name, start, empty } => {
+                if !empty {
+                    loop {
+                        match scanner.next_markup()? {
+                            Markup::End { name: end } if end == name => break,
+                            _ => {}
+                        }
+                    }
+                }
+                sections.push(SectionRange {
+                    name,
+                    range: start..scanner.pos,
+                });
+            }
+        }
+    }
+}
+
+/// Parses single section of the document, as if it was the only child of `<registry>`.
+pub(crate) fn parse_section(
+    bytes: &[u8],
+    section: &SectionRange,
+    options: &ParseOptions,
+) -> Result<(Registry, Vec<Error>), FatalError> {
+    let stream = (&b"<registry>"[..])
+        .chain(&bytes[section.range.clone()])
+        .chain(&b"</registry>"[..]);
+    parse_stream_with_options(stream, options)
+}
+
+enum Markup<'a> {
+    Start {
+        name: &'a str,
+        start: usize,
+        empty: bool,
+    },
+    End {
+        name: &'a str,
+    },
+    /// Comments, processing instructions, CDATA sections and declarations.
+    Other,
+}
+
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    /// Moves past the prolog and the `<registry>` start tag, returning whether it's an empty
+    /// element.
+    ///
+    /// Sections are parsed as if they were the only child of a bare `<registry>` element, so
+    /// `None` is returned if the prolog or the start tag affect how the rest of the document is
+    /// read: a document type declaration, which may define entities used by sections, encoding
+    /// other than UTF-8, and attributes of `<registry>`, such as namespace declarations.
+    fn skip_prolog(&mut self) -> Option<bool> {
+        loop {
+            let from = self.pos;
+            match self.next_markup()? {
+                Markup::Other => {
+                    let markup = std::str::from_utf8(&self.bytes[from..self.pos]).ok()?;
+                    let markup = markup
+                        .trim_start_matches(|c: char| c == '\u{feff}' || c.is_ascii_whitespace());
+                    if markup.starts_with("<!DOCTYPE") {
+                        return None;
+                    }
+                    if markup.starts_with("<?xml") {
+                        match declared_encoding(markup) {
+                            Some(encoding) if !encoding.eq_ignore_ascii_case("utf-8") => {
+                                return None
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                Markup::Start { name, start, empty } => {
+                    let attributes = &self.bytes[start + 1 + name.len()..self.pos - 1];
+                    if name != "registry"
+                        || attributes
+                            .iter()
+                            .any(|&b| b != b'/' && !b.is_ascii_whitespace())
+                    {
+                        return None;
+                    }
+                    return Some(empty);
+                }
+                Markup::End { .. } => return None,
+            }
+        }
+    }
+
+    /// Finds next markup, skipping over character data, and moves past it.
+    fn next_markup(&mut self) -> Option<Markup<'a>> {
+        let start = self.pos + self.bytes[self.pos..].iter().position(|&b| b == b'<')?;
+        let rest = &self.bytes[start..];
+        if rest.starts_with(b"<!--") {
+            self.skip_past(start, b"-->")?;
+            Some(Markup::Other)
+        } else if rest.starts_with(b"<![CDATA[") {
+            self.skip_past(start, b"]]>")?;
+            Some(Markup::Other)
+        } else if rest.starts_with(b"<?") {
+            self.skip_past(start, b"?>")?;
+            Some(Markup::Other)
+        } else if rest.starts_with(b"<!") {
+            self.skip_past(start, b">")?;
+            Some(Markup::Other)
+        } else if rest.starts_with(b"</") {
+            self.skip_past(start, b">")?;
+            let name = std::str::from_utf8(&self.bytes[start + 2..self.pos - 1]).ok()?;
+            Some(Markup::End { name: name.trim() })
+        } else {
+            let name_len = rest[1..]
+                .iter()
+                .position(|&b| b == b'>' || b == b'/' || b.is_ascii_whitespace())?;
+            let name = std::str::from_utf8(&rest[1..1 + name_len]).ok()?;
+            let end = self.tag_end(start + 1 + name_len)?;
+            self.pos = end + 1;
+            Some(Markup::Start {
+                name,
+                start,
+                empty: self.bytes[end - 1] == b'/',
+            })
+        }
+    }
+
+    fn skip_past(&mut self, from: usize, pattern: &[u8]) -> Option<()> {
+        let offset = self.bytes[from..]
+            .windows(pattern.len())
+            .position(|w| w == pattern)?;
+        self.pos = from + offset + pattern.len();
+        Some(())
+    }
+
+    /// Position of `>` which ends a start tag, skipping over quoted attribute values.
+    fn tag_end(&self, from: usize) -> Option<usize> {
+        let mut quote = None;
+        for (i, &b) in self.bytes[from..].iter().enumerate() {
+            match quote {
+                Some(q) if b == q => quote = None,
+                Some(_) => {}
+                None if b == b'"' || b == b'\'' => quote = Some(b),
+                None if b == b'>' => return Some(from + i),
+                None => {}
+            }
+        }
+        None
+    }
+}
+
+/// Value of `encoding` of the XML declaration, if given.
+fn declared_encoding(declaration: &str) -> Option<&str> {
+    let (_, rest) = declaration.split_once("encoding")?;
+    let rest = rest.trim_start().strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next()?;
+    rest[1..].split(quote).next()
+}
@@ -0,0 +1,80 @@
+use std;
+use std::path::{Path, PathBuf};
+
+use options::ParseOptions;
+use parse::parse_file_with_options;
+use types::*;
+
+/// Registries parsed from all registry files of a directory, see `parse_dir`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RegistrySet {
+    /// Parsed files, with `vk.xml` first and remaining ones ordered by file name.
+    pub files: Vec<RegistryFile>,
+}
+
+/// Single registry file of `RegistrySet`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryFile {
+    pub path: PathBuf,
+    pub registry: Registry,
+    pub errors: Vec<Error>,
+}
+
+impl RegistrySet {
+    /// Finds file by its name, e.g. `video.xml`.
+    pub fn get(&self, file_name: &str) -> Option<&RegistryFile> {
+        self.files
+            .iter()
+            .find(|f| f.path.file_name().map_or(false, |n| n == file_name))
+    }
+
+    /// Top-level children of all registries, each along with path of the file it comes from.
+    pub fn children(&self) -> impl Iterator<Item = (&Path, &RegistryChild)> {
+        self.files
+            .iter()
+            .flat_map(|f| f.registry.0.iter().map(move |c| (f.path.as_path(), c)))
+    }
+
+    /// Combines all registries into one, by concatenating their top-level children.
+    pub fn combined(&self) -> Registry {
+        Registry(self.children().map(|(_, c)| c.clone()).collect())
+    }
+}
+
+/// Parses all registry files in directory, such as the `xml` directory of Vulkan-Docs, which
+/// contains `vk.xml` and `video.xml`.
+///
+/// All files with `.xml` extension are parsed, and those without `<registry>` root element are
+/// skipped.
+pub fn parse_dir(path: &Path) -> Result<RegistrySet, FatalError> {
+    parse_dir_with_options(path, &ParseOptions::default())
+}
+
+/// Parses all registry files in directory, using given options. See `parse_dir`.
+pub fn parse_dir_with_options(
+    path: &Path,
+    options: &ParseOptions,
+) -> Result<RegistrySet, FatalError> {
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().map_or(false, |e| e == "xml") {
+            paths.push(path);
+        }
+    }
+    paths.sort_by_key(|p| (p.file_name().map_or(true, |n| n != "vk.xml"), p.clone()));
+
+    let mut set = RegistrySet::default();
+    for path in paths {
+        match parse_file_with_options(&path, options) {
+            Ok((registry, errors)) => set.files.push(RegistryFile {
+                path,
+                registry,
+                errors,
+            }),
+            Err(FatalError::MissingRegistryElement) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(set)
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use types::*;
+
+/// Immutable registry along with indices for looking up its items by name.
+///
+/// Clones share the same data, so they are cheap and can be sent to other threads, e.g. to
+/// answer concurrent queries of a language server. The registry can be accessed through
+/// `Deref`.
+///
+/// When multiple items have the same name, such as types defined separately for `vulkan` and
+/// `vulkansc` APIs, lookups return the first one.
+#[derive(Debug, Clone)]
+pub struct SharedRegistry(Arc<Indexed>);
+
+#[derive(Debug)]
+struct Indexed {
+    registry: Registry,
+    /// Position of item by name, as indices of the registry child and of the item within it.
+    types: HashMap<Box<str>, (usize, usize)>,
+    commands: HashMap<Box<str>, (usize, usize)>,
+    enums: HashMap<Box<str>, (usize, usize)>,
+    extensions: HashMap<Box<str>, (usize, usize)>,
+    /// Index of registry child by name, for items which are registry children themselves.
+    enums_blocks: HashMap<Box<str>, usize>,
+    features: HashMap<Box<str>, usize>,
+}
+
+impl SharedRegistry {
+    /// Builds indices of the registry.
+    pub fn new(registry: Registry) -> Self {
+        let mut indexed = Indexed {
+            registry,
+            types: HashMap::new(),
+            commands: HashMap::new(),
+            enums: HashMap::new(),
+            extensions: HashMap::new(),
+            enums_blocks: HashMap::new(),
+            features: HashMap::new(),
+        };
+        for (i, child) in indexed.registry.0.iter().enumerate() {
+            match *child {
+                RegistryChild::Types(ref types) => {
+                    for (j, child) in types.children.iter().enumerate() {
+                        if let TypesChild::Type(ref ty) = *child {
+                            if let Some(name) = ty.declared_name() {
+                                indexed.types.entry(name.into()).or_insert((i, j));
+                            }
+                        }
+                    }
+                }
+                RegistryChild::Commands(ref commands) => {
+                    for (j, command) in commands.children.iter().enumerate() {
+                        indexed
+                            .commands
+                            .entry(command.name().into())
+                            .or_insert((i, j));
+                    }
+                }
+                RegistryChild::Enums(ref enums) => {
+                    if let Some(ref name) = enums.name {
+                        indexed.enums_blocks.entry(name.clone()).or_insert(i);
+                    }
+                    for (j, child) in enums.children.iter().enumerate() {
+                        if let EnumsChild::Enum(ref e) = *child {
+                            indexed.enums.entry(e.name.clone()).or_insert((i, j));
+                        }
+                    }
+                }
+                RegistryChild::Feature(ref feature) => {
+                    indexed.features.entry(feature.name.clone()).or_insert(i);
+                }
+                RegistryChild::Extensions(ref extensions) => {
+                    for (j, ext) in extensions.children.iter().enumerate() {
+                        indexed.extensions.entry(ext.name.clone()).or_insert((i, j));
+                    }
+                }
+                _ => {}
+            }
+        }
+        SharedRegistry(Arc::new(indexed))
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.0.registry
+    }
+
+    /// Finds type by its name, see `Type::declared_name`.
+    pub fn get_type(&self, name: &str) -> Option<&Type> {
+        let &(i, j) = self.0.types.get(name)?;
+        match self.0.registry.0[i] {
+            RegistryChild::Types(ref types) => match types.children[j] {
+                TypesChild::Type(ref ty) => Some(ty),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Finds command or command alias by its name.
+    pub fn get_command(&self, name: &str) -> Option<&Command> {
+        let &(i, j) = self.0.commands.get(name)?;
+        match self.0.registry.0[i] {
+            RegistryChild::Commands(ref commands) => Some(&commands.children[j]),
+            _ => None,
+        }
+    }
+
+    /// Finds `<enums>` block by its name, e.g. `VkResult` or `API Constants`.
+    pub fn get_enums(&self, name: &str) -> Option<&Enums> {
+        match self.0.registry.0[*self.0.enums_blocks.get(name)?] {
+            RegistryChild::Enums(ref enums) => Some(enums),
+            _ => None,
+        }
+    }
+
+    /// Finds enum defined in an `<enums>` block by its name. Enums added by features and
+    /// extensions aren't included.
+    pub fn get_enum(&self, name: &str) -> Option<&Enum> {
+        let &(i, j) = self.0.enums.get(name)?;
+        match self.0.registry.0[i] {
+            RegistryChild::Enums(ref enums) => match enums.children[j] {
+                EnumsChild::Enum(ref e) => Some(e),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Finds feature by its name, e.g. `VK_VERSION_1_0`.
+    pub fn get_feature(&self, name: &str) -> Option<&Feature> {
+        match self.0.registry.0[*self.0.features.get(name)?] {
+            RegistryChild::Feature(ref feature) => Some(feature),
+            _ => None,
+        }
+    }
+
+    /// Finds extension by its name, e.g. `VK_KHR_surface`.
+    pub fn get_extension(&self, name: &str) -> Option<&Extension> {
+        let &(i, j) = self.0.extensions.get(name)?;
+        match self.0.registry.0[i] {
+            RegistryChild::Extensions(ref extensions) => Some(&extensions.children[j]),
+            _ => None,
+        }
+    }
+}
+
+impl Deref for SharedRegistry {
+    type Target = Registry;
+
+    fn deref(&self) -> &Registry {
+        &self.0.registry
+    }
+}
+
+impl From<Registry> for SharedRegistry {
+    fn from(registry: Registry) -> Self {
+        SharedRegistry::new(registry)
+    }
+}
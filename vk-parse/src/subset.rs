@@ -0,0 +1,384 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeSet, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+use depends::DependsExpr;
+use types::*;
+use version::ApiVersion;
+
+impl Registry {
+    /// Minimal registry containing the given features and extensions, along with everything
+    /// they depend on, e.g. for shipping a trimmed registry with an SDK or as a test fixture.
+    ///
+    /// Besides the named ones, the result contains:
+    /// * earlier features of the same API, extensions listed by `requires` of extensions and
+    ///   the feature given by their `requiresCore`, transitively;
+    /// * `<require>` blocks whose `extension`, `feature` or `depends` condition is satisfied by
+    ///   these;
+    /// * types and commands required by them, along with types and constants they refer to,
+    ///   e.g. by `alias`, `requires`, `parent`, `structextends` or in their declarations;
+    /// * `<enums>` blocks of the included types and the constants referred to;
+    /// * formats and SPIR-V extensions and capabilities which refer to the included items;
+    /// * pipeline stages, access flags and pipelines of the `<sync>` section which refer to the
+    ///   included enums;
+    /// * video codecs whose codec operation flag or structs are included.
+    ///
+    /// Vendor IDs, platforms and tags are kept, while comments between types are removed.
+    /// Use `Registry::to_xml` to write the result.
+    pub fn minimized<'a, I: IntoIterator<Item = &'a str>>(&self, interfaces: I) -> Registry {
+        let interfaces = self.interface_closure(interfaces);
+        let (types, commands, constants) = self.required_closure(&interfaces);
+
+        let mut children = Vec::new();
+        let mut enumerants = BTreeSet::new();
+        for child in &self.0 {
+            let child = match *child {
+                RegistryChild::Types(ref block) => {
+                    let mut block = block.clone();
+                    block.children.retain(|child| match *child {
+                        TypesChild::Type(ref ty) => ty
+                            .declared_name()
+                            .map_or(false, |name| types.contains(name)),
+                        TypesChild::Comment(_) => false,
+                    });
+                    RegistryChild::Types(block)
+                }
+                RegistryChild::Enums(ref block) => {
+                    let mut block = block.clone();
+                    if !block.name.as_deref().map_or(false, |n| types.contains(n)) {
+                        block.children.retain(|child| match *child {
+                            EnumsChild::Enum(ref e) => constants.contains(&*e.name),
+                            _ => false,
+                        });
+                        if block.children.is_empty() {
+                            continue;
+                        }
+                    }
+                    for child in &block.children {
+                        if let EnumsChild::Enum(ref e) = *child {
+                            enumerants.insert(e.name.clone());
+                        }
+                    }
+                    RegistryChild::Enums(block)
+                }
+                RegistryChild::Commands(ref block) => {
+                    let mut block = block.clone();
+                    block
+                        .children
+                        .retain(|command| commands.contains(command.name()));
+                    RegistryChild::Commands(block)
+                }
+                RegistryChild::Feature(ref feature) => {
+                    if !interfaces.contains(&*feature.name) {
+                        continue;
+                    }
+                    let mut feature = feature.clone();
+                    retain_satisfied(&mut feature.children, &interfaces, &mut enumerants);
+                    RegistryChild::Feature(feature)
+                }
+                RegistryChild::Extensions(ref block) => {
+                    let mut block = block.clone();
+                    block.children.retain(|e| interfaces.contains(&*e.name));
+                    for extension in &mut block.children {
+                        retain_satisfied(&mut extension.children, &interfaces, &mut enumerants);
+                    }
+                    if block.children.is_empty() {
+                        continue;
+                    }
+                    RegistryChild::Extensions(block)
+                }
+                RegistryChild::Formats(ref block) => {
+                    let mut block = block.clone();
+                    block
+                        .children
+                        .retain(|format| enumerants.contains(&format.name));
+                    if block.children.is_empty() {
+                        continue;
+                    }
+                    RegistryChild::Formats(block)
+                }
+                RegistryChild::SpirvExtensions(ref block) => {
+                    RegistryChild::SpirvExtensions(retain_enables(block, &interfaces, &types))
+                }
+                RegistryChild::SpirvCapabilities(ref block) => {
+                    RegistryChild::SpirvCapabilities(retain_enables(block, &interfaces, &types))
+                }
+                RegistryChild::Sync(ref block) => {
+                    let mut block = block.clone();
+                    block.children.retain(|child| match *child {
+                        SyncChild::Pipeline(ref pipeline) => pipeline
+                            .stages
+                            .iter()
+                            .any(|stage| enumerants.contains(&stage.name)),
+                        ref child => enumerants.contains(child.name()),
+                    });
+                    if block.children.is_empty() {
+                        continue;
+                    }
+                    RegistryChild::Sync(block)
+                }
+                RegistryChild::VideoCodecs(ref block) => {
+                    let mut block = block.clone();
+                    block.children.retain(|codec| {
+                        codec
+                            .value
+                            .as_ref()
+                            .map_or(false, |v| enumerants.contains(v))
+                            || codec.structs().any(|name| types.contains(name))
+                    });
+                    if block.children.is_empty() {
+                        continue;
+                    }
+                    RegistryChild::VideoCodecs(block)
+                }
+                ref child => child.clone(),
+            };
+            children.push(child);
+        }
+        Registry(children)
+    }
+
+    /// Names of the given features and extensions along with those they depend on.
+    fn interface_closure<'a, 'b, I: IntoIterator<Item = &'b str>>(
+        &'a self,
+        interfaces: I,
+    ) -> BTreeSet<&'a str> {
+        let features: Vec<&Feature> = self.features().collect();
+        let mut found = BTreeSet::new();
+        let mut pending: Vec<&str> = interfaces.into_iter().collect();
+        while let Some(name) = pending.pop() {
+            if let Some(i) = features.iter().position(|f| &*f.name == name) {
+                let apis = &features[i].api.apis;
+                let earlier = features[..i]
+                    .iter()
+                    .filter(|f| f.api.apis.iter().any(|api| apis.contains(api)));
+                found.insert(&*features[i].name);
+                pending.extend(earlier.map(|f| &*f.name).filter(|n| !found.contains(n)));
+            } else if let Some(extension) = self.extensions().find(|e| &*e.name == name) {
+                found.insert(&*extension.name);
+                let core = extension
+                    .requires_core
+                    .as_deref()
+                    .and_then(ApiVersion::parse)
+                    .and_then(|number| features.iter().find(|f| f.number == number))
+                    .map(|f| &*f.name);
+                let requires = extension.requires.iter().flat_map(|r| r.split(','));
+                pending.extend(requires.chain(core).filter(|n| !found.contains(n)));
+            }
+        }
+        found
+    }
+
+    /// Names of types, commands and constants required by `<require>` blocks applying to
+    /// `interfaces`, along with those they refer to.
+    fn required_closure(
+        &self,
+        interfaces: &BTreeSet<&str>,
+    ) -> (BTreeSet<&str>, BTreeSet<&str>, BTreeSet<&str>) {
+        let mut pending_types = Vec::new();
+        let mut pending_commands = Vec::new();
+        let mut constants = BTreeSet::new();
+        let blocks = self
+            .features()
+            .map(|f| (&f.name, &f.children))
+            .chain(self.extensions().map(|e| (&e.name, &e.children)));
+        for (_, children) in blocks.filter(|&(name, _)| interfaces.contains(&**name)) {
+            for child in children {
+                let items = match *child {
+                    ExtensionChild::Require {
+                        ref extension,
+                        ref feature,
+                        ref depends,
+                        ref items,
+                        ..
+                    } if is_satisfied(extension, interfaces)
+                        && is_satisfied(feature, interfaces)
+                        && depends_satisfied(depends, interfaces) =>
+                    {
+                        items
+                    }
+                    _ => continue,
+                };
+                for item in items {
+                    match *item {
+                        InterfaceItem::Type { ref name, .. } => pending_types.push(&**name),
+                        InterfaceItem::Command { ref name, .. } => pending_commands.push(&**name),
+                        InterfaceItem::Enum(ref e) => match e.spec {
+                            EnumSpec::Value {
+                                extends: Some(ref extends),
+                                ..
+                            }
+                            | EnumSpec::Bitpos {
+                                extends: Some(ref extends),
+                                ..
+                            }
+                            | EnumSpec::Alias {
+                                extends: Some(ref extends),
+                                ..
+                            }
+                            | EnumSpec::Offset { ref extends, .. } => pending_types.push(extends),
+                            EnumSpec::None => {
+                                constants.insert(&*e.name);
+                            }
+                            _ => {}
+                        },
+                        InterfaceItem::Comment(_) => {}
+                    }
+                }
+            }
+        }
+
+        let mut commands = BTreeSet::new();
+        while let Some(name) = pending_commands.pop() {
+            if !commands.insert(name) {
+                continue;
+            }
+            for command in self.commands().filter(|c| c.name() == name) {
+                match *command {
+                    Command::Alias { ref alias, .. } => pending_commands.push(alias),
+                    Command::Definition(ref def) => {
+                        pending_commands.extend(def.alias.as_deref());
+                        let declarations = Some(&def.proto)
+                            .into_iter()
+                            .chain(def.params.iter().map(|p| &p.definition));
+                        pending_types.extend(declarations.filter_map(|d| d.type_name.as_deref()));
+                    }
+                }
+            }
+        }
+
+        let mut types = BTreeSet::new();
+        while let Some(name) = pending_types.pop() {
+            if !types.insert(name) {
+                continue;
+            }
+            for ty in self.types().filter(|ty| ty.declared_name() == Some(name)) {
+                let attributes = [
+                    &ty.alias,
+                    &ty.requires,
+                    &ty.parent,
+                    &ty.structextends,
+                    &ty.bitvalues,
+                ];
+                for value in attributes.iter().filter_map(|v| v.as_deref()) {
+                    pending_types.extend(value.split(','));
+                }
+                match ty.spec {
+                    TypeSpec::Code(ref code) => {
+                        for markup in &code.markup {
+                            if let TypeCodeMarkup::Type(ref name) = *markup {
+                                pending_types.push(name);
+                            }
+                        }
+                    }
+                    TypeSpec::Members(ref members) => {
+                        for member in members {
+                            let def = match *member {
+                                TypeMember::Definition(ref def) => def,
+                                TypeMember::Comment(_) => continue,
+                            };
+                            for markup in &def.markup {
+                                match *markup {
+                                    TypeMemberMarkup::Type(ref name) => pending_types.push(name),
+                                    TypeMemberMarkup::Enum(ref name) => {
+                                        constants.insert(name);
+                                    }
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Constants may be aliases of other constants.
+        let mut pending_constants: Vec<_> = constants.iter().cloned().collect();
+        while let Some(constant) = pending_constants.pop() {
+            for block in self.enums_blocks() {
+                for child in &block.children {
+                    match *child {
+                        EnumsChild::Enum(Enum {
+                            ref name,
+                            spec: EnumSpec::Alias { ref alias, .. },
+                            ..
+                        }) if &**name == constant && constants.insert(alias) => {
+                            pending_constants.push(alias)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        (types, commands, constants)
+    }
+}
+
+/// Whether an `extension` or `feature` condition of a `<require>` block is satisfied by
+/// `interfaces`. Conditions have the syntax of `depends`, see `DependsExpr`; malformed ones
+/// are never satisfied.
+fn is_satisfied(condition: &Option<Box<str>>, interfaces: &BTreeSet<&str>) -> bool {
+    condition.as_deref().map_or(true, |condition| {
+        DependsExpr::parse(condition)
+            .map_or(false, |d| d.is_satisfied(|name| interfaces.contains(name)))
+    })
+}
+
+/// Whether a `depends` condition of a `<require>` block is satisfied by `interfaces`.
+fn depends_satisfied(depends: &Option<DependsExpr>, interfaces: &BTreeSet<&str>) -> bool {
+    depends
+        .as_ref()
+        .map_or(true, |d| d.is_satisfied(|name| interfaces.contains(name)))
+}
+
+/// Removes `<require>` blocks whose condition isn't satisfied by `interfaces`, and collects
+/// names of enumerants of the remaining ones.
+fn retain_satisfied(
+    children: &mut Vec<ExtensionChild>,
+    interfaces: &BTreeSet<&str>,
+    enumerants: &mut BTreeSet<Box<str>>,
+) {
+    children.retain(|child| match *child {
+        ExtensionChild::Require {
+            ref extension,
+            ref feature,
+            ref depends,
+            ..
+        } => {
+            is_satisfied(extension, interfaces)
+                && is_satisfied(feature, interfaces)
+                && depends_satisfied(depends, interfaces)
+        }
+        _ => true,
+    });
+    for child in children.iter() {
+        if let ExtensionChild::Require { ref items, .. } = *child {
+            for item in items {
+                if let InterfaceItem::Enum(ref e) = *item {
+                    enumerants.insert(e.name.clone());
+                }
+            }
+        }
+    }
+}
+
+/// SPIR-V extensions or capabilities with only enables referring to the included features,
+/// extensions and types, without those which then have no enables.
+fn retain_enables(
+    block: &SpirvExtensions,
+    interfaces: &BTreeSet<&str>,
+    types: &BTreeSet<&str>,
+) -> SpirvExtensions {
+    let mut block = block.clone();
+    for child in &mut block.children {
+        child.enables.retain(|enable| match *enable {
+            Enable::Version(ref name) | Enable::Extension(ref name) => interfaces.contains(&**name),
+            Enable::Feature(ref f) => types.contains(&*f.struct_),
+            Enable::Property(ref p) => types.contains(&*p.property),
+        });
+    }
+    block.children.retain(|child| !child.enables.is_empty());
+    block
+}
@@ -0,0 +1,216 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use api::ApiSet;
+use deprecation::{DeprecatedExtension, DeprecatedItem};
+use types::*;
+
+/// Feature or extension taking part in the history of an item, see `Timeline`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct TimelineEntry<'a> {
+    /// Name of the feature or extension, e.g. `VK_VERSION_1_1` or `VK_KHR_maintenance1`.
+    pub by: &'a str,
+    /// Whether `by` is a feature rather than an extension.
+    pub is_feature: bool,
+    /// APIs of the `<require>` or `<remove>` block if it's specific to some, or else the APIs
+    /// of the feature, e.g. `vulkan,vulkansc`. `None` for blocks of extensions without `api`.
+    pub api: Option<&'a ApiSet>,
+}
+
+/// History of a type, command or enum, see `Registry::timeline`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Timeline<'a> {
+    pub name: &'a str,
+    /// Feature or extension which introduced the item: the first feature requiring it in order
+    /// of the registry, or the first extension if no feature requires it.
+    pub introduced: Option<TimelineEntry<'a>>,
+    /// All features and extensions requiring the item, in order of the registry.
+    pub required_by: Vec<TimelineEntry<'a>>,
+    /// Feature or extension requiring an item the item is an alias of, possibly through other
+    /// aliases, along with the name there, e.g. `VK_VERSION_1_1` and
+    /// `vkGetPhysicalDeviceFeatures2` for `vkGetPhysicalDeviceFeatures2KHR`. Features are
+    /// preferred over extensions. `None` for items introduced by a feature.
+    pub promoted: Option<(TimelineEntry<'a>, &'a str)>,
+    /// Deprecations of the item by `<deprecate>` blocks, in order of the registry.
+    pub deprecated: Vec<DeprecatedItem<'a>>,
+    /// Deprecated or obsoleted extensions among those requiring the item.
+    pub deprecated_extensions: Vec<DeprecatedExtension<'a>>,
+    /// Features and extensions removing the item by `<remove>` blocks, e.g. `VKSC_VERSION_1_0`
+    /// for items which aren't part of Vulkan SC, in order of the registry.
+    pub removed_by: Vec<TimelineEntry<'a>>,
+}
+
+impl Registry {
+    /// Reports history of the type, command or enum with given name: the feature or extension
+    /// introducing it, its promotion, and its deprecations and removals, combining
+    /// `Registry::deprecations` with `<require>` and `<remove>` blocks and aliases. Disabled
+    /// extensions are skipped. Returns `None` if no feature or extension refers to the item.
+    pub fn timeline<'a>(&'a self, name: &str) -> Option<Timeline<'a>> {
+        let required_by = self.entries_listing(name, false);
+        let removed_by = self.entries_listing(name, true);
+        let deprecations = self.deprecations();
+        let deprecated: Vec<_> = deprecations
+            .types
+            .into_iter()
+            .chain(deprecations.commands)
+            .chain(deprecations.enums)
+            .filter(|d| d.name == name)
+            .collect();
+        let name = match (required_by.first(), removed_by.first(), deprecated.first()) {
+            (Some(&(_, item)), _, _) | (None, Some(&(_, item)), _) => item_name(item),
+            (None, None, Some(d)) => d.name,
+            (None, None, None) => return None,
+        };
+        let required_by: Vec<_> = required_by.into_iter().map(|(entry, _)| entry).collect();
+        let removed_by = removed_by.into_iter().map(|(entry, _)| entry).collect();
+
+        let introduced = required_by
+            .iter()
+            .find(|e| e.is_feature)
+            .or_else(|| required_by.first())
+            .copied();
+        let promoted = match introduced {
+            Some(entry) if entry.is_feature => None,
+            _ => self.promotion_of(name),
+        };
+        let deprecated_extensions = deprecations
+            .extensions
+            .into_iter()
+            .filter(|d| required_by.iter().any(|e| e.by == &*d.extension.name))
+            .collect();
+
+        Some(Timeline {
+            name,
+            introduced,
+            required_by,
+            promoted,
+            deprecated,
+            deprecated_extensions,
+            removed_by,
+        })
+    }
+
+    /// Features and extensions listing the item by `<remove>` blocks if `removed`, or else by
+    /// `<require>` blocks, along with the listed item.
+    fn entries_listing<'a>(
+        &'a self,
+        name: &str,
+        removed: bool,
+    ) -> Vec<(TimelineEntry<'a>, &'a InterfaceItem)> {
+        let features = self
+            .features()
+            .map(|f| (&*f.name, true, Some(&f.api), &f.children));
+        let extensions = self
+            .extensions()
+            .filter(|e| !e.is_disabled())
+            .map(|e| (&*e.name, false, None, &e.children));
+        let mut entries = Vec::new();
+        for (by, is_feature, api, children) in features.chain(extensions) {
+            for child in children {
+                let (block_api, items) = match *child {
+                    ExtensionChild::Require {
+                        ref api, ref items, ..
+                    } if !removed => (api, items),
+                    ExtensionChild::Remove {
+                        ref api, ref items, ..
+                    } if removed => (api, items),
+                    _ => continue,
+                };
+                if let Some(item) = items.iter().find(|item| item_name(item) == name) {
+                    let entry = TimelineEntry {
+                        by,
+                        is_feature,
+                        api: block_api.as_ref().or(api),
+                    };
+                    // Items may be listed by several blocks of the same feature or extension.
+                    if !entries.iter().any(|&(e, _)| e == entry) {
+                        entries.push((entry, item));
+                    }
+                }
+            }
+        }
+        entries
+    }
+
+    /// Feature or extension requiring an item `name` is an alias of, see `Timeline::promoted`.
+    fn promotion_of(&self, name: &str) -> Option<(TimelineEntry<'_>, &str)> {
+        let mut extension = None;
+        for target in alias_chain(name, |target| self.alias_of(target)) {
+            let required_by = self.entries_listing(target, false);
+            if let Some(&(entry, item)) = required_by.iter().find(|&&(e, _)| e.is_feature) {
+                return Some((entry, item_name(item)));
+            }
+            if extension.is_none() {
+                extension = required_by
+                    .first()
+                    .map(|&(entry, item)| (entry, item_name(item)));
+            }
+        }
+        extension
+    }
+
+    /// Item the type, command or enum with given name is an alias of.
+    fn alias_of(&self, name: &str) -> Option<&str> {
+        let types = self.types().filter_map(|ty| match ty.alias {
+            Some(ref alias) if ty.declared_name() == Some(name) => Some(&**alias),
+            _ => None,
+        });
+        let commands = self.commands().filter_map(|command| match *command {
+            Command::Alias {
+                name: ref n,
+                ref alias,
+                ..
+            } if &**n == name => Some(&**alias),
+            _ => None,
+        });
+        let block_enums = self
+            .enums_blocks()
+            .flat_map(|block| &block.children)
+            .filter_map(|child| match *child {
+                EnumsChild::Enum(ref e) => Some(e),
+                _ => None,
+            });
+        let item_enums = self
+            .features()
+            .flat_map(|f| &f.children)
+            .chain(self.extensions().flat_map(|e| &e.children))
+            .flat_map(|child| match *child {
+                ExtensionChild::Require { ref items, .. } => &items[..],
+                _ => &[],
+            })
+            .filter_map(|item| match *item {
+                InterfaceItem::Enum(ref e) => Some(e),
+                _ => None,
+            });
+        let enums = block_enums.chain(item_enums).filter_map(|e| match e.spec {
+            EnumSpec::Alias { ref alias, .. } if &*e.name == name => Some(&**alias),
+            _ => None,
+        });
+        types.chain(commands).chain(enums).next()
+    }
+}
+
+/// Items `name` is an alias of, in order, as given by `alias_of` until it returns `None`.
+pub(crate) fn alias_chain<'a, F>(name: &'a str, mut alias_of: F) -> impl Iterator<Item = &'a str>
+where
+    F: FnMut(&'a str) -> Option<&'a str>,
+{
+    let mut target = name;
+    // Aliases may form chains, e.g. of an EXT to a KHR extension promoted to core. The length
+    // is bounded in case of cycles.
+    core::iter::from_fn(move || {
+        target = alias_of(target).filter(|&alias| alias != target)?;
+        Some(target)
+    })
+    .take(16)
+}
+
+pub(crate) fn item_name(item: &InterfaceItem) -> &str {
+    match *item {
+        InterfaceItem::Type { ref name, .. } | InterfaceItem::Command { ref name, .. } => name,
+        InterfaceItem::Enum(ref e) => &e.name,
+        InterfaceItem::Comment(_) => "",
+    }
+}
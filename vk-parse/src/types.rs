@@ -1,13 +1,65 @@
 #![allow(non_snake_case)]
+#![allow(clippy::large_enum_variant)]
+
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap;
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+use api::{Api, ApiSet, ExtensionSupport};
+use commands::QueueKind;
+#[cfg(not(feature = "std"))]
+use core::fmt::{self, Write};
+use depends::DependsExpr;
+use deprecation::Deprecation;
+use inline::InlineVec;
+use options::ResourceLimit;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::fmt::{self, Write};
+use version::ApiVersion;
+use video::VideoCoding;
 
 /// Errors from which parser cannot recover.
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum FatalError {
     MissingRegistryElement,
+    #[cfg(feature = "std")]
     IoError(std::io::Error),
+    /// Recoverable errors which were encountered while parsing with `ParseOptions::strict`.
+    Strict(Vec<Error>),
+    /// Document exceeded one of the resource limits set in `ParseOptions`. Parsing stops at the
+    /// element given by `xpath`.
+    LimitExceeded {
+        limit: ResourceLimit,
+        xpath: String,
+    },
+}
+
+impl FatalError {
+    /// Stable code identifying kind of the error, e.g. for allowing specific errors in CI
+    /// without matching their messages. Codes of fatal errors are in the `VKP01xx` range, see
+    /// `Error::code`.
+    ///
+    /// | Code      | Variant                  |
+    /// |-----------|--------------------------|
+    /// | `VKP0101` | `MissingRegistryElement` |
+    /// | `VKP0102` | `IoError`                |
+    /// | `VKP0103` | `Strict`                 |
+    /// | `VKP0104` | `LimitExceeded`          |
+    pub fn code(&self) -> &'static str {
+        match *self {
+            FatalError::MissingRegistryElement => "VKP0101",
+            #[cfg(feature = "std")]
+            FatalError::IoError(_) => "VKP0102",
+            FatalError::Strict(_) => "VKP0103",
+            FatalError::LimitExceeded { .. } => "VKP0104",
+        }
+    }
 }
 
+#[cfg(feature = "std")]
 impl From<std::io::Error> for FatalError {
     fn from(v: std::io::Error) -> FatalError {
         FatalError::IoError(v)
@@ -19,7 +71,7 @@ impl From<std::io::Error> for FatalError {
 /// which it occurs. For example, unrecognized attribute will simply be skipped
 /// without affecting anything around it, while unrecognized element will have
 /// all of its contents skipped.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Error {
     UnexpectedElement {
@@ -50,28 +102,157 @@ pub enum Error {
     ParseIntError {
         xpath: String,
         text: String,
-        error: std::num::ParseIntError,
+        /// Base in which the number was parsed, determined by its prefix.
+        radix: u32,
+        error: core::num::ParseIntError,
     },
     Internal {
         desc: &'static str,
     },
+    /// Element which isn't part of the schema, e.g. within an element which only has
+    /// attributes, and which was skipped without being reported otherwise. Only reported with
+    /// `ParseOptions::coverage`.
+    IgnoredElement {
+        xpath: String,
+        name: String,
+    },
+    /// Attribute which isn't parsed, e.g. on an element which only has text. Only reported with
+    /// `ParseOptions::coverage`.
+    IgnoredAttribute {
+        xpath: String,
+        name: String,
+    },
+    /// Text within an element which only has attributes or child elements. Only reported with
+    /// `ParseOptions::coverage`.
+    IgnoredText {
+        xpath: String,
+    },
+    /// Document couldn't be read past the element given by `xpath`, e.g. because of malformed
+    /// XML. Only reported by `parse_stream_lossy`.
+    ReadError {
+        xpath: String,
+        desc: String,
+    },
+}
+
+impl Error {
+    /// Stable code identifying kind of the error, e.g. for allowing specific errors in CI
+    /// without matching their messages. Codes never change once assigned, and new kinds of
+    /// errors get new codes. Recoverable errors are in the `VKP00xx` range, while fatal ones
+    /// are in the `VKP01xx` range, see `FatalError::code`.
+    ///
+    /// | Code      | Variant                    |
+    /// |-----------|----------------------------|
+    /// | `VKP0001` | `UnexpectedElement`        |
+    /// | `VKP0002` | `UnexpectedAttribute`      |
+    /// | `VKP0003` | `UnexpectedAttributeValue` |
+    /// | `VKP0004` | `MissingElement`           |
+    /// | `VKP0005` | `MissingAttribute`         |
+    /// | `VKP0006` | `SchemaViolation`          |
+    /// | `VKP0007` | `ParseIntError`            |
+    /// | `VKP0008` | `Internal`                 |
+    /// | `VKP0009` | `IgnoredElement`           |
+    /// | `VKP0010` | `IgnoredAttribute`         |
+    /// | `VKP0011` | `IgnoredText`              |
+    /// | `VKP0012` | `ReadError`                |
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Error::UnexpectedElement { .. } => "VKP0001",
+            Error::UnexpectedAttribute { .. } => "VKP0002",
+            Error::UnexpectedAttributeValue { .. } => "VKP0003",
+            Error::MissingElement { .. } => "VKP0004",
+            Error::MissingAttribute { .. } => "VKP0005",
+            Error::SchemaViolation { .. } => "VKP0006",
+            Error::ParseIntError { .. } => "VKP0007",
+            Error::Internal { .. } => "VKP0008",
+            Error::IgnoredElement { .. } => "VKP0009",
+            Error::IgnoredAttribute { .. } => "VKP0010",
+            Error::IgnoredText { .. } => "VKP0011",
+            Error::ReadError { .. } => "VKP0012",
+        }
+    }
+
+    /// Whether the error only reports part of the document which the parser doesn't model, see
+    /// `ParseOptions::coverage`. Such errors don't make parsing with `ParseOptions::strict` fail.
+    pub fn is_coverage(&self) -> bool {
+        matches!(
+            *self,
+            Error::IgnoredElement { .. }
+                | Error::IgnoredAttribute { .. }
+                | Error::IgnoredText { .. }
+        )
+    }
 }
 
 /// Rust structure representing the Vulkan registry.
 ///
 /// The registry contains all the information contained in a certain version
 /// of the Vulkan specification, stored within a programmer-accessible format.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct Registry(pub Vec<RegistryChild>);
 
+impl Registry {
+    /// All types, from all `<types>` blocks.
+    pub fn types(&self) -> impl Iterator<Item = &Type> {
+        self.0.iter().flat_map(|child| {
+            let children: &[TypesChild] = match *child {
+                RegistryChild::Types(ref types) => &types.children,
+                _ => &[],
+            };
+            children.iter().filter_map(|child| match *child {
+                TypesChild::Type(ref ty) => Some(ty),
+                _ => None,
+            })
+        })
+    }
+
+    /// All commands, including aliases, from all `<commands>` blocks.
+    pub fn commands(&self) -> impl Iterator<Item = &Command> {
+        self.0.iter().flat_map(|child| {
+            let children: &[Command] = match *child {
+                RegistryChild::Commands(ref commands) => &commands.children,
+                _ => &[],
+            };
+            children.iter()
+        })
+    }
+
+    /// All `<enums>` blocks, each of which defines either an enum type or constants.
+    pub fn enums_blocks(&self) -> impl Iterator<Item = &Enums> {
+        self.0.iter().filter_map(|child| match *child {
+            RegistryChild::Enums(ref enums) => Some(enums),
+            _ => None,
+        })
+    }
+
+    /// All features, i.e. core API versions.
+    pub fn features(&self) -> impl Iterator<Item = &Feature> {
+        self.0.iter().filter_map(|child| match *child {
+            RegistryChild::Feature(ref feature) => Some(feature),
+            _ => None,
+        })
+    }
+
+    /// All extensions, from all `<extensions>` blocks.
+    pub fn extensions(&self) -> impl Iterator<Item = &Extension> {
+        self.0.iter().flat_map(|child| {
+            let children: &[Extension] = match *child {
+                RegistryChild::Extensions(ref extensions) => &extensions.children,
+                _ => &[],
+            };
+            children.iter()
+        })
+    }
+}
+
 /// An element of the Vulkan registry.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum RegistryChild {
     /// Comments are human-readable strings which contain registry meta-data.
-    Comment(String),
+    Comment(Box<str>),
 
     /// IDs of all known Vulkan vendors.
     VendorIds(VendorIds),
@@ -104,20 +285,48 @@ pub enum RegistryChild {
     SpirvExtensions(SpirvExtensions),
 
     SpirvCapabilities(SpirvCapabilities),
+
+    /// Semantics of pipeline stages and access flags used for synchronization.
+    Sync(SyncSection),
+
+    /// Video codecs along with their profiles, capabilities and formats.
+    VideoCodecs(VideoCodecs),
+
+    /// Placeholder for a section which couldn't be read, see `parse_stream_lossy`.
+    Malformed(MalformedSection),
+}
+
+/// Top-level element of the registry which couldn't be read, kept in place of its contents by
+/// `parse_stream_lossy`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct MalformedSection {
+    /// Name of the element, e.g. `types`.
+    pub name: Box<str>,
+
+    /// Source text of the whole element, from its start tag to its end tag.
+    pub text: Box<str>,
+
+    /// Description of the problem, which is also reported as `Error::ReadError`.
+    pub desc: Box<str>,
 }
 
+/// Original values of XML attributes of an element, by attribute name.
+pub type RawAttributes = BTreeMap<Box<str>, Box<str>>;
+
 pub type VendorIds = CommentedChildren<VendorId>;
 
 /// Unique identifier for a Vulkan vendor.
 ///
 /// Note: in newer versions of the Vulkan spec (1.1.79 and later), this tag is
 /// not used, instead it has been replaced by the `VKVendorId` enum.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct VendorId {
     /// Name of the vendor.
-    pub name: String,
+    pub name: Box<str>,
 
     /// The unique ID.
     pub id: u32,
@@ -127,7 +336,14 @@ pub struct VendorId {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
 }
 
 pub type Platforms = CommentedChildren<Platform>;
@@ -138,22 +354,182 @@ pub type Platforms = CommentedChildren<Platform>;
 /// but Linux has multiple (XCB, Wayland, etc.)
 ///
 /// Used in versions 1.1.70 and later.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Platform {
     /// Short identifier.
-    pub name: String,
+    pub name: PlatformName,
 
     /// C macro name which is used to guard platform-specific definitions.
-    pub protect: String,
+    pub protect: Box<str>,
 
     /// Human readable description of the platform.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
+}
+
+/// Short identifier of a platform, e.g. `xlib`, see `Platform::name`.
+///
+/// Platforms unknown to this crate, e.g. those added to the registry later, are kept as
+/// `PlatformName::Other`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(Serialize, Deserialize),
+    serde(from = "Box<str>", into = "Box<str>")
+)]
+#[non_exhaustive]
+pub enum PlatformName {
+    Xlib,
+    XlibXrandr,
+    Xcb,
+    Wayland,
+    DirectFb,
+    Android,
+    Win32,
+    Vi,
+    Ios,
+    MacOs,
+    Metal,
+    Fuchsia,
+    Ggp,
+    Sci,
+    Provisional,
+    Screen,
+    Other(Box<str>),
+}
+
+impl PlatformName {
+    /// Identifier as used in the registry.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            PlatformName::Xlib => "xlib",
+            PlatformName::XlibXrandr => "xlib_xrandr",
+            PlatformName::Xcb => "xcb",
+            PlatformName::Wayland => "wayland",
+            PlatformName::DirectFb => "directfb",
+            PlatformName::Android => "android",
+            PlatformName::Win32 => "win32",
+            PlatformName::Vi => "vi",
+            PlatformName::Ios => "ios",
+            PlatformName::MacOs => "macos",
+            PlatformName::Metal => "metal",
+            PlatformName::Fuchsia => "fuchsia",
+            PlatformName::Ggp => "ggp",
+            PlatformName::Sci => "sci",
+            PlatformName::Provisional => "provisional",
+            PlatformName::Screen => "screen",
+            PlatformName::Other(ref name) => name,
+        }
+    }
+
+    /// C macro guarding definitions for the platform, e.g. `VK_USE_PLATFORM_XLIB_KHR`, as
+    /// given by `Platform::protect` in current registries. `None` for `PlatformName::Other`.
+    pub fn guard_macro(&self) -> Option<&'static str> {
+        Some(match *self {
+            PlatformName::Xlib => "VK_USE_PLATFORM_XLIB_KHR",
+            PlatformName::XlibXrandr => "VK_USE_PLATFORM_XLIB_XRANDR_EXT",
+            PlatformName::Xcb => "VK_USE_PLATFORM_XCB_KHR",
+            PlatformName::Wayland => "VK_USE_PLATFORM_WAYLAND_KHR",
+            PlatformName::DirectFb => "VK_USE_PLATFORM_DIRECTFB_EXT",
+            PlatformName::Android => "VK_USE_PLATFORM_ANDROID_KHR",
+            PlatformName::Win32 => "VK_USE_PLATFORM_WIN32_KHR",
+            PlatformName::Vi => "VK_USE_PLATFORM_VI_NN",
+            PlatformName::Ios => "VK_USE_PLATFORM_IOS_MVK",
+            PlatformName::MacOs => "VK_USE_PLATFORM_MACOS_MVK",
+            PlatformName::Metal => "VK_USE_PLATFORM_METAL_EXT",
+            PlatformName::Fuchsia => "VK_USE_PLATFORM_FUCHSIA",
+            PlatformName::Ggp => "VK_USE_PLATFORM_GGP",
+            PlatformName::Sci => "VK_USE_PLATFORM_SCI",
+            PlatformName::Provisional => "VK_ENABLE_BETA_EXTENSIONS",
+            PlatformName::Screen => "VK_USE_PLATFORM_SCREEN_QNX",
+            PlatformName::Other(_) => return None,
+        })
+    }
+
+    /// Headers which code for the platform typically needs to include before the Vulkan
+    /// headers, e.g. `X11/Xlib.h`. The registry itself lists them as `<type>` elements with
+    /// category `include`, which are authoritative.
+    pub fn headers(&self) -> &'static [&'static str] {
+        match *self {
+            PlatformName::Xlib => &["X11/Xlib.h"],
+            PlatformName::XlibXrandr => &["X11/Xlib.h", "X11/extensions/Xrandr.h"],
+            PlatformName::Xcb => &["xcb/xcb.h"],
+            PlatformName::Wayland => &["wayland-client.h"],
+            PlatformName::DirectFb => &["directfb.h"],
+            PlatformName::Win32 => &["windows.h"],
+            PlatformName::Fuchsia => &["zircon/types.h"],
+            PlatformName::Ggp => &["ggp_c/vulkan_types.h"],
+            PlatformName::Sci => &["nvscisync.h", "nvscibuf.h"],
+            PlatformName::Screen => &["screen/screen.h"],
+            _ => &[],
+        }
+    }
+}
+
+impl<'a> From<&'a str> for PlatformName {
+    fn from(name: &'a str) -> Self {
+        match name {
+            "xlib" => PlatformName::Xlib,
+            "xlib_xrandr" => PlatformName::XlibXrandr,
+            "xcb" => PlatformName::Xcb,
+            "wayland" => PlatformName::Wayland,
+            "directfb" => PlatformName::DirectFb,
+            "android" => PlatformName::Android,
+            "win32" => PlatformName::Win32,
+            "vi" => PlatformName::Vi,
+            "ios" => PlatformName::Ios,
+            "macos" => PlatformName::MacOs,
+            "metal" => PlatformName::Metal,
+            "fuchsia" => PlatformName::Fuchsia,
+            "ggp" => PlatformName::Ggp,
+            "sci" => PlatformName::Sci,
+            "provisional" => PlatformName::Provisional,
+            "screen" => PlatformName::Screen,
+            name => PlatformName::Other(name.into()),
+        }
+    }
+}
+
+impl From<Box<str>> for PlatformName {
+    fn from(name: Box<str>) -> Self {
+        match PlatformName::from(&*name) {
+            PlatformName::Other(_) => PlatformName::Other(name),
+            v => v,
+        }
+    }
+}
+
+impl From<PlatformName> for Box<str> {
+    fn from(name: PlatformName) -> Self {
+        match name {
+            PlatformName::Other(name) => name,
+            name => name.as_str().into(),
+        }
+    }
+}
+
+impl Default for PlatformName {
+    fn default() -> Self {
+        PlatformName::Other(Box::default())
+    }
+}
+
+impl fmt::Display for PlatformName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 pub type Tags = CommentedChildren<Tag>;
@@ -163,30 +539,30 @@ pub type Tags = CommentedChildren<Tag>;
 /// Some examples:
 /// - KHR for Khronos extensions
 /// - EXT for multi-vendor extensions
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Tag {
     /// The name of the tag, e.g. "KHR".
-    pub name: String,
+    pub name: Box<str>,
     /// Author of the extensions associated with the tag, e.g. "Khronos".
-    pub author: String,
+    pub author: Box<str>,
     /// Contact information for the extension author(s).
-    pub contact: String,
+    pub contact: Box<str>,
 }
 
 pub type Types = CommentedChildren<TypesChild>;
 
 /// An item making up a type definition.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum TypesChild {
     Type(Type),
-    Comment(String),
+    Comment(Box<str>),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Type {
@@ -194,89 +570,137 @@ pub struct Type {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub name: Option<String>,
+    pub name: Option<Box<str>>,
+
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub alias: Option<Box<str>>,
 
+    /// Whether the type is deprecated, given by `deprecated`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub alias: Option<String>,
+    pub deprecated: Option<Deprecation>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub api: Option<String>,
+    pub api: Option<ApiSet>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub requires: Option<String>,
+    pub requires: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub category: Option<String>,
+    pub category: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub parent: Option<String>,
+    pub parent: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub returnedonly: Option<String>,
+    pub returnedonly: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub structextends: Option<String>,
+    pub structextends: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub allowduplicate: Option<String>,
+    pub allowduplicate: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub objtypeenum: Option<String>,
+    pub objtypeenum: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub bitvalues: Option<String>,
+    pub bitvalues: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
     pub spec: TypeSpec,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
+}
+
+impl Type {
+    /// Name of the type, given either by the `name` attribute or by the `<name>` element in
+    /// code, as for e.g. handles and basetypes.
+    pub fn declared_name(&self) -> Option<&str> {
+        if let Some(ref name) = self.name {
+            return Some(name);
+        }
+        match self.spec {
+            TypeSpec::Code(ref code) => code.markup.iter().find_map(|m| match *m {
+                TypeCodeMarkup::Name(ref name) => Some(&**name),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
 }
 
 /// The contents of a type definition.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum TypeSpec {
+    /// Type without any definition, e.g. one provided by a platform header.
     None,
     Code(TypeCode),
     Members(Vec<TypeMember>),
+    /// Handle type defined as an alias of another handle, given by `alias`.
+    HandleAlias {
+        alias: Box<str>,
+    },
+    /// Bitmask type defined as an alias of another bitmask, given by `alias`.
+    BitmaskAlias {
+        alias: Box<str>,
+    },
+    /// Struct type defined as an alias of another struct, given by `alias`.
+    StructAlias {
+        alias: Box<str>,
+    },
+    /// Union type defined as an alias of another union, given by `alias`.
+    UnionAlias {
+        alias: Box<str>,
+    },
 }
 
 impl Default for TypeSpec {
@@ -285,134 +709,257 @@ impl Default for TypeSpec {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct TypeCode {
-    pub code: String,
+    pub code: Box<str>,
+
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub markup: InlineVec<TypeCodeMarkup>,
+
+    /// The code split into preprocessor directives, comments and remaining C code. Empty if the
+    /// code doesn't contain any preprocessor directives.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub directives: Vec<PreprocessorItem>,
 
+    /// Calling convention of a `funcpointer` type, needed for the correct ABI on platforms such
+    /// as 32-bit Windows. It's the text of `<apientry>` markup, which is empty for the empty
+    /// `<apientry/>` element used by the OpenGL registry in place of its `APIENTRY` macro, or
+    /// else the macro preceding `*` of the declarator, e.g. `VKAPI_PTR` in
+    /// `typedef void (VKAPI_PTR *PFN_vkVoidFunction)(void);`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub markup: Vec<TypeCodeMarkup>,
+    pub calling_convention: Option<Box<str>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum TypeCodeMarkup {
-    Name(String),
-    Type(String),
-    ApiEntry(String),
+    Name(Box<str>),
+    Type(Box<str>),
+    ApiEntry(Box<str>),
+}
+
+/// Part of code containing preprocessor directives, see `TypeCode::directives`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum PreprocessorItem {
+    /// Text of a `//` or `/* */` comment, without the delimiters.
+    Comment(Box<str>),
+    /// A `#define` directive.
+    Define(TypeDefine),
+    /// An `#include` directive with a header name.
+    Include(TypeInclude),
+    /// An `#if`, `#ifdef` or `#ifndef` block, with one branch for it and each following `#elif`
+    /// and `#else`.
+    Conditional(Vec<PreprocessorBranch>),
+    /// Any other directive, e.g. `#undef` or `#pragma`, with the leading `#`. Also used for
+    /// unbalanced `#elif`, `#else` and `#endif`, and for `#define` and `#include` directives
+    /// which can't be parsed, e.g. `#include VK_HEADER` without a header name.
+    Directive(Box<str>),
+    /// C code outside of directives, e.g. a typedef.
+    Code(Box<str>),
+}
+
+/// Single branch of `PreprocessorItem::Conditional`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct PreprocessorBranch {
+    /// Condition of the branch as used by `#if`, so `#ifdef X` gives `defined(X)` and `#ifndef X`
+    /// gives `!defined(X)`. `None` for the `#else` branch.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub condition: Option<Box<str>>,
+
+    pub items: Vec<PreprocessorItem>,
+}
+
+/// An `#include` directive.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct TypeInclude {
+    /// Path of the header, e.g. `vk_video/vulkan_video_codec_h264std.h`.
+    pub path: Box<str>,
+    /// Whether the path is in angle brackets, as for system headers, rather than in quotes.
+    pub system: bool,
+}
+
+/// A `#define` directive.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct TypeDefine {
+    pub name: Box<str>,
+    pub value: TypeDefineValue,
+}
+
+/// Replacement text of a `#define` directive, with line continuations and comments removed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum TypeDefineValue {
+    /// Macro defined without replacement text, e.g. `#define VK_NO_PROTOTYPES`.
+    Empty,
+    /// Object-like macro, e.g. `#define VK_HEADER_VERSION 250`.
+    Value(Box<str>),
+    /// Function-like macro, e.g. `#define VK_VERSION_MAJOR(version) ((uint32_t)(version) >> 22U)`.
+    Function {
+        params: Vec<Box<str>>,
+        body: Box<str>,
+    },
 }
 
 /// A member of a type definition, i.e. a struct member.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum TypeMember {
     /// Human-readable comment.
-    Comment(String),
+    Comment(Box<str>),
 
     /// A structure field definition.
     Definition(TypeMemberDefinition),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct TypeMemberDefinition {
+    /// APIs the member is defined for, e.g. `vulkansc`, if it isn't defined for all of them.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub len: Option<String>,
+    pub api: Option<ApiSet>,
 
+    /// Whether the member is deprecated, usually `Ignored`, given by `deprecated`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub altlen: Option<String>,
+    pub deprecated: Option<Deprecation>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub externsync: Option<String>,
+    pub len: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub optional: Option<String>,
+    pub altlen: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub selector: Option<String>,
+    pub externsync: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub selection: Option<String>,
+    pub optional: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub noautovalidity: Option<String>,
+    pub selector: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub validextensionstructs: Option<String>,
+    pub selection: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub values: Option<String>,
+    pub noautovalidity: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub limittype: Option<String>,
+    pub validextensionstructs: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub objecttype: Option<String>,
+    pub values: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub code: String,
+    pub limittype: Option<Box<str>>,
+
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub objecttype: Option<Box<str>>,
+
+    /// C code of the declaration. Empty if parsed with `ParseOptions::omit_code` and `c_type`
+    /// was recognized, in which case `Display` can be used to regenerate it.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub code: Box<str>,
+
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub markup: InlineVec<TypeMemberMarkup>,
+
+    /// Structured type of the declaration in `code`, `None` if it couldn't be recognized.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub c_type: Option<CType>,
 
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub markup: Vec<TypeMemberMarkup>,
+    pub raw: RawAttributes,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum TypeMemberMarkup {
-    Name(String),
-    Type(String),
-    Enum(String),
-    Comment(String),
+    Name(Box<str>),
+    Type(Box<str>),
+    Enum(Box<str>),
+    Comment(Box<str>),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Enums {
@@ -420,13 +967,13 @@ pub struct Enums {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub name: Option<String>,
+    pub name: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub kind: Option<String>,
+    pub kind: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
@@ -444,29 +991,36 @@ pub struct Enums {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub vendor: Option<String>,
+    pub vendor: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub children: Vec<EnumsChild>,
+    pub children: InlineVec<EnumsChild>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
     pub bitwidth: Option<u32>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
 }
 
 /// An item which forms an enum.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum EnumsChild {
@@ -477,11 +1031,11 @@ pub enum EnumsChild {
     Unused(Unused),
 
     /// Human-readable comment.
-    Comment(String),
+    Comment(Box<str>),
 }
 
 /// An unused range of enum values.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Unused {
@@ -500,71 +1054,100 @@ pub struct Unused {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub vendor: Option<String>,
+    pub vendor: Option<Box<str>>,
 
     /// Human-readable description.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
 }
 
 /// An item of an enumeration type.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Enum {
     /// Name of this enum.
-    pub name: String,
+    pub name: Box<str>,
 
     /// Human-readable description.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
+
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub type_suffix: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub type_suffix: Option<String>,
+    pub api: Option<ApiSet>,
 
+    /// Whether the enum is deprecated, e.g. `Aliased` for old names, given by `deprecated`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub api: Option<String>,
+    pub deprecated: Option<Deprecation>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub protect: Option<String>,
+    pub protect: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
     pub spec: EnumSpec,
+
+    /// Feature or extension defining the enum, if it was moved from there to the `<enums>` block
+    /// of the type it extends by `Registry::materialize_enums`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub required_by: Option<Box<str>>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
 }
 
 /// An enum specifier, which assigns a value to the enum.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum EnumSpec {
     None,
 
     Alias {
-        alias: String,
+        alias: Box<str>,
 
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        extends: Option<String>,
+        extends: Option<Box<str>>,
     },
 
     Offset {
@@ -574,7 +1157,7 @@ pub enum EnumSpec {
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        extends: String,
+        extends: Box<str>,
 
         #[cfg_attr(
             feature = "serialize",
@@ -599,20 +1182,20 @@ pub enum EnumSpec {
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        extends: Option<String>,
+        extends: Option<Box<str>>,
     },
 
     /// An enum value.
     Value {
         /// Hard coded value for an enum.
-        value: String, // rnc says this is an Integer, but validates it as text, and that's what it sometimes really is.
+        value: Box<str>, // rnc says this is an Integer, but validates it as text, and that's what it sometimes really is.
 
         /// Which structure this enum extends.
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        extends: Option<String>,
+        extends: Option<Box<str>>,
     },
 }
 
@@ -625,68 +1208,128 @@ impl Default for EnumSpec {
 pub type Commands = CommentedChildren<Command>;
 
 /// A command is just a Vulkan function.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum Command {
     /// Indicates this function is an alias for another one.
-    Alias { name: String, alias: String },
+    Alias {
+        name: Box<str>,
+        alias: Box<str>,
+        /// Whether the alias is deprecated, usually `Aliased`, given by `deprecated`.
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        deprecated: Option<Deprecation>,
+
+        /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        raw: RawAttributes,
+    },
 
     /// Defines a new Vulkan function.
     Definition(CommandDefinition),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+impl Command {
+    /// Name of the command, whether it's defined or an alias.
+    pub fn name(&self) -> &str {
+        match *self {
+            Command::Alias { ref name, .. } => name,
+            Command::Definition(ref def) => &def.proto.name,
+        }
+    }
+
+    /// Whether the command is deprecated, whether it's defined or an alias.
+    pub fn deprecated(&self) -> Option<Deprecation> {
+        match *self {
+            Command::Alias { deprecated, .. } => deprecated,
+            Command::Definition(ref def) => def.deprecated,
+        }
+    }
+
+    /// `api` of the definition, `None` for aliases, which are shared by all APIs.
+    pub fn api(&self) -> Option<&ApiSet> {
+        match *self {
+            Command::Alias { .. } => None,
+            Command::Definition(ref def) => def.api.as_ref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct CommandDefinition {
+    /// APIs the command is defined for, e.g. `vulkansc`, if it isn't defined for all of them.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub api: Option<ApiSet>,
+
+    /// Whether the command is deprecated, given by `deprecated`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub deprecated: Option<Deprecation>,
+
+    /// Kinds of queues the command can be executed on, given by `queues`. Empty if it's not
+    /// set, as for commands which aren't recorded or submitted to queues.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub queues: Option<String>,
+    pub queues: Vec<QueueKind>,
 
+    /// Result codes returned on success, given by `successcodes`, e.g. `VK_SUCCESS`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub successcodes: Option<String>,
+    pub successcodes: Vec<Box<str>>,
 
+    /// Result codes returned on failure, given by `errorcodes`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub errorcodes: Option<String>,
+    pub errorcodes: Vec<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub renderpass: Option<String>,
+    pub renderpass: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub videocoding: Option<String>,
+    pub videocoding: Option<VideoCoding>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub cmdbufferlevel: Option<String>,
+    pub cmdbufferlevel: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub pipeline: Option<String>,
+    pub pipeline: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
@@ -704,72 +1347,96 @@ pub struct CommandDefinition {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub alias: Option<String>,
+    pub alias: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub description: Option<String>,
+    pub description: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub implicitexternsyncparams: Vec<String>,
+    pub implicitexternsyncparams: Vec<Box<str>>,
 
+    /// C code of the prototype. Empty if parsed with `ParseOptions::omit_code` and `c_type` of
+    /// the prototype and all parameters was recognized, in which case `Display` can be used to
+    /// regenerate it.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub code: String,
+    pub code: Box<str>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
 }
 
 /// Parameter for this Vulkan function.
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct CommandParam {
+    /// APIs the parameter is defined for, e.g. `vulkansc`, if it isn't defined for all of them.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub api: Option<ApiSet>,
+
+    /// Whether the parameter is deprecated, usually `Ignored`, given by `deprecated`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub deprecated: Option<Deprecation>,
+
     /// The expression which indicates the length of this array.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub len: Option<String>,
+    pub len: Option<Box<str>>,
 
     /// Alternate description of the length of this parameter.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub altlen: Option<String>,
+    pub altlen: Option<Box<str>>,
 
     /// Whether this parameter must be externally synchronised by the app.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub externsync: Option<String>,
+    pub externsync: Option<Box<str>>,
 
     /// Whether this parameter must have a non-null value.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub optional: Option<String>,
+    pub optional: Option<Box<str>>,
 
     /// Disables automatic validity language being generated for this item.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub noautovalidity: Option<String>,
+    pub noautovalidity: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub objecttype: Option<String>,
+    pub objecttype: Option<Box<str>>,
 
     /// The definition of this parameter.
     #[cfg_attr(
@@ -786,10 +1453,17 @@ pub struct CommandParam {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub validstructs: Vec<String>,
+    pub validstructs: Vec<Box<str>>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Feature {
@@ -797,56 +1471,63 @@ pub struct Feature {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub api: String,
+    pub api: ApiSet,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub name: String,
+    pub name: Box<str>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub number: String,
+    pub number: ApiVersion,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub protect: Option<String>,
+    pub protect: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
     pub children: Vec<FeatureChild>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
 }
 
 pub type FeatureChild = ExtensionChild;
 
 pub type Extensions = CommentedChildren<Extension>;
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Extension {
     /// Name of the extension.
-    pub name: String,
+    pub name: Box<str>,
 
     /// Human-readable description.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
 
     /// The unique index of this extension.
     #[cfg_attr(
@@ -859,72 +1540,89 @@ pub struct Extension {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub protect: Option<String>,
+    pub protect: Option<Box<str>>,
 
     /// Which platform it works with, if any.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub platform: Option<String>,
+    pub platform: Option<Box<str>>,
 
     /// Tag name of the author.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub author: Option<String>,
+    pub author: Option<Box<str>>,
 
     /// Contact information for extension author(s).
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub contact: Option<String>,
+    pub contact: Option<Box<str>>,
 
     /// The level at which the extension applies (instance / device).
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub ext_type: Option<String>,
+    pub ext_type: Option<Box<str>>,
+
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub requires: Option<Box<str>>,
+
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub requires_core: Option<Box<str>>,
 
+    /// Features and extensions the extension depends on, given by `depends`, which replaced
+    /// `requires` and `requiresCore`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub requires: Option<String>,
+    pub depends: Option<DependsExpr>,
 
+    /// APIs the extension is supported for, given by `supported`.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub requires_core: Option<String>,
+    pub supported: Option<ExtensionSupport>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub supported: Option<String>, // mk:TODO StringGroup?
+    pub deprecatedby: Option<Box<str>>,
 
+    /// Whether the extension is deprecated, given by `deprecated`. Unlike `deprecatedby`, it
+    /// doesn't name a replacement.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub deprecatedby: Option<String>,
+    pub deprecated: Option<Deprecation>,
 
     /// Whether this extension was promoted to core, and in which version.
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub promotedto: Option<String>,
+    pub promotedto: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub obsoletedby: Option<String>,
+    pub obsoletedby: Option<Box<str>>,
 
     /// 'true' if this extension is released provisionally
     #[cfg_attr(
@@ -938,7 +1636,7 @@ pub struct Extension {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub specialuse: Option<String>,
+    pub specialuse: Option<Box<str>>,
 
     /// Relative sortorder
     #[cfg_attr(
@@ -953,12 +1651,54 @@ pub struct Extension {
         serde(default, skip_serializing_if = "is_default")
     )]
     pub children: Vec<ExtensionChild>,
-}
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
+}
+
+impl Extension {
+    /// Whether the extension is disabled, i.e. has `supported="disabled"`, which is used for
+    /// extensions that reserve a number but aren't part of any API.
+    pub fn is_disabled(&self) -> bool {
+        self.supported == Some(ExtensionSupport::Disabled)
+    }
+
+    /// Attributes naming the replacement of the extension, `promotedto`, `deprecatedby` and
+    /// `obsoletedby`, along with their values if set to a replacement.
+    pub(crate) fn replacements(&self) -> [(&'static str, Option<&str>); 3] {
+        fn replacement(by: &Option<Box<str>>) -> Option<&str> {
+            // Empty attribute means there's no replacement.
+            by.as_deref().filter(|by| !by.is_empty())
+        }
+        [
+            ("promotedto", replacement(&self.promotedto)),
+            ("deprecatedby", replacement(&self.deprecatedby)),
+            ("obsoletedby", replacement(&self.obsoletedby)),
+        ]
+    }
+
+    /// Whether the extension is supported for API with given name, e.g. `vulkan`. Extensions
+    /// without `supported` are supported for all APIs.
+    pub fn supports(&self, api: &str) -> bool {
+        self.supported.as_ref().map_or(true, |s| s.supports(api))
+    }
+
+    /// Whether the extension is supported for `api`, see `Extension::supports`.
+    pub fn supports_api(&self, api: &Api) -> bool {
+        self.supported
+            .as_ref()
+            .map_or(true, |s| s.supports_api(api))
+    }
+}
 
 /// A part of an extension declaration.
 ///
 /// Extensions either include functionality from the spec, or remove some functionality.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum ExtensionChild {
@@ -968,35 +1708,50 @@ pub enum ExtensionChild {
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        api: Option<String>,
+        api: Option<ApiSet>,
 
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        profile: Option<String>,
+        profile: Option<Box<str>>,
 
         /// The extension which provides these required items, if any.
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        extension: Option<String>,
+        extension: Option<Box<str>>,
 
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        feature: Option<String>,
+        feature: Option<Box<str>>,
 
+        /// Features and extensions the items are required with, given by `depends`, which
+        /// replaced `extension` and `feature`.
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        comment: Option<String>,
+        depends: Option<DependsExpr>,
+
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        comment: Option<Box<str>>,
 
         /// The items which form this require block.
         items: Vec<InterfaceItem>,
+
+        /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        raw: RawAttributes,
     },
 
     /// Indicates the items this extension removes.
@@ -1005,68 +1760,117 @@ pub enum ExtensionChild {
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        api: Option<String>,
+        api: Option<ApiSet>,
 
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        profile: Option<String>,
+        profile: Option<Box<str>>,
 
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        comment: Option<String>,
+        comment: Option<Box<str>>,
 
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
         items: Vec<InterfaceItem>,
+
+        /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        raw: RawAttributes,
+    },
+
+    /// Indicates the items this feature or extension deprecates, which remain available.
+    Deprecate {
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        api: Option<ApiSet>,
+
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        profile: Option<Box<str>>,
+
+        /// Anchor of the section of the spec explaining the deprecation, e.g.
+        /// `deprecation-gpdp2`.
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        explanationlink: Option<Box<str>>,
+
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        comment: Option<Box<str>>,
+
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        items: Vec<InterfaceItem>,
+
+        /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+        #[cfg_attr(
+            feature = "serialize",
+            serde(default, skip_serializing_if = "is_default")
+        )]
+        raw: RawAttributes,
     },
 }
 
 /// An interface item is a function or an enum which makes up a Vulkan interface.
 ///
 /// This structure is used by extensions to express dependencies or include functionality.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum InterfaceItem {
-    Comment(String),
+    Comment(Box<str>),
 
     Type {
-        name: String,
+        name: Box<str>,
 
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        comment: Option<String>,
+        comment: Option<Box<str>>,
     },
 
     Enum(Enum),
 
     Command {
-        name: String,
+        name: Box<str>,
 
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
         )]
-        comment: Option<String>,
+        comment: Option<Box<str>>,
     },
 }
 
 pub type Formats = CommentedChildren<Format>;
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct Format {
-    pub name: String,
-    pub class: String,
+    pub name: Box<str>,
+    pub class: Box<str>,
     pub blockSize: u8,
     pub texelsPerBlock: u8,
 
@@ -1074,7 +1878,7 @@ pub struct Format {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub blockExtent: Option<String>,
+    pub blockExtent: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
@@ -1086,26 +1890,33 @@ pub struct Format {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub compressed: Option<String>,
+    pub compressed: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub chroma: Option<String>,
+    pub chroma: Option<Box<str>>,
 
     pub children: Vec<FormatChild>,
+
+    /// Original values of all attributes, see `ParseOptions::raw_attributes`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub raw: RawAttributes,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum FormatChild {
     #[non_exhaustive]
     Component {
-        name: String,
-        bits: String,
-        numericFormat: String,
+        name: Box<str>,
+        bits: Box<str>,
+        numericFormat: Box<str>,
         #[cfg_attr(
             feature = "serialize",
             serde(default, skip_serializing_if = "is_default")
@@ -1118,14 +1929,14 @@ pub enum FormatChild {
         index: u8,
         widthDivisor: u8,
         heightDivisor: u8,
-        compatible: String,
+        compatible: Box<str>,
     },
 
     #[non_exhaustive]
-    SpirvImageFormat { name: String },
+    SpirvImageFormat { name: Box<str> },
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct NameWithType {
@@ -1133,16 +1944,528 @@ pub struct NameWithType {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub type_name: Option<String>,
+    pub type_name: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub name: String,
+    pub name: Box<str>,
+
+    /// Structured type of the declaration, `None` if it couldn't be recognized.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub c_type: Option<CType>,
+
+    /// Text of `<apientry>` markup of a command prototype, which is empty for the empty
+    /// `<apientry/>` element used by the OpenGL registry, see `TypeCode::calling_convention`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub calling_convention: Option<Box<str>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+impl TypeMemberDefinition {
+    /// Name of the member, given by the `<name>` element.
+    pub fn name(&self) -> Option<&str> {
+        self.markup.iter().find_map(|m| match *m {
+            TypeMemberMarkup::Name(ref name) => Some(&**name),
+            _ => None,
+        })
+    }
+}
+
+/// C type of a declaration, such as of a struct member or command parameter.
+///
+/// Types are nested the same way as in C, e.g. `const char* const* ppEnabledLayerNames` is a
+/// non-const pointer to a const pointer to const `char`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum CType {
+    /// Type referred to by name, e.g. `uint32_t`, `VkInstance` or `struct wl_display`.
+    Named {
+        name: Box<str>,
+        is_const: bool,
+        /// Whether the name is preceded by the `struct` keyword.
+        is_struct: bool,
+    },
+    /// Pointer to `inner` type. `is_const` refers to the pointer itself, as in `T* const`.
+    Pointer { is_const: bool, inner: Box<CType> },
+    /// Array of `inner` type, with length given by a number or name of a constant.
+    Array { len: Box<str>, inner: Box<CType> },
+    /// Bitfield of given width in bits, with `inner` being the declared type.
+    Bitfield { width: u8, inner: Box<CType> },
+}
+
+impl CType {
+    /// Wraps `inner` in pointers with given constness, listed in declaration order. For example,
+    /// levels `[true, false]` turn `const char` into `const char* const*`.
+    pub fn pointer_to(inner: CType, levels: &[bool]) -> CType {
+        levels
+            .iter()
+            .fold(inner, |inner, &is_const| CType::Pointer {
+                is_const,
+                inner: Box::new(inner),
+            })
+    }
+
+    /// Constness of each pointer level, in declaration order, i.e. the reverse of `pointer_to`.
+    /// Empty if the type isn't a pointer.
+    pub fn pointer_levels(&self) -> Vec<bool> {
+        let mut levels = Vec::new();
+        let mut c_type = self;
+        while let CType::Pointer {
+            is_const,
+            ref inner,
+        } = *c_type
+        {
+            levels.push(is_const);
+            c_type = inner;
+        }
+        levels.reverse();
+        levels
+    }
+
+    /// Number of pointer levels, e.g. 2 for `void**`.
+    pub fn pointer_depth(&self) -> usize {
+        let mut depth = 0;
+        let mut c_type = self;
+        while let CType::Pointer { ref inner, .. } = *c_type {
+            depth += 1;
+            c_type = inner;
+        }
+        depth
+    }
+
+    /// Lengths of array dimensions, outermost first, e.g. `["3", "4"]` for `float matrix[3][4]`.
+    /// Empty if the type isn't an array.
+    pub fn array_shape(&self) -> InlineVec<&str> {
+        let mut shape = InlineVec::new();
+        let mut c_type = self;
+        while let CType::Array { ref len, ref inner } = *c_type {
+            shape.push(&**len);
+            c_type = inner;
+        }
+        shape
+    }
+
+    /// Type pointed to after following all pointer levels, or `self` if it isn't a pointer.
+    pub fn pointee(&self) -> &CType {
+        match *self {
+            CType::Pointer { ref inner, .. } => inner.pointee(),
+            _ => self,
+        }
+    }
+}
+
+impl CType {
+    /// C declaration of `name` with this type, e.g. `const char* const* ppEnabledLayerNames`.
+    /// With empty `name`, this gives the type alone, as used in casts, e.g. `float[4]`.
+    pub fn to_c_decl(&self, name: &str) -> String {
+        self.declarator(String::from(name))
+    }
+
+    /// Completes `declarator`, which already contains the name and all enclosing types.
+    fn declarator(&self, mut declarator: String) -> String {
+        match *self {
+            CType::Named {
+                ref name,
+                is_const,
+                is_struct,
+            } => {
+                let mut decl = String::new();
+                if is_const {
+                    decl.push_str("const ");
+                }
+                if is_struct {
+                    decl.push_str("struct ");
+                }
+                decl.push_str(name);
+                if !declarator.is_empty() && !declarator.starts_with(&['*', '['][..]) {
+                    decl.push(' ');
+                }
+                decl.push_str(&declarator);
+                decl
+            }
+            CType::Pointer {
+                is_const,
+                ref inner,
+            } => {
+                let mut decl = String::from(if is_const { "* const" } else { "*" });
+                if !declarator.is_empty() && !declarator.starts_with(&['*', '['][..]) {
+                    decl.push(' ');
+                }
+                decl.push_str(&declarator);
+                inner.declarator(decl)
+            }
+            CType::Array { ref len, ref inner } => {
+                if declarator.starts_with('*') {
+                    declarator.insert(0, '(');
+                    declarator.push(')');
+                }
+                declarator.push('[');
+                declarator.push_str(len);
+                declarator.push(']');
+                inner.declarator(declarator)
+            }
+            CType::Bitfield { width, ref inner } => {
+                let _ = write!(declarator, ":{}", width);
+                inner.declarator(declarator)
+            }
+        }
+    }
+}
+
+/// Formats as C type without a declared name, e.g. `const char* const*` or `float[4]`.
+impl fmt::Display for CType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_c_decl(""))
+    }
+}
+
+/// Formats as C declaration, e.g. `const char* pName`. If the declaration wasn't recognized
+/// while parsing, only the type name and name are written.
+impl fmt::Display for NameWithType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.c_type, &self.type_name) {
+            (Some(c_type), _) => f.write_str(&c_type.to_c_decl(&self.name)),
+            (None, Some(type_name)) => write!(f, "{} {}", type_name, self.name),
+            (None, None) => f.write_str(&self.name),
+        }
+    }
+}
+
+/// Formats as C declaration of the member, regenerated from `c_type` if possible, otherwise
+/// the stored `code` is written.
+impl fmt::Display for TypeMemberDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.c_type, self.name()) {
+            (Some(c_type), Some(name)) => f.write_str(&c_type.to_c_decl(name)),
+            _ => f.write_str(&self.code),
+        }
+    }
+}
+
+/// Formats as C declaration of the parameter, same as its `definition`.
+impl fmt::Display for CommandParam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.definition, f)
+    }
+}
+
+/// Formats as C function prototype, e.g. `void vkDestroyDevice(VkDevice device, const
+/// VkAllocationCallbacks* pAllocator);`, regenerated from `proto` and `params`.
+impl fmt::Display for CommandDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(", self.proto)?;
+        for (i, param) in self.params.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", param)?;
+        }
+        f.write_str(");")
+    }
+}
+
+/// Definition of a macro along with conditions under which it's made, see
+/// `TypeCode::define_expansions`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct DefineExpansion<'a> {
+    /// Conditions of enclosing preprocessor branches, outermost first, each with whether it
+    /// has to hold. Branches following `#elif` and `#else` include conditions of the preceding
+    /// branches as ones which must not hold.
+    pub conditions: Vec<(&'a str, bool)>,
+    pub define: &'a TypeDefine,
+}
+
+/// C code of a preprocessor branch along with conditions under which it applies, see
+/// `TypeCode::code_alternatives`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct CodeAlternative<'a> {
+    /// Conditions of enclosing preprocessor branches, same as in `DefineExpansion`.
+    pub conditions: Vec<(&'a str, bool)>,
+    pub code: String,
+}
+
+impl TypeCode {
+    /// All definitions of macro `name` found in `directives`, one for each preprocessor branch
+    /// which defines it. For example, `VK_DEFINE_NON_DISPATCHABLE_HANDLE` has separate
+    /// expansions for when `VK_USE_64_BIT_PTR_DEFINES==1` holds and when it doesn't.
+    pub fn define_expansions(&self, name: &str) -> Vec<DefineExpansion<'_>> {
+        let mut expansions = Vec::new();
+        visit_conditional_items(
+            &self.directives,
+            &mut Vec::new(),
+            &mut |conditions, item| {
+                if let PreprocessorItem::Define(ref define) = *item {
+                    if &*define.name == name {
+                        expansions.push(DefineExpansion {
+                            conditions: conditions.to_vec(),
+                            define,
+                        });
+                    }
+                }
+            },
+        );
+        expansions
+    }
+
+    /// The first `#include` directive in `directives`. Types with `include` category don't need
+    /// to contain any directive, in which case their `name` is the header path.
+    pub fn include(&self) -> Option<&TypeInclude> {
+        self.directives.iter().find_map(|item| match *item {
+            PreprocessorItem::Include(ref include) => Some(include),
+            _ => None,
+        })
+    }
+
+    /// C code outside of preprocessor directives, split by the preprocessor branches it's in.
+    /// For example, basetypes of Apple platforms have one alternative for Objective-C, where
+    /// `defined(__OBJC__)` holds, and another one for C.
+    ///
+    /// Code without directives gives a single alternative without conditions, containing the
+    /// whole `code`.
+    pub fn code_alternatives(&self) -> Vec<CodeAlternative<'_>> {
+        let mut alternatives: Vec<CodeAlternative> = Vec::new();
+        if self.directives.is_empty() {
+            alternatives.push(CodeAlternative {
+                conditions: Vec::new(),
+                code: String::from(self.code.trim()),
+            });
+            return alternatives;
+        }
+
+        visit_conditional_items(
+            &self.directives,
+            &mut Vec::new(),
+            &mut |conditions, item| {
+                if let PreprocessorItem::Code(ref code) = *item {
+                    match alternatives.last_mut() {
+                        Some(last) if last.conditions == conditions => {
+                            last.code.push('\n');
+                            last.code.push_str(code);
+                        }
+                        _ => alternatives.push(CodeAlternative {
+                            conditions: conditions.to_vec(),
+                            code: String::from(&**code),
+                        }),
+                    }
+                }
+            },
+        );
+        alternatives
+    }
+}
+
+/// Calls `f` for every item other than conditional blocks, along with conditions of its
+/// enclosing branches.
+fn visit_conditional_items<'a, F>(
+    items: &'a [PreprocessorItem],
+    conditions: &mut Vec<(&'a str, bool)>,
+    f: &mut F,
+) where
+    F: FnMut(&[(&'a str, bool)], &'a PreprocessorItem),
+{
+    for item in items {
+        match *item {
+            PreprocessorItem::Conditional(ref branches) => {
+                let depth = conditions.len();
+                for branch in branches {
+                    if let Some(ref condition) = branch.condition {
+                        conditions.push((condition, true));
+                    }
+                    visit_conditional_items(&branch.items, conditions, f);
+                    // Following branches apply only when this one doesn't.
+                    if branch.condition.is_some() {
+                        if let Some(last) = conditions.last_mut() {
+                            last.1 = false;
+                        }
+                    }
+                }
+                conditions.truncate(depth);
+            }
+            _ => f(conditions, item),
+        }
+    }
+}
+
+impl TypeDefine {
+    /// Expands the macro with given arguments, substituting parameters in the body of a
+    /// function-like macro and applying `##` token pasting. E.g. `VK_DEFINE_HANDLE(VkDevice)`
+    /// expands to `typedef struct VkDevice_T* VkDevice;`. Nested macros are not expanded.
+    ///
+    /// Returns `None` if the number of arguments doesn't match the macro's parameters.
+    pub fn expand(&self, args: &[&str]) -> Option<String> {
+        let (params, body) = match self.value {
+            TypeDefineValue::Empty if args.is_empty() => return Some(String::new()),
+            TypeDefineValue::Value(ref value) if args.is_empty() => {
+                return Some(String::from(&**value))
+            }
+            TypeDefineValue::Function {
+                ref params,
+                ref body,
+            } if params.len() == args.len() => (params, body),
+            _ => return None,
+        };
+
+        let mut expanded = String::new();
+        let mut rest = &**body;
+        while let Some(c) = rest.chars().next() {
+            if c == '"' || c.is_ascii_digit() {
+                // String literals and numbers are copied as they are.
+                let len = if c == '"' {
+                    rest[1..].find('"').map_or(rest.len(), |i| i + 2)
+                } else {
+                    rest.find(|c: char| c != '.' && !c.is_ascii_alphanumeric())
+                        .unwrap_or(rest.len())
+                };
+                expanded.push_str(&rest[..len]);
+                rest = &rest[len..];
+            } else if c == '_' || c.is_ascii_alphabetic() {
+                let len = rest
+                    .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+                    .unwrap_or(rest.len());
+                let identifier = &rest[..len];
+                match params.iter().position(|p| &**p == identifier) {
+                    Some(i) => expanded.push_str(args[i]),
+                    None => expanded.push_str(identifier),
+                }
+                rest = &rest[len..];
+            } else if rest.starts_with("##") {
+                let trimmed = expanded.trim_end().len();
+                expanded.truncate(trimmed);
+                rest = rest[2..].trim_start();
+            } else {
+                expanded.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        Some(expanded)
+    }
+}
+
+impl TypeDefineValue {
+    /// Value of an object-like macro interpreted as a literal, e.g. `VK_HEADER_VERSION`.
+    /// Returns `None` for other macros and for values which are not a single literal, optionally
+    /// in parentheses.
+    pub fn literal(&self) -> Option<CLiteral> {
+        match *self {
+            TypeDefineValue::Value(ref value) => CLiteral::parse(value),
+            _ => None,
+        }
+    }
+}
+
+/// C literal, as found in values of macros.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum CLiteral {
+    /// Integer literal, e.g. `0x7FFFFFFF` or `1000UL`. `suffix` is kept as written.
+    Integer {
+        value: u64,
+        radix: u32,
+        suffix: Box<str>,
+    },
+    /// Floating point literal, e.g. `1000.0f`. `value` is the literal without the suffix, which
+    /// is kept as written. See `CLiteral::as_f64`.
+    Float { value: Box<str>, suffix: Box<str> },
+    /// String literal, with escape sequences resolved.
+    String(Box<str>),
+}
+
+impl CLiteral {
+    /// Parses literal, which may be enclosed in parentheses.
+    pub fn parse(text: &str) -> Option<CLiteral> {
+        let mut text = text.trim();
+        while text.starts_with('(') && text.ends_with(')') {
+            text = text[1..text.len() - 1].trim();
+        }
+
+        if text.len() >= 2 && text.starts_with('"') && text.ends_with('"') {
+            let mut value = String::new();
+            let mut chars = text[1..text.len() - 1].chars();
+            while let Some(c) = chars.next() {
+                value.push(match c {
+                    '\\' => match chars.next()? {
+                        'n' => '\n',
+                        't' => '\t',
+                        'r' => '\r',
+                        '0' => '\0',
+                        c @ ('\\' | '"' | '\'') => c,
+                        _ => return None,
+                    },
+                    '"' => return None,
+                    c => c,
+                });
+            }
+            return Some(CLiteral::String(value.into_boxed_str()));
+        }
+
+        if !text.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+            return None;
+        }
+        let is_hex = text.starts_with("0x") || text.starts_with("0X");
+        let is_float = !is_hex && text.contains(|c| matches!(c, '.' | 'e' | 'E'));
+        if is_float {
+            let suffix_start = text
+                .trim_end_matches(|c| matches!(c, 'f' | 'F' | 'l' | 'L'))
+                .len();
+            let (value, suffix) = text.split_at(suffix_start);
+            if suffix.len() > 1 || value.parse::<f64>().is_err() {
+                return None;
+            }
+            return Some(CLiteral::Float {
+                value: value.into(),
+                suffix: suffix.into(),
+            });
+        }
+
+        let suffix_start = text
+            .trim_end_matches(|c| matches!(c, 'u' | 'U' | 'l' | 'L'))
+            .len();
+        let (number, suffix) = text.split_at(suffix_start);
+        let valid_suffix = matches!(
+            &*suffix.to_ascii_lowercase(),
+            "" | "u" | "l" | "ul" | "lu" | "ll" | "ull" | "llu"
+        );
+        if !valid_suffix {
+            return None;
+        }
+        let (digits, radix) = if is_hex {
+            (&number[2..], 16)
+        } else if number.starts_with("0b") || number.starts_with("0B") {
+            (&number[2..], 2)
+        } else if number.len() > 1 && number.starts_with('0') {
+            (&number[1..], 8)
+        } else {
+            (number, 10)
+        };
+        let value = u64::from_str_radix(digits, radix).ok()?;
+        Some(CLiteral::Integer {
+            value,
+            radix,
+            suffix: suffix.into(),
+        })
+    }
+
+    /// Value of a floating point literal.
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            CLiteral::Float { ref value, .. } => value.parse().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct CommentedChildren<T> {
@@ -1150,12 +2473,12 @@ pub struct CommentedChildren<T> {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub comment: Option<String>,
+    pub comment: Option<Box<str>>,
 
     pub children: Vec<T>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct SpirvExtOrCap {
@@ -1163,7 +2486,7 @@ pub struct SpirvExtOrCap {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub name: String,
+    pub name: Box<str>,
 
     #[cfg_attr(
         feature = "serialize",
@@ -1178,17 +2501,17 @@ pub type SpirvExtensions = CommentedChildren<SpirvExtension>;
 pub type SpirvCapability = SpirvExtOrCap;
 pub type SpirvCapabilities = CommentedChildren<SpirvCapability>;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub enum Enable {
-    Version(String),
-    Extension(String),
+    Version(Box<str>),
+    Extension(Box<str>),
     Feature(FeatureEnable),
     Property(PropertyEnable),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct FeatureEnable {
@@ -1196,28 +2519,28 @@ pub struct FeatureEnable {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub struct_: String,
+    pub struct_: Box<str>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub feature: String,
+    pub feature: Box<str>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub requires: Option<String>,
+    pub requires: Option<Box<str>>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub alias: Option<String>,
+    pub alias: Option<Box<str>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 #[non_exhaustive]
 pub struct PropertyEnable {
@@ -1225,25 +2548,338 @@ pub struct PropertyEnable {
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub property: String,
+    pub property: Box<str>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub member: String,
+    pub member: Box<str>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub value: String,
+    pub value: Box<str>,
 
     #[cfg_attr(
         feature = "serialize",
         serde(default, skip_serializing_if = "is_default")
     )]
-    pub requires: Option<String>,
+    pub requires: Option<Box<str>>,
+}
+
+pub type SyncSection = CommentedChildren<SyncChild>;
+
+/// An item of the `<sync>` section.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum SyncChild {
+    Stage(SyncStage),
+    Access(SyncAccess),
+    Pipeline(SyncPipeline),
+}
+
+impl SyncChild {
+    /// Name of the pipeline stage flag, access flag or pipeline.
+    pub fn name(&self) -> &str {
+        match *self {
+            SyncChild::Stage(ref stage) => &stage.name,
+            SyncChild::Access(ref access) => &access.name,
+            SyncChild::Pipeline(ref pipeline) => &pipeline.name,
+        }
+    }
+}
+
+/// Pipeline stage flag, e.g. `VK_PIPELINE_STAGE_2_VERTEX_SHADER_BIT`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct SyncStage {
+    pub name: Box<str>,
+
+    /// Name of the flag this is an alias of.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub alias: Option<Box<str>>,
+
+    /// Queues supporting the stage, given by `<syncsupport>`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub support: Option<SyncSupport>,
+
+    /// Stages equivalent to the stage, given by `<syncequivalent>`, e.g. all shader stages
+    /// for `VK_PIPELINE_STAGE_2_ALL_GRAPHICS_BIT`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub equivalent: Option<SyncEquivalent>,
+}
+
+/// Access flag, e.g. `VK_ACCESS_2_SHADER_READ_BIT`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct SyncAccess {
+    pub name: Box<str>,
+
+    /// Name of the flag this is an alias of.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub alias: Option<Box<str>>,
+
+    /// Human-readable description, given by `<comment>`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub comment: Option<Box<str>>,
+
+    /// Stages the access may be performed by, given by `<syncsupport>`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub support: Option<SyncSupport>,
+
+    /// Accesses equivalent to the access, given by `<syncequivalent>`, e.g. all read
+    /// accesses for `VK_ACCESS_2_MEMORY_READ_BIT`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub equivalent: Option<SyncEquivalent>,
+}
+
+/// Contents of `<syncsupport>`, see `SyncStage::support` and `SyncAccess::support`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct SyncSupport {
+    /// Queue types, given by `queues`, e.g. `graphics,compute`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub queues: Vec<QueueKind>,
+
+    /// Pipeline stage flags, given by `stage`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub stage: Vec<Box<str>>,
+}
+
+/// Contents of `<syncequivalent>`, see `SyncStage::equivalent` and `SyncAccess::equivalent`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct SyncEquivalent {
+    /// Pipeline stage flags, given by `stage`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub stage: Vec<Box<str>>,
+
+    /// Access flags, given by `access`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub access: Vec<Box<str>>,
+}
+
+/// Ordering of pipeline stages in a pipeline, e.g. `graphics primitive pipeline`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct SyncPipeline {
+    pub name: Box<str>,
+
+    /// Feature or extension the pipeline depends on.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub depends: Option<Box<str>>,
+
+    /// Stages of the pipeline, in logical order.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub stages: Vec<SyncPipelineStage>,
+}
+
+/// Stage of a `SyncPipeline`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct SyncPipelineStage {
+    /// Name of the pipeline stage flag, given by contents of the element.
+    pub name: Box<str>,
+
+    /// Ordering constraint, e.g. `None` for stages which aren't ordered relative to others.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub order: Option<Box<str>>,
+
+    /// Stage this stage comes before.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub before: Option<Box<str>>,
+
+    /// Stage this stage comes after.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub after: Option<Box<str>>,
+}
+
+pub type VideoCodecs = CommentedChildren<VideoCodec>;
+
+/// Video codec, e.g. `H.264 Decode`, or video coding operation extended by codecs, e.g.
+/// `Decode`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct VideoCodec {
+    pub name: Box<str>,
+
+    /// Name of the codec this one extends, e.g. `Decode` for `H.264 Decode`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub extend: Option<Box<str>>,
+
+    /// Codec operation flag, e.g. `VK_VIDEO_CODEC_OPERATION_DECODE_H264_BIT_KHR`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub value: Option<Box<str>>,
+
+    /// Structs describing video profiles of the codec, given by `<videoprofiles>`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub profiles: Vec<VideoProfiles>,
+
+    /// Names of structs describing capabilities of the codec, given by `struct` of
+    /// `<videocapabilities>`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub capabilities: Vec<Box<str>>,
+
+    /// Video format categories of the codec, given by `<videoformat>`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub formats: Vec<VideoFormat>,
+}
+
+/// Struct describing video profiles, e.g. `VkVideoDecodeH264ProfileInfoKHR`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct VideoProfiles {
+    /// Name of the struct.
+    pub struct_: Box<str>,
+
+    /// Members of the struct which select the profile.
+    pub members: Vec<VideoProfileMember>,
+}
+
+/// Member of a `VideoProfiles` struct, e.g. `stdProfileIdc`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct VideoProfileMember {
+    pub name: Box<str>,
+
+    /// Values of the member, along with names of the profiles they select.
+    pub profiles: Vec<VideoProfile>,
+}
+
+/// Video profile, e.g. `Main` selected by `STD_VIDEO_H264_PROFILE_IDC_MAIN`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct VideoProfile {
+    pub name: Box<str>,
+    pub value: Box<str>,
+}
+
+/// Category of video formats used by a codec, e.g. `Decode Output`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct VideoFormat {
+    /// Name of the category, unless it extends one.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub name: Option<Box<str>>,
+
+    /// Name of the category this one extends, e.g. for codec-specific format properties.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub extend: Option<Box<str>>,
+
+    /// Image usage flags of the formats, e.g. `VK_IMAGE_USAGE_VIDEO_DECODE_DST_BIT_KHR`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub usage: Option<Box<str>>,
+
+    /// Capabilities required for the formats to be used, given by `<videorequirecapabilities>`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub required_capabilities: Vec<VideoRequiredCapability>,
+
+    /// Names of structs describing properties of the formats, given by `struct` of
+    /// `<videoformatproperties>`.
+    #[cfg_attr(
+        feature = "serialize",
+        serde(default, skip_serializing_if = "is_default")
+    )]
+    pub properties: Vec<Box<str>>,
+}
+
+/// Flag which must be set in a member of a capabilities struct, see `VideoFormat`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub struct VideoRequiredCapability {
+    pub struct_: Box<str>,
+    pub member: Box<str>,
+    pub value: Box<str>,
 }
 
 #[cfg(feature = "serialize")]
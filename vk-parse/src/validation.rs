@@ -0,0 +1,31 @@
+use types::*;
+
+/// Options controlling which parts of the registry are checked by
+/// `Registry::dangling_references_with_options`, `Registry::add_extension_with_options` and
+/// `Registry::add_enum_with_options`.
+///
+/// By default, contents of extensions with `supported="disabled"` aren't checked, since they
+/// only reserve extension numbers and often refer to items which were never published, see
+/// `Extension::is_disabled`. Including them is useful when auditing changes of the registry.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    pub(crate) include_disabled: bool,
+}
+
+impl ValidationOptions {
+    /// Creates options with default behavior.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks references and enums of disabled extensions like those of other extensions.
+    pub fn include_disabled(mut self, include: bool) -> Self {
+        self.include_disabled = include;
+        self
+    }
+
+    /// Whether contents of `extension` are checked.
+    pub(crate) fn checks(&self, extension: &Extension) -> bool {
+        self.include_disabled || !extension.is_disabled()
+    }
+}
@@ -0,0 +1,220 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::fmt;
+
+use expr::CValue;
+use types::*;
+
+/// Version of the API defined by a feature, given by `number`, e.g. `1.3`. Versions are
+/// ordered by major and then minor version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ApiVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+impl ApiVersion {
+    pub fn new(major: u32, minor: u32) -> Self {
+        ApiVersion { major, minor }
+    }
+
+    /// Parses version in the form `major.minor`, returning `None` if it's malformed.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (major, minor) = text.split_once('.')?;
+        let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        if !digits(major) || !digits(minor) {
+            return None;
+        }
+        Some(ApiVersion::new(major.parse().ok()?, minor.parse().ok()?))
+    }
+}
+
+/// Writes the version as in the registry, e.g. `1.3`.
+impl fmt::Display for ApiVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Version number packed into `u32` as done by `VK_MAKE_API_VERSION`, e.g. `VK_API_VERSION_1_3`
+/// or `VK_HEADER_VERSION_COMPLETE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PackedVersion {
+    pub variant: u32,
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl PackedVersion {
+    pub fn new(variant: u32, major: u32, minor: u32, patch: u32) -> Self {
+        PackedVersion {
+            variant,
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Unpacks version, as done by `VK_API_VERSION_VARIANT`, `VK_API_VERSION_MAJOR` etc.
+    pub fn from_packed(packed: u32) -> Self {
+        PackedVersion {
+            variant: packed >> 29,
+            major: (packed >> 22) & 0x7F,
+            minor: (packed >> 12) & 0x3FF,
+            patch: packed & 0xFFF,
+        }
+    }
+
+    /// Packed value, same as given by `VK_MAKE_API_VERSION`. Components out of their range
+    /// are truncated.
+    pub fn packed(self) -> u32 {
+        ((self.variant & 0x7) << 29)
+            | ((self.major & 0x7F) << 22)
+            | ((self.minor & 0x3FF) << 12)
+            | (self.patch & 0xFFF)
+    }
+
+    /// Evaluates invocation of `VK_MAKE_API_VERSION` or the deprecated `VK_MAKE_VERSION`, such
+    /// as `VK_MAKE_API_VERSION(0, 1, 3, VK_HEADER_VERSION)`. Names in arguments are resolved
+    /// using `lookup`. Returns `None` if an argument doesn't fit in `u32`.
+    pub fn from_make_version<F>(text: &str, lookup: F) -> Option<Self>
+    where
+        F: Fn(&str) -> Option<CValue>,
+    {
+        let text = text.trim();
+        let (args, with_variant) = if let Some(args) = text.strip_prefix("VK_MAKE_API_VERSION") {
+            (args, true)
+        } else {
+            (text.strip_prefix("VK_MAKE_VERSION")?, false)
+        };
+        let args = args.trim().strip_prefix('(')?.strip_suffix(')')?;
+
+        let mut values = Vec::new();
+        for arg in args.split(',') {
+            let value = CValue::evaluate_with(arg, &lookup)?.as_u64()?;
+            if value > u64::from(u32::MAX) {
+                return None;
+            }
+            values.push(value as u32);
+        }
+        match (with_variant, &values[..]) {
+            (true, &[variant, major, minor, patch]) => {
+                Some(PackedVersion::new(variant, major, minor, patch))
+            }
+            (false, &[major, minor, patch]) => Some(PackedVersion::new(0, major, minor, patch)),
+            _ => None,
+        }
+    }
+}
+
+impl Registry {
+    /// All defines of the registry whose value is given by `VK_MAKE_API_VERSION` or
+    /// `VK_MAKE_VERSION`, such as `VK_API_VERSION_1_0` or `VK_HEADER_VERSION_COMPLETE`, along
+    /// with their evaluated values. Other defines with integer values, such as
+    /// `VK_HEADER_VERSION`, can be used as arguments.
+    pub fn api_versions(&self) -> Vec<(&str, PackedVersion)> {
+        let defines = self.object_like_defines();
+        let lookup = |name: &str| {
+            defines
+                .iter()
+                .find(|&&(n, _)| n == name)
+                .and_then(|&(_, value)| CValue::evaluate(value))
+        };
+        defines
+            .iter()
+            .filter_map(|&(name, value)| {
+                PackedVersion::from_make_version(value, lookup).map(|v| (name, v))
+            })
+            .collect()
+    }
+
+    /// Value of `VK_HEADER_VERSION`, i.e. the patch version of the registry.
+    pub fn header_version(&self) -> Option<u32> {
+        let defines = self.object_like_defines();
+        let &(_, value) = defines.iter().find(|&&(n, _)| n == "VK_HEADER_VERSION")?;
+        let value = CValue::evaluate(value)?.as_u64()?;
+        if value <= u64::from(u32::MAX) {
+            Some(value as u32)
+        } else {
+            None
+        }
+    }
+
+    /// Value of `VK_HEADER_VERSION_COMPLETE`, i.e. the full version of the registry.
+    pub fn header_version_complete(&self) -> Option<PackedVersion> {
+        self.api_versions()
+            .into_iter()
+            .find(|&(name, _)| name == "VK_HEADER_VERSION_COMPLETE")
+            .map(|(_, version)| version)
+    }
+
+    /// Names and values of object-like macros defined outside of any conditional blocks.
+    pub(crate) fn object_like_defines(&self) -> Vec<(&str, &str)> {
+        let mut defines = Vec::new();
+        for ty in self.types() {
+            let code = match ty.spec {
+                TypeSpec::Code(ref code) => code,
+                _ => continue,
+            };
+            for item in &code.directives {
+                if let PreprocessorItem::Define(TypeDefine {
+                    ref name,
+                    value: TypeDefineValue::Value(ref value),
+                }) = *item
+                {
+                    defines.push((&**name, &**value));
+                }
+            }
+        }
+        defines
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_packing() {
+        let version = PackedVersion::new(0, 1, 3, 250);
+        assert_eq!(version.packed(), (1 << 22) | (3 << 12) | 250);
+        assert_eq!(PackedVersion::from_packed(version.packed()), version);
+        assert_eq!(PackedVersion::from_packed(u32::MAX).variant, 7);
+    }
+
+    #[test]
+    fn test_from_make_version() {
+        let lookup = |name: &str| match name {
+            "VK_HEADER_VERSION" => Some(CValue::I32(250)),
+            _ => None,
+        };
+        assert_eq!(
+            PackedVersion::from_make_version(
+                "VK_MAKE_API_VERSION(0, 1, 3, VK_HEADER_VERSION)",
+                lookup
+            ),
+            Some(PackedVersion::new(0, 1, 3, 250))
+        );
+        assert_eq!(
+            PackedVersion::from_make_version("VK_MAKE_VERSION(1, 0, 0)", lookup),
+            Some(PackedVersion::new(0, 1, 0, 0))
+        );
+        assert_eq!(
+            PackedVersion::from_make_version("VK_MAKE_VERSION(1, 0)", lookup),
+            None
+        );
+        assert_eq!(
+            PackedVersion::from_make_version("VK_MAKE_API_VERSION(0, 1, 3, VK_UNKNOWN)", lookup),
+            None
+        );
+        assert_eq!(
+            PackedVersion::from_make_version("VK_MAKE_VERSION(1, 0, 0x100000000)", lookup),
+            None
+        );
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeSet, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeSet;
+
+use types::*;
+
+/// Whether a command may be recorded within a video coding scope, given by `videocoding` of
+/// commands, see `CommandDefinition::video_coding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum VideoCoding {
+    /// `inside`, the command may only be recorded within a video coding scope.
+    Inside,
+    /// `outside`, the command may only be recorded outside of video coding scopes.
+    Outside,
+    /// `both`, the command may be recorded either way.
+    Both,
+}
+
+impl VideoCoding {
+    /// Value with given name, as used in `videocoding`, e.g. `inside`.
+    pub fn from_name(name: &str) -> Option<VideoCoding> {
+        Some(match name {
+            "inside" => VideoCoding::Inside,
+            "outside" => VideoCoding::Outside,
+            "both" => VideoCoding::Both,
+            _ => return None,
+        })
+    }
+
+    /// Name of the value, as used in `videocoding`.
+    pub fn name(self) -> &'static str {
+        match self {
+            VideoCoding::Inside => "inside",
+            VideoCoding::Outside => "outside",
+            VideoCoding::Both => "both",
+        }
+    }
+}
+
+impl CommandDefinition {
+    /// Whether the command may be recorded within a video coding scope, given by `videocoding`,
+    /// which is `Outside` when not set.
+    pub fn video_coding(&self) -> VideoCoding {
+        self.videocoding.unwrap_or(VideoCoding::Outside)
+    }
+}
+
+impl VideoCodec {
+    /// Names of structs referred to by the codec: those describing its profiles, capabilities,
+    /// required capabilities and format properties.
+    pub fn structs(&self) -> impl Iterator<Item = &str> {
+        let profiles = self.profiles.iter().map(|p| &*p.struct_);
+        let capabilities = self.capabilities.iter().map(|c| &**c);
+        let formats = self.formats.iter().flat_map(|f| {
+            let required = f.required_capabilities.iter().map(|c| &*c.struct_);
+            required.chain(f.properties.iter().map(|p| &**p))
+        });
+        profiles.chain(capabilities).chain(formats)
+    }
+}
+
+/// Video coding operation, see `VideoGroup`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum VideoOperation {
+    Decode,
+    Encode,
+}
+
+/// Video coding extensions for a single codec, along with items they require, see
+/// `Registry::video_api`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct VideoGroup<'a> {
+    /// Codec of the `<videocodecs>` section, e.g. `H.264 Decode` or `Decode`, or `None` for
+    /// extensions shared by all codecs, such as `VK_KHR_video_queue`.
+    pub codec: Option<&'a VideoCodec>,
+    /// Operation of the codec, given by the codec it extends, directly or through others, being
+    /// named `Decode` or `Encode`.
+    pub operation: Option<VideoOperation>,
+    pub extensions: Vec<&'a Extension>,
+    /// Names of types required by the extensions, in order of first appearance.
+    pub types: Vec<&'a str>,
+    /// Names of commands required by the extensions, in order of first appearance.
+    pub commands: Vec<&'a str>,
+    /// Names of structs describing capabilities of the codec, given by `<videocapabilities>`.
+    pub capabilities: Vec<&'a str>,
+}
+
+impl Registry {
+    /// Groups video coding extensions by the codec of the `<videocodecs>` section they provide,
+    /// i.e. whose codec operation flag or structs they require. Extensions which don't provide a
+    /// codec are shared by all codecs if they require a struct extended by the structs of a
+    /// codec, such as `VkVideoCapabilitiesKHR`, or a command which may be recorded within a
+    /// video coding scope. The shared group comes first, followed by groups in order of the
+    /// codecs. Disabled extensions are omitted.
+    ///
+    /// The shared group also lists commands of features and other extensions which may be
+    /// recorded within a video coding scope, see `CommandDefinition::video_coding`, after those
+    /// required by video coding extensions.
+    pub fn video_api<'a>(&'a self) -> Vec<VideoGroup<'a>> {
+        let codecs: Vec<&VideoCodec> = self
+            .0
+            .iter()
+            .flat_map(|child| match *child {
+                RegistryChild::VideoCodecs(ref codecs) => &codecs.children[..],
+                _ => &[],
+            })
+            .collect();
+        let group = |codec: Option<&'a VideoCodec>| VideoGroup {
+            codec,
+            operation: codec.and_then(|codec| operation(&codecs, codec)),
+            extensions: Vec::new(),
+            types: Vec::new(),
+            commands: Vec::new(),
+            capabilities: codec.map_or(Vec::new(), |codec| {
+                codec.capabilities.iter().map(|c| &**c).collect()
+            }),
+        };
+        let mut groups = Vec::new();
+        groups.push(group(None));
+        groups.extend(codecs.iter().map(|&codec| group(Some(codec))));
+
+        let structs: BTreeSet<&str> = codecs.iter().flat_map(|codec| codec.structs()).collect();
+        let mut extended = BTreeSet::new();
+        for ty in self.types() {
+            if ty
+                .declared_name()
+                .map_or(false, |name| structs.contains(name))
+            {
+                extended.extend(ty.structextends.iter().flat_map(|list| list.split(',')));
+            }
+        }
+        let recorded_inside: Vec<&str> = self
+            .commands()
+            .filter_map(|command| match *command {
+                Command::Definition(ref def) if def.video_coding() != VideoCoding::Outside => {
+                    Some(&*def.proto.name)
+                }
+                _ => None,
+            })
+            .collect();
+
+        // Items already added to a group, by index of the group.
+        let mut seen = BTreeSet::new();
+        for extension in self.extensions().filter(|e| !e.is_disabled()) {
+            let items = || {
+                extension.children.iter().flat_map(|child| match *child {
+                    ExtensionChild::Require { ref items, .. } => &items[..],
+                    _ => &[],
+                })
+            };
+            let provides = |codec: &VideoCodec| {
+                items().any(|item| match *item {
+                    InterfaceItem::Type { ref name, .. } => codec.structs().any(|s| s == &**name),
+                    InterfaceItem::Enum(ref e) => codec.value.as_ref() == Some(&e.name),
+                    _ => false,
+                })
+            };
+            let shared = || {
+                items().any(|item| match *item {
+                    InterfaceItem::Type { ref name, .. } => extended.contains(&**name),
+                    InterfaceItem::Command { ref name, .. } => recorded_inside.contains(&&**name),
+                    _ => false,
+                })
+            };
+            let index = match codecs.iter().position(|&codec| provides(codec)) {
+                Some(i) => i + 1,
+                None if shared() => 0,
+                None => continue,
+            };
+            let group = &mut groups[index];
+            group.extensions.push(extension);
+            for item in items() {
+                let (is_command, name) = match *item {
+                    InterfaceItem::Type { ref name, .. } => (false, &**name),
+                    InterfaceItem::Command { ref name, .. } => (true, &**name),
+                    _ => continue,
+                };
+                if !seen.insert((index, is_command, name)) {
+                    continue;
+                }
+                if is_command {
+                    group.commands.push(name);
+                } else {
+                    group.types.push(name);
+                }
+            }
+        }
+
+        for name in recorded_inside {
+            if !groups.iter().any(|g| g.commands.contains(&name)) {
+                groups[0].commands.push(name);
+            }
+        }
+        groups.retain(|g| !g.extensions.is_empty() || !g.commands.is_empty());
+        groups
+    }
+}
+
+/// Operation of a codec, given by the name of the codec at the root of its `extend` chain.
+fn operation<'a>(codecs: &[&'a VideoCodec], mut codec: &'a VideoCodec) -> Option<VideoOperation> {
+    // Bounded by the number of codecs, in case of a cycle.
+    for _ in 0..codecs.len() {
+        let extend = match codec.extend {
+            Some(ref extend) => extend,
+            None => break,
+        };
+        codec = codecs.iter().find(|c| c.name == *extend)?;
+    }
+    match &*codec.name {
+        "Decode" => Some(VideoOperation::Decode),
+        "Encode" => Some(VideoOperation::Encode),
+        _ => None,
+    }
+}
@@ -0,0 +1,192 @@
+use types::*;
+
+/// Callbacks for items of a registry, called by `walk`.
+///
+/// All methods do nothing by default, so visitors only implement those for items they're
+/// interested in. Items are visited in document order, each followed by its children, so
+/// e.g. items of a require block are visited after the feature or extension they belong to.
+/// Comments aren't visited.
+///
+/// The lifetime allows visitors to keep references to visited items:
+///
+/// ```
+/// extern crate vk_parse;
+/// use vk_parse::{CommandDefinition, CommandParam, RegistryVisitor};
+///
+/// /// Collects names of commands which take a parameter of given type.
+/// struct CommandsTaking<'a> {
+///     type_name: &'a str,
+///     commands: Vec<&'a str>,
+/// }
+///
+/// impl<'a> RegistryVisitor<'a> for CommandsTaking<'a> {
+///     fn visit_command_param(&mut self, command: &'a CommandDefinition, param: &'a CommandParam) {
+///         if param.definition.type_name.as_deref() == Some(self.type_name) {
+///             self.commands.push(&command.proto.name);
+///         }
+///     }
+/// }
+///
+/// let registry = vk_parse::Registry::default();
+/// let mut visitor = CommandsTaking { type_name: "VkDevice", commands: Vec::new() };
+/// vk_parse::walk(&registry, &mut visitor);
+/// ```
+#[allow(unused_variables)]
+pub trait RegistryVisitor<'a> {
+    fn visit_vendor_id(&mut self, vendor_id: &'a VendorId) {}
+
+    fn visit_platform(&mut self, platform: &'a Platform) {}
+
+    fn visit_tag(&mut self, tag: &'a Tag) {}
+
+    fn visit_type(&mut self, ty: &'a Type) {}
+
+    /// Member of a struct or union type.
+    fn visit_type_member(&mut self, ty: &'a Type, member: &'a TypeMemberDefinition) {}
+
+    fn visit_enums(&mut self, enums: &'a Enums) {}
+
+    /// Enum defined in an `<enums>` block. Enums added by features and extensions are visited
+    /// by `visit_interface_item`.
+    fn visit_enum(&mut self, enums: &'a Enums, e: &'a Enum) {}
+
+    fn visit_command(&mut self, command: &'a Command) {}
+
+    fn visit_command_param(&mut self, command: &'a CommandDefinition, param: &'a CommandParam) {}
+
+    fn visit_feature(&mut self, feature: &'a Feature) {}
+
+    fn visit_extension(&mut self, extension: &'a Extension) {}
+
+    /// Require or remove block of the most recently visited feature or extension.
+    fn visit_extension_child(&mut self, child: &'a ExtensionChild) {}
+
+    /// Item of the most recently visited require or remove block.
+    fn visit_interface_item(&mut self, item: &'a InterfaceItem) {}
+
+    fn visit_format(&mut self, format: &'a Format) {}
+
+    fn visit_spirv_extension(&mut self, extension: &'a SpirvExtension) {}
+
+    fn visit_spirv_capability(&mut self, capability: &'a SpirvCapability) {}
+
+    /// Pipeline stage, access flag or pipeline of the `<sync>` section.
+    fn visit_sync(&mut self, child: &'a SyncChild) {}
+
+    fn visit_video_codec(&mut self, codec: &'a VideoCodec) {}
+}
+
+/// Visits all items of the registry, see `RegistryVisitor`.
+pub fn walk<'a, V: RegistryVisitor<'a>>(registry: &'a Registry, visitor: &mut V) {
+    for child in &registry.0 {
+        match *child {
+            RegistryChild::VendorIds(ref vendor_ids) => {
+                for vendor_id in &vendor_ids.children {
+                    visitor.visit_vendor_id(vendor_id);
+                }
+            }
+            RegistryChild::Platforms(ref platforms) => {
+                for platform in &platforms.children {
+                    visitor.visit_platform(platform);
+                }
+            }
+            RegistryChild::Tags(ref tags) => {
+                for tag in &tags.children {
+                    visitor.visit_tag(tag);
+                }
+            }
+            RegistryChild::Types(ref types) => {
+                for child in &types.children {
+                    if let TypesChild::Type(ref ty) = *child {
+                        walk_type(ty, visitor);
+                    }
+                }
+            }
+            RegistryChild::Enums(ref enums) => {
+                visitor.visit_enums(enums);
+                for child in &enums.children {
+                    if let EnumsChild::Enum(ref e) = *child {
+                        visitor.visit_enum(enums, e);
+                    }
+                }
+            }
+            RegistryChild::Commands(ref commands) => {
+                for command in &commands.children {
+                    visitor.visit_command(command);
+                    if let Command::Definition(ref def) = *command {
+                        for param in &def.params {
+                            visitor.visit_command_param(def, param);
+                        }
+                    }
+                }
+            }
+            RegistryChild::Feature(ref feature) => {
+                visitor.visit_feature(feature);
+                walk_extension_children(&feature.children, visitor);
+            }
+            RegistryChild::Extensions(ref extensions) => {
+                for extension in &extensions.children {
+                    visitor.visit_extension(extension);
+                    walk_extension_children(&extension.children, visitor);
+                }
+            }
+            RegistryChild::Formats(ref formats) => {
+                for format in &formats.children {
+                    visitor.visit_format(format);
+                }
+            }
+            RegistryChild::SpirvExtensions(ref extensions) => {
+                for extension in &extensions.children {
+                    visitor.visit_spirv_extension(extension);
+                }
+            }
+            RegistryChild::SpirvCapabilities(ref capabilities) => {
+                for capability in &capabilities.children {
+                    visitor.visit_spirv_capability(capability);
+                }
+            }
+            RegistryChild::Sync(ref sync) => {
+                for child in &sync.children {
+                    visitor.visit_sync(child);
+                }
+            }
+            RegistryChild::VideoCodecs(ref codecs) => {
+                for codec in &codecs.children {
+                    visitor.visit_video_codec(codec);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn walk_type<'a, V: RegistryVisitor<'a>>(ty: &'a Type, visitor: &mut V) {
+    visitor.visit_type(ty);
+    if let TypeSpec::Members(ref members) = ty.spec {
+        for member in members {
+            if let TypeMember::Definition(ref def) = *member {
+                visitor.visit_type_member(ty, def);
+            }
+        }
+    }
+}
+
+fn walk_extension_children<'a, V: RegistryVisitor<'a>>(
+    children: &'a [ExtensionChild],
+    visitor: &mut V,
+) {
+    for child in children {
+        visitor.visit_extension_child(child);
+        let items = match *child {
+            ExtensionChild::Require { ref items, .. }
+            | ExtensionChild::Remove { ref items, .. }
+            | ExtensionChild::Deprecate { ref items, .. } => items,
+        };
+        for item in items {
+            if let InterfaceItem::Comment(_) = *item {
+                continue;
+            }
+            visitor.visit_interface_item(item);
+        }
+    }
+}
@@ -0,0 +1,885 @@
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::String, vec::Vec};
+#[cfg(not(feature = "std"))]
+use reader::{Read, XmlEvent};
+#[cfg(feature = "std")]
+use std::io::Read;
+#[cfg(feature = "std")]
+use xml::reader::XmlEvent;
+
+use parse::{
+    consume_current_element, consume_leaf_element, parse_int_attribute, parse_name_list,
+    parse_queues, parse_text_element, raw_attributes, ParseCtx, XmlAttribute,
+};
+use types::*;
+
+// Layer of the parser for top-level sections of the Vulkan registry which other Khronos
+// registries don't have. Sections shared by all of them are parsed by the `parse` module.
+
+/// Whether top-level element with given name is parsed by this module.
+pub(crate) fn is_section(name: &str) -> bool {
+    matches!(
+        name,
+        "vendorids"
+            | "platforms"
+            | "tags"
+            | "formats"
+            | "spirvextensions"
+            | "spirvcapabilities"
+            | "sync"
+            | "videocodecs"
+    )
+}
+
+/// Parses top-level element for which `is_section` holds.
+pub(crate) fn parse_section<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    name: &str,
+    attributes: Vec<XmlAttribute>,
+) -> RegistryChild {
+    match name {
+        "vendorids" => parse_vendorids(ctx, attributes),
+        "platforms" => parse_platforms(ctx, attributes),
+        "tags" => parse_tags(ctx, attributes),
+        "formats" => {
+            ctx.ignored_attributes(&attributes);
+            parse_formats(ctx)
+        }
+        "spirvextensions" => parse_spirvextensions(ctx, attributes),
+        "spirvcapabilities" => parse_spirvcapabilities(ctx, attributes),
+        "sync" => parse_sync(ctx, attributes),
+        // "videocodecs"
+        _ => parse_videocodecs(ctx, attributes),
+    }
+}
+
+fn parse_vendorids<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> RegistryChild {
+    let mut comment = None;
+    let mut children = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "comment" => comment = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "vendorid" => if let Some(v) = parse_vendorid(ctx, attributes) {
+            children.push(v);
+        }
+    }
+
+    RegistryChild::VendorIds(VendorIds { comment, children })
+}
+
+fn parse_vendorid<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<VendorId> {
+    let mut name = None;
+    let mut comment = None;
+    let mut id = None;
+
+    let raw = raw_attributes(ctx, &attributes);
+    match_attributes! {ctx, a in attributes,
+        "name" => name = Some(a.value.into_boxed_str()),
+        "comment" => comment = Some(a.value.into_boxed_str()),
+        "id" => {
+            let mut v = None;
+            if a.value.starts_with("0x") {
+                v = u32::from_str_radix(a.value.split_at(2).1, 16).ok();
+            }
+
+            if let Some(v) = v {
+                id = Some(v);
+            } else {
+                ctx.errors.push(Error::UnexpectedAttributeValue {
+                    xpath: ctx.xpath(),
+                    name: String::from("id"),
+                    value: a.value.clone(),
+                });
+            }
+        }
+    }
+
+    consume_leaf_element(ctx);
+
+    unwrap_attribute!(ctx, vendorid, name);
+    unwrap_attribute!(ctx, vendorid, id);
+
+    Some(VendorId {
+        name,
+        comment,
+        id,
+        raw,
+    })
+}
+
+fn parse_platforms<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> RegistryChild {
+    let mut comment = None;
+    let mut children = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "comment" => comment = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "platform" => if let Some(v) = parse_platform(ctx, attributes) {
+            children.push(v);
+        }
+    }
+
+    RegistryChild::Platforms(Platforms { comment, children })
+}
+
+fn parse_platform<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<Platform> {
+    let mut name = None;
+    let mut comment = None;
+    let mut protect = None;
+
+    let raw = raw_attributes(ctx, &attributes);
+    match_attributes! {ctx, a in attributes,
+        "name"    => name    = Some(a.value.into_boxed_str()),
+        "comment" => comment = Some(a.value.into_boxed_str()),
+        "protect" => protect = Some(a.value.into_boxed_str())
+    }
+
+    consume_leaf_element(ctx);
+
+    unwrap_attribute!(ctx, platform, name);
+    unwrap_attribute!(ctx, platform, protect);
+
+    Some(Platform {
+        name: name.into(),
+        comment,
+        protect,
+        raw,
+    })
+}
+
+fn parse_tags<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> RegistryChild {
+    let mut comment = None;
+    let mut children = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "comment" => comment = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "tag" => if let Some(v) = parse_tag(ctx, attributes) {
+            children.push(v);
+        }
+    }
+
+    RegistryChild::Tags(Tags { comment, children })
+}
+
+fn parse_tag<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> Option<Tag> {
+    let mut name = None;
+    let mut author = None;
+    let mut contact = None;
+
+    match_attributes! {ctx, a in attributes,
+        "name"    => name    = Some(a.value.into_boxed_str()),
+        "author"  => author  = Some(a.value.into_boxed_str()),
+        "contact" => contact = Some(a.value.into_boxed_str())
+    }
+
+    consume_leaf_element(ctx);
+
+    unwrap_attribute!(ctx, tag, name);
+    unwrap_attribute!(ctx, tag, author);
+    unwrap_attribute!(ctx, tag, contact);
+
+    Some(Tag {
+        name,
+        author,
+        contact,
+    })
+}
+
+fn parse_formats<R: Read>(ctx: &mut ParseCtx<R>) -> RegistryChild {
+    let mut children = Vec::new();
+
+    match_elements! {ctx, attributes,
+        "format" => if let Some(v) = parse_format(ctx, attributes) {
+            children.push(v);
+        }
+    }
+
+    RegistryChild::Formats(Formats {
+        comment: None,
+        children,
+    })
+}
+
+#[allow(non_snake_case)]
+fn parse_format<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> Option<Format> {
+    let mut name = None;
+    let mut class = None;
+    let mut blockSize = None;
+    let mut texelsPerBlock = None;
+    let mut blockExtent = None;
+    let mut packed = None;
+    let mut compressed = None;
+    let mut chroma = None;
+    let mut children = Vec::new();
+
+    let raw = raw_attributes(ctx, &attributes);
+    match_attributes! {ctx, a in attributes,
+        "name"           => name           = Some(a.value.into_boxed_str()),
+        "class"          => class          = Some(a.value.into_boxed_str()),
+        "blockSize"      => blockSize      = Some(a.value.into_boxed_str()),
+        "texelsPerBlock" => texelsPerBlock = Some(a.value.into_boxed_str()),
+        "blockExtent"    => blockExtent    = Some(a.value.into_boxed_str()),
+        "packed"         => packed         = Some(a.value.into_boxed_str()),
+        "compressed"     => compressed     = Some(a.value.into_boxed_str()),
+        "chroma"         => chroma         = Some(a.value.into_boxed_str())
+    }
+
+    unwrap_attribute!(ctx, extension, name);
+    unwrap_attribute!(ctx, extension, class);
+    unwrap_attribute!(ctx, extension, blockSize);
+    unwrap_attribute!(ctx, extension, texelsPerBlock);
+
+    match_elements! {ctx, attributes,
+        "component"        => if let Some(v) = parse_format_component(ctx, attributes) { children.push(v); },
+        "plane"            => if let Some(v) = parse_format_plane(ctx, attributes) { children.push(v); },
+        "spirvimageformat" => if let Some(v) = parse_format_spirvimageformat(ctx, attributes) { children.push(v); }
+    }
+
+    let blockSize: Option<u8> = parse_int_attribute(ctx, blockSize, "blockSize");
+    let texelsPerBlock: Option<u8> = parse_int_attribute(ctx, texelsPerBlock, "texelsPerBlock");
+    let packed = packed.map(|v| -> Option<u8> { parse_int_attribute(ctx, v, "packed") });
+
+    let blockSize = blockSize?;
+
+    let texelsPerBlock = texelsPerBlock?;
+
+    let packed = match packed {
+        Some(Some(v)) => Some(v),
+        Some(None) => return None, // Attribute present, but parse error occurred.
+        None => None,
+    };
+
+    Some(Format {
+        name,
+        class,
+        blockSize,
+        texelsPerBlock,
+        blockExtent,
+        packed,
+        compressed,
+        chroma,
+        children,
+        raw,
+    })
+}
+
+#[allow(non_snake_case)]
+fn parse_format_component<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<FormatChild> {
+    let mut name = None;
+    let mut bits = None;
+    let mut numericFormat = None;
+    let mut planeIndex = None;
+
+    match_attributes! {ctx, a in attributes,
+        "name"          => name          = Some(a.value.into_boxed_str()),
+        "bits"          => bits          = Some(a.value.into_boxed_str()),
+        "numericFormat" => numericFormat = Some(a.value.into_boxed_str()),
+        "planeIndex"    => planeIndex    = Some(a.value.into_boxed_str())
+    }
+
+    unwrap_attribute!(ctx, extension, name);
+    unwrap_attribute!(ctx, extension, bits);
+    unwrap_attribute!(ctx, extension, numericFormat);
+
+    consume_leaf_element(ctx);
+
+    let planeIndex =
+        planeIndex.map(|v| -> Option<u8> { parse_int_attribute(ctx, v, "planeIndex") });
+
+    let planeIndex = match planeIndex {
+        Some(Some(v)) => Some(v),
+        Some(None) => return None, // Attribute present, but parse error occurred.
+        None => None,
+    };
+
+    Some(FormatChild::Component {
+        name,
+        bits,
+        numericFormat,
+        planeIndex,
+    })
+}
+
+#[allow(non_snake_case)]
+fn parse_format_plane<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<FormatChild> {
+    let mut index = None;
+    let mut widthDivisor = None;
+    let mut heightDivisor = None;
+    let mut compatible = None;
+
+    match_attributes! {ctx, a in attributes,
+        "index"         => index         = Some(a.value.into_boxed_str()),
+        "widthDivisor"  => widthDivisor  = Some(a.value.into_boxed_str()),
+        "heightDivisor" => heightDivisor = Some(a.value.into_boxed_str()),
+        "compatible"    => compatible    = Some(a.value.into_boxed_str())
+    }
+
+    unwrap_attribute!(ctx, extension, index);
+    unwrap_attribute!(ctx, extension, widthDivisor);
+    unwrap_attribute!(ctx, extension, heightDivisor);
+    unwrap_attribute!(ctx, extension, compatible);
+
+    consume_leaf_element(ctx);
+
+    let index: Option<u8> = parse_int_attribute(ctx, index, "index");
+    let widthDivisor: Option<u8> = parse_int_attribute(ctx, widthDivisor, "widthDivisor");
+    let heightDivisor: Option<u8> = parse_int_attribute(ctx, heightDivisor, "heightDivisor");
+
+    let index = index?;
+
+    let widthDivisor = widthDivisor?;
+
+    let heightDivisor = heightDivisor?;
+
+    Some(FormatChild::Plane {
+        index,
+        widthDivisor,
+        heightDivisor,
+        compatible,
+    })
+}
+
+fn parse_format_spirvimageformat<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<FormatChild> {
+    let mut name = None;
+
+    match_attributes! {ctx, a in attributes,
+        "name" => name = Some(a.value.into_boxed_str())
+    }
+
+    unwrap_attribute!(ctx, extension, name);
+
+    consume_leaf_element(ctx);
+
+    Some(FormatChild::SpirvImageFormat { name })
+}
+
+fn parse_spirvextensions<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> RegistryChild {
+    let mut comment = None;
+    let mut children = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "comment" => comment = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "spirvextension" => if let Some(v) = parse_spirvextension(ctx, attributes) {
+            children.push(v);
+        }
+    }
+
+    RegistryChild::SpirvExtensions(SpirvExtensions { comment, children })
+}
+
+fn parse_spirvextension<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<SpirvExtension> {
+    let mut name = None;
+    let mut enables = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "name" => name = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "enable" => if let Some(v) = parse_enable(ctx, attributes) {
+            enables.push(v);
+        }
+    }
+
+    unwrap_attribute!(ctx, spirvextension, name);
+
+    Some(SpirvExtension { name, enables })
+}
+
+fn parse_spirvcapabilities<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> RegistryChild {
+    let mut comment = None;
+    let mut children = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "comment" => comment = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "spirvcapability" => if let Some(v) = parse_spirvcapability(ctx, attributes) {
+            children.push(v);
+        }
+    }
+
+    RegistryChild::SpirvCapabilities(SpirvCapabilities { comment, children })
+}
+
+fn parse_spirvcapability<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<SpirvCapability> {
+    let mut name = None;
+    let mut enables = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "name" => name = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "enable" => if let Some(v) = parse_enable(ctx, attributes) {
+            enables.push(v);
+        }
+    }
+
+    unwrap_attribute!(ctx, spirvcapability, name);
+
+    Some(SpirvCapability { name, enables })
+}
+
+fn parse_sync<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> RegistryChild {
+    let mut comment = None;
+    let mut children = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "comment" => comment = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "syncstage" => if let Some(v) = parse_syncstage(ctx, attributes) {
+            children.push(SyncChild::Stage(v));
+        },
+        "syncaccess" => if let Some(v) = parse_syncaccess(ctx, attributes) {
+            children.push(SyncChild::Access(v));
+        },
+        "syncpipeline" => if let Some(v) = parse_syncpipeline(ctx, attributes) {
+            children.push(SyncChild::Pipeline(v));
+        }
+    }
+
+    RegistryChild::Sync(SyncSection { comment, children })
+}
+
+fn parse_syncstage<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<SyncStage> {
+    let mut name = None;
+    let mut alias = None;
+    let mut support = None;
+    let mut equivalent = None;
+
+    match_attributes! {ctx, a in attributes,
+        "name"  => name  = Some(a.value.into_boxed_str()),
+        "alias" => alias = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "syncsupport" => support = Some(parse_syncsupport(ctx, attributes)),
+        "syncequivalent" => equivalent = Some(parse_syncequivalent(ctx, attributes))
+    }
+
+    unwrap_attribute!(ctx, syncstage, name);
+
+    Some(SyncStage {
+        name,
+        alias,
+        support,
+        equivalent,
+    })
+}
+
+fn parse_syncaccess<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<SyncAccess> {
+    let mut name = None;
+    let mut alias = None;
+    let mut comment = None;
+    let mut support = None;
+    let mut equivalent = None;
+
+    match_attributes! {ctx, a in attributes,
+        "name"  => name  = Some(a.value.into_boxed_str()),
+        "alias" => alias = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "comment" => {
+            ctx.ignored_attributes(&attributes);
+            comment = Some(parse_text_element(ctx));
+        },
+        "syncsupport" => support = Some(parse_syncsupport(ctx, attributes)),
+        "syncequivalent" => equivalent = Some(parse_syncequivalent(ctx, attributes))
+    }
+
+    unwrap_attribute!(ctx, syncaccess, name);
+
+    Some(SyncAccess {
+        name,
+        alias,
+        comment,
+        support,
+        equivalent,
+    })
+}
+
+fn parse_syncsupport<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> SyncSupport {
+    let mut queues = Vec::new();
+    let mut stage = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "queues" => queues = parse_queues(ctx, a.value),
+        "stage"  => stage  = parse_name_list(ctx, "stage", a.value)
+    }
+
+    consume_leaf_element(ctx);
+
+    SyncSupport { queues, stage }
+}
+
+fn parse_syncequivalent<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> SyncEquivalent {
+    let mut stage = Vec::new();
+    let mut access = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "stage"  => stage  = parse_name_list(ctx, "stage", a.value),
+        "access" => access = parse_name_list(ctx, "access", a.value)
+    }
+
+    consume_leaf_element(ctx);
+
+    SyncEquivalent { stage, access }
+}
+
+fn parse_syncpipeline<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<SyncPipeline> {
+    let mut name = None;
+    let mut depends = None;
+    let mut stages = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "name"    => name    = Some(a.value.into_boxed_str()),
+        "depends" => depends = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "syncpipelinestage" => {
+            let mut order = None;
+            let mut before = None;
+            let mut after = None;
+            match_attributes! {ctx, a in attributes,
+                "order"  => order  = Some(a.value.into_boxed_str()),
+                "before" => before = Some(a.value.into_boxed_str()),
+                "after"  => after  = Some(a.value.into_boxed_str())
+            }
+            stages.push(SyncPipelineStage {
+                name: parse_text_element(ctx),
+                order,
+                before,
+                after,
+            });
+        }
+    }
+
+    unwrap_attribute!(ctx, syncpipeline, name);
+
+    Some(SyncPipeline {
+        name,
+        depends,
+        stages,
+    })
+}
+
+fn parse_videocodecs<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> RegistryChild {
+    let mut comment = None;
+    let mut children = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "comment" => comment = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "videocodec" => if let Some(v) = parse_videocodec(ctx, attributes) {
+            children.push(v);
+        }
+    }
+
+    RegistryChild::VideoCodecs(VideoCodecs { comment, children })
+}
+
+fn parse_videocodec<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<VideoCodec> {
+    let mut name = None;
+    let mut extend = None;
+    let mut value = None;
+    let mut profiles = Vec::new();
+    let mut capabilities = Vec::new();
+    let mut formats = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "name"   => name   = Some(a.value.into_boxed_str()),
+        "extend" => extend = Some(a.value.into_boxed_str()),
+        "value"  => value  = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "videoprofiles" => if let Some(v) = parse_videoprofiles(ctx, attributes) {
+            profiles.push(v);
+        },
+        "videocapabilities" => if let Some(v) = parse_video_struct(ctx, attributes) {
+            capabilities.push(v);
+        },
+        "videoformat" => formats.push(parse_videoformat(ctx, attributes))
+    }
+
+    unwrap_attribute!(ctx, videocodec, name);
+
+    Some(VideoCodec {
+        name,
+        extend,
+        value,
+        profiles,
+        capabilities,
+        formats,
+    })
+}
+
+fn parse_videoprofiles<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<VideoProfiles> {
+    let mut struct_ = None;
+    let mut members = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "struct" => struct_ = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "videoprofilemember" => if let Some(v) = parse_videoprofilemember(ctx, attributes) {
+            members.push(v);
+        }
+    }
+
+    let struct_ = missing_struct(ctx, struct_)?;
+
+    Some(VideoProfiles { struct_, members })
+}
+
+fn parse_videoprofilemember<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<VideoProfileMember> {
+    let mut name = None;
+    let mut profiles = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "name" => name = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "videoprofile" => if let Some(v) = parse_videoprofile(ctx, attributes) {
+            profiles.push(v);
+        }
+    }
+
+    unwrap_attribute!(ctx, videoprofilemember, name);
+
+    Some(VideoProfileMember { name, profiles })
+}
+
+fn parse_videoprofile<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<VideoProfile> {
+    let mut name = None;
+    let mut value = None;
+
+    match_attributes! {ctx, a in attributes,
+        "name"  => name  = Some(a.value.into_boxed_str()),
+        "value" => value = Some(a.value.into_boxed_str())
+    }
+
+    consume_leaf_element(ctx);
+
+    unwrap_attribute!(ctx, videoprofile, name);
+    unwrap_attribute!(ctx, videoprofile, value);
+
+    Some(VideoProfile { name, value })
+}
+
+fn parse_videoformat<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> VideoFormat {
+    let mut name = None;
+    let mut extend = None;
+    let mut usage = None;
+    let mut required_capabilities = Vec::new();
+    let mut properties = Vec::new();
+
+    match_attributes! {ctx, a in attributes,
+        "name"   => name   = Some(a.value.into_boxed_str()),
+        "extend" => extend = Some(a.value.into_boxed_str()),
+        "usage"  => usage  = Some(a.value.into_boxed_str())
+    }
+
+    match_elements! {ctx, attributes,
+        "videorequirecapabilities" => {
+            if let Some(v) = parse_videorequirecapabilities(ctx, attributes) {
+                required_capabilities.push(v);
+            }
+        },
+        "videoformatproperties" => if let Some(v) = parse_video_struct(ctx, attributes) {
+            properties.push(v);
+        }
+    }
+
+    VideoFormat {
+        name,
+        extend,
+        usage,
+        required_capabilities,
+        properties,
+    }
+}
+
+fn parse_videorequirecapabilities<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<VideoRequiredCapability> {
+    let mut struct_ = None;
+    let mut member = None;
+    let mut value = None;
+
+    match_attributes! {ctx, a in attributes,
+        "struct" => struct_ = Some(a.value.into_boxed_str()),
+        "member" => member  = Some(a.value.into_boxed_str()),
+        "value"  => value   = Some(a.value.into_boxed_str())
+    }
+
+    consume_leaf_element(ctx);
+
+    let struct_ = missing_struct(ctx, struct_)?;
+    unwrap_attribute!(ctx, videorequirecapabilities, member);
+    unwrap_attribute!(ctx, videorequirecapabilities, value);
+
+    Some(VideoRequiredCapability {
+        struct_,
+        member,
+        value,
+    })
+}
+
+/// Name given by `struct` of `<videocapabilities>` and `<videoformatproperties>`.
+fn parse_video_struct<R: Read>(
+    ctx: &mut ParseCtx<R>,
+    attributes: Vec<XmlAttribute>,
+) -> Option<Box<str>> {
+    let mut struct_ = None;
+
+    match_attributes! {ctx, a in attributes,
+        "struct" => struct_ = Some(a.value.into_boxed_str())
+    }
+
+    consume_leaf_element(ctx);
+
+    missing_struct(ctx, struct_)
+}
+
+/// Reports missing `struct` attribute, which `unwrap_attribute!` would name `struct_`.
+fn missing_struct<R: Read>(ctx: &mut ParseCtx<R>, struct_: Option<Box<str>>) -> Option<Box<str>> {
+    if struct_.is_none() {
+        ctx.errors.push(Error::MissingAttribute {
+            xpath: ctx.xpath(),
+            name: String::from("struct"),
+        });
+    }
+    struct_
+}
+
+fn parse_enable<R: Read>(ctx: &mut ParseCtx<R>, attributes: Vec<XmlAttribute>) -> Option<Enable> {
+    let mut version = None;
+    let mut extension = None;
+    let mut struct_ = None;
+    let mut feature = None;
+    let mut requires = None;
+    let mut alias = None;
+    let mut property = None;
+    let mut member = None;
+    let mut value = None;
+
+    match_attributes! {ctx, a in attributes,
+        "version" => version = Some(a.value.into_boxed_str()),
+        "extension" => extension = Some(a.value.into_boxed_str()),
+        "struct" => struct_ = Some(a.value.into_boxed_str()),
+        "feature" => feature = Some(a.value.into_boxed_str()),
+        "requires" => requires = Some(a.value.into_boxed_str()),
+        "alias" => alias = Some(a.value.into_boxed_str()),
+        "property" => property = Some(a.value.into_boxed_str()),
+        "member" => member = Some(a.value.into_boxed_str()),
+        "value" => value = Some(a.value.into_boxed_str())
+    }
+
+    consume_leaf_element(ctx);
+
+    if let Some(version) = version {
+        Some(Enable::Version(version))
+    } else if let Some(extension) = extension {
+        Some(Enable::Extension(extension))
+    } else if let Some(struct_) = struct_ {
+        unwrap_attribute!(ctx, enable, feature);
+        Some(Enable::Feature(FeatureEnable {
+            struct_,
+            feature,
+            requires,
+            alias,
+        }))
+    } else if let Some(property) = property {
+        unwrap_attribute!(ctx, enable, member);
+        unwrap_attribute!(ctx, enable, value);
+        Some(Enable::Property(PropertyEnable {
+            property,
+            member,
+            value,
+            requires,
+        }))
+    } else {
+        ctx.errors.push(Error::SchemaViolation {
+            xpath: ctx.xpath(),
+            desc: String::from("Missing version, extension, struct or property on enable."),
+        });
+        None
+    }
+}
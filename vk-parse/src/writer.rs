@@ -0,0 +1,1142 @@
+#[cfg(not(feature = "std"))]
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::io;
+
+use commands::QueueKind;
+use deprecation::Deprecation;
+use types::*;
+use video::VideoCoding;
+
+/// Options controlling how `Registry::write_xml` formats the registry.
+///
+/// Options are constructed using builder-style methods, starting from `WriteOptions::new()`
+/// (or `Default::default()`), which follows the conventions of vk.xml, so that diffs against
+/// the upstream registry only show actual changes.
+///
+/// ```
+/// extern crate vk_parse;
+///
+/// let options = vk_parse::WriteOptions::new()
+///     .indent(vk_parse::IndentStyle::Tabs)
+///     .block_line_width(Some(100));
+/// let xml = vk_parse::Registry::default().to_xml(&options);
+/// assert!(xml.ends_with("<registry>\n</registry>\n"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WriteOptions {
+    pub(crate) indent: IndentStyle,
+    pub(crate) attribute_order: AttributeOrder,
+    pub(crate) comment_placement: CommentPlacement,
+    pub(crate) align_enums: bool,
+    pub(crate) block_line_width: Option<usize>,
+    pub(crate) xml_declaration: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        WriteOptions {
+            indent: IndentStyle::Spaces(4),
+            attribute_order: AttributeOrder::Registry,
+            comment_placement: CommentPlacement::Last,
+            align_enums: true,
+            block_line_width: None,
+            xml_declaration: true,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Creates options following the conventions of vk.xml.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Indentation of each level of nested elements, 4 spaces by default.
+    pub fn indent(mut self, indent: IndentStyle) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// Order in which attributes of each element are written.
+    pub fn attribute_order(mut self, order: AttributeOrder) -> Self {
+        self.attribute_order = order;
+        self
+    }
+
+    /// Where `comment` attributes are written, relative to other attributes.
+    pub fn comment_placement(mut self, placement: CommentPlacement) -> Self {
+        self.comment_placement = placement;
+        self
+    }
+
+    /// Pads attributes of enums preceding `name`, so that names of all enums in a block start
+    /// in the same column, as in vk.xml. Enabled by default.
+    pub fn align_enums(mut self, align: bool) -> Self {
+        self.align_enums = align;
+        self
+    }
+
+    /// Writes `<require>`, `<remove>` and `<deprecate>` blocks on a single line when they fit
+    /// within `width` columns, including indentation. By default, each item of the block is
+    /// written on its own line, as in vk.xml.
+    pub fn block_line_width(mut self, width: Option<usize>) -> Self {
+        self.block_line_width = width;
+        self
+    }
+
+    /// Starts the document with the XML declaration. Enabled by default.
+    pub fn xml_declaration(mut self, declaration: bool) -> Self {
+        self.xml_declaration = declaration;
+        self
+    }
+}
+
+/// Indentation of nested elements, see `WriteOptions::indent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IndentStyle {
+    /// Given number of spaces per level.
+    Spaces(usize),
+    /// One tab per level.
+    Tabs,
+}
+
+/// Order of attributes of an element, see `WriteOptions::attribute_order`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AttributeOrder {
+    /// Order usually used by vk.xml for the element, e.g. `offset`, `extends` and `name` for
+    /// enums of extensions.
+    Registry,
+    /// Attributes sorted by name.
+    Alphabetical,
+}
+
+/// Placement of `comment` attributes, see `WriteOptions::comment_placement`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CommentPlacement {
+    /// After all other attributes, as in vk.xml.
+    Last,
+    /// Before all other attributes.
+    First,
+}
+
+impl Registry {
+    /// Writes the registry as XML, formatted according to `options`.
+    ///
+    /// Parsing the output gives back an equal registry, except for whitespace in code which
+    /// isn't significant in C, and values of attributes which are normalized while parsing,
+    /// e.g. numbers. Code omitted while parsing, see `ParseOptions::omit_code`, is regenerated
+    /// from the parsed declarations.
+    #[cfg(feature = "std")]
+    pub fn write_xml<W: io::Write>(&self, mut writer: W, options: &WriteOptions) -> io::Result<()> {
+        writer.write_all(self.to_xml(options).as_bytes())
+    }
+
+    /// Formats the registry as XML, see `Registry::write_xml`.
+    pub fn to_xml(&self, options: &WriteOptions) -> String {
+        let mut writer = Writer {
+            out: String::new(),
+            options,
+        };
+        if options.xml_declaration {
+            writer
+                .out
+                .push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        }
+        writer.out.push_str("<registry>\n");
+        for child in &self.0 {
+            writer.registry_child(child);
+        }
+        writer.out.push_str("</registry>\n");
+        writer.out
+    }
+}
+
+/// Version of the form written by `canonical_xml`, which is bumped whenever the form changes,
+/// e.g. when a new attribute is written.
+pub(crate) const CANONICAL_VERSION: u8 = 1;
+
+/// Writes the section in a canonical form, with attributes sorted by name and without any
+/// indentation or alignment, which doesn't depend on how the registry was formatted.
+pub(crate) fn canonical_xml(child: &RegistryChild) -> String {
+    let options = WriteOptions {
+        indent: IndentStyle::Spaces(0),
+        attribute_order: AttributeOrder::Alphabetical,
+        comment_placement: CommentPlacement::Last,
+        align_enums: false,
+        block_line_width: None,
+        xml_declaration: false,
+    };
+    let mut writer = Writer {
+        out: String::new(),
+        options: &options,
+    };
+    writer.registry_child(child);
+    writer.out
+}
+
+/// Element along with its attributes in the order of `AttributeOrder::Registry`.
+struct Element<'a> {
+    name: &'static str,
+    attributes: Vec<(&'static str, Cow<'a, str>)>,
+}
+
+impl<'a> Element<'a> {
+    fn new(name: &'static str) -> Self {
+        Element {
+            name,
+            attributes: Vec::new(),
+        }
+    }
+
+    fn attr<V: Into<Cow<'a, str>>>(mut self, name: &'static str, value: V) -> Self {
+        self.attributes.push((name, value.into()));
+        self
+    }
+
+    fn opt<V: Into<Cow<'a, str>>>(self, name: &'static str, value: Option<V>) -> Self {
+        match value {
+            Some(value) => self.attr(name, value),
+            None => self,
+        }
+    }
+}
+
+struct Writer<'o> {
+    out: String,
+    options: &'o WriteOptions,
+}
+
+impl<'o> Writer<'o> {
+    fn indent(&mut self, depth: usize) {
+        for _ in 0..depth {
+            match self.options.indent {
+                IndentStyle::Spaces(n) => self.out.extend((0..n).map(|_| ' ')),
+                IndentStyle::Tabs => self.out.push('\t'),
+            }
+        }
+    }
+
+    /// Start tag without the closing `>`, with `pad` spaces before the `name` attribute.
+    fn start_tag(&self, element: &Element, pad: usize) -> String {
+        let mut attributes: Vec<_> = element.attributes.iter().collect();
+        if self.options.attribute_order == AttributeOrder::Alphabetical {
+            attributes.sort_by_key(|a| a.0);
+        }
+        if let Some(i) = attributes.iter().position(|a| a.0 == "comment") {
+            let comment = attributes.remove(i);
+            match self.options.comment_placement {
+                CommentPlacement::Last => attributes.push(comment),
+                CommentPlacement::First => attributes.insert(0, comment),
+            }
+        }
+        let mut tag = format!("<{}", element.name);
+        for &&(name, ref value) in &attributes {
+            tag.push(' ');
+            if name == "name" {
+                tag.extend((0..pad).map(|_| ' '));
+            }
+            tag.push_str(name);
+            tag.push_str("=\"");
+            tag.push_str(&escape(value, true));
+            tag.push('"');
+        }
+        tag
+    }
+
+    /// Length of the start tag up to its `name` attribute, used to align the attribute.
+    fn name_column(&self, element: &Element) -> Option<usize> {
+        let tag = self.start_tag(element, 0);
+        tag.find(" name=\"").filter(|_| {
+            element.attributes.iter().any(|a| a.0 == "name") && self.options.align_enums
+        })
+    }
+
+    fn empty(&mut self, depth: usize, element: &Element) {
+        self.indent(depth);
+        let tag = self.start_tag(element, 0);
+        self.out.push_str(&tag);
+        self.out.push_str("/>\n");
+    }
+
+    fn open(&mut self, depth: usize, element: &Element) {
+        self.indent(depth);
+        let tag = self.start_tag(element, 0);
+        self.out.push_str(&tag);
+        self.out.push_str(">\n");
+    }
+
+    fn close(&mut self, depth: usize, name: &str) {
+        self.indent(depth);
+        self.out.push_str("</");
+        self.out.push_str(name);
+        self.out.push_str(">\n");
+    }
+
+    /// Element with given content, which is already escaped, on a single line.
+    fn inline(&mut self, depth: usize, element: &Element, content: &str) {
+        self.indent(depth);
+        let tag = self.start_tag(element, 0);
+        self.out.push_str(&tag);
+        self.out.push('>');
+        self.out.push_str(content);
+        self.out.push_str("</");
+        self.out.push_str(element.name);
+        self.out.push_str(">\n");
+    }
+
+    fn text_element(&mut self, depth: usize, name: &'static str, text: &str) {
+        self.inline(depth, &Element::new(name), &escape(text, false));
+    }
+
+    /// Block of elements, which are empty or written with a `<comment>`, with names of enums
+    /// aligned.
+    fn aligned(&self, elements: &[Element]) -> Vec<String> {
+        let columns: Vec<_> = elements
+            .iter()
+            .map(|e| match e.name {
+                "enum" => self.name_column(e),
+                _ => None,
+            })
+            .collect();
+        let max = columns.iter().flatten().max().cloned().unwrap_or(0);
+        elements
+            .iter()
+            .zip(columns)
+            .map(|(e, column)| {
+                let pad = column.map_or(0, |column| max - column);
+                match (e.name, e.attributes.first()) {
+                    ("comment", Some((_, text))) => {
+                        format!("<comment>{}</comment>", escape(text, false))
+                    }
+                    _ => format!("{}/>", self.start_tag(e, pad)),
+                }
+            })
+            .collect()
+    }
+
+    fn registry_child(&mut self, child: &RegistryChild) {
+        match *child {
+            RegistryChild::Comment(ref text) => self.text_element(1, "comment", text),
+            RegistryChild::VendorIds(ref vendors) => {
+                let elements = vendors.children.iter().map(|v| {
+                    Element::new("vendorid")
+                        .attr("name", &*v.name)
+                        .attr("id", format!("0x{:X}", v.id))
+                        .opt("comment", v.comment.as_deref())
+                });
+                self.container("vendorids", vendors.comment.as_deref(), elements);
+            }
+            RegistryChild::Platforms(ref platforms) => {
+                let elements = platforms.children.iter().map(|p| {
+                    Element::new("platform")
+                        .attr("name", p.name.as_str())
+                        .attr("protect", &*p.protect)
+                        .opt("comment", p.comment.as_deref())
+                });
+                self.container("platforms", platforms.comment.as_deref(), elements);
+            }
+            RegistryChild::Tags(ref tags) => {
+                let elements = tags.children.iter().map(|t| {
+                    Element::new("tag")
+                        .attr("name", &*t.name)
+                        .attr("author", &*t.author)
+                        .attr("contact", &*t.contact)
+                });
+                self.container("tags", tags.comment.as_deref(), elements);
+            }
+            RegistryChild::Types(ref types) => {
+                let element = Element::new("types").opt("comment", types.comment.as_deref());
+                self.open(1, &element);
+                for child in &types.children {
+                    match *child {
+                        TypesChild::Comment(ref text) => self.text_element(2, "comment", text),
+                        TypesChild::Type(ref ty) => self.type_(ty),
+                    }
+                }
+                self.close(1, "types");
+            }
+            RegistryChild::Enums(ref enums) => {
+                let element = Element::new("enums")
+                    .opt("name", enums.name.as_deref())
+                    .opt("type", enums.kind.as_deref())
+                    .opt("start", enums.start.map(|v| v.to_string()))
+                    .opt("end", enums.end.map(|v| v.to_string()))
+                    .opt("vendor", enums.vendor.as_deref())
+                    .opt("bitwidth", enums.bitwidth.map(|v| v.to_string()))
+                    .opt("comment", enums.comment.as_deref());
+                let children: Vec<_> = enums
+                    .children
+                    .iter()
+                    .map(|child| match *child {
+                        EnumsChild::Enum(ref e) => enum_element(e),
+                        EnumsChild::Unused(ref unused) => Element::new("unused")
+                            .attr("start", unused.start.to_string())
+                            .opt("end", unused.end.map(|v| v.to_string()))
+                            .opt("vendor", unused.vendor.as_deref())
+                            .opt("comment", unused.comment.as_deref()),
+                        EnumsChild::Comment(ref text) => Element::new("comment").attr("", &**text),
+                    })
+                    .collect();
+                self.lines(1, &element, &self.aligned(&children));
+            }
+            RegistryChild::Commands(ref commands) => {
+                let element = Element::new("commands").opt("comment", commands.comment.as_deref());
+                self.open(1, &element);
+                for command in &commands.children {
+                    self.command(command);
+                }
+                self.close(1, "commands");
+            }
+            RegistryChild::Feature(ref feature) => {
+                let element = Element::new("feature")
+                    .attr("api", feature.api.to_string())
+                    .attr("name", &*feature.name)
+                    .attr("number", feature.number.to_string())
+                    .opt("protect", feature.protect.as_deref())
+                    .opt("comment", feature.comment.as_deref());
+                self.interface(1, &element, &feature.children);
+            }
+            RegistryChild::Extensions(ref extensions) => {
+                let element =
+                    Element::new("extensions").opt("comment", extensions.comment.as_deref());
+                self.open(1, &element);
+                for e in &extensions.children {
+                    let element = Element::new("extension")
+                        .attr("name", &*e.name)
+                        .opt("number", e.number.map(|v| v.to_string()))
+                        .opt("type", e.ext_type.as_deref())
+                        .opt("requires", e.requires.as_deref())
+                        .opt("requiresCore", e.requires_core.as_deref())
+                        .opt("depends", e.depends.as_ref().map(|d| d.to_string()))
+                        .opt("platform", e.platform.as_deref())
+                        .opt("protect", e.protect.as_deref())
+                        .opt("author", e.author.as_deref())
+                        .opt("contact", e.contact.as_deref())
+                        .opt("supported", e.supported.as_ref().map(|s| s.to_string()))
+                        .opt("promotedto", e.promotedto.as_deref())
+                        .opt("deprecatedby", e.deprecatedby.as_deref())
+                        .opt("deprecated", e.deprecated.map(Deprecation::name))
+                        .opt("obsoletedby", e.obsoletedby.as_deref())
+                        .opt("provisional", Some("true").filter(|_| e.provisional))
+                        .opt("specialuse", e.specialuse.as_deref())
+                        .opt("sortorder", e.sortorder.map(|v| v.to_string()))
+                        .opt("comment", e.comment.as_deref());
+                    self.interface(2, &element, &e.children);
+                }
+                self.close(1, "extensions");
+            }
+            RegistryChild::Formats(ref formats) => {
+                let element = Element::new("formats").opt("comment", formats.comment.as_deref());
+                self.open(1, &element);
+                for format in &formats.children {
+                    let element = Element::new("format")
+                        .attr("name", &*format.name)
+                        .attr("class", &*format.class)
+                        .attr("blockSize", format.blockSize.to_string())
+                        .attr("texelsPerBlock", format.texelsPerBlock.to_string())
+                        .opt("blockExtent", format.blockExtent.as_deref())
+                        .opt("packed", format.packed.map(|v| v.to_string()))
+                        .opt("compressed", format.compressed.as_deref())
+                        .opt("chroma", format.chroma.as_deref());
+                    let children = format.children.iter().map(|child| match *child {
+                        FormatChild::Component {
+                            ref name,
+                            ref bits,
+                            ref numericFormat,
+                            planeIndex,
+                        } => Element::new("component")
+                            .attr("name", &**name)
+                            .attr("bits", &**bits)
+                            .attr("numericFormat", &**numericFormat)
+                            .opt("planeIndex", planeIndex.map(|v| v.to_string())),
+                        FormatChild::Plane {
+                            index,
+                            widthDivisor,
+                            heightDivisor,
+                            ref compatible,
+                        } => Element::new("plane")
+                            .attr("index", index.to_string())
+                            .attr("widthDivisor", widthDivisor.to_string())
+                            .attr("heightDivisor", heightDivisor.to_string())
+                            .attr("compatible", &**compatible),
+                        FormatChild::SpirvImageFormat { ref name } => {
+                            Element::new("spirvimageformat").attr("name", &**name)
+                        }
+                    });
+                    let lines: Vec<_> = children
+                        .map(|e| format!("{}/>", self.start_tag(&e, 0)))
+                        .collect();
+                    self.lines(2, &element, &lines);
+                }
+                self.close(1, "formats");
+            }
+            RegistryChild::SpirvExtensions(ref extensions) => {
+                self.spirv("spirvextensions", "spirvextension", extensions)
+            }
+            RegistryChild::SpirvCapabilities(ref capabilities) => {
+                self.spirv("spirvcapabilities", "spirvcapability", capabilities)
+            }
+            RegistryChild::Sync(ref sync) => self.sync(sync),
+            RegistryChild::VideoCodecs(ref codecs) => self.videocodecs(codecs),
+            // Written back as it was, so that it can be fixed by hand.
+            RegistryChild::Malformed(ref section) => {
+                self.indent(1);
+                self.out.push_str(&section.text);
+                self.out.push('\n');
+            }
+        }
+    }
+
+    /// Element containing only empty elements.
+    fn container<'a, I: Iterator<Item = Element<'a>>>(
+        &mut self,
+        name: &'static str,
+        comment: Option<&str>,
+        elements: I,
+    ) {
+        let element = Element::new(name).opt("comment", comment);
+        let lines: Vec<_> = elements
+            .map(|e| format!("{}/>", self.start_tag(&e, 0)))
+            .collect();
+        self.lines(1, &element, &lines);
+    }
+
+    /// Element containing given lines, or an empty element if there are none.
+    fn lines(&mut self, depth: usize, element: &Element, lines: &[String]) {
+        if lines.is_empty() {
+            return self.empty(depth, element);
+        }
+        self.open(depth, element);
+        for line in lines {
+            self.indent(depth + 1);
+            self.out.push_str(line);
+            self.out.push('\n');
+        }
+        self.close(depth, element.name);
+    }
+
+    fn spirv(&mut self, name: &'static str, child_name: &'static str, block: &SpirvExtensions) {
+        let element = Element::new(name).opt("comment", block.comment.as_deref());
+        self.open(1, &element);
+        for child in &block.children {
+            let lines: Vec<_> = child
+                .enables
+                .iter()
+                .map(|enable| {
+                    let element = match *enable {
+                        Enable::Version(ref version) => {
+                            Element::new("enable").attr("version", &**version)
+                        }
+                        Enable::Extension(ref extension) => {
+                            Element::new("enable").attr("extension", &**extension)
+                        }
+                        Enable::Feature(ref f) => Element::new("enable")
+                            .attr("struct", &*f.struct_)
+                            .attr("feature", &*f.feature)
+                            .opt("requires", f.requires.as_deref())
+                            .opt("alias", f.alias.as_deref()),
+                        Enable::Property(ref p) => Element::new("enable")
+                            .attr("property", &*p.property)
+                            .attr("member", &*p.member)
+                            .attr("value", &*p.value)
+                            .opt("requires", p.requires.as_deref()),
+                    };
+                    format!("{}/>", self.start_tag(&element, 0))
+                })
+                .collect();
+            let element = Element::new(child_name).attr("name", &*child.name);
+            self.lines(2, &element, &lines);
+        }
+        self.close(1, name);
+    }
+
+    fn sync(&mut self, sync: &SyncSection) {
+        let element = Element::new("sync").opt("comment", sync.comment.as_deref());
+        self.open(1, &element);
+        for child in &sync.children {
+            match *child {
+                SyncChild::Stage(ref stage) => {
+                    let element = Element::new("syncstage")
+                        .attr("name", &*stage.name)
+                        .opt("alias", stage.alias.as_deref());
+                    let lines = self.sync_lines(None, &stage.support, &stage.equivalent);
+                    self.lines(2, &element, &lines);
+                }
+                SyncChild::Access(ref access) => {
+                    let element = Element::new("syncaccess")
+                        .attr("name", &*access.name)
+                        .opt("alias", access.alias.as_deref());
+                    let lines = self.sync_lines(
+                        access.comment.as_deref(),
+                        &access.support,
+                        &access.equivalent,
+                    );
+                    self.lines(2, &element, &lines);
+                }
+                SyncChild::Pipeline(ref pipeline) => {
+                    let element = Element::new("syncpipeline")
+                        .attr("name", &*pipeline.name)
+                        .opt("depends", pipeline.depends.as_deref());
+                    let lines: Vec<_> = pipeline
+                        .stages
+                        .iter()
+                        .map(|stage| {
+                            let element = Element::new("syncpipelinestage")
+                                .opt("order", stage.order.as_deref())
+                                .opt("before", stage.before.as_deref())
+                                .opt("after", stage.after.as_deref());
+                            format!(
+                                "{}>{}</syncpipelinestage>",
+                                self.start_tag(&element, 0),
+                                escape(&stage.name, false)
+                            )
+                        })
+                        .collect();
+                    self.lines(2, &element, &lines);
+                }
+            }
+        }
+        self.close(1, "sync");
+    }
+
+    fn videocodecs(&mut self, codecs: &VideoCodecs) {
+        let element = Element::new("videocodecs").opt("comment", codecs.comment.as_deref());
+        self.open(1, &element);
+        for codec in &codecs.children {
+            let element = Element::new("videocodec")
+                .attr("name", &*codec.name)
+                .opt("extend", codec.extend.as_deref())
+                .opt("value", codec.value.as_deref());
+            if codec.profiles.is_empty()
+                && codec.capabilities.is_empty()
+                && codec.formats.is_empty()
+            {
+                self.empty(2, &element);
+                continue;
+            }
+            self.open(2, &element);
+            for profiles in &codec.profiles {
+                let element = Element::new("videoprofiles").attr("struct", &*profiles.struct_);
+                self.open(3, &element);
+                for member in &profiles.members {
+                    let element = Element::new("videoprofilemember").attr("name", &*member.name);
+                    let lines: Vec<_> = member
+                        .profiles
+                        .iter()
+                        .map(|profile| {
+                            let element = Element::new("videoprofile")
+                                .attr("value", &*profile.value)
+                                .attr("name", &*profile.name);
+                            format!("{}/>", self.start_tag(&element, 0))
+                        })
+                        .collect();
+                    self.lines(4, &element, &lines);
+                }
+                self.close(3, "videoprofiles");
+            }
+            for capabilities in &codec.capabilities {
+                self.empty(
+                    3,
+                    &Element::new("videocapabilities").attr("struct", &**capabilities),
+                );
+            }
+            for format in &codec.formats {
+                let element = Element::new("videoformat")
+                    .opt("name", format.name.as_deref())
+                    .opt("extend", format.extend.as_deref())
+                    .opt("usage", format.usage.as_deref());
+                let required = format.required_capabilities.iter().map(|c| {
+                    Element::new("videorequirecapabilities")
+                        .attr("struct", &*c.struct_)
+                        .attr("member", &*c.member)
+                        .attr("value", &*c.value)
+                });
+                let properties = format
+                    .properties
+                    .iter()
+                    .map(|p| Element::new("videoformatproperties").attr("struct", &**p));
+                let lines: Vec<_> = required
+                    .chain(properties)
+                    .map(|e| format!("{}/>", self.start_tag(&e, 0)))
+                    .collect();
+                self.lines(3, &element, &lines);
+            }
+            self.close(2, "videocodec");
+        }
+        self.close(1, "videocodecs");
+    }
+
+    /// Contents of `<syncstage>` and `<syncaccess>`.
+    fn sync_lines(
+        &self,
+        comment: Option<&str>,
+        support: &Option<SyncSupport>,
+        equivalent: &Option<SyncEquivalent>,
+    ) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(comment) = comment {
+            lines.push(format!("<comment>{}</comment>", escape(comment, false)));
+        }
+        if let Some(ref support) = *support {
+            let element = Element::new("syncsupport")
+                .opt(
+                    "queues",
+                    Some(QueueKind::join(&support.queues)).filter(|q| !q.is_empty()),
+                )
+                .opt(
+                    "stage",
+                    Some(support.stage.join(",")).filter(|s| !s.is_empty()),
+                );
+            lines.push(format!("{}/>", self.start_tag(&element, 0)));
+        }
+        if let Some(ref equivalent) = *equivalent {
+            let element = Element::new("syncequivalent")
+                .opt(
+                    "stage",
+                    Some(equivalent.stage.join(",")).filter(|s| !s.is_empty()),
+                )
+                .opt(
+                    "access",
+                    Some(equivalent.access.join(",")).filter(|a| !a.is_empty()),
+                );
+            lines.push(format!("{}/>", self.start_tag(&element, 0)));
+        }
+        lines
+    }
+
+    fn type_(&mut self, ty: &Type) {
+        let element = Element::new("type")
+            .opt("requires", ty.requires.as_deref())
+            .opt("bitvalues", ty.bitvalues.as_deref())
+            .opt("api", ty.api.as_ref().map(|a| a.to_string()))
+            .opt("category", ty.category.as_deref())
+            .opt("name", ty.name.as_deref())
+            .opt("alias", ty.alias.as_deref())
+            .opt("deprecated", ty.deprecated.map(Deprecation::name))
+            .opt("parent", ty.parent.as_deref())
+            .opt("objtypeenum", ty.objtypeenum.as_deref())
+            .opt("returnedonly", ty.returnedonly.as_deref())
+            .opt("structextends", ty.structextends.as_deref())
+            .opt("allowduplicate", ty.allowduplicate.as_deref())
+            .opt("comment", ty.comment.as_deref());
+        match ty.spec {
+            TypeSpec::Code(ref code) => {
+                let markup: Vec<_> = code
+                    .markup
+                    .iter()
+                    .map(|m| match *m {
+                        TypeCodeMarkup::Name(ref text) => ("name", &**text),
+                        TypeCodeMarkup::Type(ref text) => ("type", &**text),
+                        TypeCodeMarkup::ApiEntry(ref text) => ("apientry", &**text),
+                    })
+                    .collect();
+                self.inline(2, &element, &marked_text(&code.code, &markup, false));
+            }
+            TypeSpec::Members(ref members) => {
+                self.open(2, &element);
+                for member in members {
+                    match *member {
+                        TypeMember::Comment(ref text) => self.text_element(3, "comment", text),
+                        TypeMember::Definition(ref def) => self.member(def),
+                    }
+                }
+                self.close(2, "type");
+            }
+            _ => self.empty(2, &element),
+        }
+    }
+
+    fn member(&mut self, def: &TypeMemberDefinition) {
+        let element = Element::new("member")
+            .opt("api", def.api.as_ref().map(|a| a.to_string()))
+            .opt("values", def.values.as_deref())
+            .opt("optional", def.optional.as_deref())
+            .opt("selector", def.selector.as_deref())
+            .opt("selection", def.selection.as_deref())
+            .opt("noautovalidity", def.noautovalidity.as_deref())
+            .opt("externsync", def.externsync.as_deref())
+            .opt("len", def.len.as_deref())
+            .opt("altlen", def.altlen.as_deref())
+            .opt(
+                "validextensionstructs",
+                def.validextensionstructs.as_deref(),
+            )
+            .opt("limittype", def.limittype.as_deref())
+            .opt("objecttype", def.objecttype.as_deref())
+            .opt("deprecated", def.deprecated.map(Deprecation::name));
+        let mut markup = Vec::new();
+        let mut comments = Vec::new();
+        for m in &def.markup {
+            match *m {
+                TypeMemberMarkup::Name(ref text) => markup.push(("name", &**text)),
+                TypeMemberMarkup::Type(ref text) => markup.push(("type", &**text)),
+                TypeMemberMarkup::Enum(ref text) => markup.push(("enum", &**text)),
+                TypeMemberMarkup::Comment(ref text) => comments.push(text),
+            }
+        }
+        let mut content = if def.code.is_empty() {
+            marked_text(&def.to_string(), &markup, false)
+        } else {
+            // Comments are moved to the end, each having added a space there while parsing.
+            let code = strip_spaces(&def.code, 1 + comments.len());
+            marked_text(code, &markup, true)
+        };
+        for comment in comments {
+            content.push_str("<comment>");
+            content.push_str(&escape(comment, false));
+            content.push_str("</comment>");
+        }
+        self.inline(3, &element, &content);
+    }
+
+    fn command(&mut self, command: &Command) {
+        let def = match *command {
+            Command::Alias {
+                ref name,
+                ref alias,
+                deprecated,
+                ..
+            } => {
+                let element = Element::new("command")
+                    .attr("name", &**name)
+                    .attr("alias", &**alias)
+                    .opt("deprecated", deprecated.map(Deprecation::name));
+                return self.empty(2, &element);
+            }
+            Command::Definition(ref def) => def,
+        };
+        let element = Element::new("command")
+            .opt("api", def.api.as_ref().map(|a| a.to_string()))
+            .opt(
+                "successcodes",
+                Some(def.successcodes.join(",")).filter(|c| !c.is_empty()),
+            )
+            .opt(
+                "errorcodes",
+                Some(def.errorcodes.join(",")).filter(|c| !c.is_empty()),
+            )
+            .opt(
+                "queues",
+                Some(QueueKind::join(&def.queues)).filter(|q| !q.is_empty()),
+            )
+            .opt("renderpass", def.renderpass.as_deref())
+            .opt("videocoding", def.videocoding.map(VideoCoding::name))
+            .opt("cmdbufferlevel", def.cmdbufferlevel.as_deref())
+            .opt("pipeline", def.pipeline.as_deref())
+            .opt("deprecated", def.deprecated.map(Deprecation::name))
+            .opt("comment", def.comment.as_deref());
+        self.open(2, &element);
+
+        let name_markup = |decl: &'_ NameWithType| -> Vec<(&'static str, String)> {
+            let type_name = decl.type_name.iter().map(|t| ("type", t.to_string()));
+            let convention = decl
+                .calling_convention
+                .iter()
+                .map(|c| ("apientry", c.to_string()));
+            type_name
+                .chain(convention)
+                .chain(Some(("name", decl.name.to_string())))
+                .collect()
+        };
+        let decl = |text: &str, artifacts: bool, decl: &NameWithType| -> String {
+            let markup = name_markup(decl);
+            let markup: Vec<_> = markup.iter().map(|&(tag, ref t)| (tag, &**t)).collect();
+            if artifacts {
+                marked_text(strip_spaces(text, 1), &markup, true)
+            } else {
+                marked_text(text, &markup, false)
+            }
+        };
+        let (proto, params) = match split_command_code(&def.code, def.params.len()) {
+            Some((proto, params)) => (
+                decl(proto, true, &def.proto),
+                def.params
+                    .iter()
+                    .zip(params)
+                    .map(|(p, text)| decl(text, true, &p.definition))
+                    .collect(),
+            ),
+            None => (
+                decl(&def.proto.to_string(), false, &def.proto),
+                def.params
+                    .iter()
+                    .map(|p| decl(&p.definition.to_string(), false, &p.definition))
+                    .collect::<Vec<_>>(),
+            ),
+        };
+        self.inline(3, &Element::new("proto"), &proto);
+        for (param, content) in def.params.iter().zip(params) {
+            let validstructs = param.validstructs.join(",");
+            let element = Element::new("param")
+                .opt("api", param.api.as_ref().map(|a| a.to_string()))
+                .opt("deprecated", param.deprecated.map(Deprecation::name))
+                .opt("optional", param.optional.as_deref())
+                .opt("externsync", param.externsync.as_deref())
+                .opt("len", param.len.as_deref())
+                .opt("altlen", param.altlen.as_deref())
+                .opt("noautovalidity", param.noautovalidity.as_deref())
+                .opt("objecttype", param.objecttype.as_deref())
+                .opt("validstructs", Some(validstructs).filter(|v| !v.is_empty()));
+            self.inline(3, &element, &content);
+        }
+        if let Some(ref alias) = def.alias {
+            self.empty(3, &Element::new("alias").attr("name", &**alias));
+        }
+        if let Some(ref description) = def.description {
+            self.text_element(3, "description", description);
+        }
+        if !def.implicitexternsyncparams.is_empty() {
+            self.open(3, &Element::new("implicitexternsyncparams"));
+            for param in &def.implicitexternsyncparams {
+                self.text_element(4, "param", param);
+            }
+            self.close(3, "implicitexternsyncparams");
+        }
+        self.close(2, "command");
+    }
+
+    /// Feature or extension, along with its blocks.
+    fn interface(&mut self, depth: usize, element: &Element, children: &[ExtensionChild]) {
+        if children.is_empty() {
+            return self.empty(depth, element);
+        }
+        self.open(depth, element);
+        for child in children {
+            let (block, items) = match *child {
+                ExtensionChild::Require {
+                    ref api,
+                    ref profile,
+                    ref extension,
+                    ref feature,
+                    ref depends,
+                    ref comment,
+                    ref items,
+                    ..
+                } => (
+                    Element::new("require")
+                        .opt("api", api.as_ref().map(|a| a.to_string()))
+                        .opt("profile", profile.as_deref())
+                        .opt("extension", extension.as_deref())
+                        .opt("feature", feature.as_deref())
+                        .opt("depends", depends.as_ref().map(|d| d.to_string()))
+                        .opt("comment", comment.as_deref()),
+                    items,
+                ),
+                ExtensionChild::Remove {
+                    ref api,
+                    ref profile,
+                    ref comment,
+                    ref items,
+                    ..
+                } => (
+                    Element::new("remove")
+                        .opt("api", api.as_ref().map(|a| a.to_string()))
+                        .opt("profile", profile.as_deref())
+                        .opt("comment", comment.as_deref()),
+                    items,
+                ),
+                ExtensionChild::Deprecate {
+                    ref api,
+                    ref profile,
+                    ref explanationlink,
+                    ref comment,
+                    ref items,
+                    ..
+                } => (
+                    Element::new("deprecate")
+                        .opt("api", api.as_ref().map(|a| a.to_string()))
+                        .opt("profile", profile.as_deref())
+                        .opt("explanationlink", explanationlink.as_deref())
+                        .opt("comment", comment.as_deref()),
+                    items,
+                ),
+            };
+            let elements: Vec<_> = items
+                .iter()
+                .map(|item| match *item {
+                    InterfaceItem::Comment(ref text) => Element::new("comment").attr("", &**text),
+                    InterfaceItem::Type {
+                        ref name,
+                        ref comment,
+                    } => Element::new("type")
+                        .attr("name", &**name)
+                        .opt("comment", comment.as_deref()),
+                    InterfaceItem::Command {
+                        ref name,
+                        ref comment,
+                    } => Element::new("command")
+                        .attr("name", &**name)
+                        .opt("comment", comment.as_deref()),
+                    InterfaceItem::Enum(ref e) => enum_element(e),
+                })
+                .collect();
+            let lines = self.aligned(&elements);
+            if let (Some(width), false) = (self.options.block_line_width, lines.is_empty()) {
+                let line = format!(
+                    "{}>{}</{}>",
+                    self.start_tag(&block, 0),
+                    lines.concat(),
+                    block.name
+                );
+                let start = self.out.len();
+                self.indent(depth + 1);
+                if self.out.len() - start + line.len() <= width {
+                    self.out.push_str(&line);
+                    self.out.push('\n');
+                    continue;
+                }
+                self.out.truncate(start);
+            }
+            self.lines(depth + 1, &block, &lines);
+        }
+        self.close(depth, element.name);
+    }
+}
+
+fn enum_element(e: &Enum) -> Element<'_> {
+    let mut element = Element::new("enum").opt("type", e.type_suffix.as_deref());
+    let extends = match e.spec {
+        EnumSpec::Value {
+            ref value,
+            ref extends,
+        } => {
+            element = element.attr("value", &**value);
+            extends.as_deref()
+        }
+        EnumSpec::Bitpos {
+            bitpos,
+            ref extends,
+        } => {
+            element = element.attr("bitpos", bitpos.to_string());
+            extends.as_deref()
+        }
+        EnumSpec::Offset {
+            offset,
+            ref extends,
+            extnumber,
+            dir,
+        } => {
+            element = element
+                .attr("offset", offset.to_string())
+                .attr("extends", &**extends)
+                .opt("extnumber", extnumber.map(|v| v.to_string()))
+                .opt("dir", Some("-").filter(|_| !dir));
+            None
+        }
+        EnumSpec::Alias { ref extends, .. } => extends.as_deref(),
+        EnumSpec::None => None,
+    };
+    let alias = match e.spec {
+        EnumSpec::Alias { ref alias, .. } => Some(&**alias),
+        _ => None,
+    };
+    element
+        .opt("extends", extends)
+        .opt("api", e.api.as_ref().map(|a| a.to_string()))
+        .attr("name", &*e.name)
+        .opt("alias", alias)
+        .opt("deprecated", e.deprecated.map(Deprecation::name))
+        .opt("protect", e.protect.as_deref())
+        .opt("comment", e.comment.as_deref())
+}
+
+/// Escapes text for use in XML content, or in attribute values if `attribute` is set.
+fn escape(text: &str, attribute: bool) -> Cow<'_, str> {
+    let needs_escape = |c: char| matches!(c, '&' | '<' | '>') || (attribute && c == '"');
+    if !text.contains(needs_escape) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 8);
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Removes up to `count` spaces from the end of `text`.
+fn strip_spaces(text: &str, count: usize) -> &str {
+    let mut text = text;
+    for _ in 0..count {
+        text = text.strip_suffix(' ').unwrap_or(text);
+    }
+    text
+}
+
+/// Escaped `text` with occurrences of `markup` texts, in order, wrapped in elements given by
+/// their tags. Markup which can't be found is appended. The parser adds a space before each
+/// element of declarations, which is removed if `artifacts` is set.
+fn marked_text(text: &str, markup: &[(&'static str, &str)], artifacts: bool) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    for &(tag, value) in markup {
+        let (before, after) = match find_word(rest, value) {
+            Some(i) => (&rest[..i], &rest[i + value.len()..]),
+            None => (rest, ""),
+        };
+        let before = if artifacts {
+            strip_spaces(before, 1)
+        } else {
+            before
+        };
+        result.push_str(&escape(before, false));
+        result.push('<');
+        result.push_str(tag);
+        result.push('>');
+        result.push_str(&escape(value, false));
+        result.push_str("</");
+        result.push_str(tag);
+        result.push('>');
+        rest = after;
+    }
+    result.push_str(&escape(rest, false));
+    result
+}
+
+/// Position of `word` in `text`, not directly preceded or followed by an identifier character.
+fn find_word(text: &str, word: &str) -> Option<usize> {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut start = 0;
+    while let Some(i) = text[start..].find(word).map(|i| i + start) {
+        let end = i + word.len();
+        let before = text[..i].chars().next_back();
+        let after = text[end..].chars().next();
+        if !before.map_or(false, is_ident) && !after.map_or(false, is_ident) {
+            return Some(i);
+        }
+        start = i + text[i..].chars().next().map_or(1, char::len_utf8);
+    }
+    None
+}
+
+/// Splits code of a command, as stored by the parser, into text of the prototype and of each
+/// of `count` parameters.
+fn split_command_code(code: &str, count: usize) -> Option<(&str, Vec<&str>)> {
+    let open = code.find('(')?;
+    let params = code[open + 1..].strip_suffix(");")?;
+    let params: Vec<_> = match count {
+        0 if params.is_empty() => Vec::new(),
+        0 => return None,
+        _ => params.split(", ").collect(),
+    };
+    if params.len() != count {
+        return None;
+    }
+    Some((&code[..open], params))
+}